@@ -63,15 +63,19 @@ module.exports = async function (provider) {
 
   // --- 2. (Optional) Set initial Distribution Hub if needed ---
   // This should typically be done as a separate operational step after deployment and initialization.
-  // const initialHubAddress = new PublicKey("YOUR_INITIAL_DISTRIBUTION_HUB_PUBKEY");
+  // The hub must be an initialized token account of the given mint.
+  // const initialHubAddress = new PublicKey("YOUR_INITIAL_DISTRIBUTION_HUB_TOKEN_ACCOUNT");
+  // const hubMint = new PublicKey("YOUR_HUB_MINT");
   // const currentConfig = await program.account.programConfig.fetch(programConfigPDA);
   // if (currentConfig.distributionHub.equals(new PublicKey(Buffer.alloc(32)))) { // Check if unset (all zeros)
   //   console.log(`Setting initial distribution hub to: ${initialHubAddress.toBase58()}`);
   //   await program.methods
-  //     .updateDistributionHub(initialHubAddress)
+  //     .updateDistributionHub()
   //     .accounts({
   //       admin: adminWallet.publicKey,
   //       programConfig: programConfigPDA,
+  //       mint: hubMint,
+  //       hubTokenAccount: initialHubAddress,
   //     })
   //     .rpc();
   //   console.log("Initial distribution hub set.");
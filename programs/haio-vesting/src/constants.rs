@@ -2,10 +2,80 @@
 pub const PROGRAM_CONFIG_SEED: &[u8] = b"program_config";
 pub const VESTING_SCHEDULE_SEED: &[u8] = b"vesting_schedule";
 pub const VESTING_VAULT_SEED: &[u8] = b"vesting_vault";
+pub const NATIVE_VAULT_SEED: &[u8] = b"native_vault";
+pub const UNWRAP_ACCOUNT_SEED: &[u8] = b"unwrap";
+pub const PRIMARY_SCHEDULE_SEED: &[u8] = b"primary";
+pub const MINT_SCHEDULE_COUNT_SEED: &[u8] = b"mint_schedules";
 
 // Account discriminator size (8 bytes for Anchor accounts)
 pub const DISCRIMINATOR_SIZE: usize = 8;
 
 // Account size constants for rent calculation
 pub const PROGRAM_CONFIG_LEN: usize = crate::state::ProgramConfig::LEN;
-pub const VESTING_SCHEDULE_LEN: usize = crate::state::VestingSchedule::LEN;
\ No newline at end of file
+pub const VESTING_SCHEDULE_LEN: usize = crate::state::VestingSchedule::LEN;
+pub const PRIMARY_SCHEDULE_INDEX_LEN: usize = crate::state::PrimaryScheduleIndex::LEN;
+pub const MINT_SCHEDULE_COUNT_LEN: usize = crate::state::MintScheduleCount::LEN;
+
+// Basis points denominator (10000 bps = 100%)
+pub const BPS_DENOMINATOR: u16 = 10_000;
+
+// Fixed-point scale of `ProgramConfig::rounding_dust` (counted in billionths of a token base unit)
+pub const ROUNDING_DUST_SCALE: u128 = 1_000_000_000;
+
+// Hard upper bound on the keeper reward so an admin cannot drain recipients (100 bps = 1%)
+pub const MAX_KEEPER_REWARD_BPS: u16 = 100;
+
+// Batch limits (bounded to stay within compute-unit and account limits)
+pub const MAX_TOP_UP_BATCH_SIZE: usize = 10;
+pub const MAX_SCAN_BATCH_SIZE: usize = 30;
+pub const MAX_CRANK_BATCH_SIZE: usize = 8;
+// Four accounts per entry keeps a full batch under the 64 account locks of a transaction
+pub const MAX_SCHEDULES_PER_CREATE: usize = 10;
+
+// Unlock curve points per call: 4-byte length + 8 bytes per point stays well under the
+// 1024-byte return data limit
+pub const MAX_CURVE_POINTS: usize = 64;
+
+// Creation-time bounds on a schedule's timeline against the current clock, catching typos
+// A cliff more than 90 days back unlocks most tokens at once; an end beyond 10 years is a unit slip
+pub const MAX_BACKDATE: i64 = 90 * 24 * 60 * 60;
+pub const MAX_FUTURE: i64 = 10 * 365 * 24 * 60 * 60;
+
+// Delegate keys holding admin capabilities, stored inline in ProgramConfig
+pub const MAX_DELEGATES: usize = 4;
+
+// Capability bits grantable to delegates (the admin implicitly holds all of them)
+// Create: create_vesting_schedule, create_vesting_schedules_batch, create_from_template, top_up_batch
+pub const CAPABILITY_CREATE: u8 = 1 << 0;
+// Crank management: keeper reward, reward buffer, release caps, batching and crank policy flags
+pub const CAPABILITY_CRANK_MANAGE: u8 = 1 << 1;
+// Config: read-only mode, clock skew, category policies and templates, placeholder schedules,
+// underfunding policy, per-mint schedule limit
+pub const CAPABILITY_CONFIG: u8 = 1 << 2;
+pub const ALL_CAPABILITIES: u8 = CAPABILITY_CREATE | CAPABILITY_CRANK_MANAGE | CAPABILITY_CONFIG;
+
+// Version of the `ProgramLimits` layout returned by get_limits, bumped whenever a field is added
+pub const PROGRAM_LIMITS_VERSION: u8 = 10;
+
+// Timelock before a distribution hub change can be confirmed (48 hours)
+pub const HUB_UPDATE_TIMELOCK: i64 = 48 * 60 * 60;
+
+// Timelock before a proposed admin can accept the admin role (48 hours)
+pub const ADMIN_TRANSFER_TIMELOCK: i64 = 48 * 60 * 60;
+
+// Timelock before a proposed upgrade authority record can be confirmed (7 days)
+pub const UPGRADE_AUTHORITY_TIMELOCK: i64 = 7 * 24 * 60 * 60;
+
+// Timelock before a schedule's proposed recipient token account can be confirmed (48 hours)
+pub const RECIPIENT_CHANGE_TIMELOCK: i64 = 48 * 60 * 60;
+
+// Domain separator for recipient-signed claim authorizations
+pub const CLAIM_MESSAGE_DOMAIN: &[u8] = b"haio-vesting:claim_with_signature";
+
+// Domain separator (and layout version) of the canonical schedule terms hashed by verify_terms
+// Fields added since v1 are appended only when they differ from their defaults, so the version
+// stays fixed and hashes recorded earlier keep verifying
+pub const TERMS_HASH_DOMAIN: &[u8] = b"haio-vesting:terms:v1";
+
+// Target slot duration used to estimate elapsed time from slot progression (milliseconds)
+pub const TARGET_MS_PER_SLOT: u64 = 400;
@@ -0,0 +1,157 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::ed25519_program;
+use anchor_lang::solana_program::instruction::Instruction;
+use crate::constants::CLAIM_MESSAGE_DOMAIN;
+use crate::errors::VestingError;
+
+// Ed25519 program instruction layout:
+// [num_signatures: u8][padding: u8][Ed25519SignatureOffsets; num_signatures][data...]
+const SIGNATURE_OFFSETS_START: usize = 2;
+const SIGNATURE_OFFSETS_SERIALIZED_SIZE: usize = 14;
+const PUBKEY_SERIALIZED_SIZE: usize = 32;
+const SIGNATURE_SERIALIZED_SIZE: usize = 64;
+
+/// Build the message a recipient signs to authorize a relayed claim
+///
+/// Layout: domain || vesting_schedule || destination || amount_transferred (le) || expiry (le)
+/// Binding `amount_transferred` makes each authorization single-use: once a claim moves tokens
+/// the schedule's counter changes and the old signature no longer matches.
+pub fn build_claim_message(
+    vesting_schedule: &Pubkey,
+    destination: &Pubkey,
+    amount_transferred: u64,
+    expiry: i64,
+) -> Vec<u8> {
+    let mut message = Vec::with_capacity(CLAIM_MESSAGE_DOMAIN.len() + 32 + 32 + 8 + 8);
+    message.extend_from_slice(CLAIM_MESSAGE_DOMAIN);
+    message.extend_from_slice(vesting_schedule.as_ref());
+    message.extend_from_slice(destination.as_ref());
+    message.extend_from_slice(&amount_transferred.to_le_bytes());
+    message.extend_from_slice(&expiry.to_le_bytes());
+    message
+}
+
+/// Check that an Ed25519 program instruction verified `expected_message` for `expected_signer`
+///
+/// The Ed25519 program itself aborts the transaction on an invalid signature, so this only
+/// needs to confirm which key and message it checked.
+/// Security: Offsets must point into the Ed25519 instruction's own data, otherwise the
+/// verified bytes could live in a different instruction than the ones inspected here.
+pub fn verify_ed25519_instruction(
+    ix: &Instruction,
+    expected_signer: &Pubkey,
+    expected_message: &[u8],
+) -> Result<()> {
+    require_keys_eq!(ix.program_id, ed25519_program::ID, VestingError::InvalidSignatureInstruction);
+    require!(ix.accounts.is_empty(), VestingError::InvalidSignatureInstruction);
+
+    let data = &ix.data;
+    require!(
+        data.len() >= SIGNATURE_OFFSETS_START + SIGNATURE_OFFSETS_SERIALIZED_SIZE && data[0] == 1,
+        VestingError::InvalidSignatureInstruction
+    );
+
+    let read_u16 = |offset: usize| -> u16 {
+        let start = SIGNATURE_OFFSETS_START + offset;
+        u16::from_le_bytes([data[start], data[start + 1]])
+    };
+    let signature_offset = read_u16(0) as usize;
+    let signature_instruction_index = read_u16(2);
+    let public_key_offset = read_u16(4) as usize;
+    let public_key_instruction_index = read_u16(6);
+    let message_data_offset = read_u16(8) as usize;
+    let message_data_size = read_u16(10) as usize;
+    let message_instruction_index = read_u16(12);
+
+    require!(
+        signature_instruction_index == u16::MAX
+            && public_key_instruction_index == u16::MAX
+            && message_instruction_index == u16::MAX,
+        VestingError::InvalidSignatureInstruction
+    );
+    require!(
+        signature_offset + SIGNATURE_SERIALIZED_SIZE <= data.len(),
+        VestingError::InvalidSignatureInstruction
+    );
+
+    let public_key = data
+        .get(public_key_offset..public_key_offset + PUBKEY_SERIALIZED_SIZE)
+        .ok_or(VestingError::InvalidSignatureInstruction)?;
+    let message = data
+        .get(message_data_offset..message_data_offset + message_data_size)
+        .ok_or(VestingError::InvalidSignatureInstruction)?;
+
+    require!(public_key == expected_signer.as_ref(), VestingError::SignatureMismatch);
+    require!(message == expected_message, VestingError::SignatureMismatch);
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Mirror the layout produced by the Ed25519 program's instruction builder
+    fn ed25519_ix(signer: &Pubkey, message: &[u8], instruction_index: u16) -> Instruction {
+        let public_key_offset = SIGNATURE_OFFSETS_START + SIGNATURE_OFFSETS_SERIALIZED_SIZE;
+        let signature_offset = public_key_offset + PUBKEY_SERIALIZED_SIZE;
+        let message_data_offset = signature_offset + SIGNATURE_SERIALIZED_SIZE;
+
+        let mut data = vec![1u8, 0u8];
+        for value in [
+            signature_offset as u16,
+            instruction_index,
+            public_key_offset as u16,
+            instruction_index,
+            message_data_offset as u16,
+            message.len() as u16,
+            instruction_index,
+        ] {
+            data.extend_from_slice(&value.to_le_bytes());
+        }
+        data.extend_from_slice(signer.as_ref());
+        data.extend_from_slice(&[7u8; SIGNATURE_SERIALIZED_SIZE]);
+        data.extend_from_slice(message);
+
+        Instruction { program_id: ed25519_program::ID, accounts: vec![], data }
+    }
+
+    #[test]
+    fn test_verify_accepts_matching_signer_and_message() {
+        let signer = Pubkey::new_unique();
+        let message = build_claim_message(&Pubkey::new_unique(), &Pubkey::new_unique(), 0, 100);
+        let ix = ed25519_ix(&signer, &message, u16::MAX);
+
+        assert!(verify_ed25519_instruction(&ix, &signer, &message).is_ok());
+    }
+
+    #[test]
+    fn test_verify_rejects_other_signer_or_message() {
+        let signer = Pubkey::new_unique();
+        let schedule = Pubkey::new_unique();
+        let destination = Pubkey::new_unique();
+        let message = build_claim_message(&schedule, &destination, 0, 100);
+        let ix = ed25519_ix(&signer, &message, u16::MAX);
+
+        assert!(verify_ed25519_instruction(&ix, &Pubkey::new_unique(), &message).is_err());
+
+        let redirected = build_claim_message(&schedule, &Pubkey::new_unique(), 0, 100);
+        assert!(verify_ed25519_instruction(&ix, &signer, &redirected).is_err());
+
+        let replayed = build_claim_message(&schedule, &destination, 1, 100);
+        assert!(verify_ed25519_instruction(&ix, &signer, &replayed).is_err());
+    }
+
+    #[test]
+    fn test_verify_rejects_cross_instruction_offsets_and_wrong_program() {
+        let signer = Pubkey::new_unique();
+        let message = build_claim_message(&Pubkey::new_unique(), &Pubkey::new_unique(), 0, 100);
+
+        let cross_ix = ed25519_ix(&signer, &message, 0);
+        assert!(verify_ed25519_instruction(&cross_ix, &signer, &message).is_err());
+
+        let mut wrong_program = ed25519_ix(&signer, &message, u16::MAX);
+        wrong_program.program_id = Pubkey::new_unique();
+        assert!(verify_ed25519_instruction(&wrong_program, &signer, &message).is_err());
+    }
+}
@@ -52,4 +52,190 @@ pub enum VestingError {
     
     #[msg("Vesting vault is not empty and cannot be closed.")]
     VaultNotEmpty, // 6027
-}
\ No newline at end of file
+
+    #[msg("Invalid batch size.")]
+    InvalidBatchSize, // 6028
+
+    #[msg("Remaining accounts do not match the expected layout.")]
+    InvalidRemainingAccounts, // 6029
+
+    #[msg("Depositor token account balance is insufficient.")]
+    InsufficientDepositorBalance, // 6030
+
+    #[msg("Basis points value exceeds 10000.")]
+    InvalidBps, // 6031
+
+    #[msg("Keeper reward exceeds the maximum allowed.")]
+    KeeperRewardTooHigh, // 6032
+
+    #[msg("Schedule counter is already consistent with existing schedules.")]
+    ScheduleCountConsistent, // 6033
+
+    #[msg("The next schedule slot is already occupied.")]
+    NextScheduleSlotOccupied, // 6034
+
+    #[msg("Invalid grant group.")]
+    InvalidGrantGroup, // 6035
+
+    #[msg("Schedule does not belong to the requested grant group.")]
+    GrantGroupMismatch, // 6036
+
+    #[msg("Schedules in a grant group must share the same recipient.")]
+    GrantGroupRecipientMismatch, // 6037
+
+    #[msg("Timelock has not expired yet.")]
+    TimelockNotExpired, // 6038
+
+    #[msg("Preceding instruction is not a valid Ed25519 signature verification.")]
+    InvalidSignatureInstruction, // 6039
+
+    #[msg("Signature does not match the recipient or the expected claim message.")]
+    SignatureMismatch, // 6040
+
+    #[msg("Claim authorization has expired.")]
+    ClaimAuthorizationExpired, // 6041
+
+    #[msg("Recipient token account is no longer owned by the recipient; update the schedule's recipient account.")]
+    RecipientAccountOwnershipChanged, // 6042
+
+    #[msg("Cliff and vesting start must be equal for this category.")]
+    CliffStartMismatch, // 6043
+
+    #[msg("Program is in read-only mode.")]
+    ProgramReadOnly, // 6044
+
+    #[msg("Signer is not the schedule's withdrawal authority.")]
+    UnauthorizedWithdrawalAuthority, // 6045
+
+    #[msg("Total amount does not divide evenly across the vesting steps.")]
+    UnevenStepDivision, // 6046
+
+    #[msg("Minimum claim interval has not elapsed since the last claim.")]
+    ClaimTooSoon, // 6047
+
+    #[msg("Schedule is locked by a release in progress.")]
+    ScheduleLocked, // 6048
+
+    #[msg("Cluster clock deviates too far from slot progression.")]
+    ClockSkewTooHigh, // 6049
+
+    #[msg("Schedule mint is not the wrapped SOL mint.")]
+    NotWrappedSol, // 6050
+
+    #[msg("No matching upgrade authority change is pending.")]
+    NoPendingUpgradeAuthority, // 6051
+
+    #[msg("Recipient token account is the depositor token account.")]
+    RecipientEqualsDepositor, // 6052
+
+    #[msg("Schedule has been finalized and can no longer be amended.")]
+    ScheduleImmutable, // 6053
+
+    #[msg("Creation fee destination account is missing or does not match the configuration.")]
+    InvalidFeeDestination, // 6054
+
+    #[msg("Vesting end is not in the future.")]
+    PastVestingEnd, // 6055
+
+    #[msg("No timing template is configured for this category.")]
+    TemplateNotFound, // 6056
+
+    #[msg("Schedule has no terms hash recorded")]
+    TermsHashNotSet, // 6057
+
+    #[msg("Capabilities must be a non-empty subset of the defined capability bits")]
+    InvalidCapability, // 6058
+
+    #[msg("Invalid delegate: must not be the default pubkey or the admin")]
+    InvalidDelegate, // 6059
+
+    #[msg("Maximum number of delegates reached")]
+    DelegateLimitReached, // 6060
+
+    #[msg("Signer is neither the admin nor a delegate holding the required capability")]
+    MissingCapability, // 6061
+
+    #[msg("Destination is not the configured distribution hub token account")]
+    DistributionHubMismatch, // 6062
+
+    #[msg("Maximum number of active schedules for this mint reached")]
+    MaxSchedulesPerMintReached, // 6063
+
+    #[msg("Vesting schedule has been revoked")]
+    ScheduleRevoked, // 6064
+
+    #[msg("Swap program must be an executable program other than the token program or this program")]
+    InvalidSwapProgram, // 6065
+
+    #[msg("Funding mint differs from the release mint; swap-at-release is not supported")]
+    SwapAtReleaseUnsupported, // 6066
+
+    #[msg("Program is paused; token transfers are disabled")]
+    ProgramPaused, // 6067
+
+    #[msg("No admin transfer to the signer is pending.")]
+    NoPendingAdminTransfer, // 6068
+
+    #[msg("Vesting schedule has already been archived")]
+    ScheduleAlreadyArchived, // 6069
+
+    #[msg("Vesting schedule is not archived")]
+    ScheduleNotArchived, // 6070
+
+    #[msg("Archived schedule's retention period has not elapsed")]
+    RetentionPeriodNotElapsed, // 6071
+
+    #[msg("Checkpoint interval must be a percentage that divides 100")]
+    InvalidCheckpointInterval, // 6072
+
+    #[msg("Distribution hub must be an initialized token account")]
+    InvalidDistributionHub, // 6073
+
+    #[msg("Recipient token account has a delegate, which a non-transferable schedule does not allow")]
+    DelegatedAccountNotAllowed, // 6074
+
+    #[msg("Non-transferable schedules release only to the recorded recipient token account")]
+    NonTransferableSchedule, // 6075
+
+    #[msg("Schedules can only be merged with the same recipient, mint and identical vesting terms")]
+    SchedulesNotMergeable, // 6076
+
+    #[msg("Merging would delay tokens that have already vested")]
+    MergeDelaysVesting, // 6077
+
+    #[msg("Allocation would exceed the source category's cap")]
+    CategoryCapExceeded, // 6078
+
+    #[msg("Release would exceed the program-wide release limit of the current window")]
+    ReleaseRateLimitExceeded, // 6079
+
+    #[msg("No recipient token account change is pending for this schedule")]
+    NoPendingRecipientChange, // 6080
+
+    #[msg("Operation is only allowed while the program is paused")]
+    ProgramNotPaused, // 6081
+
+    #[msg("Schedule label must be UTF-8 padded with trailing zeros")]
+    InvalidLabel, // 6082
+
+    #[msg("Schedule has already fully vested")]
+    ScheduleFullyVested, // 6083
+
+    #[msg("No distribution hub update is pending")]
+    NoPendingHubUpdate, // 6084
+
+    #[msg("Cliff is too far in the past")]
+    CliffTooFarInPast, // 6085
+
+    #[msg("Vesting end is too far in the future")]
+    VestingEndTooFarInFuture, // 6086
+
+    #[msg("Close grace period since the schedule completed has not elapsed")]
+    CloseGracePeriodNotElapsed, // 6087
+
+    #[msg("Schedule is not a native SOL schedule")]
+    ScheduleNotNative, // 6088
+
+    #[msg("Program config account does not have the expected layout.")]
+    UnexpectedConfigLayout, // 6089
+}
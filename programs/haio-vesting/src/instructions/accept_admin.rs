@@ -0,0 +1,40 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::state::ProgramConfig;
+use crate::AdminTransferred;
+
+/// Context for the proposed admin accepting the admin role
+#[derive(Accounts)]
+pub struct AcceptAdmin<'info> {
+    /// Proposed admin - must sign to prove control of the new key
+    pub pending_admin: Signer<'info>,
+
+    /// Program configuration account
+    /// Security: The signer is checked against `pending_admin` in the handler
+    #[account(
+        mut,
+        seeds = [PROGRAM_CONFIG_SEED],
+        bump = program_config.bump
+    )]
+    pub program_config: Account<'info, ProgramConfig>,
+}
+
+/// Accept the admin role after the timelock
+pub fn handler(ctx: Context<AcceptAdmin>) -> Result<()> {
+    ctx.accounts.program_config.require_writable()?;
+
+    let current_timestamp = Clock::get()?.unix_timestamp;
+    let new_admin = ctx.accounts.pending_admin.key();
+    let old_admin = ctx.accounts.program_config
+        .accept_admin_transfer(new_admin, current_timestamp)?;
+
+    emit!(AdminTransferred {
+        old_admin,
+        new_admin,
+        timestamp: current_timestamp,
+    });
+
+    msg!("Admin transferred from {} to {}", old_admin, new_admin);
+    Ok(())
+}
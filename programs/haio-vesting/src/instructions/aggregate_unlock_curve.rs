@@ -0,0 +1,44 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::errors::VestingError;
+use crate::state::VestingSchedule;
+use crate::{return_data, ScanSchedules, UnlockCurvePage};
+
+/// Total tokens unlocked across a set of schedules at each requested timestamp
+pub fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, ScanSchedules<'info>>,
+    timestamps: Vec<i64>,
+    offset: u32,
+) -> Result<UnlockCurvePage> {
+    require!(
+        !ctx.remaining_accounts.is_empty() && ctx.remaining_accounts.len() <= MAX_SCAN_BATCH_SIZE,
+        VestingError::InvalidRemainingAccounts
+    );
+    require!(
+        !timestamps.is_empty() && timestamps.len() <= MAX_CURVE_POINTS,
+        VestingError::InvalidBatchSize
+    );
+
+    let mut curve = vec![0u64; timestamps.len()];
+    let mut curve_mint: Option<Pubkey> = None;
+
+    for schedule_info in ctx.remaining_accounts.iter() {
+        let vesting_schedule = Account::<VestingSchedule>::try_from(schedule_info)?;
+        let mint = *curve_mint.get_or_insert(vesting_schedule.mint);
+        require_keys_eq!(vesting_schedule.mint, mint, VestingError::MintMismatch);
+
+        for (point, timestamp) in curve.iter_mut().zip(timestamps.iter()) {
+            *point = point
+                .checked_add(vesting_schedule.calculate_unlocked_amount(*timestamp)?)
+                .ok_or(VestingError::MathOverflow)?;
+        }
+    }
+
+    let page = return_data::paginate(curve, offset)?;
+    Ok(UnlockCurvePage {
+        points: page.items,
+        truncated: page.truncated,
+        next_offset: page.next_offset,
+    })
+}
@@ -0,0 +1,58 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::TokenAccount;
+
+use crate::constants::*;
+use crate::errors::VestingError;
+use crate::state::{ProgramConfig, VestingSchedule, CrankBlockReason};
+use crate::{clock_skew_exceeded, CrankPreflight};
+
+/// Read-only context for crank pre-flight checks
+#[derive(Accounts)]
+pub struct CanCrank<'info> {
+    #[account(
+        seeds = [PROGRAM_CONFIG_SEED],
+        bump = program_config.bump
+    )]
+    pub program_config: Account<'info, ProgramConfig>,
+
+    #[account(
+        seeds = [VESTING_SCHEDULE_SEED, vesting_schedule.schedule_id.to_le_bytes().as_ref()],
+        bump = vesting_schedule.bump
+    )]
+    pub vesting_schedule: Account<'info, VestingSchedule>,
+
+    #[account(address = vesting_schedule.token_vault @ VestingError::InvalidVaultState)]
+    pub vesting_vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(address = vesting_schedule.recipient_token_account @ VestingError::RecipientAccountMismatch)]
+    pub recipient_token_account: InterfaceAccount<'info, TokenAccount>,
+}
+
+/// Pre-flight whether `caller` can crank a schedule right now
+pub fn handler(ctx: Context<CanCrank>, caller: Pubkey) -> Result<CrankPreflight> {
+    let clock = Clock::get()?;
+    let vesting_schedule = &ctx.accounts.vesting_schedule;
+    msg!("Crank pre-flight for schedule {} by {}", vesting_schedule.schedule_id, caller);
+
+    let (reason, transferable) = if ctx.accounts.program_config.read_only {
+        (CrankBlockReason::ReadOnly, 0)
+    } else if clock_skew_exceeded(&ctx.accounts.program_config, &clock)? {
+        (CrankBlockReason::ClockSkewTooHigh, 0)
+    } else if ctx.accounts.program_config.enforce_claim_interval_on_crank
+        && vesting_schedule.check_claim_interval(clock.unix_timestamp).is_err()
+    {
+        (CrankBlockReason::ClaimIntervalNotElapsed, 0)
+    } else {
+        vesting_schedule.crank_block_reason(
+            clock.unix_timestamp,
+            ctx.accounts.vesting_vault.amount,
+            &ctx.accounts.recipient_token_account.owner,
+        )?
+    };
+
+    Ok(CrankPreflight {
+        allowed: reason == CrankBlockReason::None,
+        reason,
+        transferable,
+    })
+}
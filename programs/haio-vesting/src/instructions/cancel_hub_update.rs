@@ -0,0 +1,19 @@
+use anchor_lang::prelude::*;
+
+use crate::{DistributionHubUpdateCancelled, UpdateProgramConfig};
+
+/// Cancel the pending distribution hub change, e.g. after proposing a wrong address
+pub fn handler(ctx: Context<UpdateProgramConfig>) -> Result<()> {
+    ctx.accounts.program_config.require_writable()?;
+
+    let cancelled_hub = ctx.accounts.program_config.cancel_hub_update()?;
+
+    emit!(DistributionHubUpdateCancelled {
+        admin: ctx.accounts.admin.key(),
+        cancelled_hub,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    msg!("Distribution hub change to {} cancelled", cancelled_hub);
+    Ok(())
+}
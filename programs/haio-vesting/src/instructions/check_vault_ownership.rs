@@ -0,0 +1,45 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::TokenAccount;
+
+use crate::constants::*;
+use crate::errors::VestingError;
+use crate::state::VestingSchedule;
+use crate::{return_data, ScanSchedules, ScheduleIdPage};
+
+/// Audit view reporting schedules whose vault is not controlled by the schedule PDA
+pub fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, ScanSchedules<'info>>,
+    offset: u32,
+) -> Result<ScheduleIdPage> {
+    let pair_count = ctx.remaining_accounts.len() / 2;
+    require!(
+        pair_count > 0
+            && pair_count * 2 == ctx.remaining_accounts.len()
+            && pair_count <= MAX_SCAN_BATCH_SIZE,
+        VestingError::InvalidRemainingAccounts
+    );
+
+    let mut inconsistent_schedule_ids = Vec::new();
+    for pair in ctx.remaining_accounts.chunks(2) {
+        let vesting_schedule = Account::<VestingSchedule>::try_from(&pair[0])?;
+        let vesting_vault = InterfaceAccount::<TokenAccount>::try_from(&pair[1])?;
+
+        if vesting_vault.key() != vesting_schedule.token_vault
+            || vesting_vault.owner != vesting_schedule.key()
+        {
+            msg!(
+                "Schedule {} vault {} is owned by {} (expected {})",
+                vesting_schedule.schedule_id, vesting_vault.key(),
+                vesting_vault.owner, vesting_schedule.key()
+            );
+            inconsistent_schedule_ids.push(vesting_schedule.schedule_id);
+        }
+    }
+
+    let page = return_data::paginate(inconsistent_schedule_ids, offset)?;
+    Ok(ScheduleIdPage {
+        schedule_ids: page.items,
+        truncated: page.truncated,
+        next_offset: page.next_offset,
+    })
+}
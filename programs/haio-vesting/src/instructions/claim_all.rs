@@ -0,0 +1,164 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::constants::*;
+use crate::errors::VestingError;
+use crate::state::{ProgramConfig, CrankBlockReason};
+use crate::{
+    emit_crank_skipped, emit_release_checkpoints, extend_if_underfunded, is_duplicate_in_batch, is_vault_closed,
+    load_schedule_and_vault, release_timestamp, skip_if_crank_interval_pending, skip_if_revoked,
+    token_account_balance, transfer_from_vault_checked, TokensReleased,
+};
+
+/// Recipient-signed claim across several schedules of the same recipient token account
+/// Schedules are passed as (vesting_schedule, vesting_vault) pairs in remaining accounts
+#[derive(Accounts)]
+pub struct ClaimAll<'info> {
+    /// Recipient of every schedule in the claim
+    pub recipient: Signer<'info>,
+
+    /// Program configuration - checked for read-only mode, release limits and the release rate window
+    #[account(
+        mut,
+        seeds = [PROGRAM_CONFIG_SEED],
+        bump = program_config.bump
+    )]
+    pub program_config: Account<'info, ProgramConfig>,
+
+    /// Recipient token account shared by every schedule in the claim
+    /// Security: Matched against each schedule's recorded account in the handler
+    #[account(
+        mut,
+        constraint = recipient_token_account.owner == recipient.key() @ VestingError::Unauthorized
+    )]
+    pub recipient_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// Token mint shared by every schedule in the claim
+    #[account(address = recipient_token_account.mint @ VestingError::MintMismatch)]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+/// Claim the transferable amount of several schedules into one recipient token account
+pub fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, ClaimAll<'info>>,
+) -> Result<u64> {
+    ctx.accounts.program_config.require_writable()?;
+    ctx.accounts.program_config.require_not_paused()?;
+
+    let current_timestamp = release_timestamp(&ctx.accounts.program_config)?;
+
+    let schedule_count = ctx.remaining_accounts.len() / 2;
+    require!(
+        schedule_count > 0
+            && schedule_count * 2 == ctx.remaining_accounts.len()
+            && schedule_count <= MAX_CRANK_BATCH_SIZE,
+        VestingError::InvalidRemainingAccounts
+    );
+
+    let recipient = ctx.accounts.recipient.key();
+    let max_release_per_transaction = ctx.accounts.program_config.max_release_per_transaction;
+    let mut total_claimed: u64 = 0;
+    let mut seen_schedules: Vec<Pubkey> = Vec::with_capacity(schedule_count);
+
+    for accounts in ctx.remaining_accounts.chunks(2) {
+        if is_duplicate_in_batch(&mut seen_schedules, &ctx.accounts.program_config, &accounts[0], current_timestamp)?
+            || is_vault_closed(&ctx.accounts.program_config, &accounts[0], current_timestamp)?
+        {
+            continue;
+        }
+
+        let remaining_allowance = if max_release_per_transaction == 0 {
+            u64::MAX
+        } else {
+            max_release_per_transaction.saturating_sub(total_claimed)
+        };
+        if remaining_allowance == 0 {
+            msg!("Batch release cap of {} reached. Stopping.", max_release_per_transaction);
+            break;
+        }
+
+        let (mut vesting_schedule, vesting_vault) = load_schedule_and_vault(&accounts[0], &accounts[1])?;
+
+        require_keys_eq!(vesting_schedule.recipient, recipient, VestingError::Unauthorized);
+        require_keys_eq!(vesting_vault.owner, vesting_schedule.key(), VestingError::VaultAuthorityMismatch);
+        require_keys_eq!(vesting_vault.mint, ctx.accounts.mint.key(), VestingError::MintMismatch);
+        require_keys_eq!(
+            ctx.accounts.recipient_token_account.key(),
+            vesting_schedule.recipient_token_account,
+            VestingError::RecipientAccountMismatch
+        );
+        vesting_schedule.require_undelegated(ctx.accounts.recipient_token_account.delegate.is_some())?;
+
+        if skip_if_revoked(&ctx.accounts.program_config, &vesting_schedule, current_timestamp) {
+            continue;
+        }
+
+        if vesting_schedule.check_claim_interval(current_timestamp).is_err() {
+            msg!("Claim interval for schedule {} has not elapsed. Skipping.", vesting_schedule.schedule_id);
+            emit_crank_skipped(&ctx.accounts.program_config, vesting_schedule.schedule_id, CrankBlockReason::ClaimIntervalNotElapsed, current_timestamp);
+            continue;
+        }
+
+        extend_if_underfunded(&ctx.accounts.program_config, &mut vesting_schedule, vesting_vault.amount, current_timestamp)?;
+
+        let claim_amount = vesting_schedule
+            .get_transferable_amount_capped(current_timestamp, vesting_vault.amount)?
+            .min(remaining_allowance);
+        if claim_amount == 0 {
+            msg!("No transferable amount for schedule {} at timestamp {}. Skipping.",
+                 vesting_schedule.schedule_id, current_timestamp);
+            emit_crank_skipped(&ctx.accounts.program_config, vesting_schedule.schedule_id, CrankBlockReason::NothingVested, current_timestamp);
+            continue;
+        }
+
+        if skip_if_crank_interval_pending(&ctx.accounts.program_config, &vesting_schedule, current_timestamp) {
+            continue;
+        }
+
+        ctx.accounts.program_config.record_outflow(claim_amount, current_timestamp)?;
+        // The deserialized balance is stale after the first claim, so read it from the account data
+        let recipient_info = ctx.accounts.recipient_token_account.to_account_info();
+        let balance_before = token_account_balance(&recipient_info)?;
+        transfer_from_vault_checked(
+            &mut vesting_schedule,
+            &vesting_vault,
+            &ctx.accounts.mint,
+            recipient_info.clone(),
+            ctx.accounts.token_program.to_account_info(),
+            claim_amount,
+        )?;
+        let delivered_amount = token_account_balance(&recipient_info)?
+            .checked_sub(balance_before)
+            .ok_or(VestingError::MathOverflow)?;
+        vesting_schedule.record_release(claim_amount, delivered_amount)?;
+        vesting_schedule.last_claim_timestamp = current_timestamp;
+        vesting_schedule.last_crank_timestamp = current_timestamp;
+        vesting_schedule.record_completion(current_timestamp);
+        ctx.accounts.program_config.record_distribution(delivered_amount)?;
+        ctx.accounts.program_config.record_rounding_dust(vesting_schedule.release_truncation(current_timestamp)?)?;
+        emit_release_checkpoints(&ctx.accounts.program_config, &mut vesting_schedule, current_timestamp);
+
+        // Persist the updated schedule since it was not part of the validated accounts struct
+        vesting_schedule.exit(&crate::ID)?;
+
+        emit!(TokensReleased {
+            schedule_id: vesting_schedule.schedule_id,
+            recipient,
+            mint: vesting_schedule.mint,
+            amount: delivered_amount,
+            source_category: vesting_schedule.source_category.clone(),
+            timestamp: current_timestamp,
+            total_released: vesting_schedule.amount_transferred,
+            keeper_fee: 0,
+        });
+
+        total_claimed = total_claimed
+            .checked_add(claim_amount)
+            .ok_or(VestingError::MathOverflow)?;
+    }
+
+    msg!("Claimed {} tokens across {} schedules", total_claimed, schedule_count);
+    Ok(total_claimed)
+}
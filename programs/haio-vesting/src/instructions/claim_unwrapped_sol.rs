@@ -0,0 +1,153 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{self, CloseAccount, Mint, TokenAccount, TokenInterface};
+
+use crate::constants::*;
+use crate::errors::VestingError;
+use crate::state::{ProgramConfig, VestingSchedule};
+use crate::{
+    emit_release_checkpoints, extend_if_underfunded, release_timestamp, skip_if_revoked, token_account_balance,
+    transfer_from_vault_checked, TokensReleased,
+};
+
+/// Recipient self-claim of a wrapped SOL schedule, paid out as native SOL
+#[derive(Accounts)]
+pub struct ClaimUnwrappedSol<'info> {
+    /// Recipient of the schedule - pays the temporary account rent and receives the SOL
+    #[account(mut)]
+    pub recipient: Signer<'info>,
+
+    /// Program configuration - checked for read-only mode and clock skew, tracks the release rate window
+    #[account(
+        mut,
+        seeds = [PROGRAM_CONFIG_SEED],
+        bump = program_config.bump
+    )]
+    pub program_config: Account<'info, ProgramConfig>,
+
+    /// Vesting schedule being claimed
+    /// Security: Only the recipient may claim, and only for the canonical wSOL mint
+    #[account(
+        mut,
+        seeds = [VESTING_SCHEDULE_SEED, vesting_schedule.schedule_id.to_le_bytes().as_ref()],
+        bump = vesting_schedule.bump,
+        has_one = recipient @ VestingError::Unauthorized,
+        constraint = vesting_schedule.mint == anchor_spl::token::spl_token::native_mint::ID @ VestingError::NotWrappedSol
+    )]
+    pub vesting_schedule: Account<'info, VestingSchedule>,
+
+    /// Vesting vault that holds the tokens for this specific schedule
+    /// Security: Authority must be the vesting_schedule PDA
+    #[account(
+        mut,
+        seeds = [VESTING_VAULT_SEED, vesting_schedule.schedule_id.to_le_bytes().as_ref()],
+        bump,
+        constraint = vesting_vault.owner == vesting_schedule.key() @ VestingError::VaultAuthorityMismatch,
+        constraint = vesting_vault.mint == vesting_schedule.mint @ VestingError::MintMismatch
+    )]
+    pub vesting_vault: InterfaceAccount<'info, TokenAccount>,
+
+    /// Temporary wSOL account, created and closed within this instruction
+    #[account(
+        init,
+        payer = recipient,
+        seeds = [UNWRAP_ACCOUNT_SEED, vesting_schedule.schedule_id.to_le_bytes().as_ref()],
+        bump,
+        token::mint = mint,
+        token::authority = vesting_schedule,
+        token::token_program = token_program
+    )]
+    pub unwrap_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// Wrapped SOL mint
+    #[account(address = vesting_schedule.mint @ VestingError::MintMismatch)]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+/// Claim the transferable amount of a wrapped SOL schedule as native SOL
+pub fn handler(ctx: Context<ClaimUnwrappedSol>) -> Result<()> {
+    ctx.accounts.program_config.require_writable()?;
+    ctx.accounts.program_config.require_not_paused()?;
+
+    let current_timestamp = release_timestamp(&ctx.accounts.program_config)?;
+    let vesting_schedule = &ctx.accounts.vesting_schedule;
+    require!(vesting_schedule.is_initialized, VestingError::InvalidVestingScheduleData);
+    if skip_if_revoked(&ctx.accounts.program_config, vesting_schedule, current_timestamp) {
+        return Ok(());
+    }
+    // Unwrapping pays out to the recipient wallet instead of the recorded token account
+    vesting_schedule.require_transferable_to(&ctx.accounts.unwrap_account.key())?;
+    vesting_schedule.check_claim_interval(current_timestamp)?;
+
+    extend_if_underfunded(
+        &ctx.accounts.program_config,
+        &mut ctx.accounts.vesting_schedule,
+        ctx.accounts.vesting_vault.amount,
+        current_timestamp,
+    )?;
+
+    let vesting_schedule = &ctx.accounts.vesting_schedule;
+    let transfer_amount = vesting_schedule
+        .get_transferable_amount_capped(current_timestamp, ctx.accounts.vesting_vault.amount)?;
+    require!(transfer_amount > 0, VestingError::NoTransferableAmount);
+
+    ctx.accounts.program_config.record_outflow(transfer_amount, current_timestamp)?;
+    let unwrap_info = ctx.accounts.unwrap_account.to_account_info();
+    let balance_before = ctx.accounts.unwrap_account.amount;
+    transfer_from_vault_checked(
+        &mut ctx.accounts.vesting_schedule,
+        &ctx.accounts.vesting_vault,
+        &ctx.accounts.mint,
+        unwrap_info.clone(),
+        ctx.accounts.token_program.to_account_info(),
+        transfer_amount,
+    )?;
+    // Measured before the close below, which empties the account
+    let delivered_amount = token_account_balance(&unwrap_info)?
+        .checked_sub(balance_before)
+        .ok_or(VestingError::MathOverflow)?;
+
+    // Closing a wSOL account releases its lamports, unwrapping the tokens to native SOL
+    let schedule_id_bytes = ctx.accounts.vesting_schedule.schedule_id.to_le_bytes();
+    let signer_seeds = &[
+        VESTING_SCHEDULE_SEED,
+        schedule_id_bytes.as_ref(),
+        &[ctx.accounts.vesting_schedule.bump],
+    ];
+    let signer = &[&signer_seeds[..]];
+    let cpi_accounts = CloseAccount {
+        account: ctx.accounts.unwrap_account.to_account_info(),
+        destination: ctx.accounts.recipient.to_account_info(),
+        authority: ctx.accounts.vesting_schedule.to_account_info(),
+    };
+    token_interface::close_account(CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        cpi_accounts,
+        signer,
+    ))?;
+
+    let vesting_schedule = &mut ctx.accounts.vesting_schedule;
+    vesting_schedule.record_release(transfer_amount, delivered_amount)?;
+    vesting_schedule.last_claim_timestamp = current_timestamp;
+    vesting_schedule.record_completion(current_timestamp);
+    ctx.accounts.program_config.record_distribution(delivered_amount)?;
+    ctx.accounts.program_config.record_rounding_dust(vesting_schedule.release_truncation(current_timestamp)?)?;
+    emit_release_checkpoints(&ctx.accounts.program_config, vesting_schedule, current_timestamp);
+
+    emit!(TokensReleased {
+        schedule_id: vesting_schedule.schedule_id,
+        recipient: vesting_schedule.recipient,
+        mint: vesting_schedule.mint,
+        amount: delivered_amount,
+        source_category: vesting_schedule.source_category.clone(),
+        timestamp: current_timestamp,
+        total_released: vesting_schedule.amount_transferred,
+        keeper_fee: 0,
+    });
+
+    msg!("Released {} lamports of wSOL from schedule {} as native SOL", delivered_amount, vesting_schedule.schedule_id);
+    Ok(())
+}
@@ -0,0 +1,127 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::constants::*;
+use crate::errors::VestingError;
+use crate::state::{ProgramConfig, VestingSchedule};
+use crate::{emit_release_checkpoints, extend_if_underfunded, release_timestamp, skip_if_revoked, token_account_balance, transfer_from_vault_checked, TokensReleased};
+
+/// Recipient self-claim of a schedule's vested tokens
+#[derive(Accounts)]
+pub struct ClaimVested<'info> {
+    /// Recipient of the schedule - decides when to claim
+    pub recipient: Signer<'info>,
+
+    /// Program configuration - checked for read-only mode and clock skew, tracks the release rate window
+    #[account(
+        mut,
+        seeds = [PROGRAM_CONFIG_SEED],
+        bump = program_config.bump
+    )]
+    pub program_config: Account<'info, ProgramConfig>,
+
+    /// Vesting schedule being claimed
+    /// Security: Only the recipient may claim
+    #[account(
+        mut,
+        seeds = [VESTING_SCHEDULE_SEED, vesting_schedule.schedule_id.to_le_bytes().as_ref()],
+        bump = vesting_schedule.bump,
+        has_one = recipient @ VestingError::Unauthorized
+    )]
+    pub vesting_schedule: Account<'info, VestingSchedule>,
+
+    /// Vesting vault that holds the tokens for this specific schedule
+    /// Security: Authority must be the vesting_schedule PDA
+    #[account(
+        mut,
+        seeds = [VESTING_VAULT_SEED, vesting_schedule.schedule_id.to_le_bytes().as_ref()],
+        bump,
+        constraint = vesting_vault.owner == vesting_schedule.key() @ VestingError::VaultAuthorityMismatch,
+        constraint = vesting_vault.mint == vesting_schedule.mint @ VestingError::MintMismatch
+    )]
+    pub vesting_vault: InterfaceAccount<'info, TokenAccount>,
+
+    /// Recipient token account that receives the vested tokens
+    /// Security: Must be the account stored in vesting_schedule and still owned by the recipient
+    #[account(
+        mut,
+        constraint = recipient_token_account.key() == vesting_schedule.recipient_token_account @ VestingError::RecipientAccountMismatch,
+        constraint = recipient_token_account.owner == vesting_schedule.recipient @ VestingError::RecipientAccountOwnerMismatch,
+        constraint = recipient_token_account.mint == vesting_schedule.mint @ VestingError::RecipientAccountMintMismatch
+    )]
+    pub recipient_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// Token mint of the schedule
+    #[account(address = vesting_schedule.mint @ VestingError::MintMismatch)]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+/// Transfer everything currently claimable to the recipient token account
+/// Emits a zero-amount `TokensReleased` and succeeds when nothing is claimable yet
+pub fn handler(ctx: Context<ClaimVested>) -> Result<()> {
+    ctx.accounts.program_config.require_writable()?;
+    ctx.accounts.program_config.require_not_paused()?;
+
+    let current_timestamp = release_timestamp(&ctx.accounts.program_config)?;
+    let vesting_schedule = &ctx.accounts.vesting_schedule;
+    require!(vesting_schedule.is_initialized, VestingError::InvalidVestingScheduleData);
+    if skip_if_revoked(&ctx.accounts.program_config, vesting_schedule, current_timestamp) {
+        return Ok(());
+    }
+    vesting_schedule.require_undelegated(ctx.accounts.recipient_token_account.delegate.is_some())?;
+    vesting_schedule.check_claim_interval(current_timestamp)?;
+
+    extend_if_underfunded(
+        &ctx.accounts.program_config,
+        &mut ctx.accounts.vesting_schedule,
+        ctx.accounts.vesting_vault.amount,
+        current_timestamp,
+    )?;
+
+    let transfer_amount = ctx.accounts.vesting_schedule
+        .get_transferable_amount_capped(current_timestamp, ctx.accounts.vesting_vault.amount)?;
+
+    let mut delivered_amount = 0;
+    if transfer_amount > 0 {
+        ctx.accounts.program_config.record_outflow(transfer_amount, current_timestamp)?;
+        let recipient_info = ctx.accounts.recipient_token_account.to_account_info();
+        let balance_before = ctx.accounts.recipient_token_account.amount;
+        transfer_from_vault_checked(
+            &mut ctx.accounts.vesting_schedule,
+            &ctx.accounts.vesting_vault,
+            &ctx.accounts.mint,
+            recipient_info.clone(),
+            ctx.accounts.token_program.to_account_info(),
+            transfer_amount,
+        )?;
+        delivered_amount = token_account_balance(&recipient_info)?
+            .checked_sub(balance_before)
+            .ok_or(VestingError::MathOverflow)?;
+
+        let vesting_schedule = &mut ctx.accounts.vesting_schedule;
+        vesting_schedule.record_release(transfer_amount, delivered_amount)?;
+        vesting_schedule.last_claim_timestamp = current_timestamp;
+        vesting_schedule.record_completion(current_timestamp);
+        ctx.accounts.program_config.record_distribution(delivered_amount)?;
+        ctx.accounts.program_config.record_rounding_dust(vesting_schedule.release_truncation(current_timestamp)?)?;
+        emit_release_checkpoints(&ctx.accounts.program_config, vesting_schedule, current_timestamp);
+    }
+
+    let vesting_schedule = &ctx.accounts.vesting_schedule;
+    emit!(TokensReleased {
+        schedule_id: vesting_schedule.schedule_id,
+        recipient: vesting_schedule.recipient,
+        mint: vesting_schedule.mint,
+        amount: delivered_amount,
+        source_category: vesting_schedule.source_category.clone(),
+        timestamp: current_timestamp,
+        total_released: vesting_schedule.amount_transferred,
+        keeper_fee: 0,
+    });
+
+    msg!("Recipient claimed {} tokens from schedule {}. Total released: {}",
+         delivered_amount, vesting_schedule.schedule_id, vesting_schedule.amount_transferred);
+    Ok(())
+}
@@ -0,0 +1,163 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::sysvar::instructions as instructions_sysvar;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::constants::*;
+use crate::errors::VestingError;
+use crate::state::{ProgramConfig, VestingSchedule};
+use crate::{
+    ed25519, emit_release_checkpoints, extend_if_underfunded, release_timestamp, skip_if_revoked,
+    token_account_balance, transfer_from_vault_checked, ClaimedWithSignature, TokensReleased,
+};
+
+/// Relayed claim context authorized by an off-chain recipient signature
+/// The transaction must place an Ed25519 program instruction immediately before this one,
+/// verifying the recipient's signature over the claim message
+#[derive(Accounts)]
+pub struct ClaimWithSignature<'info> {
+    /// Relayer submitting the claim - pays fees but gains no authority over the tokens
+    pub relayer: Signer<'info>,
+
+    /// Program configuration - checked for read-only mode, tracks the release rate window
+    #[account(
+        mut,
+        seeds = [PROGRAM_CONFIG_SEED],
+        bump = program_config.bump
+    )]
+    pub program_config: Account<'info, ProgramConfig>,
+
+    /// Vesting schedule being claimed
+    #[account(
+        mut,
+        seeds = [VESTING_SCHEDULE_SEED, vesting_schedule.schedule_id.to_le_bytes().as_ref()],
+        bump = vesting_schedule.bump
+    )]
+    pub vesting_schedule: Account<'info, VestingSchedule>,
+
+    /// Vesting vault that holds the tokens for this specific schedule
+    /// Security: Authority must be the vesting_schedule PDA
+    #[account(
+        mut,
+        seeds = [VESTING_VAULT_SEED, vesting_schedule.schedule_id.to_le_bytes().as_ref()],
+        bump,
+        constraint = vesting_vault.owner == vesting_schedule.key() @ VestingError::VaultAuthorityMismatch,
+        constraint = vesting_vault.mint == vesting_schedule.mint @ VestingError::MintMismatch
+    )]
+    pub vesting_vault: InterfaceAccount<'info, TokenAccount>,
+
+    /// Destination named in the signed authorization
+    /// Security: Must hold the schedule's mint
+    #[account(
+        mut,
+        constraint = destination_token_account.mint == vesting_schedule.mint @ VestingError::RecipientAccountMintMismatch
+    )]
+    pub destination_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// Token mint of the schedule
+    #[account(address = vesting_schedule.mint @ VestingError::MintMismatch)]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    /// CHECK: Instructions sysvar, used to inspect the preceding Ed25519 instruction
+    #[account(address = instructions_sysvar::ID)]
+    pub instructions: UncheckedAccount<'info>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+/// Claim vested tokens on a recipient's behalf using an off-chain Ed25519 authorization
+pub fn handler(ctx: Context<ClaimWithSignature>, expiry: i64) -> Result<()> {
+    ctx.accounts.program_config.require_writable()?;
+    ctx.accounts.program_config.require_not_paused()?;
+
+    let current_timestamp = release_timestamp(&ctx.accounts.program_config)?;
+    require!(current_timestamp <= expiry, VestingError::ClaimAuthorizationExpired);
+
+    let vesting_schedule = &ctx.accounts.vesting_schedule;
+    require!(vesting_schedule.is_initialized, VestingError::InvalidVestingScheduleData);
+    if skip_if_revoked(&ctx.accounts.program_config, vesting_schedule, current_timestamp) {
+        return Ok(());
+    }
+
+    // Inspect the Ed25519 instruction placed directly before this one
+    let instructions_info = ctx.accounts.instructions.to_account_info();
+    let current_index = instructions_sysvar::load_current_index_checked(&instructions_info)?;
+    require!(current_index > 0, VestingError::InvalidSignatureInstruction);
+    let signature_ix = instructions_sysvar::load_instruction_at_checked(
+        (current_index - 1) as usize,
+        &instructions_info,
+    )?;
+
+    let expected_message = ed25519::build_claim_message(
+        &vesting_schedule.key(),
+        &ctx.accounts.destination_token_account.key(),
+        vesting_schedule.amount_transferred,
+        expiry,
+    );
+    ed25519::verify_ed25519_instruction(&signature_ix, &vesting_schedule.recipient, &expected_message)?;
+
+    vesting_schedule.require_transferable_to(&ctx.accounts.destination_token_account.key())?;
+    vesting_schedule.require_undelegated(ctx.accounts.destination_token_account.delegate.is_some())?;
+    vesting_schedule.check_claim_interval(current_timestamp)?;
+
+    extend_if_underfunded(
+        &ctx.accounts.program_config,
+        &mut ctx.accounts.vesting_schedule,
+        ctx.accounts.vesting_vault.amount,
+        current_timestamp,
+    )?;
+
+    let vesting_schedule = &ctx.accounts.vesting_schedule;
+    let transfer_amount = vesting_schedule
+        .get_transferable_amount_capped(current_timestamp, ctx.accounts.vesting_vault.amount)?;
+
+    if transfer_amount == 0 {
+        msg!("No transferable amount for schedule {} at timestamp {}.",
+             vesting_schedule.schedule_id, current_timestamp);
+        return Ok(());
+    }
+
+    ctx.accounts.program_config.record_outflow(transfer_amount, current_timestamp)?;
+    let destination_info = ctx.accounts.destination_token_account.to_account_info();
+    let balance_before = ctx.accounts.destination_token_account.amount;
+    transfer_from_vault_checked(
+        &mut ctx.accounts.vesting_schedule,
+        &ctx.accounts.vesting_vault,
+        &ctx.accounts.mint,
+        destination_info.clone(),
+        ctx.accounts.token_program.to_account_info(),
+        transfer_amount,
+    )?;
+    let delivered_amount = token_account_balance(&destination_info)?
+        .checked_sub(balance_before)
+        .ok_or(VestingError::MathOverflow)?;
+
+    let vesting_schedule = &mut ctx.accounts.vesting_schedule;
+    vesting_schedule.record_release(transfer_amount, delivered_amount)?;
+    vesting_schedule.last_claim_timestamp = current_timestamp;
+    vesting_schedule.record_completion(current_timestamp);
+    ctx.accounts.program_config.record_distribution(delivered_amount)?;
+    ctx.accounts.program_config.record_rounding_dust(vesting_schedule.release_truncation(current_timestamp)?)?;
+    emit_release_checkpoints(&ctx.accounts.program_config, vesting_schedule, current_timestamp);
+
+    emit!(TokensReleased {
+        schedule_id: vesting_schedule.schedule_id,
+        recipient: vesting_schedule.recipient,
+        mint: vesting_schedule.mint,
+        amount: delivered_amount,
+        source_category: vesting_schedule.source_category.clone(),
+        timestamp: current_timestamp,
+        total_released: vesting_schedule.amount_transferred,
+        keeper_fee: 0,
+    });
+
+    emit!(ClaimedWithSignature {
+        schedule_id: vesting_schedule.schedule_id,
+        recipient: vesting_schedule.recipient,
+        relayer: ctx.accounts.relayer.key(),
+        destination: ctx.accounts.destination_token_account.key(),
+        amount: delivered_amount,
+        timestamp: current_timestamp,
+    });
+
+    Ok(())
+}
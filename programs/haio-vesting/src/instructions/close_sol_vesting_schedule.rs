@@ -0,0 +1,79 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::errors::VestingError;
+use crate::state::{ProgramConfig, VestingSchedule};
+use crate::{retire_schedule, VestingScheduleClosed};
+
+/// Closing of a completed native SOL schedule
+/// The vault was already emptied by the final release (or revocation), so only the schedule
+/// account's rent is reclaimed here, subject to the same grace and archive rules as token schedules
+#[derive(Accounts)]
+pub struct CloseSolVestingSchedule<'info> {
+    /// Receives the schedule account's rent
+    #[account(mut)]
+    pub beneficiary: Signer<'info>,
+
+    /// Program configuration - supplies the close grace and archive retention periods
+    #[account(
+        seeds = [PROGRAM_CONFIG_SEED],
+        bump = program_config.bump
+    )]
+    pub program_config: Account<'info, ProgramConfig>,
+
+    /// Native vesting schedule to close
+    /// Security: Must be fully processed with its vault already emptied
+    #[account(
+        mut,
+        seeds = [VESTING_SCHEDULE_SEED, vesting_schedule.schedule_id.to_le_bytes().as_ref()],
+        bump = vesting_schedule.bump,
+        constraint = vesting_schedule.is_native @ VestingError::ScheduleNotNative,
+        constraint = vesting_schedule.is_fully_processed() @ VestingError::ScheduleNotFullyVested,
+        constraint = vesting_schedule.vault_closed @ VestingError::VaultNotEmpty,
+        constraint = !vesting_schedule.closed @ VestingError::ScheduleAlreadyArchived
+    )]
+    pub vesting_schedule: Account<'info, VestingSchedule>,
+}
+
+/// Delete (or archive) a completed native schedule once the close grace period has passed
+pub fn handler(ctx: Context<CloseSolVestingSchedule>) -> Result<()> {
+    let schedule_id = ctx.accounts.vesting_schedule.schedule_id;
+    let current_timestamp = Clock::get()?.unix_timestamp;
+
+    // Completed schedules stay open for the configured dispute window
+    require!(
+        ctx.accounts.vesting_schedule.close_grace_elapsed(
+            ctx.accounts.program_config.close_grace_period,
+            current_timestamp,
+        ),
+        VestingError::CloseGracePeriodNotElapsed
+    );
+
+    let schedule_lamports = ctx.accounts.vesting_schedule.to_account_info().lamports();
+    let archived = retire_schedule(
+        &ctx.accounts.program_config,
+        &mut ctx.accounts.vesting_schedule,
+        ctx.accounts.beneficiary.to_account_info(),
+    )?;
+
+    emit!(VestingScheduleClosed {
+        schedule_id,
+        beneficiary: ctx.accounts.beneficiary.key(),
+        vault_lamports: 0,
+        // An archived schedule keeps its account and rent until it is purged
+        schedule_lamports: if archived.is_some() { 0 } else { schedule_lamports },
+        amount_transferred: ctx.accounts.vesting_schedule.amount_transferred,
+        archived: archived.is_some(),
+        timestamp: current_timestamp,
+    });
+
+    match archived {
+        Some(purgeable_at) => msg!(
+            "Archived native schedule {}, purgeable at {}", schedule_id, purgeable_at
+        ),
+        None => msg!(
+            "Closed native schedule {}. Rent returned to {}.", schedule_id, ctx.accounts.beneficiary.key()
+        ),
+    }
+    Ok(())
+}
@@ -0,0 +1,76 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{TokenAccount, TokenInterface};
+
+use crate::constants::*;
+use crate::errors::VestingError;
+use crate::state::{MintScheduleCount, VestingSchedule};
+use crate::{close_vault, VaultClosed};
+
+/// Closing of a completed schedule's vault that keeps the schedule account
+/// Preconditions match `CloseVestingSchedule`: fully processed schedule, empty vault
+#[derive(Accounts)]
+pub struct CloseVaultOnly<'info> {
+    /// Receives the vault rent
+    #[account(mut)]
+    pub beneficiary: Signer<'info>,
+
+    /// The vesting schedule kept as a permanent record
+    /// Security: Must be fully vested, not archived and still have its vault
+    #[account(
+        mut,
+        seeds = [VESTING_SCHEDULE_SEED, vesting_schedule.schedule_id.to_le_bytes().as_ref()],
+        bump = vesting_schedule.bump,
+        constraint = vesting_schedule.is_fully_processed() @ VestingError::ScheduleNotFullyVested,
+        constraint = !vesting_schedule.closed @ VestingError::ScheduleAlreadyArchived,
+        constraint = !vesting_schedule.vault_closed @ VestingError::InvalidVaultState
+    )]
+    pub vesting_schedule: Account<'info, VestingSchedule>,
+
+    /// The vesting vault to be closed.
+    /// Security: Must be empty and belong to the vesting schedule.
+    #[account(
+        mut,
+        seeds = [VESTING_VAULT_SEED, vesting_schedule.schedule_id.to_le_bytes().as_ref()],
+        bump,
+        constraint = vesting_vault.amount == 0 @ VestingError::VaultNotEmpty,
+        constraint = vesting_vault.owner == vesting_schedule.key() @ VestingError::VaultAuthorityMismatch
+    )]
+    pub vesting_vault: InterfaceAccount<'info, TokenAccount>,
+
+    /// Open-schedule counter of the schedule's mint, decremented since the schedule is finished
+    #[account(
+        mut,
+        seeds = [MINT_SCHEDULE_COUNT_SEED, vesting_schedule.mint.as_ref()],
+        bump = mint_schedule_count.bump
+    )]
+    pub mint_schedule_count: Account<'info, MintScheduleCount>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+/// Close the vault, return its rent to `beneficiary` and flag the schedule `vault_closed`
+/// Unlike an archived schedule, the record is never purgeable
+pub fn handler(ctx: Context<CloseVaultOnly>) -> Result<()> {
+    let vault_lamports = ctx.accounts.vesting_vault.to_account_info().lamports();
+
+    close_vault(
+        &ctx.accounts.vesting_schedule,
+        &ctx.accounts.vesting_vault,
+        ctx.accounts.beneficiary.to_account_info(),
+        ctx.accounts.token_program.to_account_info(),
+    )?;
+    ctx.accounts.vesting_schedule.vault_closed = true;
+    ctx.accounts.mint_schedule_count.record_closed();
+
+    let schedule_id = ctx.accounts.vesting_schedule.schedule_id;
+    emit!(VaultClosed {
+        schedule_id,
+        beneficiary: ctx.accounts.beneficiary.key(),
+        vault_lamports,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    msg!("Closed the vault of schedule {} and kept the schedule. Vault rent returned to {}.",
+         schedule_id, ctx.accounts.beneficiary.key());
+    Ok(())
+}
@@ -0,0 +1,104 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{TokenAccount, TokenInterface};
+
+use crate::constants::*;
+use crate::errors::VestingError;
+use crate::state::{ProgramConfig, VestingSchedule, MintScheduleCount};
+use crate::close_completed_schedule;
+
+/// Context for closing a fully vested and empty schedule
+/// Security: Strict validation ensures only completed schedules can be closed
+///
+/// Close preconditions:
+/// - Every release is sent directly to the schedule's `recipient_token_account`, so
+///   `amount_transferred == total_amount` means the recipient has received the full allocation
+///   (less any Token-2022 transfer fees, tracked in `transfer_fees_paid`)
+/// - The vault must be empty, so no tokens can be stranded by closing it
+/// - Token-2022 refuses to close an account holding withheld transfer fees, so for
+///   transfer-fee mints the vault's fees must be harvested to the mint first (permissionless)
+/// - With `archive_retention_period` set the schedule account is kept as an archived record
+#[derive(Accounts)]
+pub struct CloseVestingSchedule<'info> {
+    /// The account that will receive the rent back, typically the original admin or the recipient.
+    /// Must be the signer of the transaction.
+    #[account(mut)]
+    pub beneficiary: Signer<'info>,
+
+    /// Program configuration - supplies the archive retention period
+    #[account(
+        seeds = [PROGRAM_CONFIG_SEED],
+        bump = program_config.bump
+    )]
+    pub program_config: Account<'info, ProgramConfig>,
+
+    /// The vesting schedule to be closed.
+    /// Security: Must be fully vested to be closed.
+    #[account(
+        mut,
+        seeds = [VESTING_SCHEDULE_SEED, vesting_schedule.schedule_id.to_le_bytes().as_ref()],
+        bump = vesting_schedule.bump,
+        constraint = vesting_schedule.is_fully_processed() @ VestingError::ScheduleNotFullyVested,
+        constraint = !vesting_schedule.closed @ VestingError::ScheduleAlreadyArchived
+    )]
+    pub vesting_schedule: Account<'info, VestingSchedule>,
+
+    /// The vesting vault to be closed.
+    /// Security: Must be empty and belong to the vesting schedule.
+    #[account(
+        mut,
+        seeds = [VESTING_VAULT_SEED, vesting_schedule.schedule_id.to_le_bytes().as_ref()],
+        bump,
+        constraint = vesting_vault.amount == 0 @ VestingError::VaultNotEmpty,
+        constraint = vesting_vault.owner == vesting_schedule.key() @ VestingError::VaultAuthorityMismatch
+    )]
+    pub vesting_vault: InterfaceAccount<'info, TokenAccount>,
+
+    /// Open-schedule counter of the schedule's mint, decremented on close
+    #[account(
+        mut,
+        seeds = [MINT_SCHEDULE_COUNT_SEED, vesting_schedule.mint.as_ref()],
+        bump = mint_schedule_count.bump
+    )]
+    pub mint_schedule_count: Account<'info, MintScheduleCount>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+/// Close a vesting schedule and its vault after completion
+pub fn handler(ctx: Context<CloseVestingSchedule>) -> Result<()> {
+    let schedule_id = ctx.accounts.vesting_schedule.schedule_id;
+
+    // Completed schedules stay open for the configured dispute window
+    require!(
+        ctx.accounts.vesting_schedule.close_grace_elapsed(
+            ctx.accounts.program_config.close_grace_period,
+            Clock::get()?.unix_timestamp,
+        ),
+        VestingError::CloseGracePeriodNotElapsed
+    );
+
+    let archived = close_completed_schedule(
+        &ctx.accounts.program_config,
+        &mut ctx.accounts.vesting_schedule,
+        &ctx.accounts.vesting_vault,
+        ctx.accounts.beneficiary.to_account_info(),
+        ctx.accounts.token_program.to_account_info(),
+    )?;
+    ctx.accounts.mint_schedule_count.record_closed();
+
+    match archived {
+        Some(purgeable_at) => msg!(
+            "Archived vesting schedule {} and closed its vault, purgeable at {}. Vault rent returned to {}.",
+            schedule_id,
+            purgeable_at,
+            ctx.accounts.beneficiary.key()
+        ),
+        None => msg!(
+            "Successfully closed vesting schedule {} and its vault. Rent returned to {}.",
+            schedule_id,
+            ctx.accounts.beneficiary.key()
+        ),
+    }
+
+    Ok(())
+}
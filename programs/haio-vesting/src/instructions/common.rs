@@ -0,0 +1,230 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::constants::*;
+use crate::errors::VestingError;
+use crate::state::{ProgramConfig, VestingSchedule, PrimaryScheduleIndex, MintScheduleCount};
+
+// Account contexts shared by several instructions; each instruction's own context lives in its module
+
+#[derive(Accounts)]
+#[instruction(schedule_id: u64)]
+pub struct CreateVestingSchedule<'info> {
+    /// Admin signer, or a delegate holding CAPABILITY_CREATE
+    /// Funds the deposit and is recorded as the schedule's depositor
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    /// Program configuration account
+    /// Security: Validates the signer's capability
+    #[account(
+        mut,
+        seeds = [PROGRAM_CONFIG_SEED],
+        bump = program_config.bump,
+        constraint = program_config.has_capability(&admin.key(), CAPABILITY_CREATE) @ VestingError::MissingCapability
+    )]
+    pub program_config: Account<'info, ProgramConfig>,
+
+    /// Vesting schedule PDA - deterministic address based on schedule_id
+    /// Security: Schedule ID must follow sequential order to prevent gaps
+    #[account(
+        init,
+        payer = admin,
+        space = DISCRIMINATOR_SIZE + VESTING_SCHEDULE_LEN,
+        seeds = [VESTING_SCHEDULE_SEED, schedule_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub vesting_schedule: Account<'info, VestingSchedule>,
+
+    /// Token mint account
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    /// Source token account from which tokens are deposited
+    /// Security: Must be owned by admin and have correct mint
+    #[account(
+        mut,
+        constraint = depositor_token_account.mint == mint.key() @ VestingError::MintMismatch,
+        constraint = depositor_token_account.owner == admin.key() @ VestingError::Unauthorized
+    )]
+    pub depositor_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// Recipient token account that will receive the vested tokens
+    /// Security: Must have correct mint (owner validation done in instruction)
+    #[account(
+        constraint = recipient_token_account.mint == mint.key() @ VestingError::RecipientAccountMintMismatch
+    )]
+    pub recipient_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// Vesting vault PDA that holds the tokens
+    /// Security: Authority is set to vesting_schedule PDA, preventing unauthorized access
+    #[account(
+        init,
+        payer = admin,
+        seeds = [VESTING_VAULT_SEED, schedule_id.to_le_bytes().as_ref()],
+        bump,
+        token::mint = mint,
+        token::authority = vesting_schedule,
+        token::token_program = token_program
+    )]
+    pub vesting_vault: InterfaceAccount<'info, TokenAccount>,
+
+    /// Primary schedule index for (recipient, mint), written only by the pair's first schedule
+    /// Keyed by the recipient token account owner, which the handler requires to be the recipient
+    #[account(
+        init_if_needed,
+        payer = admin,
+        space = DISCRIMINATOR_SIZE + PRIMARY_SCHEDULE_INDEX_LEN,
+        seeds = [PRIMARY_SCHEDULE_SEED, recipient_token_account.owner.as_ref(), mint.key().as_ref()],
+        bump
+    )]
+    pub primary_schedule_index: Box<Account<'info, PrimaryScheduleIndex>>,
+
+    /// Open-schedule counter of the mint, checked against `max_schedules_per_mint`
+    #[account(
+        init_if_needed,
+        payer = admin,
+        space = DISCRIMINATOR_SIZE + MINT_SCHEDULE_COUNT_LEN,
+        seeds = [MINT_SCHEDULE_COUNT_SEED, mint.key().as_ref()],
+        bump
+    )]
+    pub mint_schedule_count: Box<Account<'info, MintScheduleCount>>,
+
+    /// Creation fee token account, required only while `creation_fee_bps` is set
+    /// Security: Must be owned by the configured `fee_destination` and hold the schedule's mint
+    /// (checked in the handler)
+    #[account(mut)]
+    pub fee_destination: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+/// Permissionless batch crank context
+/// Schedules are passed as (vesting_schedule, vesting_vault, recipient_token_account, mint)
+/// entries in remaining accounts and each one is validated with the same rules as the
+/// single-schedule crank. Every schedule in the batch must use `token_program`
+#[derive(Accounts)]
+pub struct CrankVestingSchedulesBatch<'info> {
+    /// Program configuration - release policy and the release rate window
+    #[account(
+        mut,
+        seeds = [PROGRAM_CONFIG_SEED],
+        bump = program_config.bump
+    )]
+    pub program_config: Account<'info, ProgramConfig>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+/// Context for admin-only updates to program configuration
+#[derive(Accounts)]
+pub struct UpdateProgramConfig<'info> {
+    /// Admin signer - only admin can change program configuration
+    pub admin: Signer<'info>,
+
+    /// Program configuration account
+    /// Security: Validates admin authority
+    #[account(
+        mut,
+        seeds = [PROGRAM_CONFIG_SEED],
+        bump = program_config.bump,
+        has_one = admin @ VestingError::Unauthorized
+    )]
+    pub program_config: Account<'info, ProgramConfig>,
+}
+
+/// Context for config updates delegable with CAPABILITY_CONFIG
+#[derive(Accounts)]
+pub struct UpdateConfigPolicy<'info> {
+    /// Admin, or a delegate holding CAPABILITY_CONFIG
+    pub authority: Signer<'info>,
+
+    /// Program configuration account
+    /// Security: Validates the signer's capability
+    #[account(
+        mut,
+        seeds = [PROGRAM_CONFIG_SEED],
+        bump = program_config.bump,
+        constraint = program_config.has_capability(&authority.key(), CAPABILITY_CONFIG) @ VestingError::MissingCapability
+    )]
+    pub program_config: Account<'info, ProgramConfig>,
+}
+
+/// Context for crank policy updates delegable with CAPABILITY_CRANK_MANAGE
+#[derive(Accounts)]
+pub struct UpdateCrankPolicy<'info> {
+    /// Admin, or a delegate holding CAPABILITY_CRANK_MANAGE
+    pub authority: Signer<'info>,
+
+    /// Program configuration account
+    /// Security: Validates the signer's capability
+    #[account(
+        mut,
+        seeds = [PROGRAM_CONFIG_SEED],
+        bump = program_config.bump,
+        constraint = program_config.has_capability(&authority.key(), CAPABILITY_CRANK_MANAGE) @ VestingError::MissingCapability
+    )]
+    pub program_config: Account<'info, ProgramConfig>,
+}
+
+/// Read-only context for views that inspect schedules passed through remaining accounts
+#[derive(Accounts)]
+pub struct ScanSchedules<'info> {
+    /// Program configuration account
+    #[account(
+        seeds = [PROGRAM_CONFIG_SEED],
+        bump = program_config.bump
+    )]
+    pub program_config: Account<'info, ProgramConfig>,
+}
+
+/// Read-only context for program configuration views
+#[derive(Accounts)]
+pub struct ViewProgramConfig<'info> {
+    #[account(
+        seeds = [PROGRAM_CONFIG_SEED],
+        bump = program_config.bump
+    )]
+    pub program_config: Account<'info, ProgramConfig>,
+}
+
+/// Read-only context for single-schedule views
+#[derive(Accounts)]
+pub struct ViewVestingSchedule<'info> {
+    #[account(
+        seeds = [VESTING_SCHEDULE_SEED, vesting_schedule.schedule_id.to_le_bytes().as_ref()],
+        bump = vesting_schedule.bump
+    )]
+    pub vesting_schedule: Account<'info, VestingSchedule>,
+}
+
+/// Admin context for replacing a schedule's recipient token account under a timelock
+#[derive(Accounts)]
+pub struct UpdateRecipientTokenAccount<'info> {
+    pub admin: Signer<'info>,
+
+    /// Program configuration account
+    /// Security: Validates admin authority
+    #[account(
+        seeds = [PROGRAM_CONFIG_SEED],
+        bump = program_config.bump,
+        has_one = admin @ VestingError::Unauthorized
+    )]
+    pub program_config: Account<'info, ProgramConfig>,
+
+    #[account(
+        mut,
+        seeds = [VESTING_SCHEDULE_SEED, vesting_schedule.schedule_id.to_le_bytes().as_ref()],
+        bump = vesting_schedule.bump
+    )]
+    pub vesting_schedule: Account<'info, VestingSchedule>,
+
+    /// Replacement token account
+    /// Security: Must belong to the schedule's recipient and hold the schedule's mint
+    #[account(
+        constraint = new_recipient_token_account.owner == vesting_schedule.recipient @ VestingError::RecipientAccountOwnerMismatch,
+        constraint = new_recipient_token_account.mint == vesting_schedule.mint @ VestingError::RecipientAccountMintMismatch
+    )]
+    pub new_recipient_token_account: InterfaceAccount<'info, TokenAccount>,
+}
@@ -0,0 +1,25 @@
+use anchor_lang::prelude::*;
+
+use crate::{RecipientTokenAccountChanged, UpdateRecipientTokenAccount};
+
+/// Confirm a schedule's pending recipient token account after the timelock
+pub fn handler(ctx: Context<UpdateRecipientTokenAccount>) -> Result<()> {
+    ctx.accounts.program_config.require_writable()?;
+
+    let current_timestamp = Clock::get()?.unix_timestamp;
+    let new_account = ctx.accounts.new_recipient_token_account.key();
+    let vesting_schedule = &mut ctx.accounts.vesting_schedule;
+    vesting_schedule.require_mutable()?;
+    let old_account = vesting_schedule.confirm_recipient_token_account(new_account, current_timestamp)?;
+
+    emit!(RecipientTokenAccountChanged {
+        schedule_id: vesting_schedule.schedule_id,
+        old_account,
+        new_account,
+        timestamp: current_timestamp,
+    });
+
+    msg!("Recipient token account of schedule {} changed from {} to {}",
+         vesting_schedule.schedule_id, old_account, new_account);
+    Ok(())
+}
@@ -0,0 +1,22 @@
+use anchor_lang::prelude::*;
+
+use crate::{UpdateProgramConfig, UpgradeAuthorityConfirmed};
+
+/// Confirm the pending upgrade authority record after the timelock
+pub fn handler(ctx: Context<UpdateProgramConfig>, new_authority: Pubkey) -> Result<()> {
+    ctx.accounts.program_config.require_writable()?;
+
+    let current_timestamp = Clock::get()?.unix_timestamp;
+    let old_authority = ctx.accounts.program_config
+        .confirm_upgrade_authority(new_authority, current_timestamp)?;
+
+    emit!(UpgradeAuthorityConfirmed {
+        admin: ctx.accounts.admin.key(),
+        old_authority,
+        new_authority,
+        timestamp: current_timestamp,
+    });
+
+    msg!("Upgrade authority record changed from {} to {}", old_authority, new_authority);
+    Ok(())
+}
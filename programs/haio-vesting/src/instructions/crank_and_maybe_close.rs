@@ -0,0 +1,113 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::constants::*;
+use crate::errors::VestingError;
+use crate::state::{ProgramConfig, VestingSchedule, MintScheduleCount};
+use crate::{close_completed_schedule, release_vested};
+
+/// Crank that also closes the schedule and its vault when the release completes it
+/// Same accounts as `CrankVestingSchedules` plus the keeper, who receives the reclaimed rent,
+/// and the mint's schedule counter
+#[derive(Accounts)]
+pub struct CrankAndMaybeClose<'info> {
+    /// Caller submitting the crank - receives the rent if the schedule is closed
+    #[account(mut)]
+    pub keeper: Signer<'info>,
+
+    /// Program configuration - release policy and the release rate window
+    #[account(
+        mut,
+        seeds = [PROGRAM_CONFIG_SEED],
+        bump = program_config.bump
+    )]
+    pub program_config: Account<'info, ProgramConfig>,
+
+    /// Vesting schedule that defines the recipient and vesting parameters
+    #[account(
+        mut,
+        seeds = [VESTING_SCHEDULE_SEED, vesting_schedule.schedule_id.to_le_bytes().as_ref()],
+        bump = vesting_schedule.bump
+    )]
+    pub vesting_schedule: Account<'info, VestingSchedule>,
+
+    /// Vesting vault that holds the tokens for this specific schedule
+    /// Security: Authority must be the vesting_schedule PDA
+    #[account(
+        mut,
+        seeds = [VESTING_VAULT_SEED, vesting_schedule.schedule_id.to_le_bytes().as_ref()],
+        bump,
+        constraint = vesting_vault.owner == vesting_schedule.key() @ VestingError::VaultAuthorityMismatch,
+        constraint = vesting_vault.mint == vesting_schedule.mint @ VestingError::MintMismatch
+    )]
+    pub vesting_vault: InterfaceAccount<'info, TokenAccount>,
+
+    /// Recipient token account that receives the vested tokens
+    /// Security: Must match the specific account stored in vesting_schedule
+    /// Security: Owner is checked in the handler (see `check_recipient_account_owner`)
+    #[account(
+        mut,
+        constraint = recipient_token_account.key() == vesting_schedule.recipient_token_account @ VestingError::RecipientAccountMismatch,
+        constraint = recipient_token_account.mint == vesting_schedule.mint @ VestingError::RecipientAccountMintMismatch
+    )]
+    pub recipient_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// Token mint - validated against vesting schedule
+    #[account(
+        constraint = mint.key() == vesting_schedule.mint @ VestingError::MintMismatch
+    )]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    /// Open-schedule counter of the schedule's mint, decremented on close
+    #[account(
+        mut,
+        seeds = [MINT_SCHEDULE_COUNT_SEED, vesting_schedule.mint.as_ref()],
+        bump = mint_schedule_count.bump
+    )]
+    pub mint_schedule_count: Account<'info, MintScheduleCount>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+/// Crank a schedule and close it together with its vault once it is fully vested and emptied
+pub fn handler(ctx: Context<CrankAndMaybeClose>) -> Result<bool> {
+    ctx.accounts.program_config.require_writable()?;
+
+    release_vested(
+        &mut ctx.accounts.program_config,
+        &mut ctx.accounts.vesting_schedule,
+        &ctx.accounts.vesting_vault,
+        &ctx.accounts.recipient_token_account,
+        &ctx.accounts.mint,
+        ctx.accounts.token_program.to_account_info(),
+        None,
+    )?;
+
+    ctx.accounts.vesting_vault.reload()?;
+    if !ctx.accounts.vesting_schedule.is_fully_processed() || ctx.accounts.vesting_vault.amount != 0 {
+        return Ok(false);
+    }
+
+    // Within the close grace period the schedule stays open, the crank itself still succeeds
+    let current_timestamp = Clock::get()?.unix_timestamp;
+    let close_grace_period = ctx.accounts.program_config.close_grace_period;
+    if !ctx.accounts.vesting_schedule.close_grace_elapsed(close_grace_period, current_timestamp) {
+        return Ok(false);
+    }
+
+    close_completed_schedule(
+        &ctx.accounts.program_config,
+        &mut ctx.accounts.vesting_schedule,
+        &ctx.accounts.vesting_vault,
+        ctx.accounts.keeper.to_account_info(),
+        ctx.accounts.token_program.to_account_info(),
+    )?;
+    ctx.accounts.mint_schedule_count.record_closed();
+
+    msg!(
+        "Closed completed vesting schedule {} and its vault. Rent returned to {}.",
+        ctx.accounts.vesting_schedule.schedule_id,
+        ctx.accounts.keeper.key()
+    );
+    Ok(true)
+}
@@ -0,0 +1,195 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{self, Transfer};
+
+use crate::constants::*;
+use crate::errors::VestingError;
+use crate::state::{CrankBlockReason, ProgramConfig, VestingSchedule};
+use crate::{emit_crank_skipped, emit_release_checkpoints, release_timestamp, skip_if_crank_interval_pending, skip_if_revoked, TokensReleased, VaultClosed};
+
+/// Permissionless release of a native SOL schedule
+#[derive(Accounts)]
+pub struct CrankSolVestingSchedule<'info> {
+    /// Program configuration - checked for pause, read-only mode and the release rate window
+    #[account(
+        mut,
+        seeds = [PROGRAM_CONFIG_SEED],
+        bump = program_config.bump
+    )]
+    pub program_config: Account<'info, ProgramConfig>,
+
+    /// Native vesting schedule to crank
+    #[account(
+        mut,
+        seeds = [VESTING_SCHEDULE_SEED, vesting_schedule.schedule_id.to_le_bytes().as_ref()],
+        bump = vesting_schedule.bump,
+        constraint = vesting_schedule.is_native @ VestingError::ScheduleNotNative
+    )]
+    pub vesting_schedule: Account<'info, VestingSchedule>,
+
+    /// System-owned vault PDA of the schedule
+    #[account(
+        mut,
+        seeds = [NATIVE_VAULT_SEED, vesting_schedule.schedule_id.to_le_bytes().as_ref()],
+        bump,
+        address = vesting_schedule.token_vault @ VestingError::InvalidVaultState
+    )]
+    pub native_vault: SystemAccount<'info>,
+
+    /// Recipient wallet receiving the lamports
+    #[account(mut, address = vesting_schedule.recipient @ VestingError::InvalidRecipient)]
+    pub recipient: SystemAccount<'info>,
+
+    /// Depositor receiving the vault's rent reserve with the final release
+    #[account(mut, address = vesting_schedule.depositor @ VestingError::Unauthorized)]
+    pub depositor: SystemAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Release the vested lamports, always leaving the vault's rent-exempt reserve behind
+/// Once the schedule is fully processed the reserve and any surplus go back to the depositor,
+/// the vault is emptied and the schedule is kept as a record with `vault_closed` set
+pub fn handler(ctx: Context<CrankSolVestingSchedule>) -> Result<()> {
+    ctx.accounts.program_config.require_writable()?;
+    ctx.accounts.program_config.require_not_paused()?;
+
+    let current_timestamp = release_timestamp(&ctx.accounts.program_config)?;
+    let schedule_id = ctx.accounts.vesting_schedule.schedule_id;
+    require!(ctx.accounts.vesting_schedule.is_initialized, VestingError::InvalidVestingScheduleData);
+
+    // Revoked schedules never release again
+    if skip_if_revoked(&ctx.accounts.program_config, &ctx.accounts.vesting_schedule, current_timestamp) {
+        return Ok(());
+    }
+
+    if ctx.accounts.vesting_schedule.vault_closed {
+        msg!("Vault of schedule {} is closed. Skipping.", schedule_id);
+        emit_crank_skipped(&ctx.accounts.program_config, schedule_id, CrankBlockReason::VaultClosed, current_timestamp);
+        return Ok(());
+    }
+
+    // Lamports above the rent reserve are what the vault can release
+    let rent_reserve = Rent::get()?.minimum_balance(0);
+    let releasable_lamports = ctx.accounts.native_vault.lamports().saturating_sub(rent_reserve);
+    let amount = ctx.accounts.vesting_schedule.get_transferable_amount_capped(current_timestamp, releasable_lamports)?;
+
+    if amount > 0 {
+        // Keeper cranks bypass the recipient claim interval unless configured otherwise
+        let enforce_claim_interval = ctx.accounts.program_config.enforce_claim_interval_on_crank;
+        if enforce_claim_interval {
+            ctx.accounts.vesting_schedule.check_claim_interval(current_timestamp)?;
+        }
+
+        // Rate limit on micro-releases; skipped rather than failed so keepers can retry later
+        if skip_if_crank_interval_pending(&ctx.accounts.program_config, &ctx.accounts.vesting_schedule, current_timestamp) {
+            return Ok(());
+        }
+
+        ctx.accounts.program_config.record_outflow(amount, current_timestamp)?;
+        transfer_from_native_vault(
+            &ctx.accounts.system_program,
+            &ctx.accounts.native_vault,
+            schedule_id,
+            ctx.bumps.native_vault,
+            ctx.accounts.recipient.to_account_info(),
+            amount,
+        )?;
+
+        let vesting_schedule = &mut ctx.accounts.vesting_schedule;
+        vesting_schedule.record_release(amount, amount)?;
+        vesting_schedule.last_crank_timestamp = current_timestamp;
+        if enforce_claim_interval {
+            vesting_schedule.last_claim_timestamp = current_timestamp;
+        }
+        vesting_schedule.record_completion(current_timestamp);
+        ctx.accounts.program_config.record_distribution(amount)?;
+        ctx.accounts.program_config.record_rounding_dust(vesting_schedule.release_truncation(current_timestamp)?)?;
+        emit_release_checkpoints(&ctx.accounts.program_config, vesting_schedule, current_timestamp);
+
+        emit!(TokensReleased {
+            schedule_id,
+            recipient: vesting_schedule.recipient,
+            mint: Pubkey::default(),
+            amount,
+            source_category: vesting_schedule.source_category.clone(),
+            timestamp: current_timestamp,
+            total_released: vesting_schedule.amount_transferred,
+            keeper_fee: 0,
+        });
+        msg!("Released {} lamports for native schedule {}", amount, schedule_id);
+    } else if !ctx.accounts.vesting_schedule.is_fully_processed() {
+        msg!("No releasable lamports for native schedule {} at timestamp {}", schedule_id, current_timestamp);
+        emit_crank_skipped(&ctx.accounts.program_config, schedule_id, CrankBlockReason::NothingVested, current_timestamp);
+        return Ok(());
+    }
+
+    if ctx.accounts.vesting_schedule.is_fully_processed() {
+        let vault_lamports = drain_native_vault(
+            &mut ctx.accounts.vesting_schedule,
+            &ctx.accounts.native_vault,
+            ctx.bumps.native_vault,
+            ctx.accounts.depositor.to_account_info(),
+            &ctx.accounts.system_program,
+            current_timestamp,
+        )?;
+        msg!("Native schedule {} completed, returned {} vault lamports to the depositor",
+             schedule_id, vault_lamports);
+    }
+
+    Ok(())
+}
+
+/// Send every lamport left in a native vault, rent reserve included, to `beneficiary`
+/// Emptying the vault lets the runtime reclaim the account; the schedule is marked `vault_closed`
+/// Returns the lamports moved
+pub(crate) fn drain_native_vault<'info>(
+    vesting_schedule: &mut Account<'info, VestingSchedule>,
+    native_vault: &SystemAccount<'info>,
+    vault_bump: u8,
+    beneficiary: AccountInfo<'info>,
+    system_program: &Program<'info, System>,
+    timestamp: i64,
+) -> Result<u64> {
+    let vault_lamports = native_vault.lamports();
+    transfer_from_native_vault(
+        system_program,
+        native_vault,
+        vesting_schedule.schedule_id,
+        vault_bump,
+        beneficiary.clone(),
+        vault_lamports,
+    )?;
+    vesting_schedule.vault_closed = true;
+
+    emit!(VaultClosed {
+        schedule_id: vesting_schedule.schedule_id,
+        beneficiary: beneficiary.key(),
+        vault_lamports,
+        timestamp,
+    });
+    Ok(vault_lamports)
+}
+
+/// Move `lamports` out of the native vault, signed with the vault's own PDA seeds
+pub(crate) fn transfer_from_native_vault<'info>(
+    system_program: &Program<'info, System>,
+    native_vault: &SystemAccount<'info>,
+    schedule_id: u64,
+    vault_bump: u8,
+    to: AccountInfo<'info>,
+    lamports: u64,
+) -> Result<()> {
+    let schedule_id_bytes = schedule_id.to_le_bytes();
+    let vault_seeds: &[&[u8]] = &[NATIVE_VAULT_SEED, schedule_id_bytes.as_ref(), &[vault_bump]];
+    system_program::transfer(
+        CpiContext::new_with_signer(
+            system_program.to_account_info(),
+            Transfer {
+                from: native_vault.to_account_info(),
+                to,
+            },
+            &[vault_seeds],
+        ),
+        lamports,
+    )
+}
@@ -0,0 +1,89 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::constants::*;
+use crate::errors::VestingError;
+use crate::state::{ProgramConfig, VestingSchedule};
+use crate::release_vested;
+
+/// Individual recipient crank context for direct token transfers
+/// Replaces batch processing with single-schedule processing for enhanced security
+#[derive(Accounts)]
+pub struct CrankVestingSchedules<'info> {
+    /// Program configuration - release policy and the release rate window
+    #[account(
+        mut,
+        seeds = [PROGRAM_CONFIG_SEED],
+        bump = program_config.bump
+    )]
+    pub program_config: Account<'info, ProgramConfig>,
+
+    /// Vesting schedule that defines the recipient and vesting parameters
+    #[account(
+        mut,
+        seeds = [VESTING_SCHEDULE_SEED, vesting_schedule.schedule_id.to_le_bytes().as_ref()],
+        bump = vesting_schedule.bump
+    )]
+    pub vesting_schedule: Account<'info, VestingSchedule>,
+
+    /// Vesting vault that holds the tokens for this specific schedule
+    /// Security: Authority must be the vesting_schedule PDA
+    #[account(
+        mut,
+        seeds = [VESTING_VAULT_SEED, vesting_schedule.schedule_id.to_le_bytes().as_ref()],
+        bump,
+        constraint = vesting_vault.owner == vesting_schedule.key() @ VestingError::VaultAuthorityMismatch,
+        constraint = vesting_vault.mint == vesting_schedule.mint @ VestingError::MintMismatch
+    )]
+    pub vesting_vault: InterfaceAccount<'info, TokenAccount>,
+
+    /// Recipient token account that receives the vested tokens
+    /// Security: Must match the specific account stored in vesting_schedule
+    /// Security: Must have the same mint as the vesting schedule
+    /// Security: Owner is checked in the handler so an ownership change can be reported
+    /// (see `check_recipient_account_owner`)
+    #[account(
+        mut,
+        constraint = recipient_token_account.key() == vesting_schedule.recipient_token_account @ VestingError::RecipientAccountMismatch,
+        constraint = recipient_token_account.mint == vesting_schedule.mint @ VestingError::RecipientAccountMintMismatch
+    )]
+    pub recipient_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// Token mint - validated against vesting schedule
+    #[account(
+        constraint = mint.key() == vesting_schedule.mint @ VestingError::MintMismatch
+    )]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+
+    /// Keeper submitting the crank, only needed to collect the keeper reward
+    pub keeper: Option<Signer<'info>>,
+
+    /// Keeper token account receiving the keeper reward (`keeper_reward_bps` of the release)
+    /// Without it the whole release goes to the recipient
+    /// Security: Must be owned by the keeper signer and hold the schedule's mint
+    #[account(
+        mut,
+        constraint = keeper.as_ref().is_some_and(|keeper| keeper.key() == keeper_token_account.owner) @ VestingError::Unauthorized,
+        constraint = keeper_token_account.mint == vesting_schedule.mint @ VestingError::MintMismatch
+    )]
+    pub keeper_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+}
+
+/// Process individual vesting schedule with direct-to-recipient transfer
+pub fn handler(
+    ctx: Context<CrankVestingSchedules>,
+) -> Result<()> {
+    ctx.accounts.program_config.require_writable()?;
+
+    release_vested(
+        &mut ctx.accounts.program_config,
+        &mut ctx.accounts.vesting_schedule,
+        &ctx.accounts.vesting_vault,
+        &ctx.accounts.recipient_token_account,
+        &ctx.accounts.mint,
+        ctx.accounts.token_program.to_account_info(),
+        ctx.accounts.keeper_token_account.as_ref(),
+    )
+}
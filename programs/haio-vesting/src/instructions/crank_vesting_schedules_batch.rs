@@ -0,0 +1,243 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::program_pack::IsInitialized;
+use anchor_spl::token_interface::{Mint, TokenAccount};
+
+use crate::constants::*;
+use crate::errors::VestingError;
+use crate::state::{VestingSchedule, CrankBlockReason};
+use crate::{
+    check_recipient_account_owner, emit_crank_skipped, emit_release_checkpoints, extend_if_underfunded,
+    is_duplicate_in_batch, is_vault_closed, load_schedule_and_vault, release_timestamp,
+    skip_if_crank_interval_pending, skip_if_revoked, token_account_balance, transfer_from_vault_checked,
+    warn_if_release_rate_too_low, BatchCrankSummary, CrankVestingSchedulesBatch, ReleaseAccrued, TokensReleased,
+};
+
+/// Process several vesting schedules in one transaction
+pub fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, CrankVestingSchedulesBatch<'info>>,
+) -> Result<BatchCrankSummary> {
+    ctx.accounts.program_config.require_writable()?;
+    ctx.accounts.program_config.require_not_paused()?;
+
+    let current_timestamp = release_timestamp(&ctx.accounts.program_config)?;
+
+    let schedule_count = ctx.remaining_accounts.len() / 4;
+    require!(
+        schedule_count > 0
+            && schedule_count * 4 == ctx.remaining_accounts.len()
+            && schedule_count <= MAX_CRANK_BATCH_SIZE,
+        VestingError::InvalidRemainingAccounts
+    );
+
+    // ================================================================================================
+    // PRE-SCAN: SCHEDULE STATE ONLY
+    // ================================================================================================
+
+    let mut any_ready = false;
+    for accounts in ctx.remaining_accounts.chunks(4) {
+        let vesting_schedule = Account::<VestingSchedule>::try_from(&accounts[0])?;
+        if vesting_schedule.get_transferable_amount(current_timestamp)? > 0 {
+            any_ready = true;
+            break;
+        }
+    }
+
+    if !any_ready {
+        msg!("No schedules in batch are ready at timestamp {}. Skipping.", current_timestamp);
+        if ctx.accounts.program_config.emit_skip_events {
+            for accounts in ctx.remaining_accounts.chunks(4) {
+                let vesting_schedule = Account::<VestingSchedule>::try_from(&accounts[0])?;
+                let reason = if vesting_schedule.revoked {
+                    CrankBlockReason::Revoked
+                } else if vesting_schedule.is_fully_processed() {
+                    CrankBlockReason::FullyProcessed
+                } else {
+                    CrankBlockReason::NothingVested
+                };
+                emit_crank_skipped(&ctx.accounts.program_config, vesting_schedule.schedule_id, reason, current_timestamp);
+            }
+        }
+        return Ok(BatchCrankSummary {
+            nothing_ready: true,
+            ..Default::default()
+        });
+    }
+
+    // ================================================================================================
+    // FULL PROCESSING
+    // ================================================================================================
+
+    let max_release_per_transaction = ctx.accounts.program_config.max_release_per_transaction;
+    let enforce_claim_interval = ctx.accounts.program_config.enforce_claim_interval_on_crank;
+    let accumulate_releases = ctx.accounts.program_config.accumulate_releases;
+    let mut summary = BatchCrankSummary::default();
+    let mut seen_schedules: Vec<Pubkey> = Vec::with_capacity(schedule_count);
+
+    for accounts in ctx.remaining_accounts.chunks(4) {
+        if is_duplicate_in_batch(&mut seen_schedules, &ctx.accounts.program_config, &accounts[0], current_timestamp)?
+            || is_vault_closed(&ctx.accounts.program_config, &accounts[0], current_timestamp)?
+        {
+            continue;
+        }
+
+        // Stop once the per-transaction release cap has been fully used
+        let remaining_allowance = if max_release_per_transaction == 0 {
+            u64::MAX
+        } else {
+            max_release_per_transaction.saturating_sub(summary.total_released)
+        };
+        if remaining_allowance == 0 {
+            summary.stopped_at_cap = true;
+            msg!("Batch release cap of {} reached. Stopping.", max_release_per_transaction);
+            break;
+        }
+
+        let (mut vesting_schedule, vesting_vault) = load_schedule_and_vault(&accounts[0], &accounts[1])?;
+        let recipient_token_account = InterfaceAccount::<TokenAccount>::try_from(&accounts[2])?;
+        let mint = InterfaceAccount::<Mint>::try_from(&accounts[3])?;
+
+        require_keys_eq!(vesting_vault.owner, vesting_schedule.key(), VestingError::VaultAuthorityMismatch);
+        require_keys_eq!(vesting_vault.mint, vesting_schedule.mint, VestingError::MintMismatch);
+        require_keys_eq!(mint.key(), vesting_schedule.mint, VestingError::MintMismatch);
+        require_keys_eq!(vesting_schedule.funding_mint, vesting_schedule.mint, VestingError::SwapAtReleaseUnsupported);
+        require!(vesting_vault.is_initialized(), VestingError::InvalidVaultState);
+        require_keys_eq!(
+            recipient_token_account.key(),
+            vesting_schedule.recipient_token_account,
+            VestingError::RecipientAccountMismatch
+        );
+        require_keys_eq!(
+            recipient_token_account.mint,
+            vesting_schedule.mint,
+            VestingError::RecipientAccountMintMismatch
+        );
+        check_recipient_account_owner(&vesting_schedule, recipient_token_account.owner, current_timestamp)?;
+        vesting_schedule.require_undelegated(recipient_token_account.delegate.is_some())?;
+
+        summary.schedules_processed = summary.schedules_processed
+            .checked_add(1)
+            .ok_or(VestingError::MathOverflow)?;
+
+        if skip_if_revoked(&ctx.accounts.program_config, &vesting_schedule, current_timestamp) {
+            continue;
+        }
+
+        if enforce_claim_interval && vesting_schedule.check_claim_interval(current_timestamp).is_err() {
+            msg!("Claim interval not elapsed for schedule {}. Skipping.", vesting_schedule.schedule_id);
+            emit_crank_skipped(&ctx.accounts.program_config, vesting_schedule.schedule_id, CrankBlockReason::ClaimIntervalNotElapsed, current_timestamp);
+            continue;
+        }
+
+        extend_if_underfunded(&ctx.accounts.program_config, &mut vesting_schedule, vesting_vault.amount, current_timestamp)?;
+
+        // Release-batching mode: record what is owed, flush_releases moves the tokens later
+        if accumulate_releases {
+            if vesting_schedule.get_transferable_amount(current_timestamp)? == 0 {
+                emit_crank_skipped(&ctx.accounts.program_config, vesting_schedule.schedule_id, CrankBlockReason::NothingVested, current_timestamp);
+                continue;
+            }
+            if skip_if_crank_interval_pending(&ctx.accounts.program_config, &vesting_schedule, current_timestamp) {
+                continue;
+            }
+
+            let accrued_amount = vesting_schedule.accrue_release(current_timestamp)?;
+            if enforce_claim_interval {
+                vesting_schedule.last_claim_timestamp = current_timestamp;
+            }
+            vesting_schedule.exit(&crate::ID)?;
+
+            emit!(ReleaseAccrued {
+                schedule_id: vesting_schedule.schedule_id,
+                amount: accrued_amount,
+                pending_release: vesting_schedule.pending_release,
+                timestamp: current_timestamp,
+            });
+            summary.total_accrued = summary.total_accrued
+                .checked_add(accrued_amount)
+                .ok_or(VestingError::MathOverflow)?;
+            continue;
+        }
+
+        let transferable_amount = vesting_schedule
+            .get_transferable_amount_capped(current_timestamp, vesting_vault.amount)?;
+        let actual_transfer_amount = transferable_amount.min(remaining_allowance);
+        if actual_transfer_amount < transferable_amount {
+            summary.stopped_at_cap = true;
+        }
+        if actual_transfer_amount == 0 {
+            msg!("No transferable amount for schedule {}. Skipping.", vesting_schedule.schedule_id);
+            let (reason, _) = vesting_schedule.crank_block_reason(
+                current_timestamp,
+                vesting_vault.amount,
+                &recipient_token_account.owner,
+            )?;
+            emit_crank_skipped(&ctx.accounts.program_config, vesting_schedule.schedule_id, reason, current_timestamp);
+            warn_if_release_rate_too_low(&vesting_schedule, current_timestamp)?;
+            continue;
+        }
+
+        if skip_if_crank_interval_pending(&ctx.accounts.program_config, &vesting_schedule, current_timestamp) {
+            continue;
+        }
+
+        ctx.accounts.program_config.record_outflow(actual_transfer_amount, current_timestamp)?;
+        let recipient_info = recipient_token_account.to_account_info();
+        let balance_before = recipient_token_account.amount;
+        transfer_from_vault_checked(
+            &mut vesting_schedule,
+            &vesting_vault,
+            &mint,
+            recipient_info.clone(),
+            ctx.accounts.token_program.to_account_info(),
+            actual_transfer_amount,
+        )?;
+
+        // Transfer-fee mints deliver less than the vault was debited
+        let delivered_amount = token_account_balance(&recipient_info)?
+            .checked_sub(balance_before)
+            .ok_or(VestingError::MathOverflow)?;
+
+        vesting_schedule.record_release(actual_transfer_amount, delivered_amount)?;
+        vesting_schedule.last_crank_timestamp = current_timestamp;
+        if enforce_claim_interval {
+            vesting_schedule.last_claim_timestamp = current_timestamp;
+        }
+        vesting_schedule.record_completion(current_timestamp);
+        ctx.accounts.program_config.record_distribution(delivered_amount)?;
+        ctx.accounts.program_config.record_rounding_dust(vesting_schedule.release_truncation(current_timestamp)?)?;
+        emit_release_checkpoints(&ctx.accounts.program_config, &mut vesting_schedule, current_timestamp);
+
+        // Persist the updated schedule since it was not part of the validated accounts struct
+        vesting_schedule.exit(&crate::ID)?;
+
+        emit!(TokensReleased {
+            schedule_id: vesting_schedule.schedule_id,
+            recipient: vesting_schedule.recipient,
+            mint: vesting_schedule.mint,
+            amount: delivered_amount,
+            source_category: vesting_schedule.source_category.clone(),
+            timestamp: current_timestamp,
+            total_released: vesting_schedule.amount_transferred,
+            keeper_fee: 0,
+        });
+
+        summary.schedules_released = summary.schedules_released
+            .checked_add(1)
+            .ok_or(VestingError::MathOverflow)?;
+        summary.total_released = summary.total_released
+            .checked_add(actual_transfer_amount)
+            .ok_or(VestingError::MathOverflow)?;
+
+        if summary.stopped_at_cap {
+            msg!("Batch release cap of {} reached. Stopping.", max_release_per_transaction);
+            break;
+        }
+    }
+
+    msg!(
+        "Batch crank released {} and accrued {} tokens across {} schedules",
+        summary.total_released, summary.total_accrued, summary.schedules_processed
+    );
+
+    Ok(summary)
+}
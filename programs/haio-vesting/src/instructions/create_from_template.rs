@@ -0,0 +1,47 @@
+use anchor_lang::prelude::*;
+
+use crate::state::VestingCurve;
+use crate::{
+    create_schedule, CreateFromTemplateParams, CreateVestingSchedule, CreateVestingScheduleParams, TemplateApplied,
+};
+
+/// Create a schedule whose cliff, vesting start and end come from its category's template
+pub fn handler(
+    ctx: Context<CreateVestingSchedule>,
+    schedule_id: u64,
+    params: CreateFromTemplateParams,
+) -> Result<()> {
+    let template = ctx.accounts.program_config.category_templates[params.source_category.index()];
+    let (cliff_timestamp, vesting_start_timestamp, vesting_end_timestamp) =
+        template.derive_timestamps(params.start_time)?;
+
+    emit!(TemplateApplied {
+        schedule_id,
+        source_category: params.source_category.clone(),
+        start_time: params.start_time,
+        cliff_timestamp,
+        vesting_start_timestamp,
+        vesting_end_timestamp,
+    });
+
+    create_schedule(ctx, schedule_id, CreateVestingScheduleParams {
+        recipient: params.recipient,
+        total_amount: params.total_amount,
+        cliff_timestamp,
+        vesting_start_timestamp,
+        vesting_end_timestamp,
+        source_category: params.source_category,
+        round_up: false,
+        grant_group_id: 0,
+        min_claim_interval: 0,
+        allow_self_return: false,
+        allow_past_end: false,
+        terms_hash: params.terms_hash,
+        initial_unlock_bps: 0,
+        second_cliff_timestamp: 0,
+        second_cliff_bps: 0,
+        vesting_curve: VestingCurve::Linear,
+        non_transferable: false,
+        label: [0; 32],
+    })
+}
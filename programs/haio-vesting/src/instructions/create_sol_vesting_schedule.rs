@@ -0,0 +1,103 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{self, Transfer};
+
+use crate::constants::*;
+use crate::errors::VestingError;
+use crate::state::{ProgramConfig, VestingSchedule};
+use crate::{emit_schedule_created, init_schedule_from_params, validate_schedule_params, CreateVestingScheduleParams};
+
+/// Creation of a vesting schedule denominated in native SOL
+#[derive(Accounts)]
+#[instruction(schedule_id: u64)]
+pub struct CreateSolVestingSchedule<'info> {
+    /// Admin signer, or a delegate holding CAPABILITY_CREATE
+    /// Funds the deposit and the vault's rent reserve, and is recorded as the schedule's depositor
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    /// Program configuration account
+    /// Security: Validates the signer's capability
+    #[account(
+        mut,
+        seeds = [PROGRAM_CONFIG_SEED],
+        bump = program_config.bump,
+        constraint = program_config.has_capability(&admin.key(), CAPABILITY_CREATE) @ VestingError::MissingCapability
+    )]
+    pub program_config: Account<'info, ProgramConfig>,
+
+    /// Vesting schedule PDA - deterministic address based on schedule_id
+    /// Security: Schedule ID must follow sequential order to prevent gaps
+    #[account(
+        init,
+        payer = admin,
+        space = DISCRIMINATOR_SIZE + VESTING_SCHEDULE_LEN,
+        seeds = [VESTING_SCHEDULE_SEED, schedule_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub vesting_schedule: Account<'info, VestingSchedule>,
+
+    /// System-owned vault PDA holding the lamports
+    /// Security: Only this program can sign for it, with the vault's own seeds
+    #[account(
+        mut,
+        seeds = [NATIVE_VAULT_SEED, schedule_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub native_vault: SystemAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Create a native schedule and deposit `total_amount` plus the vault's rent-exempt reserve
+/// The reserve keeps the zero-data vault alive between releases and goes back to the depositor
+/// with the final release. No creation fee, mint counter or primary index applies to SOL grants
+pub fn handler(
+    ctx: Context<CreateSolVestingSchedule>,
+    schedule_id: u64,
+    params: CreateVestingScheduleParams,
+) -> Result<()> {
+    ctx.accounts.program_config.require_writable()?;
+
+    validate_schedule_params(&ctx.accounts.program_config, &params, Clock::get()?.unix_timestamp)?;
+    // Native schedules cannot be topped up later, so placeholders are refused
+    require!(params.total_amount > 0, VestingError::InvalidAmount);
+    require!(schedule_id == ctx.accounts.program_config.total_schedules, VestingError::ScheduleIdConflict);
+
+    let vesting_schedule = &mut ctx.accounts.vesting_schedule;
+    init_schedule_from_params(
+        vesting_schedule,
+        schedule_id,
+        &params,
+        Pubkey::default(),
+        Pubkey::default(),
+        ctx.accounts.native_vault.key(),
+        ctx.accounts.admin.key(),
+        ctx.bumps.vesting_schedule,
+    )?;
+    vesting_schedule.is_native = true;
+
+    let rent_reserve = Rent::get()?.minimum_balance(0);
+    let deposit_amount = params.total_amount
+        .checked_add(rent_reserve)
+        .ok_or(VestingError::MathOverflow)?;
+    system_program::transfer(
+        CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.admin.to_account_info(),
+                to: ctx.accounts.native_vault.to_account_info(),
+            },
+        ),
+        deposit_amount,
+    )?;
+
+    let program_config = &mut ctx.accounts.program_config;
+    program_config.increment_total_schedules()?;
+    program_config.allocate_to_category(&params.source_category, params.total_amount)?;
+
+    emit_schedule_created(schedule_id, &params, Pubkey::default(), ctx.accounts.admin.key());
+
+    msg!("Created native vesting schedule {} with {} lamports plus a {} lamport rent reserve",
+         schedule_id, params.total_amount, rent_reserve);
+    Ok(())
+}
@@ -0,0 +1,12 @@
+use anchor_lang::prelude::*;
+
+use crate::{create_schedule, CreateVestingSchedule, CreateVestingScheduleParams};
+
+/// Create a new vesting schedule with token deposit
+pub fn handler(
+    ctx: Context<CreateVestingSchedule>,
+    schedule_id: u64,
+    params: CreateVestingScheduleParams,
+) -> Result<()> {
+    create_schedule(ctx, schedule_id, params)
+}
@@ -0,0 +1,285 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::program_pack::Pack;
+use anchor_lang::system_program::{self, CreateAccount};
+use anchor_spl::token_2022::spl_token_2022::{
+    self,
+    extension::{BaseStateWithExtensions, ExtensionType, StateWithExtensions},
+};
+use anchor_spl::token_interface::{self, InitializeAccount3, Mint, TokenAccount, TokenInterface};
+
+use crate::constants::*;
+use crate::errors::VestingError;
+use crate::pda;
+use crate::state::{MintScheduleCount, PrimaryScheduleIndex, ProgramConfig, VestingSchedule};
+use crate::{
+    charge_creation_fee, deposit_from, emit_schedule_created, init_schedule_from_params,
+    validate_schedule_params, CreateVestingScheduleParams,
+};
+
+/// Remaining accounts per batch entry:
+/// (vesting_schedule, vesting_vault, recipient_token_account, primary_schedule_index)
+pub const ACCOUNTS_PER_CREATE_ENTRY: usize = 4;
+
+/// Creation of several schedules of one mint in a single transaction
+#[derive(Accounts)]
+pub struct CreateVestingSchedulesBatch<'info> {
+    /// Admin signer, or a delegate holding CAPABILITY_CREATE
+    /// Funds every deposit and account, and is recorded as each schedule's depositor
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    /// Program configuration account
+    /// Security: Validates the signer's capability
+    #[account(
+        mut,
+        seeds = [PROGRAM_CONFIG_SEED],
+        bump = program_config.bump,
+        constraint = program_config.has_capability(&admin.key(), CAPABILITY_CREATE) @ VestingError::MissingCapability
+    )]
+    pub program_config: Account<'info, ProgramConfig>,
+
+    /// Token mint of every schedule in the batch
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    /// Source token account from which all deposits are made
+    /// Security: Must be owned by admin and have correct mint
+    #[account(
+        mut,
+        constraint = depositor_token_account.mint == mint.key() @ VestingError::MintMismatch,
+        constraint = depositor_token_account.owner == admin.key() @ VestingError::Unauthorized
+    )]
+    pub depositor_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// Open-schedule counter of the mint, checked against `max_schedules_per_mint`
+    #[account(
+        init_if_needed,
+        payer = admin,
+        space = DISCRIMINATOR_SIZE + MINT_SCHEDULE_COUNT_LEN,
+        seeds = [MINT_SCHEDULE_COUNT_SEED, mint.key().as_ref()],
+        bump
+    )]
+    pub mint_schedule_count: Box<Account<'info, MintScheduleCount>>,
+
+    /// Creation fee token account, required only while `creation_fee_bps` is set
+    /// Security: Must be owned by the configured `fee_destination` and hold the mint
+    /// (checked in the handler)
+    #[account(mut)]
+    pub fee_destination: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+/// Create one schedule per entry with sequential IDs starting at `program_config.total_schedules`
+/// Remaining accounts: ACCOUNTS_PER_CREATE_ENTRY accounts per entry, in order; the schedule,
+/// vault and primary index PDAs are derived from the next schedule ID and the entry's recipient.
+/// Every entry is checked like `create_vesting_schedule` and the whole batch fails if any does.
+///
+/// Compute: roughly 35k CU per schedule (PDA derivations, two account creations, vault
+/// initialization, deposit) plus about 5k CU when a creation fee is charged. A full batch of
+/// MAX_SCHEDULES_PER_CREATE needs a compute budget instruction raising the limit to ~400k CU;
+/// the default 200k CU fits about 5 schedules. Without an address lookup table, the transaction
+/// size limit allows about 5 entries.
+pub fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, CreateVestingSchedulesBatch<'info>>,
+    params: Vec<CreateVestingScheduleParams>,
+) -> Result<()> {
+    ctx.accounts.program_config.require_writable()?;
+    require!(
+        !params.is_empty() && params.len() <= MAX_SCHEDULES_PER_CREATE,
+        VestingError::InvalidBatchSize
+    );
+    require!(
+        ctx.remaining_accounts.len() == params.len() * ACCOUNTS_PER_CREATE_ENTRY,
+        VestingError::InvalidRemainingAccounts
+    );
+
+    let current_timestamp = Clock::get()?.unix_timestamp;
+    let mint = ctx.accounts.mint.key();
+    let first_schedule_id = ctx.accounts.program_config.total_schedules;
+
+    for (entry, accounts) in params.iter().zip(ctx.remaining_accounts.chunks(ACCOUNTS_PER_CREATE_ENTRY)) {
+        create_entry(&ctx, entry, accounts, current_timestamp)?;
+
+        let schedule_id = ctx.accounts.program_config.total_schedules;
+        ctx.accounts.program_config.increment_total_schedules()?;
+        ctx.accounts.program_config.allocate_to_category(&entry.source_category, entry.total_amount)?;
+        ctx.accounts.mint_schedule_count.record_created(
+            mint,
+            ctx.bumps.mint_schedule_count,
+            ctx.accounts.program_config.max_schedules_per_mint,
+        )?;
+
+        emit_schedule_created(schedule_id, entry, mint, ctx.accounts.admin.key());
+    }
+
+    msg!("Created {} vesting schedules, IDs {} to {}",
+         params.len(), first_schedule_id, ctx.accounts.program_config.total_schedules - 1);
+    Ok(())
+}
+
+/// Validate one entry, create its schedule and vault, deposit and charge the creation fee
+fn create_entry<'info>(
+    ctx: &Context<'_, '_, 'info, 'info, CreateVestingSchedulesBatch<'info>>,
+    params: &CreateVestingScheduleParams,
+    accounts: &'info [AccountInfo<'info>],
+    current_timestamp: i64,
+) -> Result<()> {
+    let [schedule_info, vault_info, recipient_info, primary_index_info] = accounts else {
+        return err!(VestingError::InvalidRemainingAccounts);
+    };
+    let program_config = &ctx.accounts.program_config;
+    let mint = ctx.accounts.mint.key();
+    let schedule_id = program_config.total_schedules;
+
+    validate_schedule_params(program_config, params, current_timestamp)?;
+
+    // Same recipient token account checks as `create_vesting_schedule`
+    let recipient_token_account = InterfaceAccount::<TokenAccount>::try_from(recipient_info)?;
+    require!(recipient_token_account.mint == mint, VestingError::RecipientAccountMintMismatch);
+    require!(
+        recipient_token_account.owner == params.recipient,
+        VestingError::RecipientAccountOwnerMismatch
+    );
+    require!(
+        params.allow_self_return
+            || recipient_token_account.key() != ctx.accounts.depositor_token_account.key(),
+        VestingError::RecipientEqualsDepositor
+    );
+
+    // Vesting schedule PDA for the next sequential ID
+    let schedule_id_bytes = schedule_id.to_le_bytes();
+    let (schedule_key, schedule_bump) = pda::vesting_schedule_pda(schedule_id);
+    require_keys_eq!(schedule_info.key(), schedule_key, VestingError::InvalidRemainingAccounts);
+    create_pda_account(
+        ctx,
+        schedule_info,
+        DISCRIMINATOR_SIZE + VESTING_SCHEDULE_LEN,
+        &crate::ID,
+        &[VESTING_SCHEDULE_SEED, schedule_id_bytes.as_ref(), &[schedule_bump]],
+    )?;
+
+    // Vault PDA owned by the mint's token program, with the schedule PDA as authority
+    let (vault_key, vault_bump) = pda::vesting_vault_pda(schedule_id);
+    require_keys_eq!(vault_info.key(), vault_key, VestingError::InvalidRemainingAccounts);
+    create_pda_account(
+        ctx,
+        vault_info,
+        vault_len(&ctx.accounts.mint)?,
+        ctx.accounts.token_program.key,
+        &[VESTING_VAULT_SEED, schedule_id_bytes.as_ref(), &[vault_bump]],
+    )?;
+    token_interface::initialize_account3(CpiContext::new(
+        ctx.accounts.token_program.to_account_info(),
+        InitializeAccount3 {
+            account: vault_info.clone(),
+            mint: ctx.accounts.mint.to_account_info(),
+            authority: schedule_info.clone(),
+        },
+    ))?;
+
+    let mut vesting_schedule = Account::<VestingSchedule>::try_from_unchecked(schedule_info)?;
+    init_schedule_from_params(
+        &mut vesting_schedule,
+        schedule_id,
+        params,
+        recipient_token_account.key(),
+        mint,
+        vault_key,
+        ctx.accounts.admin.key(),
+        schedule_bump,
+    )?;
+    vesting_schedule.exit(&crate::ID)?;
+
+    record_primary_schedule(ctx, params, primary_index_info, schedule_id)?;
+
+    // Deposit (placeholders start with an empty vault)
+    if params.total_amount > 0 {
+        deposit_from(
+            &ctx.accounts.depositor_token_account,
+            &ctx.accounts.mint,
+            vault_info.clone(),
+            &ctx.accounts.admin,
+            &ctx.accounts.token_program,
+            params.total_amount,
+        )?;
+    }
+
+    charge_creation_fee(
+        program_config,
+        schedule_id,
+        params.total_amount,
+        &ctx.accounts.mint,
+        &ctx.accounts.depositor_token_account,
+        &ctx.accounts.admin,
+        ctx.accounts.fee_destination.as_ref(),
+        &ctx.accounts.token_program,
+    )
+}
+
+/// Record the schedule as primary for (recipient, mint) unless one is already recorded
+/// The index is created on first use; an entry repeating an earlier recipient of the same
+/// batch finds the index already written
+fn record_primary_schedule<'info>(
+    ctx: &Context<'_, '_, 'info, 'info, CreateVestingSchedulesBatch<'info>>,
+    params: &CreateVestingScheduleParams,
+    primary_index_info: &'info AccountInfo<'info>,
+    schedule_id: u64,
+) -> Result<()> {
+    let mint = ctx.accounts.mint.key();
+    let (index_key, index_bump) = pda::primary_schedule_index_pda(&params.recipient, &mint);
+    require_keys_eq!(primary_index_info.key(), index_key, VestingError::InvalidRemainingAccounts);
+
+    if primary_index_info.owner == &System::id() {
+        create_pda_account(
+            ctx,
+            primary_index_info,
+            DISCRIMINATOR_SIZE + PRIMARY_SCHEDULE_INDEX_LEN,
+            &crate::ID,
+            &[PRIMARY_SCHEDULE_SEED, params.recipient.as_ref(), mint.as_ref(), &[index_bump]],
+        )?;
+        let mut primary_index = Account::<PrimaryScheduleIndex>::try_from_unchecked(primary_index_info)?;
+        primary_index.record_if_first(params.recipient, mint, schedule_id, index_bump);
+        primary_index.exit(&crate::ID)?;
+        msg!("Schedule {} recorded as primary for recipient {} and mint {}", schedule_id, params.recipient, mint);
+    }
+    Ok(())
+}
+
+/// Size of a vault for `mint`, including the account extensions a Token-2022 mint requires
+fn vault_len(mint: &InterfaceAccount<Mint>) -> Result<usize> {
+    let mint_info = mint.to_account_info();
+    if *mint_info.owner != spl_token_2022::ID {
+        return Ok(spl_token_2022::state::Account::LEN);
+    }
+
+    let mint_data = mint_info.try_borrow_data()?;
+    let mint_state = StateWithExtensions::<spl_token_2022::state::Mint>::unpack(&mint_data)?;
+    let account_extensions = ExtensionType::get_required_init_account_extensions(
+        &mint_state.get_extension_types()?,
+    );
+    Ok(ExtensionType::try_calculate_account_len::<spl_token_2022::state::Account>(&account_extensions)?)
+}
+
+/// Allocate a rent-exempt PDA owned by `owner`, paid by the admin
+fn create_pda_account<'info>(
+    ctx: &Context<'_, '_, 'info, 'info, CreateVestingSchedulesBatch<'info>>,
+    account: &'info AccountInfo<'info>,
+    space: usize,
+    owner: &Pubkey,
+    signer_seeds: &[&[u8]],
+) -> Result<()> {
+    system_program::create_account(
+        CpiContext::new_with_signer(
+            ctx.accounts.system_program.to_account_info(),
+            CreateAccount {
+                from: ctx.accounts.admin.to_account_info(),
+                to: account.clone(),
+            },
+            &[signer_seeds],
+        ),
+        Rent::get()?.minimum_balance(space),
+        space as u64,
+        owner,
+    )
+}
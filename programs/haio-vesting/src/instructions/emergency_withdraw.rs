@@ -0,0 +1,103 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::constants::*;
+use crate::errors::VestingError;
+use crate::state::{ProgramConfig, VestingSchedule};
+use crate::{transfer_from_vault_checked, EmergencyWithdrawn};
+
+/// Break-glass recovery of a schedule's vault
+#[derive(Accounts)]
+pub struct EmergencyWithdraw<'info> {
+    /// Admin recovering the vault
+    pub admin: Signer<'info>,
+
+    /// Program configuration - validates admin authority, must be paused
+    #[account(
+        seeds = [PROGRAM_CONFIG_SEED],
+        bump = program_config.bump,
+        has_one = admin @ VestingError::Unauthorized
+    )]
+    pub program_config: Account<'info, ProgramConfig>,
+
+    /// Vesting schedule whose vault is recovered, revoked afterwards
+    #[account(
+        mut,
+        seeds = [VESTING_SCHEDULE_SEED, vesting_schedule.schedule_id.to_le_bytes().as_ref()],
+        bump = vesting_schedule.bump
+    )]
+    pub vesting_schedule: Account<'info, VestingSchedule>,
+
+    /// Vesting vault that holds the tokens for this specific schedule
+    /// Security: Authority must be the vesting_schedule PDA
+    #[account(
+        mut,
+        seeds = [VESTING_VAULT_SEED, vesting_schedule.schedule_id.to_le_bytes().as_ref()],
+        bump,
+        constraint = vesting_vault.owner == vesting_schedule.key() @ VestingError::VaultAuthorityMismatch,
+        constraint = vesting_vault.mint == vesting_schedule.mint @ VestingError::MintMismatch
+    )]
+    pub vesting_vault: InterfaceAccount<'info, TokenAccount>,
+
+    /// Token account receiving the whole vault balance
+    /// Security: Must be owned by the admin and hold the schedule's mint
+    #[account(
+        mut,
+        constraint = recovery_token_account.owner == admin.key() @ VestingError::Unauthorized,
+        constraint = recovery_token_account.mint == vesting_schedule.mint @ VestingError::MintMismatch
+    )]
+    pub recovery_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// Token mint - validated against vesting schedule
+    #[account(
+        constraint = mint.key() == vesting_schedule.mint @ VestingError::MintMismatch
+    )]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+/// Move the entire vault balance to the admin's recovery account and revoke the schedule
+/// Meant for schedules whose tokens can no longer reach the recipient by any other path;
+/// requiring a pause makes it an explicit incident-response step
+/// Not counted against the release rate limit, so the circuit breaker cannot block recovery
+/// Security: Admin-only, paused program only, immutable and revoked schedules are refused
+pub fn handler(ctx: Context<EmergencyWithdraw>) -> Result<()> {
+    ctx.accounts.program_config.require_writable()?;
+    ctx.accounts.program_config.require_paused()?;
+
+    let vesting_schedule = &ctx.accounts.vesting_schedule;
+    require!(vesting_schedule.is_initialized, VestingError::InvalidVestingScheduleData);
+    require!(!vesting_schedule.revoked, VestingError::ScheduleRevoked);
+    vesting_schedule.require_mutable()?;
+
+    // Clock directly: the pause is what matters here, not the configured release clock
+    let current_timestamp = Clock::get()?.unix_timestamp;
+    let amount = ctx.accounts.vesting_vault.amount;
+
+    if amount > 0 {
+        transfer_from_vault_checked(
+            &mut ctx.accounts.vesting_schedule,
+            &ctx.accounts.vesting_vault,
+            &ctx.accounts.mint,
+            ctx.accounts.recovery_token_account.to_account_info(),
+            ctx.accounts.token_program.to_account_info(),
+            amount,
+        )?;
+    }
+
+    // Nothing was paid to the recipient, whatever was still owed is forfeited
+    let vesting_schedule = &mut ctx.accounts.vesting_schedule;
+    vesting_schedule.revoke(0)?;
+
+    emit!(EmergencyWithdrawn {
+        schedule_id: vesting_schedule.schedule_id,
+        amount,
+        recovery_token_account: ctx.accounts.recovery_token_account.key(),
+        timestamp: current_timestamp,
+    });
+
+    msg!("Emergency withdraw: {} tokens recovered from schedule {} to {}",
+         amount, vesting_schedule.schedule_id, ctx.accounts.recovery_token_account.key());
+    Ok(())
+}
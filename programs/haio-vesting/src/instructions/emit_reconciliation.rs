@@ -0,0 +1,78 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::errors::VestingError;
+use crate::state::ProgramConfig;
+use crate::{load_schedule_and_vault, ReconciliationReport};
+
+/// Admin context for read-only scans that emit attestations
+#[derive(Accounts)]
+pub struct AdminScanSchedules<'info> {
+    /// Admin signer - only admin can publish attestations
+    pub admin: Signer<'info>,
+
+    /// Program configuration account
+    /// Security: Validates admin authority
+    #[account(
+        seeds = [PROGRAM_CONFIG_SEED],
+        bump = program_config.bump,
+        has_one = admin @ VestingError::Unauthorized
+    )]
+    pub program_config: Account<'info, ProgramConfig>,
+}
+
+/// Publish a solvency attestation over a set of schedules
+pub fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, AdminScanSchedules<'info>>,
+) -> Result<()> {
+    let pair_count = ctx.remaining_accounts.len() / 2;
+    require!(
+        pair_count > 0
+            && pair_count * 2 == ctx.remaining_accounts.len()
+            && pair_count <= MAX_SCAN_BATCH_SIZE,
+        VestingError::InvalidRemainingAccounts
+    );
+
+    let mut total_deposited: u64 = 0;
+    let mut total_released: u64 = 0;
+    let mut total_outstanding: u64 = 0;
+    let mut total_vault_balance: u64 = 0;
+    let mut insolvent_schedules: u32 = 0;
+
+    for pair in ctx.remaining_accounts.chunks(2) {
+        let (vesting_schedule, vesting_vault) = load_schedule_and_vault(&pair[0], &pair[1])?;
+
+        total_deposited = total_deposited
+            .checked_add(vesting_schedule.total_amount)
+            .ok_or(VestingError::MathOverflow)?;
+        total_released = total_released
+            .checked_add(vesting_schedule.amount_transferred)
+            .ok_or(VestingError::MathOverflow)?;
+        total_outstanding = total_outstanding
+            .checked_add(vesting_schedule.outstanding_amount())
+            .ok_or(VestingError::MathOverflow)?;
+        total_vault_balance = total_vault_balance
+            .checked_add(vesting_vault.amount)
+            .ok_or(VestingError::MathOverflow)?;
+
+        if !vesting_schedule.is_solvent(vesting_vault.amount) {
+            msg!("Schedule {} is insolvent: vault {} < outstanding {}",
+                 vesting_schedule.schedule_id, vesting_vault.amount, vesting_schedule.outstanding_amount());
+            insolvent_schedules += 1;
+        }
+    }
+
+    emit!(ReconciliationReport {
+        admin: ctx.accounts.admin.key(),
+        schedules_checked: pair_count as u32,
+        total_deposited,
+        total_released,
+        total_outstanding,
+        total_vault_balance,
+        insolvent_schedules,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    msg!("Reconciled {} schedules, {} insolvent", pair_count, insolvent_schedules);
+    Ok(())
+}
@@ -0,0 +1,48 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::errors::VestingError;
+use crate::state::{ProgramConfig, VestingSchedule};
+use crate::ScheduleFinalized;
+
+/// Admin context for changing a single schedule
+#[derive(Accounts)]
+pub struct AmendVestingSchedule<'info> {
+    /// Admin signer - only admin can amend schedules
+    pub admin: Signer<'info>,
+
+    /// Program configuration account
+    /// Security: Validates admin authority
+    #[account(
+        seeds = [PROGRAM_CONFIG_SEED],
+        bump = program_config.bump,
+        has_one = admin @ VestingError::Unauthorized
+    )]
+    pub program_config: Account<'info, ProgramConfig>,
+
+    #[account(
+        mut,
+        seeds = [VESTING_SCHEDULE_SEED, vesting_schedule.schedule_id.to_le_bytes().as_ref()],
+        bump = vesting_schedule.bump
+    )]
+    pub vesting_schedule: Account<'info, VestingSchedule>,
+}
+
+/// Irreversibly lock a schedule's terms against admin amendments
+pub fn handler(ctx: Context<AmendVestingSchedule>) -> Result<()> {
+    ctx.accounts.program_config.require_writable()?;
+
+    let vesting_schedule = &mut ctx.accounts.vesting_schedule;
+    require!(vesting_schedule.is_initialized, VestingError::InvalidVestingScheduleData);
+    vesting_schedule.require_mutable()?;
+    vesting_schedule.immutable = true;
+
+    emit!(ScheduleFinalized {
+        schedule_id: vesting_schedule.schedule_id,
+        admin: ctx.accounts.admin.key(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    msg!("Schedule {} finalized", vesting_schedule.schedule_id);
+    Ok(())
+}
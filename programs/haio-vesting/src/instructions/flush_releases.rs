@@ -0,0 +1,121 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount};
+
+use crate::constants::*;
+use crate::errors::VestingError;
+use crate::{
+    check_recipient_account_owner, emit_release_checkpoints, is_duplicate_in_batch, is_vault_closed,
+    load_schedule_and_vault, release_timestamp, skip_if_crank_interval_pending, skip_if_revoked,
+    token_account_balance, transfer_from_vault_checked, CrankVestingSchedulesBatch, TokensReleased,
+};
+
+/// Transfer the accrued `pending_release` of several schedules in one transaction
+pub fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, CrankVestingSchedulesBatch<'info>>,
+) -> Result<()> {
+    ctx.accounts.program_config.require_writable()?;
+    ctx.accounts.program_config.require_not_paused()?;
+
+    let current_timestamp = release_timestamp(&ctx.accounts.program_config)?;
+
+    let schedule_count = ctx.remaining_accounts.len() / 4;
+    require!(
+        schedule_count > 0
+            && schedule_count * 4 == ctx.remaining_accounts.len()
+            && schedule_count <= MAX_CRANK_BATCH_SIZE,
+        VestingError::InvalidRemainingAccounts
+    );
+
+    let max_release_per_transaction = ctx.accounts.program_config.max_release_per_transaction;
+    let mut total_flushed: u64 = 0;
+    let mut seen_schedules: Vec<Pubkey> = Vec::with_capacity(schedule_count);
+
+    for accounts in ctx.remaining_accounts.chunks(4) {
+        if is_duplicate_in_batch(&mut seen_schedules, &ctx.accounts.program_config, &accounts[0], current_timestamp)?
+            || is_vault_closed(&ctx.accounts.program_config, &accounts[0], current_timestamp)?
+        {
+            continue;
+        }
+
+        let remaining_allowance = if max_release_per_transaction == 0 {
+            u64::MAX
+        } else {
+            max_release_per_transaction.saturating_sub(total_flushed)
+        };
+        if remaining_allowance == 0 {
+            msg!("Batch release cap of {} reached. Stopping.", max_release_per_transaction);
+            break;
+        }
+
+        let (mut vesting_schedule, vesting_vault) = load_schedule_and_vault(&accounts[0], &accounts[1])?;
+        let recipient_token_account = InterfaceAccount::<TokenAccount>::try_from(&accounts[2])?;
+        let mint = InterfaceAccount::<Mint>::try_from(&accounts[3])?;
+
+        require_keys_eq!(vesting_vault.owner, vesting_schedule.key(), VestingError::VaultAuthorityMismatch);
+        require_keys_eq!(mint.key(), vesting_schedule.mint, VestingError::MintMismatch);
+        require_keys_eq!(
+            recipient_token_account.key(),
+            vesting_schedule.recipient_token_account,
+            VestingError::RecipientAccountMismatch
+        );
+        check_recipient_account_owner(&vesting_schedule, recipient_token_account.owner, current_timestamp)?;
+        vesting_schedule.require_undelegated(recipient_token_account.delegate.is_some())?;
+
+        if skip_if_revoked(&ctx.accounts.program_config, &vesting_schedule, current_timestamp) {
+            continue;
+        }
+
+        let flush_amount = vesting_schedule.pending_release
+            .min(vesting_vault.amount)
+            .min(remaining_allowance);
+        if flush_amount == 0 {
+            continue;
+        }
+
+        if skip_if_crank_interval_pending(&ctx.accounts.program_config, &vesting_schedule, current_timestamp) {
+            continue;
+        }
+
+        ctx.accounts.program_config.record_outflow(flush_amount, current_timestamp)?;
+        let recipient_info = recipient_token_account.to_account_info();
+        let balance_before = recipient_token_account.amount;
+        transfer_from_vault_checked(
+            &mut vesting_schedule,
+            &vesting_vault,
+            &mint,
+            recipient_info.clone(),
+            ctx.accounts.token_program.to_account_info(),
+            flush_amount,
+        )?;
+        let delivered_amount = token_account_balance(&recipient_info)?
+            .checked_sub(balance_before)
+            .ok_or(VestingError::MathOverflow)?;
+        vesting_schedule.settle_pending_release(flush_amount, delivered_amount)?;
+        vesting_schedule.last_crank_timestamp = current_timestamp;
+        vesting_schedule.record_completion(current_timestamp);
+        ctx.accounts.program_config.record_distribution(delivered_amount)?;
+        ctx.accounts.program_config.record_rounding_dust(vesting_schedule.release_truncation(current_timestamp)?)?;
+        emit_release_checkpoints(&ctx.accounts.program_config, &mut vesting_schedule, current_timestamp);
+
+        // Persist the updated schedule since it was not part of the validated accounts struct
+        vesting_schedule.exit(&crate::ID)?;
+
+        emit!(TokensReleased {
+            schedule_id: vesting_schedule.schedule_id,
+            recipient: vesting_schedule.recipient,
+            mint: vesting_schedule.mint,
+            amount: delivered_amount,
+            source_category: vesting_schedule.source_category.clone(),
+            timestamp: current_timestamp,
+            total_released: vesting_schedule.amount_transferred,
+            keeper_fee: 0,
+        });
+
+        total_flushed = total_flushed
+            .checked_add(flush_amount)
+            .ok_or(VestingError::MathOverflow)?;
+    }
+
+    msg!("Flushed {} accrued tokens", total_flushed);
+    Ok(())
+}
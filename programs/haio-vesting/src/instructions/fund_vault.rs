@@ -0,0 +1,109 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::constants::*;
+use crate::errors::VestingError;
+use crate::state::{ProgramConfig, VestingSchedule};
+use crate::{deposit_from, VaultFunded};
+
+/// Context for re-funding a schedule's vault
+/// The vault is recreated with `init_if_needed` if it was closed while the schedule still exists
+#[derive(Accounts)]
+pub struct FundVault<'info> {
+    /// Admin signer - pays for vault recreation if needed
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    /// Program configuration account
+    /// Security: Validates admin authority
+    #[account(
+        seeds = [PROGRAM_CONFIG_SEED],
+        bump = program_config.bump,
+        has_one = admin @ VestingError::Unauthorized
+    )]
+    pub program_config: Account<'info, ProgramConfig>,
+
+    /// Existing vesting schedule being funded
+    /// Security: Must still owe tokens to the recipient
+    #[account(
+        seeds = [VESTING_SCHEDULE_SEED, vesting_schedule.schedule_id.to_le_bytes().as_ref()],
+        bump = vesting_schedule.bump,
+        constraint = !vesting_schedule.is_fully_processed() @ VestingError::ScheduleFullyProcessed
+    )]
+    pub vesting_schedule: Account<'info, VestingSchedule>,
+
+    /// Token mint of the schedule
+    #[account(
+        constraint = mint.key() == vesting_schedule.mint @ VestingError::MintMismatch
+    )]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    /// Source token account from which tokens are deposited
+    /// Security: Must be owned by admin and have correct mint
+    #[account(
+        mut,
+        constraint = depositor_token_account.mint == mint.key() @ VestingError::MintMismatch,
+        constraint = depositor_token_account.owner == admin.key() @ VestingError::Unauthorized
+    )]
+    pub depositor_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// Vesting vault PDA, recreated at the same address if missing
+    /// Security: Same seeds as at creation, and an existing vault must already have the
+    /// schedule's mint and the vesting_schedule PDA as authority
+    #[account(
+        init_if_needed,
+        payer = admin,
+        seeds = [VESTING_VAULT_SEED, vesting_schedule.schedule_id.to_le_bytes().as_ref()],
+        bump,
+        token::mint = mint,
+        token::authority = vesting_schedule,
+        token::token_program = token_program
+    )]
+    pub vesting_vault: InterfaceAccount<'info, TokenAccount>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+/// Deposit tokens into a schedule's vault without changing its allocation
+pub fn handler(ctx: Context<FundVault>, amount: u64) -> Result<()> {
+    ctx.accounts.program_config.require_writable()?;
+    require!(amount > 0, VestingError::InvalidAmount);
+
+    let vesting_schedule = &ctx.accounts.vesting_schedule;
+    require_keys_eq!(
+        ctx.accounts.vesting_vault.key(),
+        vesting_schedule.token_vault,
+        VestingError::InvalidVaultState
+    );
+
+    let outstanding_amount = vesting_schedule.outstanding_amount();
+    let new_vault_balance = ctx.accounts.vesting_vault.amount
+        .checked_add(amount)
+        .ok_or(VestingError::MathOverflow)?;
+    require!(new_vault_balance <= outstanding_amount, VestingError::InvalidAmount);
+    require!(
+        ctx.accounts.depositor_token_account.amount >= amount,
+        VestingError::InsufficientDepositorBalance
+    );
+
+    deposit_from(
+        &ctx.accounts.depositor_token_account,
+        &ctx.accounts.mint,
+        ctx.accounts.vesting_vault.to_account_info(),
+        &ctx.accounts.admin,
+        &ctx.accounts.token_program,
+        amount,
+    )?;
+
+    emit!(VaultFunded {
+        schedule_id: vesting_schedule.schedule_id,
+        amount,
+        vault_balance: new_vault_balance,
+    });
+
+    msg!("Funded vault of schedule {} with {} tokens (balance: {})",
+         vesting_schedule.schedule_id, amount, new_vault_balance);
+    Ok(())
+}
@@ -0,0 +1,25 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::TokenAccount;
+
+use crate::constants::*;
+use crate::errors::VestingError;
+use crate::state::VestingSchedule;
+
+/// Read-only context for previewing a revocation
+#[derive(Accounts)]
+pub struct GetClawbackPreview<'info> {
+    #[account(
+        seeds = [VESTING_SCHEDULE_SEED, vesting_schedule.schedule_id.to_le_bytes().as_ref()],
+        bump = vesting_schedule.bump
+    )]
+    pub vesting_schedule: Account<'info, VestingSchedule>,
+
+    #[account(address = vesting_schedule.token_vault @ VestingError::InvalidVaultState)]
+    pub vesting_vault: InterfaceAccount<'info, TokenAccount>,
+}
+
+/// Preview the amount a revocation would reclaim right now
+pub fn handler(ctx: Context<GetClawbackPreview>) -> Result<u64> {
+    let current_timestamp = Clock::get()?.unix_timestamp;
+    ctx.accounts.vesting_schedule.clawback_amount(current_timestamp, ctx.accounts.vesting_vault.amount)
+}
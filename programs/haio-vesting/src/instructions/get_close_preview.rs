@@ -0,0 +1,28 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::errors::VestingError;
+use crate::state::VestingSchedule;
+
+/// Read-only context for previewing a schedule close
+#[derive(Accounts)]
+pub struct GetClosePreview<'info> {
+    #[account(
+        seeds = [VESTING_SCHEDULE_SEED, vesting_schedule.schedule_id.to_le_bytes().as_ref()],
+        bump = vesting_schedule.bump
+    )]
+    pub vesting_schedule: Account<'info, VestingSchedule>,
+
+    /// CHECK: Only its lamports are read; it may be an SPL or Token-2022 account, a native
+    /// vault, or already closed
+    #[account(address = vesting_schedule.token_vault @ VestingError::InvalidVaultState)]
+    pub vesting_vault: UncheckedAccount<'info>,
+}
+
+/// Preview the lamports reclaimed by closing a schedule and its vault
+pub fn handler(ctx: Context<GetClosePreview>) -> Result<u64> {
+    Ok(VestingSchedule::reclaimable_rent(
+        ctx.accounts.vesting_schedule.to_account_info().lamports(),
+        ctx.accounts.vesting_vault.lamports(),
+    ))
+}
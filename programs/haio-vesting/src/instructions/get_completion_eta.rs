@@ -0,0 +1,15 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::VestingError;
+use crate::ViewVestingSchedule;
+
+/// Estimated payout completion of a schedule for a keeper's crank cadence
+pub fn handler(
+    ctx: Context<ViewVestingSchedule>,
+    crank_interval: i64,
+    min_release: u64,
+) -> Result<i64> {
+    require!(crank_interval > 0, VestingError::InvalidTimestamps);
+    let current_timestamp = Clock::get()?.unix_timestamp;
+    Ok(ctx.accounts.vesting_schedule.completion_eta(current_timestamp, crank_interval, min_release))
+}
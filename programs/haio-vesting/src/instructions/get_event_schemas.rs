@@ -0,0 +1,17 @@
+use anchor_lang::prelude::*;
+
+use crate::{all_event_schemas, return_data, EventSchemaPage};
+
+/// Context for views that need no accounts
+#[derive(Accounts)]
+pub struct GetEventSchemas {}
+
+/// Names and Anchor discriminators of every event the program emits
+pub fn handler(_ctx: Context<GetEventSchemas>, offset: u32) -> Result<EventSchemaPage> {
+    let page = return_data::paginate(all_event_schemas(), offset)?;
+    Ok(EventSchemaPage {
+        schemas: page.items,
+        truncated: page.truncated,
+        next_offset: page.next_offset,
+    })
+}
@@ -0,0 +1,53 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::errors::VestingError;
+use crate::state::VestingSchedule;
+use crate::{return_data, GrantGroupMintTotal, GrantGroupPage, ScanSchedules};
+
+/// Report per-mint totals of a grant group
+pub fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, ScanSchedules<'info>>,
+    grant_group_id: u64,
+    offset: u32,
+) -> Result<GrantGroupPage> {
+    require!(grant_group_id != 0, VestingError::InvalidGrantGroup);
+    require!(
+        !ctx.remaining_accounts.is_empty() && ctx.remaining_accounts.len() <= MAX_SCAN_BATCH_SIZE,
+        VestingError::InvalidRemainingAccounts
+    );
+
+    let mut group_recipient: Option<Pubkey> = None;
+    let mut mint_totals: Vec<GrantGroupMintTotal> = Vec::new();
+
+    for schedule_info in ctx.remaining_accounts.iter() {
+        let vesting_schedule = Account::<VestingSchedule>::try_from(schedule_info)?;
+        require!(vesting_schedule.grant_group_id == grant_group_id, VestingError::GrantGroupMismatch);
+
+        let recipient = *group_recipient.get_or_insert(vesting_schedule.recipient);
+        require_keys_eq!(vesting_schedule.recipient, recipient, VestingError::GrantGroupRecipientMismatch);
+
+        match mint_totals.iter_mut().find(|entry| entry.mint == vesting_schedule.mint) {
+            Some(entry) => {
+                entry.total_amount = entry.total_amount
+                    .checked_add(vesting_schedule.total_amount)
+                    .ok_or(VestingError::MathOverflow)?;
+                entry.amount_transferred = entry.amount_transferred
+                    .checked_add(vesting_schedule.amount_transferred)
+                    .ok_or(VestingError::MathOverflow)?;
+            }
+            None => mint_totals.push(GrantGroupMintTotal {
+                mint: vesting_schedule.mint,
+                total_amount: vesting_schedule.total_amount,
+                amount_transferred: vesting_schedule.amount_transferred,
+            }),
+        }
+    }
+
+    let page = return_data::paginate(mint_totals, offset)?;
+    Ok(GrantGroupPage {
+        mint_totals: page.items,
+        truncated: page.truncated,
+        next_offset: page.next_offset,
+    })
+}
@@ -0,0 +1,43 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::{ProgramLimits, ViewProgramConfig};
+
+/// One-stop view of every limit and policy flag, for validating transactions client-side
+pub fn handler(ctx: Context<ViewProgramConfig>) -> Result<ProgramLimits> {
+    let program_config = &ctx.accounts.program_config;
+    Ok(ProgramLimits {
+        version: PROGRAM_LIMITS_VERSION,
+        read_only: program_config.read_only,
+        keeper_reward_bps: program_config.keeper_reward_bps,
+        reward_buffer: program_config.reward_buffer,
+        max_release_per_transaction: program_config.max_release_per_transaction,
+        cliff_eq_start_categories: program_config.cliff_eq_start_categories,
+        enforce_claim_interval_on_crank: program_config.enforce_claim_interval_on_crank,
+        max_clock_skew: program_config.max_clock_skew,
+        emit_skip_events: program_config.emit_skip_events,
+        accumulate_releases: program_config.accumulate_releases,
+        creation_fee_bps: program_config.creation_fee_bps,
+        fee_destination: program_config.fee_destination,
+        max_keeper_reward_bps: MAX_KEEPER_REWARD_BPS,
+        max_top_up_batch_size: MAX_TOP_UP_BATCH_SIZE as u32,
+        max_scan_batch_size: MAX_SCAN_BATCH_SIZE as u32,
+        max_crank_batch_size: MAX_CRANK_BATCH_SIZE as u32,
+        max_curve_points: MAX_CURVE_POINTS as u32,
+        hub_update_timelock: HUB_UPDATE_TIMELOCK,
+        upgrade_authority_timelock: UPGRADE_AUTHORITY_TIMELOCK,
+        allow_zero_amount: program_config.allow_zero_amount,
+        max_delegates: MAX_DELEGATES as u32,
+        auto_extend_underfunded: program_config.auto_extend_underfunded,
+        max_schedules_per_mint: program_config.max_schedules_per_mint,
+        max_create_batch_size: MAX_SCHEDULES_PER_CREATE as u32,
+        category_caps: program_config.category_caps,
+        category_allocated: program_config.category_allocated,
+        max_release_per_window: program_config.max_release_per_window,
+        release_window_duration: program_config.release_window_duration,
+        release_window_start: program_config.release_window_start,
+        released_in_window: program_config.released_in_window,
+        min_crank_interval: program_config.min_crank_interval,
+        skip_zero_events: program_config.skip_zero_events,
+    })
+}
@@ -0,0 +1,24 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::state::PrimaryScheduleIndex;
+
+/// Read-only context for resolving a recipient's primary schedule of a mint
+#[derive(Accounts)]
+#[instruction(recipient: Pubkey, mint: Pubkey)]
+pub struct GetPrimarySchedule<'info> {
+    #[account(
+        seeds = [PRIMARY_SCHEDULE_SEED, recipient.as_ref(), mint.as_ref()],
+        bump = primary_schedule_index.bump
+    )]
+    pub primary_schedule_index: Account<'info, PrimaryScheduleIndex>,
+}
+
+/// Resolve (recipient, mint) to the schedule_id of the recipient's first schedule of that mint
+pub fn handler(
+    ctx: Context<GetPrimarySchedule>,
+    _recipient: Pubkey,
+    _mint: Pubkey,
+) -> Result<u64> {
+    Ok(ctx.accounts.primary_schedule_index.schedule_id)
+}
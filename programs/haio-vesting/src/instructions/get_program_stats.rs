@@ -0,0 +1,13 @@
+use anchor_lang::prelude::*;
+
+use crate::{ProgramStats, ViewProgramConfig};
+
+/// Program-wide schedule count, allocated and distributed totals in one call
+pub fn handler(ctx: Context<ViewProgramConfig>) -> Result<ProgramStats> {
+    let program_config = &ctx.accounts.program_config;
+    Ok(ProgramStats {
+        total_schedules: program_config.total_schedules,
+        total_allocated: program_config.total_allocated,
+        total_distributed: program_config.total_distributed,
+    })
+}
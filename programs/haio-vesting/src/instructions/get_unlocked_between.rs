@@ -0,0 +1,8 @@
+use anchor_lang::prelude::*;
+
+use crate::ViewVestingSchedule;
+
+/// Additional amount a schedule unlocks between `from_ts` and `to_ts` (`to_ts >= from_ts`)
+pub fn handler(ctx: Context<ViewVestingSchedule>, from_ts: i64, to_ts: i64) -> Result<u64> {
+    ctx.accounts.vesting_schedule.unlocked_between(from_ts, to_ts)
+}
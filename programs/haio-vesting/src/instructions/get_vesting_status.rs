@@ -0,0 +1,19 @@
+use anchor_lang::prelude::*;
+
+use crate::{VestingStatus, ViewVestingSchedule};
+
+/// Vested and claimable amounts of a schedule computed by the on-chain vesting math
+pub fn handler(ctx: Context<ViewVestingSchedule>, min_release: u64) -> Result<VestingStatus> {
+    let current_timestamp = Clock::get()?.unix_timestamp;
+    let vesting_schedule = &ctx.accounts.vesting_schedule;
+    let unlocked_amount = vesting_schedule.calculate_unlocked_amount(current_timestamp)?;
+
+    Ok(VestingStatus {
+        total_amount: vesting_schedule.total_amount,
+        amount_transferred: vesting_schedule.amount_transferred,
+        unlocked_amount,
+        transferable_amount: vesting_schedule.get_transferable_amount(current_timestamp)?,
+        is_fully_vested: !vesting_schedule.placeholder && unlocked_amount >= vesting_schedule.total_amount,
+        cranks_remaining: vesting_schedule.cranks_remaining(current_timestamp, min_release),
+    })
+}
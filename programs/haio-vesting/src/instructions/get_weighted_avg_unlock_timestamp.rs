@@ -0,0 +1,9 @@
+use anchor_lang::prelude::*;
+
+use crate::ViewVestingSchedule;
+
+/// Amount-weighted average unlock timestamp of a schedule's remaining tokens
+pub fn handler(ctx: Context<ViewVestingSchedule>) -> Result<i64> {
+    let current_timestamp = Clock::get()?.unix_timestamp;
+    ctx.accounts.vesting_schedule.weighted_avg_unlock_timestamp(current_timestamp)
+}
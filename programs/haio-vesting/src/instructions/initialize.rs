@@ -0,0 +1,41 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::state::ProgramConfig;
+use crate::ProgramInitialized;
+
+#[derive(Accounts)]
+pub struct Initialize<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    /// Program configuration PDA
+    /// Security: Establishes admin authority for the entire program
+    #[account(
+        init,
+        payer = admin,
+        space = DISCRIMINATOR_SIZE + PROGRAM_CONFIG_LEN,
+        seeds = [PROGRAM_CONFIG_SEED],
+        bump
+    )]
+    pub program_config: Account<'info, ProgramConfig>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Initialize the vesting program
+pub fn handler(ctx: Context<Initialize>) -> Result<()> {
+    let config = &mut ctx.accounts.program_config;
+    
+    // Initialize program state
+    config.init(ctx.accounts.admin.key(), ctx.bumps.program_config)?;
+
+    emit!(ProgramInitialized {
+        admin: config.admin,
+        program_config: config.key(),
+    });
+
+    msg!("Vesting program initialized with admin: {}", config.admin);
+    msg!("Program config PDA: {}", config.key());
+    Ok(())
+}
@@ -0,0 +1,163 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::constants::*;
+use crate::errors::VestingError;
+use crate::state::{MintScheduleCount, ProgramConfig, VestingSchedule};
+use crate::{close_vault, release_timestamp, token_account_balance, transfer_from_vault_checked, SchedulesMerged};
+
+/// Consolidation of two schedules of the same recipient and mint into one
+#[derive(Accounts)]
+pub struct MergeSchedules<'info> {
+    /// Admin signer - receives the rent of the merged-away schedule and its vault
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    /// Recipient of both schedules - must agree to the consolidation
+    pub recipient: Signer<'info>,
+
+    /// Program configuration account
+    /// Security: Validates admin authority
+    #[account(
+        seeds = [PROGRAM_CONFIG_SEED],
+        bump = program_config.bump,
+        has_one = admin @ VestingError::Unauthorized
+    )]
+    pub program_config: Account<'info, ProgramConfig>,
+
+    /// Schedule that absorbs the other one and stays open
+    #[account(
+        mut,
+        seeds = [VESTING_SCHEDULE_SEED, target_schedule.schedule_id.to_le_bytes().as_ref()],
+        bump = target_schedule.bump,
+        has_one = recipient @ VestingError::Unauthorized
+    )]
+    pub target_schedule: Account<'info, VestingSchedule>,
+
+    /// Vault of the target schedule, receives the source vault balance
+    /// Security: Authority must be the target_schedule PDA
+    #[account(
+        mut,
+        seeds = [VESTING_VAULT_SEED, target_schedule.schedule_id.to_le_bytes().as_ref()],
+        bump,
+        constraint = target_vault.owner == target_schedule.key() @ VestingError::VaultAuthorityMismatch,
+        constraint = target_vault.mint == target_schedule.mint @ VestingError::MintMismatch
+    )]
+    pub target_vault: InterfaceAccount<'info, TokenAccount>,
+
+    /// Schedule merged into the target, deleted afterwards
+    #[account(
+        mut,
+        seeds = [VESTING_SCHEDULE_SEED, source_schedule.schedule_id.to_le_bytes().as_ref()],
+        bump = source_schedule.bump,
+        has_one = recipient @ VestingError::Unauthorized,
+        close = admin
+    )]
+    pub source_schedule: Account<'info, VestingSchedule>,
+
+    /// Vault of the source schedule, emptied into the target vault and closed
+    /// Security: Authority must be the source_schedule PDA
+    #[account(
+        mut,
+        seeds = [VESTING_VAULT_SEED, source_schedule.schedule_id.to_le_bytes().as_ref()],
+        bump,
+        constraint = source_vault.owner == source_schedule.key() @ VestingError::VaultAuthorityMismatch,
+        constraint = source_vault.mint == source_schedule.mint @ VestingError::MintMismatch
+    )]
+    pub source_vault: InterfaceAccount<'info, TokenAccount>,
+
+    /// Token mint of both schedules
+    #[account(
+        constraint = mint.key() == target_schedule.mint @ VestingError::MintMismatch
+    )]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    /// Open-schedule counter of the mint, the source schedule gives up its slot
+    #[account(
+        mut,
+        seeds = [MINT_SCHEDULE_COUNT_SEED, mint.key().as_ref()],
+        bump = mint_schedule_count.bump
+    )]
+    pub mint_schedule_count: Account<'info, MintScheduleCount>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+/// Merge `source_schedule` into `target_schedule`: amounts add up, the source vault balance
+/// moves into the target vault and the source schedule and vault are closed
+/// Merging is limited to schedules with identical timing and curve (see `merge_from`), so the
+/// combined schedule unlocks exactly what both did and nothing already vested is delayed
+/// Security: Admin and recipient co-sign; revoked, finalized, archived and placeholder
+/// schedules cannot be merged
+pub fn handler(ctx: Context<MergeSchedules>) -> Result<()> {
+    ctx.accounts.program_config.require_writable()?;
+    ctx.accounts.program_config.require_not_paused()?;
+
+    let current_timestamp = release_timestamp(&ctx.accounts.program_config)?;
+
+    for schedule in [&ctx.accounts.target_schedule, &ctx.accounts.source_schedule] {
+        require!(schedule.is_initialized, VestingError::InvalidVestingScheduleData);
+        require!(!schedule.revoked, VestingError::ScheduleRevoked);
+        require!(!schedule.closed, VestingError::ScheduleAlreadyArchived);
+        require!(!schedule.placeholder, VestingError::InvalidAmount);
+        schedule.require_mutable()?;
+    }
+
+    let source_schedule_id = ctx.accounts.source_schedule.schedule_id;
+    let source_total = ctx.accounts.source_schedule.total_amount;
+    ctx.accounts.target_schedule.merge_from(&ctx.accounts.source_schedule, current_timestamp)?;
+
+    // ================================================================================================
+    // VAULT CONSOLIDATION
+    // ================================================================================================
+
+    let moved_amount = ctx.accounts.source_vault.amount;
+    if moved_amount > 0 {
+        let target_vault_info = ctx.accounts.target_vault.to_account_info();
+        let balance_before = ctx.accounts.target_vault.amount;
+        transfer_from_vault_checked(
+            &mut ctx.accounts.source_schedule,
+            &ctx.accounts.source_vault,
+            &ctx.accounts.mint,
+            target_vault_info.clone(),
+            ctx.accounts.token_program.to_account_info(),
+            moved_amount,
+        )?;
+
+        // A transfer-fee mint withholds part of the move; it is booked like a release fee so
+        // the target stays solvent
+        let delivered_amount = token_account_balance(&target_vault_info)?
+            .checked_sub(balance_before)
+            .ok_or(VestingError::MathOverflow)?;
+        let target_schedule = &mut ctx.accounts.target_schedule;
+        target_schedule.transfer_fees_paid = target_schedule.transfer_fees_paid
+            .checked_add(moved_amount - delivered_amount)
+            .ok_or(VestingError::MathOverflow)?;
+    }
+
+    close_vault(
+        &ctx.accounts.source_schedule,
+        &ctx.accounts.source_vault,
+        ctx.accounts.admin.to_account_info(),
+        ctx.accounts.token_program.to_account_info(),
+    )?;
+    ctx.accounts.mint_schedule_count.record_closed();
+
+    // ================================================================================================
+    // EVENT EMISSION
+    // ================================================================================================
+
+    let target_schedule = &ctx.accounts.target_schedule;
+    emit!(SchedulesMerged {
+        target_schedule_id: target_schedule.schedule_id,
+        source_schedule_id,
+        merged_amount: source_total,
+        moved_amount,
+        new_total_amount: target_schedule.total_amount,
+        timestamp: current_timestamp,
+    });
+
+    msg!("Merged schedule {} into schedule {}: {} tokens moved, new total {}",
+         source_schedule_id, target_schedule.schedule_id, moved_amount, target_schedule.total_amount);
+    Ok(())
+}
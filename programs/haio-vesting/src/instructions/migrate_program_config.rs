@@ -0,0 +1,83 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{self, Transfer};
+
+use crate::constants::*;
+use crate::errors::VestingError;
+use crate::state::ProgramConfig;
+use crate::ProgramConfigMigrated;
+
+/// One-off upgrade of a config account created with the original 41-byte layout
+#[derive(Accounts)]
+pub struct MigrateProgramConfig<'info> {
+    /// Admin recorded in the legacy config; pays for the extra rent
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    /// CHECK: Cannot be loaded as `Account<ProgramConfig>` until it has been resized.
+    /// Address, owner, discriminator and layout are verified in the handler
+    #[account(
+        mut,
+        seeds = [PROGRAM_CONFIG_SEED],
+        bump,
+        owner = crate::ID
+    )]
+    pub program_config: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Resize the legacy config to `ProgramConfig::LEN` and rewrite it in the current layout
+/// Admin, schedule counter and bump are preserved; every newer field takes its default
+pub fn handler(ctx: Context<MigrateProgramConfig>) -> Result<()> {
+    let config_info = ctx.accounts.program_config.to_account_info();
+
+    let migrated = {
+        let data = config_info.try_borrow_data()?;
+        require!(
+            data.len() == DISCRIMINATOR_SIZE + ProgramConfig::LEGACY_LEN,
+            VestingError::UnexpectedConfigLayout
+        );
+        require!(
+            data[..DISCRIMINATOR_SIZE] == *ProgramConfig::DISCRIMINATOR,
+            VestingError::UnexpectedConfigLayout
+        );
+        ProgramConfig::from_legacy(&data[DISCRIMINATOR_SIZE..])?
+    };
+    require!(migrated.admin == ctx.accounts.admin.key(), VestingError::Unauthorized);
+    require!(migrated.bump == ctx.bumps.program_config, VestingError::UnexpectedConfigLayout);
+
+    let old_len = config_info.data_len();
+    let new_len = DISCRIMINATOR_SIZE + PROGRAM_CONFIG_LEN;
+    let rent_shortfall = Rent::get()?
+        .minimum_balance(new_len)
+        .saturating_sub(config_info.lamports());
+    if rent_shortfall > 0 {
+        system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.admin.to_account_info(),
+                    to: config_info.clone(),
+                },
+            ),
+            rent_shortfall,
+        )?;
+    }
+
+    config_info.realloc(new_len, true)?;
+    {
+        let mut data = config_info.try_borrow_mut_data()?;
+        let mut writer: &mut [u8] = &mut data;
+        migrated.try_serialize(&mut writer)?;
+    }
+
+    emit!(ProgramConfigMigrated {
+        admin: migrated.admin,
+        program_config: config_info.key(),
+        old_len: old_len as u32,
+        new_len: new_len as u32,
+    });
+
+    msg!("Program config migrated from {} to {} bytes", old_len, new_len);
+    Ok(())
+}
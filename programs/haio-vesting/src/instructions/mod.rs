@@ -0,0 +1,167 @@
+pub mod accept_admin;
+pub mod aggregate_unlock_curve;
+pub mod can_crank;
+pub mod cancel_hub_update;
+pub mod check_vault_ownership;
+pub mod claim_all;
+pub mod claim_unwrapped_sol;
+pub mod claim_vested;
+pub mod claim_with_signature;
+pub mod close_sol_vesting_schedule;
+pub mod close_vault_only;
+pub mod close_vesting_schedule;
+pub mod common;
+pub mod confirm_recipient_token_account;
+pub mod confirm_upgrade_authority;
+pub mod crank_and_maybe_close;
+pub mod crank_sol_vesting_schedule;
+pub mod crank_vesting_schedule;
+pub mod crank_vesting_schedules_batch;
+pub mod create_from_template;
+pub mod create_sol_vesting_schedule;
+pub mod create_vesting_schedule;
+pub mod create_vesting_schedules_batch;
+pub mod emergency_withdraw;
+pub mod emit_reconciliation;
+pub mod finalize_schedule;
+pub mod flush_releases;
+pub mod fund_vault;
+pub mod get_clawback_preview;
+pub mod get_close_preview;
+pub mod get_completion_eta;
+pub mod get_event_schemas;
+pub mod get_grant_group;
+pub mod get_limits;
+pub mod get_primary_schedule;
+pub mod get_program_stats;
+pub mod get_unlocked_between;
+pub mod get_vesting_status;
+pub mod get_weighted_avg_unlock_timestamp;
+pub mod grant_capability;
+pub mod initialize;
+pub mod merge_schedules;
+pub mod migrate_program_config;
+pub mod propose_recipient_token_account;
+pub mod propose_upgrade_authority;
+pub mod purge_archived;
+pub mod reassign_unvested;
+pub mod recount_schedules;
+pub mod revoke_capability;
+pub mod revoke_sol_vesting_schedule;
+pub mod revoke_vesting_schedule;
+pub mod schedules_due;
+pub mod set_allow_zero_amount;
+pub mod set_archive_retention;
+pub mod set_auto_extend_underfunded;
+pub mod set_category_cap;
+pub mod set_category_template;
+pub mod set_checkpoint_interval;
+pub mod set_claim_interval_crank_enforcement;
+pub mod set_cliff_eq_start_policy;
+pub mod set_close_grace_period;
+pub mod set_creation_fee;
+pub mod set_emit_skip_events;
+pub mod set_keeper_reward;
+pub mod set_max_clock_skew;
+pub mod set_max_release_per_transaction;
+pub mod set_max_schedules_per_mint;
+pub mod set_min_crank_interval;
+pub mod set_pause;
+pub mod set_read_only;
+pub mod set_release_batching;
+pub mod set_release_rate_limit;
+pub mod set_require_even_step_division;
+pub mod set_reward_buffer;
+pub mod set_skip_zero_events;
+pub mod set_swap_program;
+pub mod set_withdrawal_authority;
+pub mod stream_withdraw;
+pub mod sweep_rounding_dust;
+pub mod top_up_batch;
+pub mod top_up_schedule;
+pub mod transfer_admin;
+pub mod update_distribution_hub;
+pub mod validate_batch;
+pub mod verify_terms;
+
+// Every instruction module exposes its own `handler`; callers always use the module path
+#[allow(ambiguous_glob_reexports)]
+pub use accept_admin::*;
+#[allow(ambiguous_glob_reexports)]
+pub use can_crank::*;
+#[allow(ambiguous_glob_reexports)]
+pub use claim_all::*;
+#[allow(ambiguous_glob_reexports)]
+pub use claim_unwrapped_sol::*;
+#[allow(ambiguous_glob_reexports)]
+pub use claim_vested::*;
+#[allow(ambiguous_glob_reexports)]
+pub use claim_with_signature::*;
+#[allow(ambiguous_glob_reexports)]
+pub use close_sol_vesting_schedule::*;
+#[allow(ambiguous_glob_reexports)]
+pub use close_vault_only::*;
+#[allow(ambiguous_glob_reexports)]
+pub use close_vesting_schedule::*;
+#[allow(ambiguous_glob_reexports)]
+pub use common::*;
+#[allow(ambiguous_glob_reexports)]
+pub use crank_and_maybe_close::*;
+#[allow(ambiguous_glob_reexports)]
+pub use crank_sol_vesting_schedule::*;
+#[allow(ambiguous_glob_reexports)]
+pub use crank_vesting_schedule::*;
+#[allow(ambiguous_glob_reexports)]
+pub use create_sol_vesting_schedule::*;
+#[allow(ambiguous_glob_reexports)]
+pub use create_vesting_schedules_batch::*;
+#[allow(ambiguous_glob_reexports)]
+pub use emergency_withdraw::*;
+#[allow(ambiguous_glob_reexports)]
+pub use emit_reconciliation::*;
+#[allow(ambiguous_glob_reexports)]
+pub use finalize_schedule::*;
+#[allow(ambiguous_glob_reexports)]
+pub use fund_vault::*;
+#[allow(ambiguous_glob_reexports)]
+pub use get_clawback_preview::*;
+#[allow(ambiguous_glob_reexports)]
+pub use get_close_preview::*;
+#[allow(ambiguous_glob_reexports)]
+pub use get_event_schemas::*;
+#[allow(ambiguous_glob_reexports)]
+pub use get_primary_schedule::*;
+#[allow(ambiguous_glob_reexports)]
+pub use initialize::*;
+#[allow(ambiguous_glob_reexports)]
+pub use merge_schedules::*;
+#[allow(ambiguous_glob_reexports)]
+pub use migrate_program_config::*;
+#[allow(ambiguous_glob_reexports)]
+pub use purge_archived::*;
+#[allow(ambiguous_glob_reexports)]
+pub use reassign_unvested::*;
+#[allow(ambiguous_glob_reexports)]
+pub use recount_schedules::*;
+#[allow(ambiguous_glob_reexports)]
+pub use revoke_sol_vesting_schedule::*;
+#[allow(ambiguous_glob_reexports)]
+pub use revoke_vesting_schedule::*;
+#[allow(ambiguous_glob_reexports)]
+pub use set_pause::*;
+#[allow(ambiguous_glob_reexports)]
+pub use set_swap_program::*;
+#[allow(ambiguous_glob_reexports)]
+pub use set_withdrawal_authority::*;
+#[allow(ambiguous_glob_reexports)]
+pub use stream_withdraw::*;
+#[allow(ambiguous_glob_reexports)]
+pub use sweep_rounding_dust::*;
+#[allow(ambiguous_glob_reexports)]
+pub use top_up_batch::*;
+#[allow(ambiguous_glob_reexports)]
+pub use top_up_schedule::*;
+#[allow(ambiguous_glob_reexports)]
+pub use update_distribution_hub::*;
+#[allow(ambiguous_glob_reexports)]
+pub use validate_batch::*;
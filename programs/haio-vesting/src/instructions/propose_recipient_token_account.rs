@@ -0,0 +1,25 @@
+use anchor_lang::prelude::*;
+
+use crate::{RecipientTokenAccountChangeProposed, UpdateRecipientTokenAccount};
+
+/// Propose a replacement recipient token account for a schedule
+pub fn handler(ctx: Context<UpdateRecipientTokenAccount>) -> Result<()> {
+    ctx.accounts.program_config.require_writable()?;
+
+    let current_timestamp = Clock::get()?.unix_timestamp;
+    let new_account = ctx.accounts.new_recipient_token_account.key();
+    let vesting_schedule = &mut ctx.accounts.vesting_schedule;
+    vesting_schedule.require_mutable()?;
+    let timelock_expiry = vesting_schedule.propose_recipient_token_account(new_account, current_timestamp)?;
+
+    emit!(RecipientTokenAccountChangeProposed {
+        schedule_id: vesting_schedule.schedule_id,
+        current_account: vesting_schedule.recipient_token_account,
+        proposed_account: new_account,
+        timelock_expiry,
+    });
+
+    msg!("Recipient token account change to {} proposed for schedule {}, confirmable at {}",
+         new_account, vesting_schedule.schedule_id, timelock_expiry);
+    Ok(())
+}
@@ -0,0 +1,22 @@
+use anchor_lang::prelude::*;
+
+use crate::{UpdateProgramConfig, UpgradeAuthorityProposed};
+
+/// Propose a new on-chain record of the intended upgrade authority
+pub fn handler(ctx: Context<UpdateProgramConfig>, new_authority: Pubkey) -> Result<()> {
+    ctx.accounts.program_config.require_writable()?;
+
+    let current_timestamp = Clock::get()?.unix_timestamp;
+    let program_config = &mut ctx.accounts.program_config;
+    let timelock_expiry = program_config.propose_upgrade_authority(new_authority, current_timestamp)?;
+
+    emit!(UpgradeAuthorityProposed {
+        admin: ctx.accounts.admin.key(),
+        current_authority: program_config.upgrade_authority,
+        proposed_authority: new_authority,
+        timelock_expiry,
+    });
+
+    msg!("Upgrade authority change to {} proposed, confirmable at {}", new_authority, timelock_expiry);
+    Ok(())
+}
@@ -0,0 +1,41 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::state::VestingSchedule;
+use crate::SchedulePurged;
+
+/// Context for deleting an archived schedule after its retention period
+#[derive(Accounts)]
+pub struct PurgeArchived<'info> {
+    /// Receives the schedule account's rent
+    #[account(mut)]
+    pub beneficiary: Signer<'info>,
+
+    /// Archived schedule to delete
+    /// Security: Retention is checked in the handler before Anchor closes the account
+    #[account(
+        mut,
+        seeds = [VESTING_SCHEDULE_SEED, vesting_schedule.schedule_id.to_le_bytes().as_ref()],
+        bump = vesting_schedule.bump,
+        close = beneficiary
+    )]
+    pub vesting_schedule: Account<'info, VestingSchedule>,
+}
+
+/// Delete an archived schedule once its retention period has elapsed
+pub fn handler(ctx: Context<PurgeArchived>) -> Result<()> {
+    let current_timestamp = Clock::get()?.unix_timestamp;
+    let vesting_schedule = &ctx.accounts.vesting_schedule;
+    vesting_schedule.require_purgeable(current_timestamp)?;
+
+    emit!(SchedulePurged {
+        schedule_id: vesting_schedule.schedule_id,
+        beneficiary: ctx.accounts.beneficiary.key(),
+        timestamp: current_timestamp,
+    });
+
+    // The vesting_schedule account is closed automatically by Anchor via the `close` constraint
+    msg!("Purged archived vesting schedule {}. Rent returned to {}.",
+         vesting_schedule.schedule_id, ctx.accounts.beneficiary.key());
+    Ok(())
+}
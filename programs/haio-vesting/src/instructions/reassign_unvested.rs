@@ -0,0 +1,214 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::constants::*;
+use crate::errors::VestingError;
+use crate::state::{ProgramConfig, VestingSchedule, MintScheduleCount};
+use crate::{transfer_from_vault_checked, ReassignUnvestedParams, UnvestedReassigned, VestingScheduleCreated};
+
+/// Context for moving a schedule's unvested remainder into a new schedule
+#[derive(Accounts)]
+#[instruction(new_schedule_id: u64)]
+pub struct ReassignUnvested<'info> {
+    /// Admin signer - pays for the new schedule and vault
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    /// Program configuration account
+    /// Security: Validates admin authority
+    #[account(
+        mut,
+        seeds = [PROGRAM_CONFIG_SEED],
+        bump = program_config.bump,
+        has_one = admin @ VestingError::Unauthorized
+    )]
+    pub program_config: Account<'info, ProgramConfig>,
+
+    /// Schedule whose unvested remainder is reassigned
+    #[account(
+        mut,
+        seeds = [VESTING_SCHEDULE_SEED, source_schedule.schedule_id.to_le_bytes().as_ref()],
+        bump = source_schedule.bump
+    )]
+    pub source_schedule: Account<'info, VestingSchedule>,
+
+    /// Vault of the source schedule
+    /// Security: Authority must be the source_schedule PDA
+    #[account(
+        mut,
+        seeds = [VESTING_VAULT_SEED, source_schedule.schedule_id.to_le_bytes().as_ref()],
+        bump,
+        constraint = source_vault.owner == source_schedule.key() @ VestingError::VaultAuthorityMismatch,
+        constraint = source_vault.mint == source_schedule.mint @ VestingError::MintMismatch
+    )]
+    pub source_vault: InterfaceAccount<'info, TokenAccount>,
+
+    /// New vesting schedule for the unvested remainder
+    #[account(
+        init,
+        payer = admin,
+        space = DISCRIMINATOR_SIZE + VESTING_SCHEDULE_LEN,
+        seeds = [VESTING_SCHEDULE_SEED, new_schedule_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub new_schedule: Account<'info, VestingSchedule>,
+
+    /// Token mint of the source schedule
+    #[account(
+        constraint = mint.key() == source_schedule.mint @ VestingError::MintMismatch
+    )]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    /// Token account of the new recipient
+    /// Security: Must have correct mint (owner validation done in instruction)
+    #[account(
+        constraint = new_recipient_token_account.mint == mint.key() @ VestingError::RecipientAccountMintMismatch
+    )]
+    pub new_recipient_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// Vault of the new schedule
+    /// Security: Authority is set to the new_schedule PDA
+    #[account(
+        init,
+        payer = admin,
+        seeds = [VESTING_VAULT_SEED, new_schedule_id.to_le_bytes().as_ref()],
+        bump,
+        token::mint = mint,
+        token::authority = new_schedule,
+        token::token_program = token_program
+    )]
+    pub new_vault: InterfaceAccount<'info, TokenAccount>,
+
+    /// Open-schedule counter of the mint, the new schedule takes a slot
+    #[account(
+        init_if_needed,
+        payer = admin,
+        space = DISCRIMINATOR_SIZE + MINT_SCHEDULE_COUNT_LEN,
+        seeds = [MINT_SCHEDULE_COUNT_SEED, mint.key().as_ref()],
+        bump
+    )]
+    pub mint_schedule_count: Box<Account<'info, MintScheduleCount>>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+/// Move a schedule's unvested remainder into a new schedule for another recipient
+pub fn handler(
+    ctx: Context<ReassignUnvested>,
+    new_schedule_id: u64,
+    params: ReassignUnvestedParams,
+) -> Result<()> {
+    ctx.accounts.program_config.require_writable()?;
+    ctx.accounts.program_config.require_not_paused()?;
+
+    let current_timestamp = Clock::get()?.unix_timestamp;
+
+    ctx.accounts.source_schedule.require_mutable()?;
+    require!(!ctx.accounts.source_schedule.non_transferable, VestingError::NonTransferableSchedule);
+    require!(params.recipient != Pubkey::default(), VestingError::InvalidRecipient);
+    require!(
+        ctx.accounts.new_recipient_token_account.owner == params.recipient,
+        VestingError::RecipientAccountOwnerMismatch
+    );
+    require!(
+        params.cliff_timestamp <= params.vesting_start_timestamp &&
+        params.vesting_start_timestamp < params.vesting_end_timestamp,
+        VestingError::InvalidTimestamps
+    );
+    require!(
+        new_schedule_id == ctx.accounts.program_config.total_schedules,
+        VestingError::ScheduleIdConflict
+    );
+
+    // ================================================================================================
+    // SOURCE SPLIT AND VAULT INVARIANTS
+    // ================================================================================================
+
+    let source_schedule = &mut ctx.accounts.source_schedule;
+    require!(source_schedule.is_initialized, VestingError::InvalidVestingScheduleData);
+    require!(!source_schedule.is_fully_processed(), VestingError::ScheduleFullyProcessed);
+
+    require!(source_schedule.is_solvent(ctx.accounts.source_vault.amount), VestingError::InvalidVaultState);
+
+    let old_total_amount = source_schedule.total_amount;
+    let unvested_amount = source_schedule.split_unvested(current_timestamp)?;
+
+    let source_category = source_schedule.source_category.clone();
+    let round_up = source_schedule.round_up;
+    let min_claim_interval = source_schedule.min_claim_interval;
+
+    // ================================================================================================
+    // NEW SCHEDULE INITIALIZATION
+    // ================================================================================================
+
+    ctx.accounts.new_schedule.init(
+        new_schedule_id,
+        params.recipient,
+        ctx.accounts.new_recipient_token_account.key(),
+        ctx.accounts.mint.key(),
+        ctx.accounts.new_vault.key(),
+        ctx.accounts.admin.key(),
+        unvested_amount,
+        params.cliff_timestamp,
+        params.vesting_start_timestamp,
+        params.vesting_end_timestamp,
+        source_category.clone(),
+        round_up,
+        0,
+        min_claim_interval,
+        ctx.bumps.new_schedule,
+    )?;
+
+    transfer_from_vault_checked(
+        &mut ctx.accounts.source_schedule,
+        &ctx.accounts.source_vault,
+        &ctx.accounts.mint,
+        ctx.accounts.new_vault.to_account_info(),
+        ctx.accounts.token_program.to_account_info(),
+        unvested_amount,
+    )?;
+
+    ctx.accounts.program_config.increment_total_schedules()?;
+    ctx.accounts.mint_schedule_count.record_created(
+        ctx.accounts.mint.key(),
+        ctx.bumps.mint_schedule_count,
+        ctx.accounts.program_config.max_schedules_per_mint,
+    )?;
+
+    // ================================================================================================
+    // EVENT EMISSION
+    // ================================================================================================
+
+    let source_schedule = &ctx.accounts.source_schedule;
+    emit!(UnvestedReassigned {
+        source_schedule_id: source_schedule.schedule_id,
+        new_schedule_id,
+        amount: unvested_amount,
+        source_old_total: old_total_amount,
+        source_new_total: source_schedule.total_amount,
+        source_vesting_end_timestamp: source_schedule.vesting_end_timestamp,
+        timestamp: current_timestamp,
+    });
+
+    emit!(VestingScheduleCreated {
+        schedule_id: new_schedule_id,
+        recipient: params.recipient,
+        mint: ctx.accounts.mint.key(),
+        total_amount: unvested_amount,
+        cliff_timestamp: params.cliff_timestamp,
+        vesting_start_timestamp: params.vesting_start_timestamp,
+        vesting_end_timestamp: params.vesting_end_timestamp,
+        source_category,
+        depositor: ctx.accounts.admin.key(),
+        grant_group_id: 0,
+        label: ctx.accounts.new_schedule.label,
+    });
+
+    msg!(
+        "Reassigned {} unvested tokens from schedule {} to new schedule {} for recipient {}",
+        unvested_amount, source_schedule.schedule_id, new_schedule_id, params.recipient
+    );
+    Ok(())
+}
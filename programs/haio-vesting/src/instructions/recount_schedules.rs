@@ -0,0 +1,63 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::errors::VestingError;
+use crate::state::{ProgramConfig, VestingSchedule};
+use crate::SchedulesRecounted;
+
+/// Context for repairing the sequential schedule counter
+#[derive(Accounts)]
+pub struct RecountSchedules<'info> {
+    /// Admin signer - only admin can repair the schedule counter
+    pub admin: Signer<'info>,
+
+    /// Program configuration account
+    /// Security: Validates admin authority
+    #[account(
+        mut,
+        seeds = [PROGRAM_CONFIG_SEED],
+        bump = program_config.bump,
+        has_one = admin @ VestingError::Unauthorized
+    )]
+    pub program_config: Account<'info, ProgramConfig>,
+
+    /// Highest existing vesting schedule
+    /// Security: Must be an initialized schedule at its canonical PDA
+    #[account(
+        seeds = [VESTING_SCHEDULE_SEED, highest_schedule.schedule_id.to_le_bytes().as_ref()],
+        bump = highest_schedule.bump,
+        constraint = highest_schedule.is_initialized @ VestingError::InvalidVestingScheduleData
+    )]
+    pub highest_schedule: Account<'info, VestingSchedule>,
+
+    /// PDA of the schedule slot right after `highest_schedule`
+    /// CHECK: Only checked to be empty so the repaired counter points at a creatable slot
+    #[account(
+        seeds = [VESTING_SCHEDULE_SEED, highest_schedule.schedule_id.saturating_add(1).to_le_bytes().as_ref()],
+        bump,
+        constraint = next_schedule.data_is_empty() && next_schedule.lamports() == 0 @ VestingError::NextScheduleSlotOccupied
+    )]
+    pub next_schedule: UncheckedAccount<'info>,
+}
+
+/// Repair `total_schedules` when it has fallen behind the schedules that actually exist
+pub fn handler(ctx: Context<RecountSchedules>) -> Result<()> {
+    ctx.accounts.program_config.require_writable()?;
+
+    let highest_schedule_id = ctx.accounts.highest_schedule.schedule_id;
+    let program_config = &mut ctx.accounts.program_config;
+
+    let old_total_schedules = program_config.repair_total_schedules(highest_schedule_id)?;
+
+    emit!(SchedulesRecounted {
+        old_total_schedules,
+        new_total_schedules: program_config.total_schedules,
+        highest_schedule_id,
+    });
+
+    msg!(
+        "Schedule counter repaired from {} to {}",
+        old_total_schedules, program_config.total_schedules
+    );
+    Ok(())
+}
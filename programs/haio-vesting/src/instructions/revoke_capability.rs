@@ -0,0 +1,24 @@
+use anchor_lang::prelude::*;
+
+use crate::{DelegateCapabilitiesUpdated, UpdateProgramConfig};
+
+/// Revoke capability bits from a delegate, removing it once it holds none
+pub fn handler(
+    ctx: Context<UpdateProgramConfig>,
+    delegate: Pubkey,
+    capabilities: u8,
+) -> Result<()> {
+    ctx.accounts.program_config.require_writable()?;
+
+    let (old_capabilities, new_capabilities) =
+        ctx.accounts.program_config.revoke_capability(delegate, capabilities)?;
+
+    emit!(DelegateCapabilitiesUpdated {
+        delegate,
+        old_capabilities,
+        new_capabilities,
+    });
+
+    msg!("Delegate {} capabilities updated from {:#05b} to {:#05b}", delegate, old_capabilities, new_capabilities);
+    Ok(())
+}
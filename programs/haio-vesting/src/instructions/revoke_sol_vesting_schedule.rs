@@ -0,0 +1,138 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::errors::VestingError;
+use crate::instructions::crank_sol_vesting_schedule::{drain_native_vault, transfer_from_native_vault};
+use crate::state::{ProgramConfig, VestingSchedule};
+use crate::{release_timestamp, TokensReleased, VestingScheduleRevoked};
+
+/// Admin revocation of a native SOL schedule
+#[derive(Accounts)]
+pub struct RevokeSolVestingSchedule<'info> {
+    /// Admin revoking the schedule - receives the clawed-back lamports
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    /// Program configuration - validates admin authority, tracks the release rate window
+    #[account(
+        mut,
+        seeds = [PROGRAM_CONFIG_SEED],
+        bump = program_config.bump,
+        has_one = admin @ VestingError::Unauthorized
+    )]
+    pub program_config: Account<'info, ProgramConfig>,
+
+    /// Native vesting schedule being revoked
+    #[account(
+        mut,
+        seeds = [VESTING_SCHEDULE_SEED, vesting_schedule.schedule_id.to_le_bytes().as_ref()],
+        bump = vesting_schedule.bump,
+        constraint = vesting_schedule.is_native @ VestingError::ScheduleNotNative
+    )]
+    pub vesting_schedule: Account<'info, VestingSchedule>,
+
+    /// System-owned vault PDA of the schedule
+    #[account(
+        mut,
+        seeds = [NATIVE_VAULT_SEED, vesting_schedule.schedule_id.to_le_bytes().as_ref()],
+        bump,
+        address = vesting_schedule.token_vault @ VestingError::InvalidVaultState
+    )]
+    pub native_vault: SystemAccount<'info>,
+
+    /// Recipient wallet receiving the vested-but-unreleased lamports
+    #[account(mut, address = vesting_schedule.recipient @ VestingError::InvalidRecipient)]
+    pub recipient: SystemAccount<'info>,
+
+    /// Depositor receiving the vault's rent reserve
+    #[account(mut, address = vesting_schedule.depositor @ VestingError::Unauthorized)]
+    pub depositor: SystemAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Pay out everything vested so far, claw the unvested lamports back to the admin and return
+/// the rent reserve to the depositor, leaving the vault empty
+/// Security: Finalized schedules cannot be revoked, and a revoked schedule cannot be revoked again
+pub fn handler(ctx: Context<RevokeSolVestingSchedule>) -> Result<()> {
+    ctx.accounts.program_config.require_writable()?;
+    ctx.accounts.program_config.require_not_paused()?;
+
+    let vesting_schedule = &ctx.accounts.vesting_schedule;
+    require!(vesting_schedule.is_initialized, VestingError::InvalidVestingScheduleData);
+    require!(!vesting_schedule.revoked, VestingError::ScheduleRevoked);
+    vesting_schedule.require_mutable()?;
+    require!(!vesting_schedule.vault_closed, VestingError::InvalidVaultState);
+
+    let current_timestamp = release_timestamp(&ctx.accounts.program_config)?;
+    let schedule_id = vesting_schedule.schedule_id;
+    let rent_reserve = Rent::get()?.minimum_balance(0);
+    let vault_amount = ctx.accounts.native_vault.lamports().saturating_sub(rent_reserve);
+
+    let owed_amount = vesting_schedule.get_transferable_amount(current_timestamp)?;
+    let paid_amount = owed_amount.min(vault_amount);
+    let clawed_back_amount = vault_amount - paid_amount;
+    // Both the payout and the clawback leave the program
+    ctx.accounts.program_config.record_outflow(vault_amount, current_timestamp)?;
+
+    if paid_amount > 0 {
+        transfer_from_native_vault(
+            &ctx.accounts.system_program,
+            &ctx.accounts.native_vault,
+            schedule_id,
+            ctx.bumps.native_vault,
+            ctx.accounts.recipient.to_account_info(),
+            paid_amount,
+        )?;
+    }
+
+    if clawed_back_amount > 0 {
+        transfer_from_native_vault(
+            &ctx.accounts.system_program,
+            &ctx.accounts.native_vault,
+            schedule_id,
+            ctx.bumps.native_vault,
+            ctx.accounts.admin.to_account_info(),
+            clawed_back_amount,
+        )?;
+    }
+
+    ctx.accounts.program_config.record_distribution(paid_amount)?;
+    ctx.accounts.program_config.record_rounding_dust(ctx.accounts.vesting_schedule.release_truncation(current_timestamp)?)?;
+    let vesting_schedule = &mut ctx.accounts.vesting_schedule;
+    vesting_schedule.revoke(paid_amount)?;
+    if paid_amount > 0 {
+        vesting_schedule.last_claim_timestamp = current_timestamp;
+        emit!(TokensReleased {
+            schedule_id,
+            recipient: vesting_schedule.recipient,
+            mint: Pubkey::default(),
+            amount: paid_amount,
+            source_category: vesting_schedule.source_category.clone(),
+            timestamp: current_timestamp,
+            total_released: vesting_schedule.amount_transferred,
+            keeper_fee: 0,
+        });
+    }
+
+    emit!(VestingScheduleRevoked {
+        schedule_id,
+        vested_paid_amount: paid_amount,
+        clawed_back_amount,
+        total_released: vesting_schedule.amount_transferred,
+        timestamp: current_timestamp,
+    });
+
+    drain_native_vault(
+        &mut ctx.accounts.vesting_schedule,
+        &ctx.accounts.native_vault,
+        ctx.bumps.native_vault,
+        ctx.accounts.depositor.to_account_info(),
+        &ctx.accounts.system_program,
+        current_timestamp,
+    )?;
+
+    msg!("Native schedule {} revoked: {} lamports paid to recipient, {} clawed back",
+         schedule_id, paid_amount, clawed_back_amount);
+    Ok(())
+}
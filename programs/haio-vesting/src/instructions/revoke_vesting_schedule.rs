@@ -0,0 +1,153 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::constants::*;
+use crate::errors::VestingError;
+use crate::state::{ProgramConfig, VestingSchedule};
+use crate::{release_timestamp, token_account_balance, transfer_from_vault_checked, TokensReleased, VestingScheduleRevoked};
+
+/// Admin revocation of a schedule
+#[derive(Accounts)]
+pub struct RevokeVestingSchedule<'info> {
+    /// Admin revoking the schedule - receives the clawed-back tokens
+    pub admin: Signer<'info>,
+
+    /// Program configuration - validates admin authority, tracks the release rate window
+    #[account(
+        mut,
+        seeds = [PROGRAM_CONFIG_SEED],
+        bump = program_config.bump,
+        has_one = admin @ VestingError::Unauthorized
+    )]
+    pub program_config: Account<'info, ProgramConfig>,
+
+    /// Vesting schedule being revoked
+    #[account(
+        mut,
+        seeds = [VESTING_SCHEDULE_SEED, vesting_schedule.schedule_id.to_le_bytes().as_ref()],
+        bump = vesting_schedule.bump
+    )]
+    pub vesting_schedule: Account<'info, VestingSchedule>,
+
+    /// Vesting vault that holds the tokens for this specific schedule
+    /// Security: Authority must be the vesting_schedule PDA
+    #[account(
+        mut,
+        seeds = [VESTING_VAULT_SEED, vesting_schedule.schedule_id.to_le_bytes().as_ref()],
+        bump,
+        constraint = vesting_vault.owner == vesting_schedule.key() @ VestingError::VaultAuthorityMismatch,
+        constraint = vesting_vault.mint == vesting_schedule.mint @ VestingError::MintMismatch
+    )]
+    pub vesting_vault: InterfaceAccount<'info, TokenAccount>,
+
+    /// Recipient token account that receives the vested-but-unclaimed tokens
+    /// Security: Must be the account stored in vesting_schedule and still owned by the recipient
+    #[account(
+        mut,
+        constraint = recipient_token_account.key() == vesting_schedule.recipient_token_account @ VestingError::RecipientAccountMismatch,
+        constraint = recipient_token_account.owner == vesting_schedule.recipient @ VestingError::RecipientAccountOwnerMismatch,
+        constraint = recipient_token_account.mint == vesting_schedule.mint @ VestingError::RecipientAccountMintMismatch
+    )]
+    pub recipient_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// Token account receiving the unvested balance
+    /// Security: Must be owned by the admin and hold the schedule's mint
+    #[account(
+        mut,
+        constraint = clawback_token_account.owner == admin.key() @ VestingError::Unauthorized,
+        constraint = clawback_token_account.mint == vesting_schedule.mint @ VestingError::MintMismatch
+    )]
+    pub clawback_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// Token mint of the schedule
+    #[account(address = vesting_schedule.mint @ VestingError::MintMismatch)]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+/// Pay out everything vested so far, claw the rest of the vault back to the admin and stop
+/// the schedule for good
+/// Security: Finalized schedules cannot be revoked, and a revoked schedule cannot be revoked again
+pub fn handler(ctx: Context<RevokeVestingSchedule>) -> Result<()> {
+    ctx.accounts.program_config.require_writable()?;
+    ctx.accounts.program_config.require_not_paused()?;
+
+    let vesting_schedule = &ctx.accounts.vesting_schedule;
+    require!(vesting_schedule.is_initialized, VestingError::InvalidVestingScheduleData);
+    require!(!vesting_schedule.revoked, VestingError::ScheduleRevoked);
+    vesting_schedule.require_mutable()?;
+
+    let current_timestamp = release_timestamp(&ctx.accounts.program_config)?;
+    let vault_amount = ctx.accounts.vesting_vault.amount;
+
+    // Vested tokens still owed to the recipient, including any accrued pending release
+    let owed_amount = vesting_schedule
+        .get_transferable_amount(current_timestamp)?
+        .checked_add(vesting_schedule.pending_release)
+        .ok_or(VestingError::MathOverflow)?;
+    let paid_amount = owed_amount.min(vault_amount);
+    let clawed_back_amount = vault_amount - paid_amount;
+    // Both the payout and the clawback leave the program
+    ctx.accounts.program_config.record_outflow(vault_amount, current_timestamp)?;
+
+    let mut delivered_amount = 0;
+    if paid_amount > 0 {
+        let recipient_info = ctx.accounts.recipient_token_account.to_account_info();
+        let balance_before = ctx.accounts.recipient_token_account.amount;
+        transfer_from_vault_checked(
+            &mut ctx.accounts.vesting_schedule,
+            &ctx.accounts.vesting_vault,
+            &ctx.accounts.mint,
+            recipient_info.clone(),
+            ctx.accounts.token_program.to_account_info(),
+            paid_amount,
+        )?;
+        delivered_amount = token_account_balance(&recipient_info)?
+            .checked_sub(balance_before)
+            .ok_or(VestingError::MathOverflow)?;
+    }
+
+    if clawed_back_amount > 0 {
+        transfer_from_vault_checked(
+            &mut ctx.accounts.vesting_schedule,
+            &ctx.accounts.vesting_vault,
+            &ctx.accounts.mint,
+            ctx.accounts.clawback_token_account.to_account_info(),
+            ctx.accounts.token_program.to_account_info(),
+            clawed_back_amount,
+        )?;
+    }
+
+    ctx.accounts.program_config.record_distribution(delivered_amount)?;
+    ctx.accounts.program_config.record_rounding_dust(ctx.accounts.vesting_schedule.release_truncation(current_timestamp)?)?;
+    let vesting_schedule = &mut ctx.accounts.vesting_schedule;
+    // Credit the net payout first so revoke() freezes total_amount at what the vault was debited
+    vesting_schedule.record_release(paid_amount, delivered_amount)?;
+    vesting_schedule.revoke(0)?;
+    if paid_amount > 0 {
+        vesting_schedule.last_claim_timestamp = current_timestamp;
+        emit!(TokensReleased {
+            schedule_id: vesting_schedule.schedule_id,
+            recipient: vesting_schedule.recipient,
+            mint: vesting_schedule.mint,
+            amount: delivered_amount,
+            source_category: vesting_schedule.source_category.clone(),
+            timestamp: current_timestamp,
+            total_released: vesting_schedule.amount_transferred,
+            keeper_fee: 0,
+        });
+    }
+
+    emit!(VestingScheduleRevoked {
+        schedule_id: vesting_schedule.schedule_id,
+        vested_paid_amount: paid_amount,
+        clawed_back_amount,
+        total_released: vesting_schedule.amount_transferred,
+        timestamp: current_timestamp,
+    });
+
+    msg!("Schedule {} revoked: {} paid to recipient, {} clawed back",
+         vesting_schedule.schedule_id, paid_amount, clawed_back_amount);
+    Ok(())
+}
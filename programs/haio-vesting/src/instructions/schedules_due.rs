@@ -0,0 +1,46 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::errors::VestingError;
+use crate::state::VestingSchedule;
+use crate::ScanSchedules;
+
+/// Keeper work queue: the schedules with the most to release right now
+pub fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, ScanSchedules<'info>>,
+    max_results: u8,
+) -> Result<Vec<u64>> {
+    require!(
+        !ctx.remaining_accounts.is_empty() && ctx.remaining_accounts.len() <= MAX_SCAN_BATCH_SIZE,
+        VestingError::InvalidRemainingAccounts
+    );
+    require!(
+        max_results > 0 && max_results as usize <= MAX_CRANK_BATCH_SIZE,
+        VestingError::InvalidBatchSize
+    );
+
+    let current_timestamp = Clock::get()?.unix_timestamp;
+    let mut seen_schedules: Vec<Pubkey> = Vec::with_capacity(ctx.remaining_accounts.len());
+    let mut due: Vec<(u64, u64)> = Vec::new();
+
+    for schedule_info in ctx.remaining_accounts.iter() {
+        if seen_schedules.contains(schedule_info.key) {
+            continue;
+        }
+        seen_schedules.push(*schedule_info.key);
+
+        let vesting_schedule = Account::<VestingSchedule>::try_from(schedule_info)?;
+        let transferable_amount = vesting_schedule.get_transferable_amount(current_timestamp)?;
+        if transferable_amount > 0 {
+            due.push((vesting_schedule.schedule_id, transferable_amount));
+        }
+    }
+
+    // Stable sort keeps input order among equal amounts
+    due.sort_by_key(|&(_, amount)| std::cmp::Reverse(amount));
+    Ok(due
+        .into_iter()
+        .take(max_results as usize)
+        .map(|(schedule_id, _)| schedule_id)
+        .collect())
+}
@@ -0,0 +1,14 @@
+use anchor_lang::prelude::*;
+
+use crate::UpdateConfigPolicy;
+
+/// Allow or forbid creating placeholder schedules with `total_amount == 0`
+pub fn handler(ctx: Context<UpdateConfigPolicy>, allow_zero_amount: bool) -> Result<()> {
+    ctx.accounts.program_config.require_writable()?;
+
+    let program_config = &mut ctx.accounts.program_config;
+    program_config.allow_zero_amount = allow_zero_amount;
+
+    msg!("Zero-amount placeholder schedules set to {}", allow_zero_amount);
+    Ok(())
+}
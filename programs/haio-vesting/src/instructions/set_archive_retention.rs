@@ -0,0 +1,17 @@
+use anchor_lang::prelude::*;
+
+use crate::UpdateConfigPolicy;
+
+/// Set how long closed schedules are kept as archived records (0 = delete on close)
+pub fn handler(
+    ctx: Context<UpdateConfigPolicy>,
+    archive_retention_period: u64,
+) -> Result<()> {
+    ctx.accounts.program_config.require_writable()?;
+
+    let program_config = &mut ctx.accounts.program_config;
+    program_config.archive_retention_period = archive_retention_period;
+
+    msg!("Archive retention period set to {} seconds", archive_retention_period);
+    Ok(())
+}
@@ -0,0 +1,17 @@
+use anchor_lang::prelude::*;
+
+use crate::UpdateConfigPolicy;
+
+/// Enable or disable stretching underfunded schedules instead of clamping releases
+pub fn handler(
+    ctx: Context<UpdateConfigPolicy>,
+    auto_extend_underfunded: bool,
+) -> Result<()> {
+    ctx.accounts.program_config.require_writable()?;
+
+    let program_config = &mut ctx.accounts.program_config;
+    program_config.auto_extend_underfunded = auto_extend_underfunded;
+
+    msg!("Auto-extend for underfunded schedules set to {}", auto_extend_underfunded);
+    Ok(())
+}
@@ -0,0 +1,29 @@
+use anchor_lang::prelude::*;
+
+use crate::state::SourceCategory;
+use crate::{CategoryCapUpdated, UpdateConfigPolicy};
+
+/// Set or clear (`cap == 0`) the allocation cap of a category
+pub fn handler(
+    ctx: Context<UpdateConfigPolicy>,
+    source_category: SourceCategory,
+    cap: u64,
+) -> Result<()> {
+    ctx.accounts.program_config.require_writable()?;
+
+    let program_config = &mut ctx.accounts.program_config;
+    let index = source_category.index();
+    let old_cap = program_config.category_caps[index];
+    program_config.category_caps[index] = cap;
+
+    emit!(CategoryCapUpdated {
+        source_category: source_category.clone(),
+        old_cap,
+        new_cap: cap,
+        allocated: program_config.category_allocated[index],
+    });
+
+    msg!("Cap for {:?} updated from {} to {} ({} allocated)",
+         source_category, old_cap, cap, program_config.category_allocated[index]);
+    Ok(())
+}
@@ -0,0 +1,32 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::VestingError;
+use crate::state::{CategoryTemplate, SourceCategory};
+use crate::{CategoryTemplateUpdated, UpdateConfigPolicy};
+
+/// Set or clear (`vesting_duration == 0`) the default timing template of a category
+pub fn handler(
+    ctx: Context<UpdateConfigPolicy>,
+    source_category: SourceCategory,
+    template: CategoryTemplate,
+) -> Result<()> {
+    ctx.accounts.program_config.require_writable()?;
+
+    require!(
+        template.cliff_duration >= 0 && template.vesting_duration >= 0,
+        VestingError::InvalidTimestamps
+    );
+
+    let program_config = &mut ctx.accounts.program_config;
+    program_config.category_templates[source_category.index()] = template;
+
+    emit!(CategoryTemplateUpdated {
+        source_category: source_category.clone(),
+        cliff_duration: template.cliff_duration,
+        vesting_duration: template.vesting_duration,
+    });
+
+    msg!("Template for {:?} set to {}s cliff + {}s vesting",
+         source_category, template.cliff_duration, template.vesting_duration);
+    Ok(())
+}
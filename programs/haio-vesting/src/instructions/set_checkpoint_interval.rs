@@ -0,0 +1,26 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::VestingError;
+use crate::{CheckpointIntervalUpdated, UpdateCrankPolicy};
+
+/// Set the release milestone step for `ReleaseCheckpoint` events (0 disables them)
+pub fn handler(ctx: Context<UpdateCrankPolicy>, checkpoint_interval_pct: u8) -> Result<()> {
+    ctx.accounts.program_config.require_writable()?;
+    require!(
+        checkpoint_interval_pct <= 100
+            && (checkpoint_interval_pct == 0 || 100 % checkpoint_interval_pct == 0),
+        VestingError::InvalidCheckpointInterval
+    );
+
+    let program_config = &mut ctx.accounts.program_config;
+    let old_checkpoint_interval_pct = program_config.checkpoint_interval_pct;
+    program_config.checkpoint_interval_pct = checkpoint_interval_pct;
+
+    emit!(CheckpointIntervalUpdated {
+        old_checkpoint_interval_pct,
+        new_checkpoint_interval_pct: checkpoint_interval_pct,
+    });
+
+    msg!("Release checkpoint interval set to {}%", checkpoint_interval_pct);
+    Ok(())
+}
@@ -0,0 +1,21 @@
+use anchor_lang::prelude::*;
+
+use crate::{ClaimIntervalCrankEnforcementUpdated, UpdateCrankPolicy};
+
+/// Choose whether keeper cranks also respect schedules' `min_claim_interval`
+pub fn handler(
+    ctx: Context<UpdateCrankPolicy>,
+    enforce_claim_interval_on_crank: bool,
+) -> Result<()> {
+    ctx.accounts.program_config.require_writable()?;
+
+    let program_config = &mut ctx.accounts.program_config;
+    program_config.enforce_claim_interval_on_crank = enforce_claim_interval_on_crank;
+
+    emit!(ClaimIntervalCrankEnforcementUpdated {
+        enforce_claim_interval_on_crank,
+    });
+
+    msg!("Claim interval enforcement on cranks set to {}", enforce_claim_interval_on_crank);
+    Ok(())
+}
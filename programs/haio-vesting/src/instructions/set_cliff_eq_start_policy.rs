@@ -0,0 +1,24 @@
+use anchor_lang::prelude::*;
+
+use crate::state::SourceCategory;
+use crate::{CliffStartPolicyUpdated, UpdateConfigPolicy};
+
+/// Require `cliff_timestamp == vesting_start_timestamp` for new schedules of a category
+pub fn handler(
+    ctx: Context<UpdateConfigPolicy>,
+    source_category: SourceCategory,
+    required: bool,
+) -> Result<()> {
+    ctx.accounts.program_config.require_writable()?;
+
+    let program_config = &mut ctx.accounts.program_config;
+    program_config.set_requires_cliff_eq_start(&source_category, required);
+
+    emit!(CliffStartPolicyUpdated {
+        source_category: source_category.clone(),
+        required,
+    });
+
+    msg!("Cliff/start equality for {:?} set to {}", source_category, required);
+    Ok(())
+}
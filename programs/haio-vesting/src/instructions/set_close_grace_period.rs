@@ -0,0 +1,14 @@
+use anchor_lang::prelude::*;
+
+use crate::UpdateConfigPolicy;
+
+/// Set how long a completed schedule must stay open before it can be closed (0 = no wait)
+pub fn handler(ctx: Context<UpdateConfigPolicy>, close_grace_period: u64) -> Result<()> {
+    ctx.accounts.program_config.require_writable()?;
+
+    let program_config = &mut ctx.accounts.program_config;
+    program_config.close_grace_period = close_grace_period;
+
+    msg!("Close grace period set to {} seconds", close_grace_period);
+    Ok(())
+}
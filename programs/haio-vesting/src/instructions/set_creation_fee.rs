@@ -0,0 +1,26 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::VestingError;
+use crate::{math, UpdateProgramConfig};
+
+/// Configure the one-time creation fee charged on top of each new schedule's deposit
+pub fn handler(
+    ctx: Context<UpdateProgramConfig>,
+    creation_fee_bps: u16,
+    fee_destination: Pubkey,
+) -> Result<()> {
+    ctx.accounts.program_config.require_writable()?;
+
+    math::validate_bps(creation_fee_bps)?;
+    require!(
+        creation_fee_bps == 0 || fee_destination != Pubkey::default(),
+        VestingError::InvalidFeeDestination
+    );
+
+    let program_config = &mut ctx.accounts.program_config;
+    program_config.creation_fee_bps = creation_fee_bps;
+    program_config.fee_destination = fee_destination;
+
+    msg!("Creation fee set to {} bps, paid to {}", creation_fee_bps, fee_destination);
+    Ok(())
+}
@@ -0,0 +1,20 @@
+use anchor_lang::prelude::*;
+
+use crate::{EmitSkipEventsUpdated, UpdateCrankPolicy};
+
+/// Enable or disable `CrankSkipped` events for schedules a crank passes over
+pub fn handler(ctx: Context<UpdateCrankPolicy>, emit_skip_events: bool) -> Result<()> {
+    ctx.accounts.program_config.require_writable()?;
+
+    let program_config = &mut ctx.accounts.program_config;
+    let old_emit_skip_events = program_config.emit_skip_events;
+    program_config.emit_skip_events = emit_skip_events;
+
+    emit!(EmitSkipEventsUpdated {
+        old_emit_skip_events,
+        new_emit_skip_events: emit_skip_events,
+    });
+
+    msg!("Crank skip events set to {}", emit_skip_events);
+    Ok(())
+}
@@ -0,0 +1,25 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::errors::VestingError;
+use crate::{math, KeeperRewardUpdated, UpdateCrankPolicy};
+
+/// Update the keeper reward rate
+pub fn handler(ctx: Context<UpdateCrankPolicy>, keeper_reward_bps: u16) -> Result<()> {
+    ctx.accounts.program_config.require_writable()?;
+
+    math::validate_bps(keeper_reward_bps)?;
+    require!(keeper_reward_bps <= MAX_KEEPER_REWARD_BPS, VestingError::KeeperRewardTooHigh);
+
+    let program_config = &mut ctx.accounts.program_config;
+    let old_keeper_reward_bps = program_config.keeper_reward_bps;
+    program_config.keeper_reward_bps = keeper_reward_bps;
+
+    emit!(KeeperRewardUpdated {
+        old_keeper_reward_bps,
+        new_keeper_reward_bps: keeper_reward_bps,
+    });
+
+    msg!("Keeper reward updated from {} bps to {} bps", old_keeper_reward_bps, keeper_reward_bps);
+    Ok(())
+}
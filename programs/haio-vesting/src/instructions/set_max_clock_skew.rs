@@ -0,0 +1,20 @@
+use anchor_lang::prelude::*;
+
+use crate::{MaxClockSkewUpdated, UpdateConfigPolicy};
+
+/// Set the clock skew tolerance for releases in seconds (0 disables the check)
+pub fn handler(ctx: Context<UpdateConfigPolicy>, max_clock_skew: u64) -> Result<()> {
+    ctx.accounts.program_config.require_writable()?;
+
+    let program_config = &mut ctx.accounts.program_config;
+    let old_max_clock_skew = program_config.max_clock_skew;
+    program_config.max_clock_skew = max_clock_skew;
+
+    emit!(MaxClockSkewUpdated {
+        old_max_clock_skew,
+        new_max_clock_skew: max_clock_skew,
+    });
+
+    msg!("Max clock skew updated from {} to {}", old_max_clock_skew, max_clock_skew);
+    Ok(())
+}
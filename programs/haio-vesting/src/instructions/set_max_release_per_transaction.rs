@@ -0,0 +1,26 @@
+use anchor_lang::prelude::*;
+
+use crate::{MaxReleasePerTransactionUpdated, UpdateCrankPolicy};
+
+/// Update the maximum total released by a single batch crank transaction
+pub fn handler(
+    ctx: Context<UpdateCrankPolicy>,
+    max_release_per_transaction: u64,
+) -> Result<()> {
+    ctx.accounts.program_config.require_writable()?;
+
+    let program_config = &mut ctx.accounts.program_config;
+    let old_max_release_per_transaction = program_config.max_release_per_transaction;
+    program_config.max_release_per_transaction = max_release_per_transaction;
+
+    emit!(MaxReleasePerTransactionUpdated {
+        old_max_release_per_transaction,
+        new_max_release_per_transaction: max_release_per_transaction,
+    });
+
+    msg!(
+        "Max release per transaction updated from {} to {}",
+        old_max_release_per_transaction, max_release_per_transaction
+    );
+    Ok(())
+}
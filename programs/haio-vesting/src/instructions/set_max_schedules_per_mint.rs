@@ -0,0 +1,17 @@
+use anchor_lang::prelude::*;
+
+use crate::UpdateConfigPolicy;
+
+/// Set the maximum number of schedules of one mint that may exist at once (0 = unlimited)
+pub fn handler(
+    ctx: Context<UpdateConfigPolicy>,
+    max_schedules_per_mint: u64,
+) -> Result<()> {
+    ctx.accounts.program_config.require_writable()?;
+
+    let program_config = &mut ctx.accounts.program_config;
+    program_config.max_schedules_per_mint = max_schedules_per_mint;
+
+    msg!("Maximum schedules per mint set to {}", max_schedules_per_mint);
+    Ok(())
+}
@@ -0,0 +1,22 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::VestingError;
+use crate::{MinCrankIntervalUpdated, UpdateCrankPolicy};
+
+/// Update the minimum seconds between two crank releases of the same schedule
+pub fn handler(ctx: Context<UpdateCrankPolicy>, min_crank_interval: i64) -> Result<()> {
+    ctx.accounts.program_config.require_writable()?;
+    require!(min_crank_interval >= 0, VestingError::InvalidTimestamps);
+
+    let program_config = &mut ctx.accounts.program_config;
+    let old_min_crank_interval = program_config.min_crank_interval;
+    program_config.min_crank_interval = min_crank_interval;
+
+    emit!(MinCrankIntervalUpdated {
+        old_min_crank_interval,
+        new_min_crank_interval: min_crank_interval,
+    });
+
+    msg!("Min crank interval updated from {} to {} seconds", old_min_crank_interval, min_crank_interval);
+    Ok(())
+}
@@ -0,0 +1,38 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::errors::VestingError;
+use crate::state::ProgramConfig;
+use crate::ProgramPaused;
+
+/// Admin emergency pause toggle
+#[derive(Accounts)]
+pub struct SetPause<'info> {
+    /// Admin signer - only admin can pause or unpause
+    pub admin: Signer<'info>,
+
+    /// Program configuration account
+    /// Security: Validates admin authority
+    #[account(
+        mut,
+        seeds = [PROGRAM_CONFIG_SEED],
+        bump = program_config.bump,
+        has_one = admin @ VestingError::Unauthorized
+    )]
+    pub program_config: Account<'info, ProgramConfig>,
+}
+
+/// Pause or resume every token movement out of vaults
+/// Deliberately allowed in read-only mode so an incident response is never blocked
+pub fn handler(ctx: Context<SetPause>, paused: bool) -> Result<()> {
+    let program_config = &mut ctx.accounts.program_config;
+    program_config.paused = paused;
+
+    emit!(ProgramPaused {
+        paused,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    msg!("Program paused set to {}", paused);
+    Ok(())
+}
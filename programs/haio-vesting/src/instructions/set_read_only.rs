@@ -0,0 +1,18 @@
+use anchor_lang::prelude::*;
+
+use crate::{ReadOnlyModeUpdated, UpdateProgramConfig};
+
+/// Enter or leave read-only (wind-down) mode
+pub fn handler(ctx: Context<UpdateProgramConfig>, read_only: bool) -> Result<()> {
+    let program_config = &mut ctx.accounts.program_config;
+    program_config.read_only = read_only;
+
+    emit!(ReadOnlyModeUpdated {
+        admin: ctx.accounts.admin.key(),
+        read_only,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    msg!("Read-only mode set to {}", read_only);
+    Ok(())
+}
@@ -0,0 +1,14 @@
+use anchor_lang::prelude::*;
+
+use crate::UpdateCrankPolicy;
+
+/// Enable or disable release-batching mode for `crank_vesting_schedule`
+pub fn handler(ctx: Context<UpdateCrankPolicy>, accumulate_releases: bool) -> Result<()> {
+    ctx.accounts.program_config.require_writable()?;
+
+    let program_config = &mut ctx.accounts.program_config;
+    program_config.accumulate_releases = accumulate_releases;
+
+    msg!("Release batching set to {}", accumulate_releases);
+    Ok(())
+}
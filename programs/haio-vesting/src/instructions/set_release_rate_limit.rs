@@ -0,0 +1,29 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::VestingError;
+use crate::{ReleaseRateLimitUpdated, UpdateProgramConfig};
+
+/// Cap the tokens released across every schedule per time window
+pub fn handler(
+    ctx: Context<UpdateProgramConfig>,
+    max_release_per_window: u64,
+    window_duration: u64,
+) -> Result<()> {
+    ctx.accounts.program_config.require_writable()?;
+    require!(
+        max_release_per_window == 0 || window_duration > 0,
+        VestingError::InvalidTimestamps
+    );
+
+    let program_config = &mut ctx.accounts.program_config;
+    program_config.max_release_per_window = max_release_per_window;
+    program_config.release_window_duration = window_duration;
+
+    emit!(ReleaseRateLimitUpdated {
+        max_release_per_window,
+        window_duration,
+    });
+
+    msg!("Release rate limit set to {} per {}s window", max_release_per_window, window_duration);
+    Ok(())
+}
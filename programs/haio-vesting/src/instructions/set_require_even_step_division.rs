@@ -0,0 +1,23 @@
+use anchor_lang::prelude::*;
+
+use crate::{RequireEvenStepDivisionUpdated, UpdateConfigPolicy};
+
+/// Require stepped schedules to split `total_amount` evenly across their periods
+pub fn handler(
+    ctx: Context<UpdateConfigPolicy>,
+    required: bool,
+) -> Result<()> {
+    ctx.accounts.program_config.require_writable()?;
+
+    let program_config = &mut ctx.accounts.program_config;
+    let old_required = program_config.require_even_step_division;
+    program_config.require_even_step_division = required;
+
+    emit!(RequireEvenStepDivisionUpdated {
+        old_required,
+        new_required: required,
+    });
+
+    msg!("Even step division required: {}", required);
+    Ok(())
+}
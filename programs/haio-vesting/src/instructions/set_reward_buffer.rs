@@ -0,0 +1,20 @@
+use anchor_lang::prelude::*;
+
+use crate::{RewardBufferUpdated, UpdateCrankPolicy};
+
+/// Set the minimum release size that earns the keeper reward
+pub fn handler(ctx: Context<UpdateCrankPolicy>, reward_buffer: u64) -> Result<()> {
+    ctx.accounts.program_config.require_writable()?;
+
+    let program_config = &mut ctx.accounts.program_config;
+    let old_reward_buffer = program_config.reward_buffer;
+    program_config.reward_buffer = reward_buffer;
+
+    emit!(RewardBufferUpdated {
+        old_reward_buffer,
+        new_reward_buffer: reward_buffer,
+    });
+
+    msg!("Reward buffer updated from {} to {}", old_reward_buffer, reward_buffer);
+    Ok(())
+}
@@ -0,0 +1,20 @@
+use anchor_lang::prelude::*;
+
+use crate::{SkipZeroEventsUpdated, UpdateCrankPolicy};
+
+/// Enable or disable the zero-amount `TokensReleased` events a crank emits when nothing moves
+pub fn handler(ctx: Context<UpdateCrankPolicy>, skip_zero_events: bool) -> Result<()> {
+    ctx.accounts.program_config.require_writable()?;
+
+    let program_config = &mut ctx.accounts.program_config;
+    let old_skip_zero_events = program_config.skip_zero_events;
+    program_config.skip_zero_events = skip_zero_events;
+
+    emit!(SkipZeroEventsUpdated {
+        old_skip_zero_events,
+        new_skip_zero_events: skip_zero_events,
+    });
+
+    msg!("Zero-amount release events skipped: {}", skip_zero_events);
+    Ok(())
+}
@@ -0,0 +1,46 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::ID as TOKEN_PROGRAM_ID;
+
+use crate::constants::*;
+use crate::errors::VestingError;
+use crate::state::ProgramConfig;
+
+/// Admin configuration of the swap program used for swap-at-release
+#[derive(Accounts)]
+pub struct SetSwapProgram<'info> {
+    /// Admin signer - only admin can choose the swap program
+    pub admin: Signer<'info>,
+
+    /// Program configuration account
+    /// Security: Validates admin authority
+    #[account(
+        mut,
+        seeds = [PROGRAM_CONFIG_SEED],
+        bump = program_config.bump,
+        has_one = admin @ VestingError::Unauthorized
+    )]
+    pub program_config: Account<'info, ProgramConfig>,
+
+    /// CHECK: Only its key and executable flag are read; it is never invoked here
+    #[account(constraint = swap_program.executable @ VestingError::InvalidSwapProgram)]
+    pub swap_program: UncheckedAccount<'info>,
+}
+
+/// Record `swap_program` as the allowed swap program, or clear it when `enabled` is false
+/// Security: The token program and this program are rejected so a swap CPI could never be
+/// used to move vault tokens directly
+pub fn handler(ctx: Context<SetSwapProgram>, enabled: bool) -> Result<()> {
+    let program_config = &mut ctx.accounts.program_config;
+    program_config.require_writable()?;
+
+    let swap_program = ctx.accounts.swap_program.key();
+    require!(
+        swap_program != TOKEN_PROGRAM_ID && swap_program != crate::ID,
+        VestingError::InvalidSwapProgram
+    );
+
+    program_config.swap_program = if enabled { swap_program } else { Pubkey::default() };
+
+    msg!("Swap program set to {}", program_config.swap_program);
+    Ok(())
+}
@@ -0,0 +1,46 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::errors::VestingError;
+use crate::state::{ProgramConfig, VestingSchedule};
+use crate::WithdrawalAuthorityUpdated;
+
+/// Recipient-only context for delegating stream withdrawals
+#[derive(Accounts)]
+pub struct SetWithdrawalAuthority<'info> {
+    /// Recipient of the schedule
+    pub recipient: Signer<'info>,
+
+    /// Program configuration - checked for read-only mode
+    #[account(
+        seeds = [PROGRAM_CONFIG_SEED],
+        bump = program_config.bump
+    )]
+    pub program_config: Account<'info, ProgramConfig>,
+
+    #[account(
+        mut,
+        seeds = [VESTING_SCHEDULE_SEED, vesting_schedule.schedule_id.to_le_bytes().as_ref()],
+        bump = vesting_schedule.bump,
+        has_one = recipient @ VestingError::Unauthorized
+    )]
+    pub vesting_schedule: Account<'info, VestingSchedule>,
+}
+
+/// Delegate (or revoke with `None`) pull-style withdrawals to another key or program
+pub fn handler(
+    ctx: Context<SetWithdrawalAuthority>,
+    withdrawal_authority: Option<Pubkey>,
+) -> Result<()> {
+    ctx.accounts.program_config.require_writable()?;
+
+    let vesting_schedule = &mut ctx.accounts.vesting_schedule;
+    vesting_schedule.withdrawal_authority = withdrawal_authority;
+
+    emit!(WithdrawalAuthorityUpdated {
+        schedule_id: vesting_schedule.schedule_id,
+        recipient: vesting_schedule.recipient,
+        withdrawal_authority,
+    });
+    Ok(())
+}
@@ -0,0 +1,94 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::constants::*;
+use crate::errors::VestingError;
+use crate::state::{ProgramConfig, VestingSchedule};
+use crate::{transfer_from_vault_checked, RoundingDustSwept};
+
+/// Admin sweep of the vault balance left in a fully processed schedule
+#[derive(Accounts)]
+pub struct SweepRoundingDust<'info> {
+    pub admin: Signer<'info>,
+
+    /// Program configuration - records the swept total
+    /// Security: Validates admin authority
+    #[account(
+        mut,
+        seeds = [PROGRAM_CONFIG_SEED],
+        bump = program_config.bump,
+        has_one = admin @ VestingError::Unauthorized
+    )]
+    pub program_config: Account<'info, ProgramConfig>,
+
+    /// Completed vesting schedule whose vault is swept
+    #[account(
+        mut,
+        seeds = [VESTING_SCHEDULE_SEED, vesting_schedule.schedule_id.to_le_bytes().as_ref()],
+        bump = vesting_schedule.bump,
+        constraint = vesting_schedule.is_fully_processed() @ VestingError::ScheduleNotFullyVested
+    )]
+    pub vesting_schedule: Account<'info, VestingSchedule>,
+
+    /// Vault of the schedule
+    /// Security: Authority must be the vesting_schedule PDA
+    #[account(
+        mut,
+        seeds = [VESTING_VAULT_SEED, vesting_schedule.schedule_id.to_le_bytes().as_ref()],
+        bump,
+        constraint = vesting_vault.owner == vesting_schedule.key() @ VestingError::VaultAuthorityMismatch,
+        constraint = vesting_vault.mint == vesting_schedule.mint @ VestingError::MintMismatch
+    )]
+    pub vesting_vault: InterfaceAccount<'info, TokenAccount>,
+
+    /// Distribution hub token account receiving the dust
+    #[account(
+        mut,
+        address = program_config.distribution_hub @ VestingError::DistributionHubMismatch,
+        constraint = hub_token_account.mint == vesting_schedule.mint @ VestingError::MintMismatch
+    )]
+    pub hub_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// Token mint of the schedule
+    #[account(address = vesting_schedule.mint @ VestingError::MintMismatch)]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+/// Move the balance left in a fully processed schedule's vault to the distribution hub
+pub fn handler(ctx: Context<SweepRoundingDust>) -> Result<u64> {
+    ctx.accounts.program_config.require_writable()?;
+    ctx.accounts.program_config.require_not_paused()?;
+
+    let amount = ctx.accounts.vesting_schedule.sweepable_dust(ctx.accounts.vesting_vault.amount);
+    let schedule_id = ctx.accounts.vesting_schedule.schedule_id;
+    if amount == 0 {
+        msg!("No dust to sweep from schedule {}", schedule_id);
+        return Ok(0);
+    }
+
+    transfer_from_vault_checked(
+        &mut ctx.accounts.vesting_schedule,
+        &ctx.accounts.vesting_vault,
+        &ctx.accounts.mint,
+        ctx.accounts.hub_token_account.to_account_info(),
+        ctx.accounts.token_program.to_account_info(),
+        amount,
+    )?;
+
+    let swept_dust = ctx.accounts.program_config.record_swept_dust(amount)?;
+
+    emit!(RoundingDustSwept {
+        schedule_id,
+        mint: ctx.accounts.vesting_schedule.mint,
+        amount,
+        swept_dust,
+        destination: ctx.accounts.hub_token_account.key(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    msg!("Swept {} dust from schedule {} to the distribution hub. Program total: {}",
+         amount, schedule_id, swept_dust);
+    Ok(amount)
+}
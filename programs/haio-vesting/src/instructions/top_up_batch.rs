@@ -0,0 +1,118 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::constants::*;
+use crate::errors::VestingError;
+use crate::state::ProgramConfig;
+use crate::{deposit_from, load_schedule_and_vault, ScheduleToppedUp};
+
+/// Context for topping up several existing schedules in one transaction
+/// Schedules and vaults are passed as (vesting_schedule, vesting_vault) pairs in remaining accounts
+#[derive(Accounts)]
+pub struct TopUpBatch<'info> {
+    /// Admin signer, or a delegate holding CAPABILITY_CREATE
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    /// Program configuration account - tracks category allocations
+    /// Security: Validates the signer's capability
+    #[account(
+        mut,
+        seeds = [PROGRAM_CONFIG_SEED],
+        bump = program_config.bump,
+        constraint = program_config.has_capability(&admin.key(), CAPABILITY_CREATE) @ VestingError::MissingCapability
+    )]
+    pub program_config: Account<'info, ProgramConfig>,
+
+    /// Token mint shared by every schedule in the batch
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    /// Source token account from which the additional tokens are deposited
+    /// Security: Must be owned by admin and have correct mint
+    #[account(
+        mut,
+        constraint = depositor_token_account.mint == mint.key() @ VestingError::MintMismatch,
+        constraint = depositor_token_account.owner == admin.key() @ VestingError::Unauthorized
+    )]
+    pub depositor_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+/// Add tokens to several existing vesting schedules atomically
+pub fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, TopUpBatch<'info>>,
+    amounts: Vec<u64>,
+) -> Result<Vec<u64>> {
+    ctx.accounts.program_config.require_writable()?;
+
+    // ================================================================================================
+    // BATCH SHAPE AND BALANCE VALIDATION
+    // ================================================================================================
+
+    require!(
+        !amounts.is_empty() && amounts.len() <= MAX_TOP_UP_BATCH_SIZE,
+        VestingError::InvalidBatchSize
+    );
+    require!(
+        ctx.remaining_accounts.len() == amounts.len() * 2,
+        VestingError::InvalidRemainingAccounts
+    );
+
+    // Ensure the admin can cover the whole batch before moving any tokens
+    let mut batch_total: u64 = 0;
+    for amount in amounts.iter() {
+        require!(*amount > 0, VestingError::InvalidAmount);
+        batch_total = batch_total
+            .checked_add(*amount)
+            .ok_or(VestingError::MathOverflow)?;
+    }
+    require!(
+        ctx.accounts.depositor_token_account.amount >= batch_total,
+        VestingError::InsufficientDepositorBalance
+    );
+
+    // ================================================================================================
+    // PER-SCHEDULE DEPOSIT AND STATE UPDATE
+    // ================================================================================================
+
+    let mint_key = ctx.accounts.mint.key();
+    let current_timestamp = Clock::get()?.unix_timestamp;
+    let mut new_totals = Vec::with_capacity(amounts.len());
+
+    for (pair, amount) in ctx.remaining_accounts.chunks(2).zip(amounts.iter()) {
+        let (mut vesting_schedule, vesting_vault) = load_schedule_and_vault(&pair[0], &pair[1])?;
+
+        require_keys_eq!(vesting_schedule.mint, mint_key, VestingError::MintMismatch);
+        require_keys_eq!(vesting_vault.owner, vesting_schedule.key(), VestingError::VaultAuthorityMismatch);
+        let new_total = vesting_schedule.top_up(*amount, current_timestamp)?;
+        ctx.accounts.program_config.allocate_to_category(&vesting_schedule.source_category, *amount)?;
+
+        deposit_from(
+            &ctx.accounts.depositor_token_account,
+            &ctx.accounts.mint,
+            vesting_vault.to_account_info(),
+            &ctx.accounts.admin,
+            &ctx.accounts.token_program,
+            *amount,
+        )?;
+
+        // Persist the updated schedule since it was not part of the validated accounts struct
+        vesting_schedule.exit(&crate::ID)?;
+
+        emit!(ScheduleToppedUp {
+            schedule_id: vesting_schedule.schedule_id,
+            added_amount: *amount,
+            new_total,
+        });
+
+        new_totals.push(new_total);
+    }
+
+    msg!(
+        "Topped up {} vesting schedules with {} tokens in total",
+        new_totals.len(), batch_total
+    );
+
+    Ok(new_totals)
+}
@@ -0,0 +1,96 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::constants::*;
+use crate::errors::VestingError;
+use crate::state::{ProgramConfig, VestingSchedule};
+use crate::{deposit_from, ScheduleToppedUp};
+
+/// Increase of a single schedule's allocation after creation
+#[derive(Accounts)]
+pub struct TopUpSchedule<'info> {
+    /// Admin signer, or a delegate holding CAPABILITY_CREATE
+    pub admin: Signer<'info>,
+
+    /// Program configuration account - tracks category allocations
+    /// Security: Validates the signer's capability
+    #[account(
+        mut,
+        seeds = [PROGRAM_CONFIG_SEED],
+        bump = program_config.bump,
+        constraint = program_config.has_capability(&admin.key(), CAPABILITY_CREATE) @ VestingError::MissingCapability
+    )]
+    pub program_config: Account<'info, ProgramConfig>,
+
+    /// Vesting schedule receiving the additional allocation
+    #[account(
+        mut,
+        seeds = [VESTING_SCHEDULE_SEED, vesting_schedule.schedule_id.to_le_bytes().as_ref()],
+        bump = vesting_schedule.bump
+    )]
+    pub vesting_schedule: Account<'info, VestingSchedule>,
+
+    /// Vesting vault that holds the tokens for this specific schedule
+    /// Security: Authority must be the vesting_schedule PDA
+    #[account(
+        mut,
+        seeds = [VESTING_VAULT_SEED, vesting_schedule.schedule_id.to_le_bytes().as_ref()],
+        bump,
+        constraint = vesting_vault.owner == vesting_schedule.key() @ VestingError::VaultAuthorityMismatch,
+        constraint = vesting_vault.mint == vesting_schedule.mint @ VestingError::MintMismatch
+    )]
+    pub vesting_vault: InterfaceAccount<'info, TokenAccount>,
+
+    /// Token mint - validated against vesting schedule
+    #[account(
+        constraint = mint.key() == vesting_schedule.mint @ VestingError::MintMismatch
+    )]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    /// Source token account from which the additional tokens are deposited
+    /// Security: Must be owned by admin and have correct mint
+    #[account(
+        mut,
+        constraint = depositor_token_account.mint == mint.key() @ VestingError::MintMismatch,
+        constraint = depositor_token_account.owner == admin.key() @ VestingError::Unauthorized
+    )]
+    pub depositor_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+/// Deposit `amount` more tokens into the schedule's vault and add them to `total_amount`
+/// The new total applies to the whole curve: what is unlocked right after the top-up already
+/// includes the added tokens' share of the elapsed vesting period
+pub fn handler(ctx: Context<TopUpSchedule>, amount: u64) -> Result<()> {
+    ctx.accounts.program_config.require_writable()?;
+
+    let current_timestamp = Clock::get()?.unix_timestamp;
+    require!(
+        ctx.accounts.depositor_token_account.amount >= amount,
+        VestingError::InsufficientDepositorBalance
+    );
+
+    let new_total = ctx.accounts.vesting_schedule.top_up(amount, current_timestamp)?;
+    let source_category = ctx.accounts.vesting_schedule.source_category.clone();
+    ctx.accounts.program_config.allocate_to_category(&source_category, amount)?;
+
+    deposit_from(
+        &ctx.accounts.depositor_token_account,
+        &ctx.accounts.mint,
+        ctx.accounts.vesting_vault.to_account_info(),
+        &ctx.accounts.admin,
+        &ctx.accounts.token_program,
+        amount,
+    )?;
+
+    let schedule_id = ctx.accounts.vesting_schedule.schedule_id;
+    emit!(ScheduleToppedUp {
+        schedule_id,
+        added_amount: amount,
+        new_total,
+    });
+
+    msg!("Topped up schedule {} with {} tokens, new total {}", schedule_id, amount, new_total);
+    Ok(())
+}
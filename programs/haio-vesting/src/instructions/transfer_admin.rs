@@ -0,0 +1,21 @@
+use anchor_lang::prelude::*;
+
+use crate::{AdminTransferProposed, UpdateProgramConfig};
+
+/// Propose a new admin, who can accept after ADMIN_TRANSFER_TIMELOCK
+pub fn handler(ctx: Context<UpdateProgramConfig>, new_admin: Pubkey) -> Result<()> {
+    ctx.accounts.program_config.require_writable()?;
+
+    let current_timestamp = Clock::get()?.unix_timestamp;
+    let program_config = &mut ctx.accounts.program_config;
+    let timelock_expiry = program_config.propose_admin_transfer(new_admin, current_timestamp)?;
+
+    emit!(AdminTransferProposed {
+        admin: ctx.accounts.admin.key(),
+        proposed_admin: new_admin,
+        timelock_expiry,
+    });
+
+    msg!("Admin transfer to {} proposed, acceptable at {}", new_admin, timelock_expiry);
+    Ok(())
+}
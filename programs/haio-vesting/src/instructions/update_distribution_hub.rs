@@ -0,0 +1,81 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::program_pack::IsInitialized;
+use anchor_spl::token_interface::{Mint, TokenAccount};
+
+use crate::constants::*;
+use crate::errors::VestingError;
+use crate::state::{ProgramConfig, HubUpdateOutcome};
+use crate::{DistributionHubInitialized, DistributionHubUpdateProposed, DistributionHubUpdated};
+
+/// Context for setting or changing the distribution hub
+#[derive(Accounts)]
+pub struct UpdateDistributionHub<'info> {
+    /// Admin signer - only admin can change the distribution hub
+    pub admin: Signer<'info>,
+
+    /// Program configuration account
+    /// Security: Validates admin authority
+    #[account(
+        mut,
+        seeds = [PROGRAM_CONFIG_SEED],
+        bump = program_config.bump,
+        has_one = admin @ VestingError::Unauthorized
+    )]
+    pub program_config: Account<'info, ProgramConfig>,
+
+    /// Mint the hub is expected to hold
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    /// Token account to record as the hub
+    /// Security: Must be an initialized token account of `mint`, so a wallet or arbitrary
+    /// address is rejected here instead of failing later at crank time
+    #[account(
+        constraint = hub_token_account.is_initialized() @ VestingError::InvalidDistributionHub,
+        constraint = hub_token_account.mint == mint.key() @ VestingError::MintMismatch
+    )]
+    pub hub_token_account: InterfaceAccount<'info, TokenAccount>,
+}
+
+/// Set or change the distribution hub token account
+pub fn handler(ctx: Context<UpdateDistributionHub>) -> Result<i64> {
+    ctx.accounts.program_config.require_writable()?;
+
+    let current_timestamp = Clock::get()?.unix_timestamp;
+    let admin = ctx.accounts.admin.key();
+    let new_hub = ctx.accounts.hub_token_account.key();
+    let program_config = &mut ctx.accounts.program_config;
+
+    let seconds_until_confirmable = match program_config.apply_hub_update(new_hub, current_timestamp)? {
+        HubUpdateOutcome::Initialized => {
+            emit!(DistributionHubInitialized {
+                admin,
+                hub: new_hub,
+                timestamp: current_timestamp,
+            });
+            msg!("Distribution hub initialized to {}", new_hub);
+            0
+        }
+        HubUpdateOutcome::Proposed { timelock_expiry } => {
+            emit!(DistributionHubUpdateProposed {
+                admin,
+                current_hub: program_config.distribution_hub,
+                proposed_hub: new_hub,
+                timelock_expiry,
+            });
+            msg!("Distribution hub change to {} proposed, confirmable at {}", new_hub, timelock_expiry);
+            timelock_expiry.saturating_sub(current_timestamp)
+        }
+        HubUpdateOutcome::Confirmed { old_hub } => {
+            emit!(DistributionHubUpdated {
+                admin,
+                old_hub,
+                new_hub,
+                timestamp: current_timestamp,
+            });
+            msg!("Distribution hub updated from {} to {}", old_hub, new_hub);
+            0
+        }
+    };
+
+    Ok(seconds_until_confirmable)
+}
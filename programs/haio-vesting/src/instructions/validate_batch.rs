@@ -0,0 +1,122 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount};
+
+use crate::constants::*;
+use crate::errors::VestingError;
+use crate::math;
+use crate::state::{ProgramConfig, SourceCategory};
+use crate::{validate_schedule_params, BatchEntryValidation, CreateVestingScheduleParams};
+
+/// Dry run of a creation batch, read-only
+#[derive(Accounts)]
+pub struct ValidateBatch<'info> {
+    /// Program configuration - supplies creation policy and fees
+    #[account(
+        seeds = [PROGRAM_CONFIG_SEED],
+        bump = program_config.bump
+    )]
+    pub program_config: Account<'info, ProgramConfig>,
+
+    /// Token mint the batch would vest
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    /// Account that would fund the batch
+    #[account(
+        constraint = depositor_token_account.mint == mint.key() @ VestingError::MintMismatch
+    )]
+    pub depositor_token_account: InterfaceAccount<'info, TokenAccount>,
+}
+
+/// Check each entry like `create_vesting_schedule` would, without creating or transferring
+/// Remaining accounts: one recipient token account per entry, in order
+/// Deposits plus creation fees of the valid entries are accumulated against the depositor
+/// balance, so an entry that would no longer be funded fails with InsufficientDepositorBalance
+pub fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, ValidateBatch<'info>>,
+    params: Vec<CreateVestingScheduleParams>,
+) -> Result<Vec<BatchEntryValidation>> {
+    require!(
+        !params.is_empty() && params.len() == ctx.remaining_accounts.len(),
+        VestingError::InvalidRemainingAccounts
+    );
+
+    let current_timestamp = Clock::get()?.unix_timestamp;
+    let mut committed: u64 = 0;
+    // Allocations earlier entries would add to each category
+    let mut category_committed = [0u64; SourceCategory::COUNT];
+
+    let mut results = Vec::with_capacity(params.len());
+    for (index, (entry, account_info)) in params.iter().zip(ctx.remaining_accounts.iter()).enumerate() {
+        let category_index = entry.source_category.index();
+        let required = validate_entry(&ctx, entry, account_info, current_timestamp)
+            .and_then(|required| {
+                let category_total = category_committed[category_index]
+                    .checked_add(entry.total_amount)
+                    .ok_or(VestingError::MathOverflow)?;
+                require!(
+                    category_total <= ctx.accounts.program_config.category_headroom(&entry.source_category),
+                    VestingError::CategoryCapExceeded
+                );
+
+                let total = committed.checked_add(required).ok_or(VestingError::MathOverflow)?;
+                require!(
+                    total <= ctx.accounts.depositor_token_account.amount,
+                    VestingError::InsufficientDepositorBalance
+                );
+                Ok((total, category_total))
+            });
+
+        let error_code = match required {
+            Ok((total, category_total)) => {
+                committed = total;
+                category_committed[category_index] = category_total;
+                None
+            }
+            Err(error) => Some(error_code(error)),
+        };
+        results.push(BatchEntryValidation { index: index as u32, error_code });
+    }
+
+    let failed = results.iter().filter(|result| result.error_code.is_some()).count();
+    msg!("Validated {} batch entries, {} would fail", results.len(), failed);
+    Ok(results)
+}
+
+/// Validate one entry and return the tokens it would draw from the depositor (deposit + fee)
+fn validate_entry<'info>(
+    ctx: &Context<'_, '_, 'info, 'info, ValidateBatch<'info>>,
+    params: &CreateVestingScheduleParams,
+    account_info: &'info AccountInfo<'info>,
+    current_timestamp: i64,
+) -> Result<u64> {
+    let program_config = &ctx.accounts.program_config;
+    validate_schedule_params(program_config, params, current_timestamp)?;
+
+    let recipient_token_account = InterfaceAccount::<TokenAccount>::try_from(account_info)?;
+    require!(
+        recipient_token_account.mint == ctx.accounts.mint.key(),
+        VestingError::RecipientAccountMintMismatch
+    );
+    require!(
+        recipient_token_account.owner == params.recipient,
+        VestingError::RecipientAccountOwnerMismatch
+    );
+    require!(
+        params.allow_self_return
+            || recipient_token_account.key() != ctx.accounts.depositor_token_account.key(),
+        VestingError::RecipientEqualsDepositor
+    );
+
+    let creation_fee = math::apply_bps(params.total_amount, program_config.creation_fee_bps)?;
+    params.total_amount
+        .checked_add(creation_fee)
+        .ok_or(VestingError::MathOverflow.into())
+}
+
+/// Numeric code of a validation failure, as clients see it in a failed transaction
+fn error_code(error: Error) -> u32 {
+    match error {
+        Error::AnchorError(error) => error.error_code_number,
+        Error::ProgramError(error) => u64::from(error.program_error) as u32,
+    }
+}
@@ -0,0 +1,8 @@
+use anchor_lang::prelude::*;
+
+use crate::ViewVestingSchedule;
+
+/// Check that a schedule's on-chain terms still hash to the `terms_hash` recorded at creation
+pub fn handler(ctx: Context<ViewVestingSchedule>) -> Result<bool> {
+    ctx.accounts.vesting_schedule.verify_terms()
+}
@@ -1,14 +1,20 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{self, Transfer, TokenAccount, Token, Mint};
+use anchor_spl::token_interface::{self, TokenInterface, TransferChecked};
 use anchor_lang::solana_program::program_pack::IsInitialized;
 
 pub mod constants;
+pub mod ed25519;
 pub mod errors;
+pub mod instructions;
+pub mod math;
+pub mod pda;
+pub mod return_data;
 pub mod state;
 
-use state::{ProgramConfig, VestingSchedule, SourceCategory};
+use state::{ProgramConfig, VestingSchedule, CategoryTemplate, SourceCategory, CrankBlockReason, VestingCurve};
 use errors::VestingError;
 use constants::*;
+pub use instructions::*;
 
 declare_id!("Haio3oNYt8MtL9traoQNJ9RXK1XEVaQBBrZZKXt2VXjz");
 
@@ -37,362 +43,792 @@ pub struct CreateVestingScheduleParams {
     pub vesting_start_timestamp: i64,
     pub vesting_end_timestamp: i64,
     pub source_category: SourceCategory,
+    /// Use ceiling division for linear unlocks (favors the recipient), defaults to rounding down
+    pub round_up: bool,
+    /// Logical grant shared with schedules of other mints (0 = standalone)
+    pub grant_group_id: u64,
+    /// Minimum seconds between recipient-initiated claims (0 = no limit)
+    pub min_claim_interval: i64,
+    /// Permit the recipient token account to be the depositor account (deliberate self-vesting)
+    pub allow_self_return: bool,
+    /// Permit a vesting end at or before the current time (deliberate historical schedule)
+    pub allow_past_end: bool,
+    /// SHA-256 of the off-chain agreed terms, see `VestingSchedule::canonical_terms`
+    pub terms_hash: Option<[u8; 32]>,
+    /// Basis points of `total_amount` unlocked at once at the cliff (at most 10000)
+    pub initial_unlock_bps: u16,
+    /// Graded cliff: extra basis points of `total_amount` unlocked at `second_cliff_timestamp`
+    /// (0 = no second cliff)
+    pub second_cliff_timestamp: i64,
+    pub second_cliff_bps: u16,
+    /// Unlock curve; `Stepped` releases the post-cliff allocation in equal tranches
+    pub vesting_curve: VestingCurve,
+    /// Compliance-restricted grant, see `VestingSchedule::non_transferable`
+    pub non_transferable: bool,
+    /// Operator-facing name, UTF-8 padded with trailing zeros (all zeros = no label)
+    pub label: [u8; 32],
 }
 
-// ================================================================================================
-// ACCOUNT VALIDATION STRUCTURES
-// ================================================================================================
+/// Schedule parameters whose timing comes from the category's template
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct CreateFromTemplateParams {
+    pub recipient: Pubkey,
+    pub total_amount: u64,
+    pub source_category: SourceCategory,
+    /// Grant start; the template's cliff and vesting durations are measured from here
+    pub start_time: i64,
+    /// SHA-256 of the off-chain agreed terms, see `VestingSchedule::canonical_terms`
+    pub terms_hash: Option<[u8; 32]>,
+}
 
-#[derive(Accounts)]
-pub struct Initialize<'info> {
-    #[account(mut)]
-    pub admin: Signer<'info>,
-
-    /// Program configuration PDA
-    /// Security: Establishes admin authority for the entire program
-    #[account(
-        init,
-        payer = admin,
-        space = DISCRIMINATOR_SIZE + PROGRAM_CONFIG_LEN,
-        seeds = [PROGRAM_CONFIG_SEED],
-        bump
-    )]
-    pub program_config: Account<'info, ProgramConfig>,
-
-    pub system_program: Program<'info, System>,
-}
-
-#[derive(Accounts)]
-#[instruction(schedule_id: u64)]
-pub struct CreateVestingSchedule<'info> {
-    /// Admin signer - only admin can create vesting schedules
-    #[account(mut)]
-    pub admin: Signer<'info>,
-
-    /// Program configuration account
-    /// Security: Validates admin authority
-    #[account(
-        mut,
-        seeds = [PROGRAM_CONFIG_SEED],
-        bump = program_config.bump,
-        has_one = admin @ VestingError::Unauthorized
-    )]
-    pub program_config: Account<'info, ProgramConfig>,
-
-    /// Vesting schedule PDA - deterministic address based on schedule_id
-    /// Security: Schedule ID must follow sequential order to prevent gaps
-    #[account(
-        init,
-        payer = admin,
-        space = DISCRIMINATOR_SIZE + VESTING_SCHEDULE_LEN,
-        seeds = [VESTING_SCHEDULE_SEED, schedule_id.to_le_bytes().as_ref()],
-        bump
-    )]
-    pub vesting_schedule: Account<'info, VestingSchedule>,
-
-    /// Token mint account
-    pub mint: Account<'info, Mint>,
-
-    /// Source token account from which tokens are deposited
-    /// Security: Must be owned by admin and have correct mint
-    #[account(
-        mut,
-        constraint = depositor_token_account.mint == mint.key() @ VestingError::MintMismatch,
-        constraint = depositor_token_account.owner == admin.key() @ VestingError::Unauthorized
-    )]
-    pub depositor_token_account: Account<'info, TokenAccount>,
-
-    /// Recipient token account that will receive the vested tokens
-    /// Security: Must have correct mint (owner validation done in instruction)
-    #[account(
-        constraint = recipient_token_account.mint == mint.key() @ VestingError::RecipientAccountMintMismatch
-    )]
-    pub recipient_token_account: Account<'info, TokenAccount>,
-
-    /// Vesting vault PDA that holds the tokens
-    /// Security: Authority is set to vesting_schedule PDA, preventing unauthorized access
-    #[account(
-        init,
-        payer = admin,
-        seeds = [VESTING_VAULT_SEED, schedule_id.to_le_bytes().as_ref()],
-        bump,
-        token::mint = mint,
-        token::authority = vesting_schedule
-    )]
-    pub vesting_vault: Account<'info, TokenAccount>,
-
-    pub system_program: Program<'info, System>,
-    pub token_program: Program<'info, Token>,
-    pub rent: Sysvar<'info, Rent>,
-}
-
-/// Individual recipient crank context for direct token transfers
-/// Replaces batch processing with single-schedule processing for enhanced security
-#[derive(Accounts)]
-pub struct CrankVestingSchedules<'info> {
-    /// Program configuration - contains admin authority info
-    #[account(
-        seeds = [PROGRAM_CONFIG_SEED],
-        bump = program_config.bump
-    )]
-    pub program_config: Account<'info, ProgramConfig>,
-
-    /// Vesting schedule that defines the recipient and vesting parameters
-    #[account(
-        mut,
-        seeds = [VESTING_SCHEDULE_SEED, vesting_schedule.schedule_id.to_le_bytes().as_ref()],
-        bump = vesting_schedule.bump
-    )]
-    pub vesting_schedule: Account<'info, VestingSchedule>,
-
-    /// Vesting vault that holds the tokens for this specific schedule
-    /// Security: Authority must be the vesting_schedule PDA
-    #[account(
-        mut,
-        seeds = [VESTING_VAULT_SEED, vesting_schedule.schedule_id.to_le_bytes().as_ref()],
-        bump,
-        constraint = vesting_vault.owner == vesting_schedule.key() @ VestingError::VaultAuthorityMismatch,
-        constraint = vesting_vault.mint == vesting_schedule.mint @ VestingError::MintMismatch
-    )]
-    pub vesting_vault: Account<'info, TokenAccount>,
-
-    /// Recipient token account that receives the vested tokens
-    /// Security: Must match the specific account stored in vesting_schedule
-    /// Security: Must have the same mint as the vesting schedule
-    /// Security: Must be owned by the original recipient (prevents SetAuthority attacks)
-    #[account(
-        mut,
-        constraint = recipient_token_account.key() == vesting_schedule.recipient_token_account @ VestingError::RecipientAccountMismatch,
-        constraint = recipient_token_account.mint == vesting_schedule.mint @ VestingError::RecipientAccountMintMismatch,
-        constraint = recipient_token_account.owner == vesting_schedule.recipient @ VestingError::RecipientAccountOwnerMismatch
-    )]
-    pub recipient_token_account: Account<'info, TokenAccount>,
-
-    /// Token mint - validated against vesting schedule
-    #[account(
-        constraint = mint.key() == vesting_schedule.mint @ VestingError::MintMismatch
-    )]
-    pub mint: Account<'info, Mint>,
-
-    pub token_program: Program<'info, Token>,
-}
-
-/// Context for closing a fully vested and empty schedule
-/// Security: Strict validation ensures only completed schedules can be closed
-#[derive(Accounts)]
-pub struct CloseVestingSchedule<'info> {
-    /// The account that will receive the rent back, typically the original admin or the recipient.
-    /// Must be the signer of the transaction.
-    #[account(mut)]
-    pub beneficiary: Signer<'info>,
-
-    /// The vesting schedule to be closed.
-    /// Security: Must be fully vested to be closed.
-    #[account(
-        mut,
-        seeds = [VESTING_SCHEDULE_SEED, vesting_schedule.schedule_id.to_le_bytes().as_ref()],
-        bump = vesting_schedule.bump,
-        constraint = vesting_schedule.amount_transferred >= vesting_schedule.total_amount @ VestingError::ScheduleNotFullyVested,
-        close = beneficiary
-    )]
-    pub vesting_schedule: Account<'info, VestingSchedule>,
-
-    /// The vesting vault to be closed.
-    /// Security: Must be empty and belong to the vesting schedule.
-    #[account(
-        mut,
-        seeds = [VESTING_VAULT_SEED, vesting_schedule.schedule_id.to_le_bytes().as_ref()],
-        bump,
-        constraint = vesting_vault.amount == 0 @ VestingError::VaultNotEmpty,
-        constraint = vesting_vault.owner == vesting_schedule.key() @ VestingError::VaultAuthorityMismatch
-    )]
-    pub vesting_vault: Account<'info, TokenAccount>,
+/// New recipient and fresh timing for a reassigned unvested remainder
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct ReassignUnvestedParams {
+    pub recipient: Pubkey,
+    pub cliff_timestamp: i64,
+    pub vesting_start_timestamp: i64,
+    pub vesting_end_timestamp: i64,
+}
+
+/// Authoritative vesting numbers of one schedule at the current time, returned via return data
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq)]
+pub struct VestingStatus {
+    pub total_amount: u64,
+    pub amount_transferred: u64,
+    /// Cumulative amount unlocked so far (`calculate_unlocked_amount`)
+    pub unlocked_amount: u64,
+    /// Unlocked but not yet released or pending; what the next crank would release given a funded vault
+    pub transferable_amount: u64,
+    /// True once the whole allocation has unlocked
+    pub is_fully_vested: bool,
+    /// Cranks still needed for a keeper releasing at least `min_release` each (see `cranks_remaining`)
+    pub cranks_remaining: u64,
+}
+
+/// Dry-run result of one `validate_batch` entry, returned via return data
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq)]
+pub struct BatchEntryValidation {
+    /// Position of the entry in the submitted batch
+    pub index: u32,
+    /// Error code creating the entry would fail with (None = would succeed)
+    pub error_code: Option<u32>,
+}
+
+/// Outcome of a batch crank, returned via return data
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, Default, PartialEq)]
+pub struct BatchCrankSummary {
+    /// True when the pre-scan found no schedule with a transferable amount (BatchNothingReady)
+    pub nothing_ready: bool,
+    /// Schedules fully validated and evaluated
+    pub schedules_processed: u32,
+    /// Schedules that actually released tokens
+    pub schedules_released: u32,
+    /// Sum of tokens released across the batch
+    pub total_released: u64,
+    /// Sum of tokens accrued into `pending_release` while release batching is on
+    pub total_accrued: u64,
+    /// True when processing stopped early at `max_release_per_transaction`
+    pub stopped_at_cap: bool,
+}
+
+/// Crank pre-flight result, returned via return data
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq)]
+pub struct CrankPreflight {
+    /// True when a crank submitted now would release tokens
+    pub allowed: bool,
+    /// First condition blocking the crank (`None` when allowed)
+    pub reason: CrankBlockReason,
+    /// Amount a crank would release now
+    pub transferable: u64,
+}
+
+/// Per-mint totals of a grant group
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq)]
+pub struct GrantGroupMintTotal {
+    pub mint: Pubkey,
+    pub total_amount: u64,
+    pub amount_transferred: u64,
+}
+
+/// Page of `get_grant_group` results, see `return_data::Page` for the pagination contract
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq)]
+pub struct GrantGroupPage {
+    pub mint_totals: Vec<GrantGroupMintTotal>,
+    pub truncated: bool,
+    pub next_offset: u32,
+}
 
-    pub token_program: Program<'info, Token>,
+/// Page of schedule ids returned by audit views
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq)]
+pub struct ScheduleIdPage {
+    pub schedule_ids: Vec<u64>,
+    pub truncated: bool,
+    pub next_offset: u32,
+}
+
+/// Program-wide totals returned by `get_program_stats`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq)]
+pub struct ProgramStats {
+    pub total_schedules: u64,
+    pub total_allocated: u64,
+    pub total_distributed: u64,
+}
+
+/// Every limit and policy flag that applies to transactions, returned by `get_limits`
+/// `version` is PROGRAM_LIMITS_VERSION; new fields are only ever appended with a version bump
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq)]
+pub struct ProgramLimits {
+    pub version: u8,
+    // Configured policy
+    pub read_only: bool,
+    pub keeper_reward_bps: u16,
+    pub reward_buffer: u64,
+    pub max_release_per_transaction: u64,
+    pub cliff_eq_start_categories: u8,
+    pub enforce_claim_interval_on_crank: bool,
+    pub max_clock_skew: u64,
+    pub emit_skip_events: bool,
+    pub accumulate_releases: bool,
+    pub creation_fee_bps: u16,
+    pub fee_destination: Pubkey,
+    // Compile-time limits
+    pub max_keeper_reward_bps: u16,
+    pub max_top_up_batch_size: u32,
+    pub max_scan_batch_size: u32,
+    pub max_crank_batch_size: u32,
+    pub max_curve_points: u32,
+    pub hub_update_timelock: i64,
+    pub upgrade_authority_timelock: i64,
+    // Added in version 2
+    pub allow_zero_amount: bool,
+    // Added in version 3
+    pub max_delegates: u32,
+    // Added in version 4
+    pub auto_extend_underfunded: bool,
+    // Added in version 5
+    pub max_schedules_per_mint: u64,
+    // Added in version 6
+    pub max_create_batch_size: u32,
+    // Added in version 7
+    pub category_caps: [u64; SourceCategory::COUNT],
+    pub category_allocated: [u64; SourceCategory::COUNT],
+    // Added in version 8
+    pub max_release_per_window: u64,
+    pub release_window_duration: u64,
+    pub release_window_start: i64,
+    pub released_in_window: u64,
+    // Added in version 9
+    pub min_crank_interval: i64,
+    // Added in version 10
+    pub skip_zero_events: bool,
+}
+
+/// Name and 8-byte Anchor discriminator of an event the program emits
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq)]
+pub struct EventSchema {
+    pub name: String,
+    pub discriminator: [u8; 8],
+}
+
+/// Page of `get_event_schemas` results
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq)]
+pub struct EventSchemaPage {
+    pub schemas: Vec<EventSchema>,
+    pub truncated: bool,
+    pub next_offset: u32,
+}
+
+/// Page of `aggregate_unlock_curve` points, one per requested timestamp from `offset`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq)]
+pub struct UnlockCurvePage {
+    pub points: Vec<u64>,
+    pub truncated: bool,
+    pub next_offset: u32,
 }
 
 // ================================================================================================
-// PROGRAM INSTRUCTIONS
+// HELPERS
 // ================================================================================================
 
-#[program]
-pub mod haio_vesting {
-    use super::*;
+/// Load a (vesting_schedule, vesting_vault) pair passed through remaining accounts
+/// Security: Schedule must be a program-owned VestingSchedule and the vault must be the one it records.
+/// Vault authority is intentionally not checked here so callers can decide how to treat a mismatch.
+fn load_schedule_and_vault<'info>(
+    schedule_info: &'info AccountInfo<'info>,
+    vault_info: &'info AccountInfo<'info>,
+) -> Result<(Account<'info, VestingSchedule>, InterfaceAccount<'info, token_interface::TokenAccount>)> {
+    let vesting_schedule = Account::<VestingSchedule>::try_from(schedule_info)?;
+    require!(vesting_schedule.is_initialized, VestingError::InvalidVestingScheduleData);
+    require_keys_eq!(vault_info.key(), vesting_schedule.token_vault, VestingError::InvalidRemainingAccounts);
+
+    let vesting_vault = InterfaceAccount::<token_interface::TokenAccount>::try_from(vault_info)?;
+    Ok((vesting_schedule, vesting_vault))
+}
 
-    /// Initialize the vesting program
-    /// Security: Can only be called once, establishes admin control
-    pub fn initialize(ctx: Context<Initialize>) -> Result<()> {
-        let config = &mut ctx.accounts.program_config;
-        
-        // Initialize program state
-        config.admin = ctx.accounts.admin.key();
-        config.total_schedules = 0;
-        config.bump = ctx.bumps.program_config;
-
-        emit!(ProgramInitialized {
-            admin: config.admin,
-            program_config: config.key(),
+/// Ensure the recorded recipient token account is still owned by the recipient
+/// Security: Releases are blocked if ownership moved (e.g. via SetAuthority). The mismatch is
+/// logged as a `RecipientAccountOwnershipChanged` event before failing, so monitoring can alert
+/// the recipient/admin that the account must be re-pointed instead of retrying the crank.
+/// `current_owner` is the owner read from the recipient token account, of either token program
+fn check_recipient_account_owner(
+    vesting_schedule: &VestingSchedule,
+    current_owner: Pubkey,
+    timestamp: i64,
+) -> Result<()> {
+    if current_owner == vesting_schedule.recipient {
+        return Ok(());
+    }
+
+    msg!("Recipient token account {} for schedule {} is now owned by {} instead of recipient {}.",
+         vesting_schedule.recipient_token_account, vesting_schedule.schedule_id,
+         current_owner, vesting_schedule.recipient);
+    emit!(RecipientAccountOwnershipChanged {
+        schedule_id: vesting_schedule.schedule_id,
+        recipient: vesting_schedule.recipient,
+        recipient_token_account: vesting_schedule.recipient_token_account,
+        current_owner,
+        timestamp,
+    });
+    err!(VestingError::RecipientAccountOwnershipChanged)
+}
+
+/// Check the cluster clock against the time implied by slot progression
+/// Returns true when `max_clock_skew` is enabled and exceeded
+/// Limitations: the only on-chain reference is the epoch start timestamp, which comes from the
+/// same validator clock, plus the slot count since then at the target slot time. Real slot
+/// times drift, so this catches gross clock manipulation only and the tolerance must be generous.
+fn clock_skew_exceeded(program_config: &ProgramConfig, clock: &Clock) -> Result<bool> {
+    if program_config.max_clock_skew == 0 {
+        return Ok(false);
+    }
+
+    let first_slot_in_epoch = EpochSchedule::get()?.get_first_slot_in_epoch(clock.epoch);
+    let clock_skew = math::clock_skew_seconds(
+        clock.unix_timestamp,
+        clock.epoch_start_timestamp,
+        clock.slot.saturating_sub(first_slot_in_epoch),
+    )?;
+    Ok(clock_skew > program_config.max_clock_skew)
+}
+
+/// Current unix timestamp for release calculations
+/// Security: Refuses with `ClockSkewTooHigh` when the clock fails the skew sanity check
+fn release_timestamp(program_config: &ProgramConfig) -> Result<i64> {
+    let clock = Clock::get()?;
+    require!(!clock_skew_exceeded(program_config, &clock)?, VestingError::ClockSkewTooHigh);
+    Ok(clock.unix_timestamp)
+}
+
+/// Emit a structured `CrankSkipped` event when skip events are enabled
+/// Off by default so high-frequency keepers do not bloat logs
+fn emit_crank_skipped(
+    program_config: &ProgramConfig,
+    schedule_id: u64,
+    reason: CrankBlockReason,
+    timestamp: i64,
+) {
+    if program_config.emit_skip_events {
+        emit!(CrankSkipped {
+            schedule_id,
+            reason,
+            timestamp,
+        });
+    }
+}
+
+/// Log and report a revoked schedule so release paths can bail out before moving tokens
+/// Returns true when the caller should skip the schedule
+fn skip_if_revoked(
+    program_config: &ProgramConfig,
+    vesting_schedule: &VestingSchedule,
+    timestamp: i64,
+) -> bool {
+    if !vesting_schedule.revoked {
+        return false;
+    }
+    msg!("Schedule {} has been revoked. Skipping.", vesting_schedule.schedule_id);
+    emit_crank_skipped(program_config, vesting_schedule.schedule_id, CrankBlockReason::Revoked, timestamp);
+    true
+}
+
+/// Log and report a schedule whose `min_crank_interval` has not elapsed yet
+/// Returns true when the caller should skip the schedule; keepers can simply retry later
+fn skip_if_crank_interval_pending(
+    program_config: &ProgramConfig,
+    vesting_schedule: &VestingSchedule,
+    timestamp: i64,
+) -> bool {
+    if vesting_schedule.crank_interval_elapsed(program_config.min_crank_interval, timestamp) {
+        return false;
+    }
+    msg!("Schedule {} was cranked at {}, next crank allowed at {}. Skipping.",
+         vesting_schedule.schedule_id, vesting_schedule.last_crank_timestamp,
+         vesting_schedule.last_crank_timestamp.saturating_add(program_config.min_crank_interval));
+    emit_crank_skipped(program_config, vesting_schedule.schedule_id, CrankBlockReason::CrankIntervalNotElapsed, timestamp);
+    true
+}
+
+/// Emit a `ReleaseCheckpoint` for each milestone the cumulative release has just crossed
+/// Call after `amount_transferred` is updated; a no-op while checkpoints are disabled
+fn emit_release_checkpoints(
+    program_config: &ProgramConfig,
+    vesting_schedule: &mut VestingSchedule,
+    timestamp: i64,
+) {
+    for milestone_pct in vesting_schedule.advance_checkpoints(program_config.checkpoint_interval_pct) {
+        emit!(ReleaseCheckpoint {
+            schedule_id: vesting_schedule.schedule_id,
+            milestone_pct,
+            cumulative_released: vesting_schedule.amount_transferred,
+            timestamp,
         });
+    }
+}
+
+/// Build `EventSchema`s from event type names
+macro_rules! event_schemas {
+    ($($event:ident),* $(,)?) => {
+        vec![$(EventSchema {
+            name: stringify!($event).to_string(),
+            discriminator: {
+                let mut discriminator = [0u8; 8];
+                discriminator.copy_from_slice(<$event as anchor_lang::Discriminator>::DISCRIMINATOR);
+                discriminator
+            },
+        }),*]
+    };
+}
 
-        msg!("Vesting program initialized with admin: {}", config.admin);
-        msg!("Program config PDA: {}", config.key());
-        Ok(())
+/// Every event type the program emits, in declaration order
+/// New events must be added here so `get_event_schemas` stays complete
+fn all_event_schemas() -> Vec<EventSchema> {
+    event_schemas![
+        ProgramInitialized,
+        VestingScheduleCreated,
+        TokensReleased,
+        ScheduleToppedUp,
+        KeeperRewardUpdated,
+        SchedulesRecounted,
+        MaxReleasePerTransactionUpdated,
+        DistributionHubInitialized,
+        DistributionHubUpdateProposed,
+        DistributionHubUpdated,
+        ClaimedWithSignature,
+        RecipientAccountOwnershipChanged,
+        CliffStartPolicyUpdated,
+        ReadOnlyModeUpdated,
+        WithdrawalAuthorityUpdated,
+        StreamWithdrawn,
+        VaultFunded,
+        ClaimIntervalCrankEnforcementUpdated,
+        MaxClockSkewUpdated,
+        UnvestedReassigned,
+        ReleaseAccrued,
+        ReleaseRateTooLow,
+        CrankSkipped,
+        UpgradeAuthorityProposed,
+        UpgradeAuthorityConfirmed,
+        AdminTransferProposed,
+        AdminTransferred,
+        ScheduleFinalized,
+        CreationFeeCharged,
+        ReconciliationReport,
+        RewardBufferUpdated,
+        CategoryTemplateUpdated,
+        TemplateApplied,
+        DelegateCapabilitiesUpdated,
+        ScheduleAutoExtended,
+        RoundingDustSwept,
+        VestingScheduleRevoked,
+        ProgramPaused,
+        VestingScheduleClosed,
+        ScheduleArchived,
+        SchedulePurged,
+        ReleaseCheckpoint,
+        SchedulesMerged,
+        CategoryCapUpdated,
+        ReleaseRateLimitUpdated,
+        RecipientTokenAccountChangeProposed,
+        RecipientTokenAccountChanged,
+        EmergencyWithdrawn,
+        MinCrankIntervalUpdated,
+        DistributionHubUpdateCancelled,
+        VaultClosed,
+    ]
+}
+
+/// Track schedules already handled in this batch, returning true for repeats
+/// Repeats are skipped (with a `DuplicateScheduleInBatch` skip reason) so a keeper passing the
+/// same schedule twice cannot have it evaluated twice in one instruction
+fn is_duplicate_in_batch<'info>(
+    seen_schedules: &mut Vec<Pubkey>,
+    program_config: &ProgramConfig,
+    schedule_info: &'info AccountInfo<'info>,
+    timestamp: i64,
+) -> Result<bool> {
+    if !seen_schedules.contains(schedule_info.key) {
+        seen_schedules.push(*schedule_info.key);
+        return Ok(false);
     }
 
-    /// Create a new vesting schedule with token deposit
-    /// Security: Admin-only, validates timing parameters, enforces sequential schedule IDs
-    pub fn create_vesting_schedule(
-        ctx: Context<CreateVestingSchedule>,
-        schedule_id: u64,
-        params: CreateVestingScheduleParams,
-    ) -> Result<()> {
-        let program_config = &mut ctx.accounts.program_config;
-        let vesting_schedule_account = &mut ctx.accounts.vesting_schedule;
+    let vesting_schedule = Account::<VestingSchedule>::try_from(schedule_info)?;
+    msg!("Schedule {} appears more than once in the batch. Skipping.", vesting_schedule.schedule_id);
+    emit_crank_skipped(program_config, vesting_schedule.schedule_id, CrankBlockReason::DuplicateScheduleInBatch, timestamp);
+    Ok(true)
+}
 
-        // ================================================================================================
-        // CRITICAL PARAMETER VALIDATIONS
-        // ================================================================================================
-        
-        // Amount validation
-        require!(params.total_amount > 0, VestingError::InvalidAmount);
-        
-        // Recipient validation
-        require!(params.recipient != Pubkey::default(), VestingError::InvalidRecipient);
-        
-        // ================================================================================================
-        // CRITICAL SECURITY: RECIPIENT TOKEN ACCOUNT VALIDATION
-        // ================================================================================================
-        
-        // Critical Security Check: Ensure recipient token account is owned by the recipient
-        require!(
-            ctx.accounts.recipient_token_account.owner == params.recipient,
-            VestingError::RecipientAccountOwnerMismatch
-        );
-        
-        // Timing validation - cliff <= start < end
-        require!(
-            params.cliff_timestamp <= params.vesting_start_timestamp &&
-            params.vesting_start_timestamp < params.vesting_end_timestamp,
-            VestingError::InvalidTimestamps
-        );
+/// Check whether a batch entry's vault was closed by `close_vault_only`, returning true to skip it
+/// Checked before the vault account is loaded, since it no longer exists
+fn is_vault_closed<'info>(
+    program_config: &ProgramConfig,
+    schedule_info: &'info AccountInfo<'info>,
+    timestamp: i64,
+) -> Result<bool> {
+    let vesting_schedule = Account::<VestingSchedule>::try_from(schedule_info)?;
+    if !vesting_schedule.vault_closed {
+        return Ok(false);
+    }
 
-        // Sequential ID enforcement - prevents gaps in schedule numbering
-        require!(schedule_id == program_config.total_schedules, VestingError::ScheduleIdConflict);
+    msg!("Vault of schedule {} is closed. Skipping.", vesting_schedule.schedule_id);
+    emit_crank_skipped(program_config, vesting_schedule.schedule_id, CrankBlockReason::VaultClosed, timestamp);
+    Ok(true)
+}
 
-        // ================================================================================================
-        // VESTING SCHEDULE INITIALIZATION
-        // ================================================================================================
-        
-        // Initialize vesting schedule state with recipient
-        vesting_schedule_account.init(
-            schedule_id,
-            params.recipient,
-            ctx.accounts.recipient_token_account.key(),
-            ctx.accounts.mint.key(),
-            ctx.accounts.vesting_vault.key(),
-            ctx.accounts.admin.key(),
-            params.total_amount,
-            params.cliff_timestamp,
-            params.vesting_start_timestamp,
-            params.vesting_end_timestamp,
-            params.source_category.clone(),
-            ctx.bumps.vesting_schedule,
-        )?;
+/// Warn operators when a mid-vesting crank found nothing new because it came too soon
+/// Suggests `min_release_interval` as the crank cadence for this schedule
+fn warn_if_release_rate_too_low(vesting_schedule: &VestingSchedule, timestamp: i64) -> Result<()> {
+    if vesting_schedule.is_release_rate_too_low(timestamp)? {
+        let suggested_crank_interval = vesting_schedule.min_release_interval()?;
+        msg!("Schedule {} unlocks one unit every {} seconds, crank less often.",
+             vesting_schedule.schedule_id, suggested_crank_interval);
+        emit!(ReleaseRateTooLow {
+            schedule_id: vesting_schedule.schedule_id,
+            total_amount: vesting_schedule.total_amount,
+            suggested_crank_interval,
+            timestamp,
+        });
+    }
+    Ok(())
+}
 
-        // ================================================================================================
-        // TOKEN DEPOSIT EXECUTION
-        // ================================================================================================
-        
-        // Transfer tokens from admin's account to vesting vault
-        let cpi_accounts = Transfer {
-            from: ctx.accounts.depositor_token_account.to_account_info(),
-            to: ctx.accounts.vesting_vault.to_account_info(),
-            authority: ctx.accounts.admin.to_account_info(),
+/// Ensure serialized vault data is still a token account owned by the vesting_schedule PDA
+/// Security: Defense in depth for the transfer CPI, in case an earlier instruction or CPI in
+/// the same transaction changed the authority after the account constraints were checked
+/// Decoded with the token interface so Token-2022 vaults carrying extensions are accepted
+fn require_vault_authority(vault_data: &[u8], vesting_schedule: &Pubkey) -> Result<()> {
+    let vesting_vault = token_interface::TokenAccount::try_deserialize(&mut &vault_data[..])?;
+    require_keys_eq!(vesting_vault.owner, *vesting_schedule, VestingError::VaultAuthorityMismatch);
+    Ok(())
+}
+
+/// Apply the auto-extend policy before a release when it is enabled
+/// Emits `ScheduleAutoExtended` with the new end when the schedule was stretched
+fn extend_if_underfunded(
+    program_config: &ProgramConfig,
+    vesting_schedule: &mut VestingSchedule,
+    vault_balance: u64,
+    timestamp: i64,
+) -> Result<()> {
+    if !program_config.auto_extend_underfunded {
+        return Ok(());
+    }
+
+    let old_vesting_end_timestamp = vesting_schedule.vesting_end_timestamp;
+    if let Some(new_vesting_end_timestamp) = vesting_schedule.extend_for_underfunding(vault_balance, timestamp)? {
+        msg!("Schedule {} is underfunded (vault balance: {}). Vesting end extended from {} to {}.",
+             vesting_schedule.schedule_id, vault_balance, old_vesting_end_timestamp, new_vesting_end_timestamp);
+        emit!(ScheduleAutoExtended {
+            schedule_id: vesting_schedule.schedule_id,
+            vault_balance,
+            old_vesting_end_timestamp,
+            new_vesting_end_timestamp,
+            timestamp,
+        });
+    }
+    Ok(())
+}
+
+/// Transfer tokens out of a schedule's vault with `transfer_checked`, signing with the
+/// vesting_schedule PDA. Works for vaults of either token program; Token-2022 requires the mint
+/// and its decimals on every transfer
+/// Security: The schedule is locked and persisted before the CPI, so a re-entrant call into
+/// any release path during the transfer (e.g. from a transfer hook) fails with `ScheduleLocked`
+/// Security: The vault authority is re-checked against current account data right before the CPI
+fn transfer_from_vault_checked<'info>(
+    vesting_schedule: &mut Account<'info, VestingSchedule>,
+    vesting_vault: &InterfaceAccount<'info, token_interface::TokenAccount>,
+    mint: &InterfaceAccount<'info, token_interface::Mint>,
+    destination: AccountInfo<'info>,
+    token_program: AccountInfo<'info>,
+    amount: u64,
+) -> Result<()> {
+    let vault_info = vesting_vault.to_account_info();
+    with_vault_signer(vesting_schedule, &vault_info, |authority, signer| {
+        let cpi_accounts = TransferChecked {
+            from: vault_info.clone(),
+            mint: mint.to_account_info(),
+            to: destination,
+            authority,
         };
+        token_interface::transfer_checked(
+            CpiContext::new_with_signer(token_program, cpi_accounts, signer),
+            amount,
+            mint.decimals,
+        )
+    })
+}
+
+/// Current balance of a token account, read from its data after a CPI changed it
+fn token_account_balance(token_account: &AccountInfo) -> Result<u64> {
+    let data = token_account.try_borrow_data()?;
+    Ok(token_interface::TokenAccount::try_deserialize(&mut &data[..])?.amount)
+}
+
+/// Run a vault transfer CPI signed by the vesting_schedule PDA while the schedule is locked
+/// The closure receives the schedule's account info (the vault authority) and signer seeds
+fn with_vault_signer<'info>(
+    vesting_schedule: &mut Account<'info, VestingSchedule>,
+    vault_info: &AccountInfo<'info>,
+    transfer: impl FnOnce(AccountInfo<'info>, &[&[&[u8]]]) -> Result<()>,
+) -> Result<()> {
+    vesting_schedule.acquire_lock()?;
+    vesting_schedule.exit(&crate::ID)?;
+
+    let schedule_id_bytes = vesting_schedule.schedule_id.to_le_bytes();
+    let signer_seeds = &[
+        VESTING_SCHEDULE_SEED,
+        schedule_id_bytes.as_ref(),
+        &[vesting_schedule.bump],
+    ];
+    let signer = &[&signer_seeds[..]];
+
+    // Re-read the vault authority from current account data rather than the entry snapshot
+    require_vault_authority(&vault_info.try_borrow_data()?, &vesting_schedule.key())?;
+
+    transfer(vesting_schedule.to_account_info(), signer)?;
+
+    // Cleared in memory, persisted with the rest of the schedule update by the caller
+    vesting_schedule.release_lock();
+    Ok(())
+}
+
+/// Close a schedule's empty vault, signing with the vesting_schedule PDA
+/// Rent lamports go to `destination`
+fn close_vault<'info>(
+    vesting_schedule: &Account<'info, VestingSchedule>,
+    vesting_vault: &InterfaceAccount<'info, token_interface::TokenAccount>,
+    destination: AccountInfo<'info>,
+    token_program: AccountInfo<'info>,
+) -> Result<()> {
+    let schedule_id_bytes = vesting_schedule.schedule_id.to_le_bytes();
+    let signer_seeds = &[
+        VESTING_SCHEDULE_SEED,
+        schedule_id_bytes.as_ref(),
+        &[vesting_schedule.bump],
+    ];
+    let signer = &[&signer_seeds[..]];
+
+    let cpi_accounts = token_interface::CloseAccount {
+        account: vesting_vault.to_account_info(),
+        destination,
+        authority: vesting_schedule.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new_with_signer(token_program, cpi_accounts, signer);
+    token_interface::close_account(cpi_ctx)
+}
 
-        let cpi_program = ctx.accounts.token_program.to_account_info();
-        let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
-        token::transfer(cpi_ctx, params.total_amount)?;
+/// Close a completed schedule's empty vault and retire the schedule (see `retire_schedule`),
+/// returning the rent to `beneficiary` and emitting `VestingScheduleClosed`
+/// Returns the purge timestamp when the schedule was archived
+fn close_completed_schedule<'info>(
+    program_config: &ProgramConfig,
+    vesting_schedule: &mut Account<'info, VestingSchedule>,
+    vesting_vault: &InterfaceAccount<'info, token_interface::TokenAccount>,
+    beneficiary: AccountInfo<'info>,
+    token_program: AccountInfo<'info>,
+) -> Result<Option<i64>> {
+    // Balances before closing are exactly the rent the beneficiary reclaims
+    let vault_lamports = vesting_vault.to_account_info().lamports();
+    let schedule_lamports = vesting_schedule.to_account_info().lamports();
+
+    close_vault(vesting_schedule, vesting_vault, beneficiary.clone(), token_program)?;
+    let archived = retire_schedule(program_config, vesting_schedule, beneficiary.clone())?;
+
+    emit!(VestingScheduleClosed {
+        schedule_id: vesting_schedule.schedule_id,
+        beneficiary: beneficiary.key(),
+        vault_lamports,
+        // An archived schedule keeps its account and rent until it is purged
+        schedule_lamports: if archived.is_some() { 0 } else { schedule_lamports },
+        amount_transferred: vesting_schedule.amount_transferred,
+        archived: archived.is_some(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    Ok(archived)
+}
+
+/// Delete a completed schedule's account, or archive it when a retention period is configured
+/// The vault must already be closed. Returns the purge timestamp when the schedule was archived
+fn retire_schedule<'info>(
+    program_config: &ProgramConfig,
+    vesting_schedule: &mut Account<'info, VestingSchedule>,
+    destination: AccountInfo<'info>,
+) -> Result<Option<i64>> {
+    if program_config.archive_retention_period == 0 {
+        vesting_schedule.close(destination)?;
+        return Ok(None);
+    }
+
+    let current_timestamp = Clock::get()?.unix_timestamp;
+    let purgeable_at = vesting_schedule.archive(current_timestamp, program_config.archive_retention_period)?;
+    emit!(ScheduleArchived {
+        schedule_id: vesting_schedule.schedule_id,
+        purgeable_at,
+        timestamp: current_timestamp,
+    });
+    Ok(Some(purgeable_at))
+}
+
+/// Deliver the vault balance left in a fully processed schedule to its recipient
+/// Rounding or a transfer fee can leave such surplus behind; it is not part of the allocation,
+/// so `amount_transferred` stays at `total_amount` and `TokensReleased` reports the dust alone
+fn release_residual_dust<'info>(
+    program_config: &mut ProgramConfig,
+    vesting_schedule: &mut Account<'info, VestingSchedule>,
+    vesting_vault: &InterfaceAccount<'info, token_interface::TokenAccount>,
+    recipient_token_account: &InterfaceAccount<'info, token_interface::TokenAccount>,
+    mint: &InterfaceAccount<'info, token_interface::Mint>,
+    token_program: AccountInfo<'info>,
+    current_timestamp: i64,
+) -> Result<()> {
+    let dust_amount = vesting_schedule.sweepable_dust(vesting_vault.amount);
+    program_config.record_outflow(dust_amount, current_timestamp)?;
+
+    let recipient_info = recipient_token_account.to_account_info();
+    let balance_before = recipient_token_account.amount;
+    transfer_from_vault_checked(
+        vesting_schedule,
+        vesting_vault,
+        mint,
+        recipient_info.clone(),
+        token_program,
+        dust_amount,
+    )?;
+    let delivered_amount = token_account_balance(&recipient_info)?
+        .checked_sub(balance_before)
+        .ok_or(VestingError::MathOverflow)?;
+
+    emit!(TokensReleased {
+        schedule_id: vesting_schedule.schedule_id,
+        recipient: vesting_schedule.recipient,
+        mint: vesting_schedule.mint,
+        amount: delivered_amount,
+        source_category: vesting_schedule.source_category.clone(),
+        timestamp: current_timestamp,
+        total_released: vesting_schedule.amount_transferred,
+        keeper_fee: 0,
+    });
+
+    msg!("Released {} residual dust from fully processed schedule {} to the recipient",
+         dust_amount, vesting_schedule.schedule_id);
+    Ok(())
+}
 
+/// Release the vested amount of one schedule directly to its recipient token account
+/// Shared by `crank_vesting_schedule` and `crank_and_maybe_close`, for either token program
+/// Security: Validates recipient account ownership, prevents unauthorized transfers
+fn release_vested<'info>(
+    program_config: &mut ProgramConfig,
+    vesting_schedule: &mut Account<'info, VestingSchedule>,
+    vesting_vault: &InterfaceAccount<'info, token_interface::TokenAccount>,
+    recipient_token_account: &InterfaceAccount<'info, token_interface::TokenAccount>,
+    mint: &InterfaceAccount<'info, token_interface::Mint>,
+    token_program: AccountInfo<'info>,
+    keeper_token_account: Option<&InterfaceAccount<'info, token_interface::TokenAccount>>,
+) -> Result<()> {
+    let current_timestamp = release_timestamp(program_config)?;
+
+    extend_if_underfunded(
+        program_config,
+        vesting_schedule,
+        vesting_vault.amount,
+        current_timestamp,
+    )?;
+
+    // Extract values early to avoid borrow conflicts
+    let schedule_id;
+    let recipient;
+    let mint_key;
+    let source_category;
+    let transferable_amount;
+    
+    {
         // ================================================================================================
-        // STATE UPDATE AND EVENT EMISSION
+        // PRE-FLIGHT SECURITY VALIDATIONS
         // ================================================================================================
         
-        // Update program state atomically
-        program_config.increment_total_schedules()?;
+        // Token movements are frozen while the program is paused
+        program_config.require_not_paused()?;
 
-        // Emit event for tracking
-        emit!(VestingScheduleCreated {
-            schedule_id,
-            recipient: params.recipient,
-            mint: ctx.accounts.mint.key(),
-            total_amount: params.total_amount,
-            cliff_timestamp: params.cliff_timestamp,
-            vesting_start_timestamp: params.vesting_start_timestamp,
-            vesting_end_timestamp: params.vesting_end_timestamp,
-            source_category: params.source_category,
-            depositor: ctx.accounts.admin.key(),
-        });
+        // Validate schedule is properly initialized
+        require!(vesting_schedule.is_initialized, VestingError::InvalidVestingScheduleData);
 
-        msg!(
-            "Created vesting schedule {} with {} tokens for recipient {}, cliff at {}, vesting from {} to {}",
-            schedule_id, params.total_amount, params.recipient, params.cliff_timestamp,
-            params.vesting_start_timestamp, params.vesting_end_timestamp
-        );
+        // Revoked schedules never release again
+        if skip_if_revoked(program_config, vesting_schedule, current_timestamp) {
+            return Ok(());
+        }
 
-        Ok(())
-    }
+        // Swap-at-release is not supported, the vault must hold the release mint
+        require_keys_eq!(vesting_schedule.funding_mint, vesting_schedule.mint, VestingError::SwapAtReleaseUnsupported);
 
-    /// Process individual vesting schedule with direct-to-recipient transfer
-    /// Replaces batch processing with single-schedule processing for enhanced security
-    /// Security: Validates recipient account ownership, prevents unauthorized transfers
-    pub fn crank_vesting_schedule(
-        ctx: Context<CrankVestingSchedules>,
-    ) -> Result<()> {
-        let current_timestamp = Clock::get()?.unix_timestamp;
-
-        // Extract values early to avoid borrow conflicts
-        let schedule_id;
-        let recipient;
-        let mint;
-        let source_category;
-        let schedule_bump;
-        let transferable_amount;
-        
-        {
-            let vesting_schedule = &ctx.accounts.vesting_schedule;
-            let vesting_vault = &ctx.accounts.vesting_vault;
+        // Validate vault state using IsInitialized trait
+        require!(vesting_vault.is_initialized(), VestingError::InvalidVaultState);
 
-            // ================================================================================================
-            // PRE-FLIGHT SECURITY VALIDATIONS
-            // ================================================================================================
-            
-            // Validate schedule is properly initialized
-            require!(vesting_schedule.is_initialized, VestingError::InvalidVestingScheduleData);
+        // Validate the recipient still owns the recorded token account (prevents SetAuthority attacks)
+        check_recipient_account_owner(vesting_schedule, recipient_token_account.owner, current_timestamp)?;
+        vesting_schedule.require_undelegated(recipient_token_account.delegate.is_some())?;
 
-            // Validate vault state using IsInitialized trait
-            require!(vesting_vault.is_initialized(), VestingError::InvalidVaultState);
+        // Keeper cranks bypass the recipient claim interval unless configured otherwise
+        if program_config.enforce_claim_interval_on_crank {
+            vesting_schedule.check_claim_interval(current_timestamp)?;
+        }
 
-            // ================================================================================================
-            // VESTING LOGIC AND TRANSFER AMOUNT CALCULATION
-            // ================================================================================================
-            
-            // Skip if schedule is already fully processed
-            if vesting_schedule.amount_transferred >= vesting_schedule.total_amount {
-                msg!("Schedule {} already fully processed (transferred: {}, total: {}). Skipping.", 
-                     vesting_schedule.schedule_id, vesting_schedule.amount_transferred, vesting_schedule.total_amount);
-                return Ok(());
-            }
+        // ================================================================================================
+        // VESTING LOGIC AND TRANSFER AMOUNT CALCULATION
+        // ================================================================================================
+        
+        // A fully processed schedule only has surplus left, which goes to the recipient so the
+        // vault empties and can be closed
+        if vesting_schedule.sweepable_dust(vesting_vault.amount) > 0 {
+            return release_residual_dust(
+                program_config,
+                vesting_schedule,
+                vesting_vault,
+                recipient_token_account,
+                mint,
+                token_program,
+                current_timestamp,
+            );
+        }
+
+        // Skip if schedule is already fully processed
+        if vesting_schedule.is_fully_processed() {
+            msg!("Schedule {} already fully processed (transferred: {}, total: {}). Skipping.", 
+                 vesting_schedule.schedule_id, vesting_schedule.amount_transferred, vesting_schedule.total_amount);
+            emit_crank_skipped(program_config, vesting_schedule.schedule_id, CrankBlockReason::FullyProcessed, current_timestamp);
+            return Ok(());
+        }
 
-            // Calculate how much can be transferred at current timestamp
-            transferable_amount = vesting_schedule.get_transferable_amount(current_timestamp)?;
+        // Calculate how much can be transferred at current timestamp
+        transferable_amount = vesting_schedule.get_transferable_amount(current_timestamp)?;
 
-            if transferable_amount == 0 {
-                msg!("No transferable amount for schedule {} at timestamp {}. Current cliff: {}, vesting start: {}.", 
-                     vesting_schedule.schedule_id, current_timestamp, 
-                     vesting_schedule.cliff_timestamp, vesting_schedule.vesting_start_timestamp);
-                
-                // Emit event for monitoring consistency even when amount is 0
+        if transferable_amount == 0 {
+            msg!("No transferable amount for schedule {} at timestamp {}. Current cliff: {}, vesting start: {}.", 
+                 vesting_schedule.schedule_id, current_timestamp, 
+                 vesting_schedule.cliff_timestamp, vesting_schedule.vesting_start_timestamp);
+            
+            // Emit event for monitoring consistency even when amount is 0, unless suppressed
+            if !program_config.skip_zero_events {
                 emit!(TokensReleased {
                     schedule_id: vesting_schedule.schedule_id,
                     recipient: vesting_schedule.recipient,
@@ -401,169 +837,1762 @@ pub mod haio_vesting {
                     source_category: vesting_schedule.source_category.clone(),
                     timestamp: current_timestamp,
                     total_released: vesting_schedule.amount_transferred,
+                    keeper_fee: 0,
                 });
-                
-                return Ok(());
             }
+            emit_crank_skipped(program_config, vesting_schedule.schedule_id, CrankBlockReason::NothingVested, current_timestamp);
+            warn_if_release_rate_too_low(vesting_schedule, current_timestamp)?;
+            
+            return Ok(());
+        }
 
-            // Extract values for later use
-            schedule_id = vesting_schedule.schedule_id;
-            recipient = vesting_schedule.recipient;
-            mint = vesting_schedule.mint;
-            source_category = vesting_schedule.source_category.clone();
-            schedule_bump = vesting_schedule.bump;
+        // Rate limit on micro-releases; skipped rather than failed so keepers can retry later
+        if skip_if_crank_interval_pending(program_config, vesting_schedule, current_timestamp) {
+            return Ok(());
         }
 
-        // Ensure we don't exceed available vault balance
-        let actual_transfer_amount = transferable_amount.min(ctx.accounts.vesting_vault.amount);
+        // Extract values for later use
+        schedule_id = vesting_schedule.schedule_id;
+        recipient = vesting_schedule.recipient;
+        mint_key = vesting_schedule.mint;
+        source_category = vesting_schedule.source_category.clone();
+    }
 
-        if actual_transfer_amount == 0 {
-            msg!("Vault for schedule {} is empty (vault balance: {}, calculated transferable: {}). Skipping.", 
-                 schedule_id, ctx.accounts.vesting_vault.amount, transferable_amount);
-            
-            // Emit event for monitoring consistency even when vault is empty
+    // Release-batching mode: record what is owed, flush_releases moves the tokens later
+    if program_config.accumulate_releases {
+        let accrued_amount = vesting_schedule.accrue_release(current_timestamp)?;
+        if program_config.enforce_claim_interval_on_crank {
+            vesting_schedule.last_claim_timestamp = current_timestamp;
+        }
+
+        emit!(ReleaseAccrued {
+            schedule_id,
+            amount: accrued_amount,
+            pending_release: vesting_schedule.pending_release,
+            timestamp: current_timestamp,
+        });
+
+        msg!("Accrued {} tokens for schedule {}. Pending release: {}",
+             accrued_amount, schedule_id, vesting_schedule.pending_release);
+        return Ok(());
+    }
+
+    // Ensure we don't exceed available vault balance
+    let actual_transfer_amount = vesting_schedule.get_transferable_amount_capped(current_timestamp, vesting_vault.amount)?;
+
+    if actual_transfer_amount == 0 {
+        msg!("Vault for schedule {} is empty (vault balance: {}, calculated transferable: {}). Skipping.", 
+             schedule_id, vesting_vault.amount, transferable_amount);
+        
+        // Emit event for monitoring consistency even when vault is empty, unless suppressed
+        if !program_config.skip_zero_events {
             emit!(TokensReleased {
                 schedule_id,
                 recipient,
-                mint,
+                mint: mint_key,
                 amount: 0,
                 source_category,
                 timestamp: current_timestamp,
-                total_released: ctx.accounts.vesting_schedule.amount_transferred,
+                total_released: vesting_schedule.amount_transferred,
+                keeper_fee: 0,
             });
-            
-            return Ok(());
         }
-
-        // ================================================================================================
-        // TOKEN TRANSFER EXECUTION
-        // ================================================================================================
+        emit_crank_skipped(program_config, schedule_id, CrankBlockReason::VaultEmpty, current_timestamp);
         
-        // Create PDA signer seeds for the vesting schedule authority
-        let schedule_id_bytes = schedule_id.to_le_bytes();
-        let signer_seeds = &[
-            VESTING_SCHEDULE_SEED,
-            schedule_id_bytes.as_ref(),
-            &[schedule_bump],
-        ];
-        let signer = &[&signer_seeds[..]];
-
-        // Execute token transfer from vault to recipient's token account
-        let cpi_accounts = Transfer {
-            from: ctx.accounts.vesting_vault.to_account_info(),
-            to: ctx.accounts.recipient_token_account.to_account_info(),
-            authority: ctx.accounts.vesting_schedule.to_account_info(),
-        };
-        let cpi_ctx = CpiContext::new_with_signer(
-            ctx.accounts.token_program.to_account_info(),
-            cpi_accounts,
-            signer,
-        );
-        token::transfer(cpi_ctx, actual_transfer_amount)?;
+        return Ok(());
+    }
 
-        // ================================================================================================
-        // STATE UPDATE AND EVENT EMISSION
-        // ================================================================================================
-        
-        // Update schedule amount_transferred atomically
-        let vesting_schedule = &mut ctx.accounts.vesting_schedule;
-        vesting_schedule.amount_transferred = vesting_schedule.amount_transferred
-            .checked_add(actual_transfer_amount)
+    // ================================================================================================
+    // TOKEN TRANSFER EXECUTION
+    // ================================================================================================
+    
+    // Circuit breaker on the program-wide release rate
+    program_config.record_outflow(actual_transfer_amount, current_timestamp)?;
+
+    // The keeper reward is drawn from the vested tokens, the recipient gets the rest
+    let keeper_fee = match keeper_token_account {
+        Some(_) => program_config.keeper_reward_for(actual_transfer_amount)?,
+        None => 0,
+    };
+    let recipient_amount = actual_transfer_amount
+        .checked_sub(keeper_fee)
+        .ok_or(VestingError::MathOverflow)?;
+
+    // Execute token transfer from vault to recipient's token account
+    let recipient_info = recipient_token_account.to_account_info();
+    let balance_before = recipient_token_account.amount;
+    transfer_from_vault_checked(
+        vesting_schedule,
+        vesting_vault,
+        mint,
+        recipient_info.clone(),
+        token_program.clone(),
+        recipient_amount,
+    )?;
+
+    // Transfer-fee mints deliver less than the vault was debited; the balance delta is what
+    // the recipient actually received
+    let delivered_amount = token_account_balance(&recipient_info)?
+        .checked_sub(balance_before)
+        .ok_or(VestingError::MathOverflow)?;
+
+    let mut keeper_delivered_amount = 0;
+    if let Some(keeper_token_account) = keeper_token_account.filter(|_| keeper_fee > 0) {
+        let keeper_info = keeper_token_account.to_account_info();
+        let keeper_balance_before = keeper_token_account.amount;
+        transfer_from_vault_checked(
+            vesting_schedule,
+            vesting_vault,
+            mint,
+            keeper_info.clone(),
+            token_program,
+            keeper_fee,
+        )?;
+        keeper_delivered_amount = token_account_balance(&keeper_info)?
+            .checked_sub(keeper_balance_before)
             .ok_or(VestingError::MathOverflow)?;
+    }
 
-        // Emit event for tracking and monitoring
-        emit!(TokensReleased {
-            schedule_id,
-            recipient,
-            mint,
-            amount: actual_transfer_amount,
-            source_category,
-            timestamp: current_timestamp,
-            total_released: vesting_schedule.amount_transferred,
-        });
+    // ================================================================================================
+    // STATE UPDATE AND EVENT EMISSION
+    // ================================================================================================
+    
+    // Update schedule amount_transferred and fees atomically; the keeper reward counts as released
+    let total_delivered = delivered_amount
+        .checked_add(keeper_delivered_amount)
+        .ok_or(VestingError::MathOverflow)?;
+    vesting_schedule.record_release(actual_transfer_amount, total_delivered)?;
+    vesting_schedule.last_crank_timestamp = current_timestamp;
+    if program_config.enforce_claim_interval_on_crank {
+        vesting_schedule.last_claim_timestamp = current_timestamp;
+    }
+    vesting_schedule.record_completion(current_timestamp);
+    program_config.record_distribution(total_delivered)?;
+    program_config.record_rounding_dust(vesting_schedule.release_truncation(current_timestamp)?)?;
+    emit_release_checkpoints(program_config, vesting_schedule, current_timestamp);
+
+    // Emit event for tracking and monitoring
+    emit!(TokensReleased {
+        schedule_id,
+        recipient,
+        mint: mint_key,
+        amount: delivered_amount,
+        source_category,
+        timestamp: current_timestamp,
+        total_released: vesting_schedule.amount_transferred,
+        keeper_fee,
+    });
+
+    msg!(
+        "Released {} tokens from schedule {} directly to recipient {} ({} keeper fee, {} withheld as transfer fee). Total released: {}",
+        delivered_amount, schedule_id, recipient, keeper_fee,
+        actual_transfer_amount - total_delivered,
+        vesting_schedule.amount_transferred
+    );
+
+    Ok(())
+}
 
-        msg!(
-            "Released {} tokens from schedule {} directly to recipient {}. Total released: {}",
-            actual_transfer_amount, schedule_id, recipient,
-            vesting_schedule.amount_transferred
+/// Account-independent checks on new schedule parameters
+/// Shared by `create_schedule` and the `validate_batch` dry run
+fn validate_schedule_params(
+    program_config: &ProgramConfig,
+    params: &CreateVestingScheduleParams,
+    current_timestamp: i64,
+) -> Result<()> {
+    // Amount validation - zero only for placeholder reservations when enabled
+    require!(
+        params.total_amount > 0 || program_config.allow_zero_amount,
+        VestingError::InvalidAmount
+    );
+    
+    // Recipient validation
+    require!(params.recipient != Pubkey::default(), VestingError::InvalidRecipient);
+
+    // Initial unlock is a share of the total
+    require!(params.initial_unlock_bps <= BPS_DENOMINATOR, VestingError::InvalidBps);
+
+    // Second cliff lies within the schedule and its chunk fits beside the initial unlock
+    if params.second_cliff_bps > 0 {
+        require!(
+            params.initial_unlock_bps.saturating_add(params.second_cliff_bps) <= BPS_DENOMINATOR,
+            VestingError::InvalidBps
+        );
+        require!(
+            params.cliff_timestamp < params.second_cliff_timestamp
+                && params.second_cliff_timestamp <= params.vesting_end_timestamp,
+            VestingError::InvalidTimestamps
         );
+    }
 
-        Ok(())
+    // Timing validation - cliff <= start < end
+    require!(
+        params.cliff_timestamp <= params.vesting_start_timestamp &&
+        params.vesting_start_timestamp < params.vesting_end_timestamp,
+        VestingError::InvalidTimestamps
+    );
+    require!(params.min_claim_interval >= 0, VestingError::InvalidTimestamps);
+
+    // Stepped curves need at least one period; even division only when the policy asks for it
+    if let VestingCurve::Stepped { num_periods } = params.vesting_curve {
+        math::validate_step_division(
+            params.total_amount,
+            u64::from(num_periods),
+            program_config.require_even_step_division,
+        )?;
     }
 
-    /// Close a vesting schedule and its vault after completion
-    /// This allows reclaiming the rent from the accounts
-    /// Security: Can only be called when the schedule is fully vested and the vault is empty
-    pub fn close_vesting_schedule(ctx: Context<CloseVestingSchedule>) -> Result<()> {
-        let schedule_id = ctx.accounts.vesting_schedule.schedule_id;
-        let _schedule_key = ctx.accounts.vesting_schedule.key();
-        let schedule_bump = ctx.accounts.vesting_schedule.bump;
-
-        // Create PDA signer seeds for the vesting schedule authority
-        let schedule_id_bytes = schedule_id.to_le_bytes();
-        let signer_seeds = &[
-            VESTING_SCHEDULE_SEED,
-            schedule_id_bytes.as_ref(),
-            &[schedule_bump],
-        ];
-        let signer = &[&signer_seeds[..]];
-
-        // Close the token vault account via CPI
-        let cpi_accounts = token::CloseAccount {
-            account: ctx.accounts.vesting_vault.to_account_info(),
-            destination: ctx.accounts.beneficiary.to_account_info(),
-            authority: ctx.accounts.vesting_schedule.to_account_info(),
-        };
-        let cpi_ctx = CpiContext::new_with_signer(
-            ctx.accounts.token_program.to_account_info(),
-            cpi_accounts,
-            signer,
+    // An end in the past unlocks everything on the first crank, usually a unit mistake
+    if !params.allow_past_end {
+        require!(
+            params.vesting_end_timestamp > current_timestamp,
+            VestingError::PastVestingEnd
         );
-        token::close_account(cpi_ctx)?;
-
-        // The vesting_schedule account is closed automatically by Anchor via the `close` constraint
+    }
 
-        msg!(
-            "Successfully closed vesting schedule {} and its vault. Rent returned to {}.",
-            schedule_id,
-            ctx.accounts.beneficiary.key()
+    // Category policy - configured categories begin linear vesting exactly at the cliff
+    if program_config.requires_cliff_eq_start(&params.source_category) {
+        require!(
+            params.cliff_timestamp == params.vesting_start_timestamp,
+            VestingError::CliffStartMismatch
         );
+    }
+
+    // Backdated cliffs and far-future ends are almost always typos
+    VestingSchedule::validate_creation_window(
+        params.cliff_timestamp,
+        params.vesting_end_timestamp,
+        current_timestamp,
+    )?;
 
-        Ok(())
+    VestingSchedule::validate_label(&params.label)?;
+
+    Ok(())
+}
+
+/// Write a new schedule's state from creation parameters
+/// Shared by `create_schedule` and `create_vesting_schedules_batch`
+#[allow(clippy::too_many_arguments)]
+fn init_schedule_from_params(
+    vesting_schedule: &mut VestingSchedule,
+    schedule_id: u64,
+    params: &CreateVestingScheduleParams,
+    recipient_token_account: Pubkey,
+    mint: Pubkey,
+    vesting_vault: Pubkey,
+    depositor: Pubkey,
+    bump: u8,
+) -> Result<()> {
+    vesting_schedule.init(
+        schedule_id,
+        params.recipient,
+        recipient_token_account,
+        mint,
+        vesting_vault,
+        depositor,
+        params.total_amount,
+        params.cliff_timestamp,
+        params.vesting_start_timestamp,
+        params.vesting_end_timestamp,
+        params.source_category.clone(),
+        params.round_up,
+        params.grant_group_id,
+        params.min_claim_interval,
+        bump,
+    )?;
+    vesting_schedule.terms_hash = params.terms_hash;
+    vesting_schedule.initial_unlock_bps = params.initial_unlock_bps;
+    vesting_schedule.second_cliff_timestamp = params.second_cliff_timestamp;
+    vesting_schedule.second_cliff_bps = params.second_cliff_bps;
+    vesting_schedule.curve = params.vesting_curve;
+    vesting_schedule.non_transferable = params.non_transferable;
+    vesting_schedule.label = params.label;
+    Ok(())
+}
+
+/// Transfer the creation fee of a new schedule from the depositor, if one is configured
+/// Creation fee is paid on top of the deposit, the schedule keeps its full total_amount
+/// Security: `fee_destination` must be owned by the configured fee destination and hold `mint`
+#[allow(clippy::too_many_arguments)]
+fn charge_creation_fee<'info>(
+    program_config: &ProgramConfig,
+    schedule_id: u64,
+    total_amount: u64,
+    mint: &InterfaceAccount<'info, token_interface::Mint>,
+    depositor_token_account: &InterfaceAccount<'info, token_interface::TokenAccount>,
+    depositor: &Signer<'info>,
+    fee_destination: Option<&InterfaceAccount<'info, token_interface::TokenAccount>>,
+    token_program: &Interface<'info, TokenInterface>,
+) -> Result<()> {
+    let creation_fee = math::apply_bps(total_amount, program_config.creation_fee_bps)?;
+    if creation_fee == 0 {
+        return Ok(());
     }
+
+    let fee_destination = fee_destination.ok_or(VestingError::InvalidFeeDestination)?;
+    require_keys_eq!(fee_destination.owner, program_config.fee_destination, VestingError::InvalidFeeDestination);
+    require_keys_eq!(fee_destination.mint, mint.key(), VestingError::InvalidFeeDestination);
+
+    deposit_from(
+        depositor_token_account,
+        mint,
+        fee_destination.to_account_info(),
+        depositor,
+        token_program,
+        creation_fee,
+    )?;
+
+    emit!(CreationFeeCharged {
+        schedule_id,
+        fee_destination: fee_destination.key(),
+        mint: mint.key(),
+        fee_bps: program_config.creation_fee_bps,
+        amount: creation_fee,
+    });
+    Ok(())
 }
 
-// ================================================================================================
-// EVENTS
-// ================================================================================================
+/// Amount to send so that `net_amount` arrives after the mint's Token-2022 transfer fee
+/// Mints without the transfer-fee extension return `net_amount` unchanged
+fn gross_up_for_transfer_fee(mint: &InterfaceAccount<token_interface::Mint>, net_amount: u64) -> Result<u64> {
+    use anchor_spl::token_2022::spl_token_2022::{
+        extension::{transfer_fee::TransferFeeConfig, BaseStateWithExtensions, StateWithExtensions},
+        state::Mint as Mint2022,
+    };
+
+    let mint_info = mint.to_account_info();
+    if *mint_info.owner != anchor_spl::token_2022::ID {
+        return Ok(net_amount);
+    }
+    let data = mint_info.try_borrow_data()?;
+    let mint_state = StateWithExtensions::<Mint2022>::unpack(&data)?;
+    let Ok(fee_config) = mint_state.get_extension::<TransferFeeConfig>() else {
+        return Ok(net_amount);
+    };
+    let fee = fee_config
+        .calculate_inverse_epoch_fee(Clock::get()?.epoch, net_amount)
+        .ok_or(VestingError::MathOverflow)?;
+    Ok(net_amount.checked_add(fee).ok_or(VestingError::MathOverflow)?)
+}
 
-#[event]
-pub struct ProgramInitialized {
-    pub admin: Pubkey,
-    pub program_config: Pubkey,
+/// Transfer `amount` into `destination` out of the depositor's token account with `transfer_checked`
+/// Works for mints of either token program; with a transfer-fee mint the depositor pays the
+/// fee on top so `amount` arrives in full
+fn deposit_from<'info>(
+    depositor_token_account: &InterfaceAccount<'info, token_interface::TokenAccount>,
+    mint: &InterfaceAccount<'info, token_interface::Mint>,
+    destination: AccountInfo<'info>,
+    depositor: &Signer<'info>,
+    token_program: &Interface<'info, TokenInterface>,
+    amount: u64,
+) -> Result<()> {
+    let cpi_accounts = TransferChecked {
+        from: depositor_token_account.to_account_info(),
+        mint: mint.to_account_info(),
+        to: destination,
+        authority: depositor.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new(token_program.to_account_info(), cpi_accounts);
+    token_interface::transfer_checked(cpi_ctx, gross_up_for_transfer_fee(mint, amount)?, mint.decimals)
 }
 
-#[event]
-pub struct VestingScheduleCreated {
-    pub schedule_id: u64,
-    pub recipient: Pubkey,
-    pub mint: Pubkey,
-    pub total_amount: u64,
-    pub cliff_timestamp: i64,
-    pub vesting_start_timestamp: i64,
-    pub vesting_end_timestamp: i64,
-    pub source_category: SourceCategory,
-    pub depositor: Pubkey,
+/// Emit `VestingScheduleCreated` and log the new schedule
+fn emit_schedule_created(schedule_id: u64, params: &CreateVestingScheduleParams, mint: Pubkey, depositor: Pubkey) {
+    emit!(VestingScheduleCreated {
+        schedule_id,
+        recipient: params.recipient,
+        mint,
+        total_amount: params.total_amount,
+        cliff_timestamp: params.cliff_timestamp,
+        vesting_start_timestamp: params.vesting_start_timestamp,
+        vesting_end_timestamp: params.vesting_end_timestamp,
+        source_category: params.source_category.clone(),
+        depositor,
+        grant_group_id: params.grant_group_id,
+        label: params.label,
+    });
+
+    msg!(
+        "Created vesting schedule {} with {} tokens for recipient {}, cliff at {}, vesting from {} to {}",
+        schedule_id, params.total_amount, params.recipient, params.cliff_timestamp,
+        params.vesting_start_timestamp, params.vesting_end_timestamp
+    );
 }
 
-/// Token release event with recipient field for complete audit trail
-#[event]
-pub struct TokensReleased {
-    pub schedule_id: u64,
-    pub recipient: Pubkey,
-    pub mint: Pubkey,
-    pub amount: u64,
-    pub source_category: SourceCategory,
-    pub timestamp: i64,
-    pub total_released: u64,
-}
\ No newline at end of file
+/// Validate parameters, initialize the schedule, deposit and record it
+/// Shared by `create_vesting_schedule` and `create_from_template`
+fn create_schedule(
+    ctx: Context<CreateVestingSchedule>,
+    schedule_id: u64,
+    params: CreateVestingScheduleParams,
+) -> Result<()> {
+    ctx.accounts.program_config.require_writable()?;
+
+    let program_config = &mut ctx.accounts.program_config;
+    let vesting_schedule_account = &mut ctx.accounts.vesting_schedule;
+
+    // ================================================================================================
+    // CRITICAL PARAMETER VALIDATIONS
+    // ================================================================================================
+    
+    validate_schedule_params(program_config, &params, Clock::get()?.unix_timestamp)?;
+    
+    // ================================================================================================
+    // CRITICAL SECURITY: RECIPIENT TOKEN ACCOUNT VALIDATION
+    // ================================================================================================
+    
+    // Critical Security Check: Ensure recipient token account is owned by the recipient
+    require!(
+        ctx.accounts.recipient_token_account.owner == params.recipient,
+        VestingError::RecipientAccountOwnerMismatch
+    );
+
+    // Vesting back into the funding account is almost always a mistake unless explicitly requested
+    require!(
+        params.allow_self_return
+            || ctx.accounts.recipient_token_account.key() != ctx.accounts.depositor_token_account.key(),
+        VestingError::RecipientEqualsDepositor
+    );
+    
+    // Sequential ID enforcement - prevents gaps in schedule numbering
+    require!(schedule_id == program_config.total_schedules, VestingError::ScheduleIdConflict);
+
+    // ================================================================================================
+    // VESTING SCHEDULE INITIALIZATION
+    // ================================================================================================
+    
+    // Initialize vesting schedule state with recipient
+    init_schedule_from_params(
+        vesting_schedule_account,
+        schedule_id,
+        &params,
+        ctx.accounts.recipient_token_account.key(),
+        ctx.accounts.mint.key(),
+        ctx.accounts.vesting_vault.key(),
+        ctx.accounts.admin.key(),
+        ctx.bumps.vesting_schedule,
+    )?;
+
+    // ================================================================================================
+    // TOKEN DEPOSIT EXECUTION
+    // ================================================================================================
+    
+    // Transfer tokens from admin's account to vesting vault (placeholders start with an empty vault)
+    if params.total_amount > 0 {
+        deposit_from(
+            &ctx.accounts.depositor_token_account,
+            &ctx.accounts.mint,
+            ctx.accounts.vesting_vault.to_account_info(),
+            &ctx.accounts.admin,
+            &ctx.accounts.token_program,
+            params.total_amount,
+        )?;
+    }
+
+    charge_creation_fee(
+        program_config,
+        schedule_id,
+        params.total_amount,
+        &ctx.accounts.mint,
+        &ctx.accounts.depositor_token_account,
+        &ctx.accounts.admin,
+        ctx.accounts.fee_destination.as_ref(),
+        &ctx.accounts.token_program,
+    )?;
+
+    // ================================================================================================
+    // STATE UPDATE AND EVENT EMISSION
+    // ================================================================================================
+    
+    // Update program state atomically
+    program_config.increment_total_schedules()?;
+    program_config.allocate_to_category(&params.source_category, params.total_amount)?;
+    ctx.accounts.mint_schedule_count.record_created(
+        ctx.accounts.mint.key(),
+        ctx.bumps.mint_schedule_count,
+        program_config.max_schedules_per_mint,
+    )?;
+
+    // First schedule of this recipient and mint becomes the primary one
+    let mint_key = ctx.accounts.mint.key();
+    if ctx.accounts.primary_schedule_index.record_if_first(
+        params.recipient,
+        mint_key,
+        schedule_id,
+        ctx.bumps.primary_schedule_index,
+    ) {
+        msg!("Schedule {} recorded as primary for recipient {} and mint {}", schedule_id, params.recipient, mint_key);
+    }
+
+    // Emit event for tracking
+    emit_schedule_created(schedule_id, &params, mint_key, ctx.accounts.admin.key());
+
+    Ok(())
+}
+
+// ================================================================================================
+// PROGRAM INSTRUCTIONS
+// ================================================================================================
+
+#[program]
+pub mod haio_vesting {
+    use super::*;
+
+    /// Initialize the vesting program
+    /// Security: Can only be called once, establishes admin control
+    pub fn initialize(ctx: Context<Initialize>) -> Result<()> {
+        instructions::initialize::handler(ctx)
+    }
+
+    /// Upgrade a config account created with the original admin/counter/bump layout
+    /// Security: Only the admin recorded in the legacy account can migrate it, and only once
+    pub fn migrate_program_config(ctx: Context<MigrateProgramConfig>) -> Result<()> {
+        instructions::migrate_program_config::handler(ctx)
+    }
+
+    /// Create a new vesting schedule with token deposit
+    /// Security: Admin or CAPABILITY_CREATE delegate, validates timing parameters, enforces sequential schedule IDs
+    pub fn create_vesting_schedule(
+        ctx: Context<CreateVestingSchedule>,
+        schedule_id: u64,
+        params: CreateVestingScheduleParams,
+    ) -> Result<()> {
+        instructions::create_vesting_schedule::handler(ctx, schedule_id, params)
+    }
+
+    /// Create several schedules of one mint in a single transaction, with sequential IDs
+    /// starting at `program_config.total_schedules`
+    /// Remaining accounts: (vesting_schedule, vesting_vault, recipient_token_account,
+    /// primary_schedule_index) per entry. At most MAX_SCHEDULES_PER_CREATE entries; see the
+    /// handler for compute-unit estimates.
+    /// Security: Same checks as `create_vesting_schedule` per entry, the batch fails atomically
+    pub fn create_vesting_schedules_batch<'info>(
+        ctx: Context<'_, '_, 'info, 'info, CreateVestingSchedulesBatch<'info>>,
+        params: Vec<CreateVestingScheduleParams>,
+    ) -> Result<()> {
+        instructions::create_vesting_schedules_batch::handler(ctx, params)
+    }
+
+    /// Process individual vesting schedule with direct-to-recipient transfer
+    /// Replaces batch processing with single-schedule processing for enhanced security
+    /// A keeper passing `keeper` and `keeper_token_account` collects `keeper_reward_bps` of the
+    /// release (subject to `reward_buffer`); the reward counts toward `amount_transferred`
+    /// Security: Validates recipient account ownership, prevents unauthorized transfers
+    pub fn crank_vesting_schedule(
+        ctx: Context<CrankVestingSchedules>,
+    ) -> Result<()> {
+        instructions::crank_vesting_schedule::handler(ctx)
+    }
+
+    /// Process several vesting schedules in one transaction
+    /// Remaining accounts: (vesting_schedule, vesting_vault, recipient_token_account, mint) entries
+    /// A cheap pre-scan over schedule state exits before any vault or recipient account work
+    /// when nothing in the batch is transferable, returning `nothing_ready = true`
+    /// When `max_release_per_transaction` is set, the schedule that reaches the cap is released
+    /// up to the remaining allowance and later schedules are left for the next call
+    /// In release-batching mode each schedule accrues into `pending_release` like the single crank
+    /// Security: Every entry is validated exactly like the single-schedule crank
+    pub fn crank_vesting_schedules_batch<'info>(
+        ctx: Context<'_, '_, 'info, 'info, CrankVestingSchedulesBatch<'info>>,
+    ) -> Result<BatchCrankSummary> {
+        instructions::crank_vesting_schedules_batch::handler(ctx)
+    }
+
+    /// Close a vesting schedule and its vault after completion
+    /// This allows reclaiming the rent from the accounts. When `archive_retention_period` is set
+    /// only the vault is closed and the schedule is kept, marked `closed`, until `purge_archived`
+    /// With `close_grace_period` set, closing waits that long after the completing release
+    /// Security: Can only be called when the schedule is fully vested and the vault is empty
+    pub fn close_vesting_schedule(ctx: Context<CloseVestingSchedule>) -> Result<()> {
+        instructions::close_vesting_schedule::handler(ctx)
+    }
+
+    /// Create a vesting schedule of native SOL held by a system-owned vault PDA
+    /// Security: Same authority and parameter validation as `create_vesting_schedule`
+    pub fn create_sol_vesting_schedule(
+        ctx: Context<CreateSolVestingSchedule>,
+        schedule_id: u64,
+        params: CreateVestingScheduleParams,
+    ) -> Result<()> {
+        instructions::create_sol_vesting_schedule::handler(ctx, schedule_id, params)
+    }
+
+    /// Release the vested lamports of a native schedule to its recipient wallet
+    /// Native schedules are only cranked here; token release paths cannot load their vault
+    /// Security: Permissionless, lamports only ever go to the recorded recipient and depositor
+    pub fn crank_sol_vesting_schedule(ctx: Context<CrankSolVestingSchedule>) -> Result<()> {
+        instructions::crank_sol_vesting_schedule::handler(ctx)
+    }
+
+    /// Revoke a native schedule: vested lamports to the recipient, the rest back to the admin
+    /// Security: Admin-only, finalized and already revoked schedules are refused
+    pub fn revoke_sol_vesting_schedule(ctx: Context<RevokeSolVestingSchedule>) -> Result<()> {
+        instructions::revoke_sol_vesting_schedule::handler(ctx)
+    }
+
+    /// Close a completed native schedule whose vault has been emptied, reclaiming its rent
+    /// Security: Honours the close grace period and archive retention like `close_vesting_schedule`
+    pub fn close_sol_vesting_schedule(ctx: Context<CloseSolVestingSchedule>) -> Result<()> {
+        instructions::close_sol_vesting_schedule::handler(ctx)
+    }
+
+    /// Close only the empty vault of a completed schedule, keeping the schedule account for good
+    /// Reclaims the vault rent while the schedule stays queryable as an audit record
+    /// Security: Same preconditions as `close_vesting_schedule`
+    pub fn close_vault_only(ctx: Context<CloseVaultOnly>) -> Result<()> {
+        instructions::close_vault_only::handler(ctx)
+    }
+
+    /// Add tokens to one existing vesting schedule
+    /// Unlocking is proportional to `total_amount`, so a top-up raises the vesting rate
+    /// Security: Admin or CAPABILITY_CREATE delegate, revoked and fully vested schedules are refused
+    pub fn top_up_schedule(ctx: Context<TopUpSchedule>, amount: u64) -> Result<()> {
+        instructions::top_up_schedule::handler(ctx, amount)
+    }
+
+    /// Add tokens to several existing vesting schedules atomically
+    /// Remaining accounts: one (vesting_schedule, vesting_vault) pair per entry in `amounts`
+    /// Returns the new `total_amount` of each schedule, in input order
+    /// Security: Admin or CAPABILITY_CREATE delegate, each entry passes the same checks as `top_up_schedule`
+    /// and any invalid entry (fully processed, revoked, finalized or past its end) rejects the whole batch
+    pub fn top_up_batch<'info>(
+        ctx: Context<'_, '_, 'info, 'info, TopUpBatch<'info>>,
+        amounts: Vec<u64>,
+    ) -> Result<Vec<u64>> {
+        instructions::top_up_batch::handler(ctx, amounts)
+    }
+
+    /// Update the keeper reward rate
+    /// Security: Admin or CAPABILITY_CRANK_MANAGE delegate, bounded by MAX_KEEPER_REWARD_BPS so releases cannot be drained into rewards
+    pub fn set_keeper_reward(ctx: Context<UpdateCrankPolicy>, keeper_reward_bps: u16) -> Result<()> {
+        instructions::set_keeper_reward::handler(ctx, keeper_reward_bps)
+    }
+
+    /// Repair `total_schedules` when it has fallen behind the schedules that actually exist
+    /// Security: Admin-only, the counter can only move forward to one past an existing schedule
+    /// whose successor slot is empty, so it can never skip over or reuse schedule IDs
+    pub fn recount_schedules(ctx: Context<RecountSchedules>) -> Result<()> {
+        instructions::recount_schedules::handler(ctx)
+    }
+
+    /// Report per-mint totals of a grant group
+    /// Remaining accounts: every vesting schedule in the group
+    /// Grouping is reporting-only, each schedule still vests independently
+    /// Mints are listed in first-seen order, paginated from `offset` (see `return_data::Page`)
+    /// Security: All schedules must belong to `grant_group_id` and share one recipient
+    pub fn get_grant_group<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ScanSchedules<'info>>,
+        grant_group_id: u64,
+        offset: u32,
+    ) -> Result<GrantGroupPage> {
+        instructions::get_grant_group::handler(ctx, grant_group_id, offset)
+    }
+
+    /// Set or change the distribution hub token account
+    /// The hub is `hub_token_account`, which must be an initialized token account of `mint`.
+    /// The first setup takes effect immediately and emits `DistributionHubInitialized`.
+    /// Later changes must be proposed and then confirmed with the same address once
+    /// HUB_UPDATE_TIMELOCK has passed, which emits `DistributionHubUpdated`.
+    /// Returns the seconds until the change is confirmable: the full timelock on a proposal,
+    /// 0 once the hub has been set. A too-early confirmation logs the seconds remaining.
+    /// Security: Admin-only, timelocked changes give observers time to react
+    pub fn update_distribution_hub(ctx: Context<UpdateDistributionHub>) -> Result<i64> {
+        instructions::update_distribution_hub::handler(ctx)
+    }
+
+    /// Cancel the pending distribution hub change, e.g. after proposing a wrong address
+    /// Security: Admin-only, the current hub stays in place
+    pub fn cancel_hub_update(ctx: Context<UpdateProgramConfig>) -> Result<()> {
+        instructions::cancel_hub_update::handler(ctx)
+    }
+
+    /// Update the maximum total released by a single batch crank transaction
+    /// Security: Admin or CAPABILITY_CRANK_MANAGE delegate, 0 disables the limit
+    pub fn set_max_release_per_transaction(
+        ctx: Context<UpdateCrankPolicy>,
+        max_release_per_transaction: u64,
+    ) -> Result<()> {
+        instructions::set_max_release_per_transaction::handler(ctx, max_release_per_transaction)
+    }
+
+    /// Update the minimum seconds between two crank releases of the same schedule
+    /// Applies to single and batch cranks, `flush_releases` and `claim_all` alike
+    /// Security: Admin or CAPABILITY_CRANK_MANAGE delegate, 0 disables the limit
+    pub fn set_min_crank_interval(ctx: Context<UpdateCrankPolicy>, min_crank_interval: i64) -> Result<()> {
+        instructions::set_min_crank_interval::handler(ctx, min_crank_interval)
+    }
+
+    /// Configure the circuit breaker on tokens leaving the program: at most
+    /// `max_release_per_window` per `window_duration` seconds across every schedule
+    /// (`max_release_per_window == 0` disables it). The running window is kept across changes.
+    /// Security: Admin-only, so a delegate key cannot lift the limit it is meant to bound
+    pub fn set_release_rate_limit(
+        ctx: Context<UpdateProgramConfig>,
+        max_release_per_window: u64,
+        window_duration: u64,
+    ) -> Result<()> {
+        instructions::set_release_rate_limit::handler(ctx, max_release_per_window, window_duration)
+    }
+
+    /// Enter or leave read-only (wind-down) mode
+    /// Read-only mode freezes every state mutation: creation, top-ups, cranks, claims and
+    /// config changes. Only view instructions, closes and this toggle remain available.
+    /// This is broader than an emergency pause, which only stops token transfers.
+    /// Security: Admin-only, never delegable
+    pub fn set_read_only(ctx: Context<UpdateProgramConfig>, read_only: bool) -> Result<()> {
+        instructions::set_read_only::handler(ctx, read_only)
+    }
+
+    /// Choose whether keeper cranks also respect schedules' `min_claim_interval`
+    /// Recipient-initiated claims always respect it
+    pub fn set_claim_interval_crank_enforcement(
+        ctx: Context<UpdateCrankPolicy>,
+        enforce_claim_interval_on_crank: bool,
+    ) -> Result<()> {
+        instructions::set_claim_interval_crank_enforcement::handler(ctx, enforce_claim_interval_on_crank)
+    }
+
+    /// Set the clock skew tolerance for releases in seconds (0 disables the check)
+    /// See `clock_skew_exceeded` for the limitations of on-chain clock validation
+    pub fn set_max_clock_skew(ctx: Context<UpdateConfigPolicy>, max_clock_skew: u64) -> Result<()> {
+        instructions::set_max_clock_skew::handler(ctx, max_clock_skew)
+    }
+
+    /// Enable or disable `CrankSkipped` events for schedules a crank passes over
+    pub fn set_emit_skip_events(ctx: Context<UpdateCrankPolicy>, emit_skip_events: bool) -> Result<()> {
+        instructions::set_emit_skip_events::handler(ctx, emit_skip_events)
+    }
+
+    /// Enable or disable the zero-amount `TokensReleased` events a crank emits when nothing moves
+    pub fn set_skip_zero_events(ctx: Context<UpdateCrankPolicy>, skip_zero_events: bool) -> Result<()> {
+        instructions::set_skip_zero_events::handler(ctx, skip_zero_events)
+    }
+
+    /// Set the release milestone step for `ReleaseCheckpoint` events (0 disables them)
+    /// The step must divide 100; milestones already reported are not repeated after a change
+    pub fn set_checkpoint_interval(ctx: Context<UpdateCrankPolicy>, checkpoint_interval_pct: u8) -> Result<()> {
+        instructions::set_checkpoint_interval::handler(ctx, checkpoint_interval_pct)
+    }
+
+    /// Require stepped schedules to split `total_amount` evenly across their periods
+    /// Security: Admin or CAPABILITY_CONFIG delegate; only affects future creations
+    pub fn set_require_even_step_division(
+        ctx: Context<UpdateConfigPolicy>,
+        required: bool,
+    ) -> Result<()> {
+        instructions::set_require_even_step_division::handler(ctx, required)
+    }
+
+    /// Require `cliff_timestamp == vesting_start_timestamp` for new schedules of a category
+    /// Categories that legitimately use a cliff-to-start gap are simply left unset
+    /// Security: Only affects future creations, existing schedules are unchanged
+    pub fn set_cliff_eq_start_policy(
+        ctx: Context<UpdateConfigPolicy>,
+        source_category: SourceCategory,
+        required: bool,
+    ) -> Result<()> {
+        instructions::set_cliff_eq_start_policy::handler(ctx, source_category, required)
+    }
+
+    /// Audit view reporting schedules whose vault is not controlled by the schedule PDA
+    /// Remaining accounts: (vesting_schedule, token_account) pairs
+    /// Returns the `schedule_id`s whose paired account is not the recorded vault or is not
+    /// owned by the schedule PDA, instead of failing, so monitoring can scan every schedule at once
+    /// Ids are listed in input order, paginated from `offset` (see `return_data::Page`)
+    pub fn check_vault_ownership<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ScanSchedules<'info>>,
+        offset: u32,
+    ) -> Result<ScheduleIdPage> {
+        instructions::check_vault_ownership::handler(ctx, offset)
+    }
+
+    /// Claim vested tokens on a recipient's behalf using an off-chain Ed25519 authorization
+    /// The preceding instruction must be an Ed25519 program instruction verifying the recipient's
+    /// signature over `build_claim_message(vesting_schedule, destination, amount_transferred, expiry)`
+    /// Security: Binding `amount_transferred` makes each authorization single-use, and the
+    /// destination must hold the schedule's mint
+    pub fn claim_with_signature(ctx: Context<ClaimWithSignature>, expiry: i64) -> Result<()> {
+        instructions::claim_with_signature::handler(ctx, expiry)
+    }
+
+    /// Preview the lamports reclaimed by closing a schedule and its vault
+    /// Returns the combined current balance of both accounts
+    pub fn get_close_preview(ctx: Context<GetClosePreview>) -> Result<u64> {
+        instructions::get_close_preview::handler(ctx)
+    }
+
+    /// Delegate (or revoke with `None`) pull-style withdrawals to another key or program
+    /// Security: Only the recipient can set it, and the destination stays fixed
+    pub fn set_withdrawal_authority(
+        ctx: Context<SetWithdrawalAuthority>,
+        withdrawal_authority: Option<Pubkey>,
+    ) -> Result<()> {
+        instructions::set_withdrawal_authority::handler(ctx, withdrawal_authority)
+    }
+
+    /// Release the currently transferable amount on behalf of the recipient
+    /// Callable only by the schedule's withdrawal authority, enabling streaming-style composability
+    /// Security: Tokens always go to the schedule's `recipient_token_account`
+    pub fn stream_withdraw(ctx: Context<StreamWithdraw>) -> Result<()> {
+        instructions::stream_withdraw::handler(ctx)
+    }
+
+    /// Pre-flight whether `caller` can crank a schedule right now
+    /// Evaluates program-wide gates first, then the schedule's own release conditions
+    /// Cranking is currently permissionless, so `caller` does not yet change the outcome
+    pub fn can_crank(ctx: Context<CanCrank>, caller: Pubkey) -> Result<CrankPreflight> {
+        instructions::can_crank::handler(ctx, caller)
+    }
+
+    /// Deposit tokens into a schedule's vault without changing its allocation
+    /// Recreates the vault if it was closed early, so funding can resume
+    /// Security: The vault may never hold more than the amount still owed to the recipient
+    pub fn fund_vault(ctx: Context<FundVault>, amount: u64) -> Result<()> {
+        instructions::fund_vault::handler(ctx, amount)
+    }
+
+    /// Move a schedule's unvested remainder into a new schedule for another recipient
+    /// The source keeps only what has already unlocked and ends vesting now; the remainder is
+    /// moved vault-to-vault into a new schedule with fresh timing
+    /// Security: The source vault must hold everything still owed before the split, so both
+    /// schedules are fully funded afterwards
+    pub fn reassign_unvested(
+        ctx: Context<ReassignUnvested>,
+        new_schedule_id: u64,
+        params: ReassignUnvestedParams,
+    ) -> Result<()> {
+        instructions::reassign_unvested::handler(ctx, new_schedule_id, params)
+    }
+
+    /// Total tokens unlocked across a set of schedules at each requested timestamp
+    /// Remaining accounts: vesting schedules of a single mint, at most MAX_SCAN_BATCH_SIZE
+    /// Returns one cumulative unlocked amount per timestamp (the circulating-supply curve)
+    /// Pagination: for larger schedule sets call once per page of schedules with the same
+    /// timestamps and sum the returned vectors element-wise off-chain. Points are also paginated
+    /// from `offset` (see `return_data::Page`)
+    pub fn aggregate_unlock_curve<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ScanSchedules<'info>>,
+        timestamps: Vec<i64>,
+        offset: u32,
+    ) -> Result<UnlockCurvePage> {
+        instructions::aggregate_unlock_curve::handler(ctx, timestamps, offset)
+    }
+
+    /// Claim the transferable amount of a wrapped SOL schedule as native SOL
+    /// wSOL moves from the vault into a temporary account that is closed to the recipient in the
+    /// same instruction, unwrapping it; the recipient also gets the temporary account's rent back
+    pub fn claim_unwrapped_sol(ctx: Context<ClaimUnwrappedSol>) -> Result<()> {
+        instructions::claim_unwrapped_sol::handler(ctx)
+    }
+
+    /// Amount-weighted average unlock timestamp of a schedule's remaining tokens
+    pub fn get_weighted_avg_unlock_timestamp(ctx: Context<ViewVestingSchedule>) -> Result<i64> {
+        instructions::get_weighted_avg_unlock_timestamp::handler(ctx)
+    }
+
+    /// Propose a new on-chain record of the intended upgrade authority
+    /// The record does not touch the BPF loader's real upgrade authority, it documents intent
+    /// so auditors can cross-check it. Confirmable after UPGRADE_AUTHORITY_TIMELOCK.
+    /// Security: Admin-only
+    pub fn propose_upgrade_authority(ctx: Context<UpdateProgramConfig>, new_authority: Pubkey) -> Result<()> {
+        instructions::propose_upgrade_authority::handler(ctx, new_authority)
+    }
+
+    /// Confirm the pending upgrade authority record after the timelock
+    /// Security: Admin-only, `new_authority` must match the pending proposal
+    pub fn confirm_upgrade_authority(ctx: Context<UpdateProgramConfig>, new_authority: Pubkey) -> Result<()> {
+        instructions::confirm_upgrade_authority::handler(ctx, new_authority)
+    }
+
+    /// Propose a replacement recipient token account for a schedule, e.g. after the recipient
+    /// closed or lost the recorded one. Confirmable after RECIPIENT_CHANGE_TIMELOCK.
+    /// Security: Admin-only; the new account must be owned by the schedule's recipient and hold
+    /// the schedule's mint, so only the destination account changes, never the beneficiary
+    pub fn propose_recipient_token_account(ctx: Context<UpdateRecipientTokenAccount>) -> Result<()> {
+        instructions::propose_recipient_token_account::handler(ctx)
+    }
+
+    /// Confirm a schedule's pending recipient token account after the timelock
+    /// Security: Admin-only, the account must match the pending proposal and is re-validated
+    pub fn confirm_recipient_token_account(ctx: Context<UpdateRecipientTokenAccount>) -> Result<()> {
+        instructions::confirm_recipient_token_account::handler(ctx)
+    }
+
+    /// Propose a new admin, who can accept after ADMIN_TRANSFER_TIMELOCK
+    /// A new proposal replaces the pending one and restarts the timelock
+    /// Security: Admin-only, the old admin keeps full control until the new one accepts
+    pub fn transfer_admin(ctx: Context<UpdateProgramConfig>, new_admin: Pubkey) -> Result<()> {
+        instructions::transfer_admin::handler(ctx, new_admin)
+    }
+
+    /// Accept the admin role after the timelock
+    /// Security: Signed by the pending admin, not the old one, proving control of the new key
+    pub fn accept_admin(ctx: Context<AcceptAdmin>) -> Result<()> {
+        instructions::accept_admin::handler(ctx)
+    }
+
+    /// Enable or disable release-batching mode for `crank_vesting_schedule`
+    /// Amounts already accrued stay pending until flushed, whichever mode is active
+    pub fn set_release_batching(ctx: Context<UpdateCrankPolicy>, accumulate_releases: bool) -> Result<()> {
+        instructions::set_release_batching::handler(ctx, accumulate_releases)
+    }
+
+    /// Transfer the accrued `pending_release` of several schedules in one transaction
+    /// Remaining accounts: (vesting_schedule, vesting_vault, recipient_token_account, mint) entries
+    /// Each transfer is bounded by the vault balance, a short vault leaves the rest pending.
+    /// Respects `max_release_per_transaction` like the batch crank.
+    /// Security: Permissionless, tokens only ever move to the recorded recipient token account
+    pub fn flush_releases<'info>(
+        ctx: Context<'_, '_, 'info, 'info, CrankVestingSchedulesBatch<'info>>,
+    ) -> Result<()> {
+        instructions::flush_releases::handler(ctx)
+    }
+
+    /// Resolve (recipient, mint) to the schedule_id of the recipient's first schedule of that mint
+    /// Fails with AccountNotInitialized when the recipient has no schedule of the mint
+    pub fn get_primary_schedule(
+        ctx: Context<GetPrimarySchedule>,
+        _recipient: Pubkey,
+        _mint: Pubkey,
+    ) -> Result<u64> {
+        instructions::get_primary_schedule::handler(ctx, _recipient, _mint)
+    }
+
+    /// Irreversibly lock a schedule's terms against admin amendments
+    /// Afterwards every amendment instruction (top-ups, recipient token account changes,
+    /// reassignment, revocation, merging) fails with ScheduleImmutable, while cranks,
+    /// claims and closing keep working
+    /// Security: Admin-only, there is no instruction to undo it
+    pub fn finalize_schedule(ctx: Context<AmendVestingSchedule>) -> Result<()> {
+        instructions::finalize_schedule::handler(ctx)
+    }
+
+    /// Configure the one-time creation fee charged on top of each new schedule's deposit
+    /// `fee_destination` is the wallet whose token account of the schedule's mint receives the fee
+    /// Security: Admin-only, `fee_destination` must be set whenever the fee is non-zero
+    pub fn set_creation_fee(
+        ctx: Context<UpdateProgramConfig>,
+        creation_fee_bps: u16,
+        fee_destination: Pubkey,
+    ) -> Result<()> {
+        instructions::set_creation_fee::handler(ctx, creation_fee_bps, fee_destination)
+    }
+
+    /// Names and Anchor discriminators of every event the program emits
+    /// Lets indexers verify their decoders against the deployed program across upgrades.
+    /// Paginated from `offset` (see `return_data::Page`)
+    pub fn get_event_schemas(ctx: Context<GetEventSchemas>, offset: u32) -> Result<EventSchemaPage> {
+        instructions::get_event_schemas::handler(ctx, offset)
+    }
+
+    /// Publish a solvency attestation over a set of schedules
+    /// Remaining accounts: (vesting_schedule, vesting_vault) pairs, at most MAX_SCAN_BATCH_SIZE
+    /// Emits one `ReconciliationReport` with aggregate figures and the number of schedules whose
+    /// vault holds less than their outstanding amount. Larger programs attest page by page.
+    /// Security: Admin-only, reads state without modifying it
+    pub fn emit_reconciliation<'info>(
+        ctx: Context<'_, '_, 'info, 'info, AdminScanSchedules<'info>>,
+    ) -> Result<()> {
+        instructions::emit_reconciliation::handler(ctx)
+    }
+
+    /// Set the minimum release size that earns the keeper reward
+    /// Security: Admin or CAPABILITY_CRANK_MANAGE delegate
+    pub fn set_reward_buffer(ctx: Context<UpdateCrankPolicy>, reward_buffer: u64) -> Result<()> {
+        instructions::set_reward_buffer::handler(ctx, reward_buffer)
+    }
+
+    /// Program-wide schedule count, allocated and distributed totals in one call
+    /// Saves dashboards from scanning every schedule account
+    pub fn get_program_stats(ctx: Context<ViewProgramConfig>) -> Result<ProgramStats> {
+        instructions::get_program_stats::handler(ctx)
+    }
+
+    /// One-stop view of every limit and policy flag, for validating transactions client-side
+    pub fn get_limits(ctx: Context<ViewProgramConfig>) -> Result<ProgramLimits> {
+        instructions::get_limits::handler(ctx)
+    }
+
+    /// Set or clear (`vesting_duration == 0`) the default timing template of a category
+    /// Security: Admin or CAPABILITY_CONFIG delegate, only affects later `create_from_template` calls
+    pub fn set_category_template(
+        ctx: Context<UpdateConfigPolicy>,
+        source_category: SourceCategory,
+        template: CategoryTemplate,
+    ) -> Result<()> {
+        instructions::set_category_template::handler(ctx, source_category, template)
+    }
+
+    /// Set or clear (`cap == 0`) the allocation cap of a category
+    /// A cap below what is already allocated is allowed and blocks further allocations
+    /// Security: Admin or CAPABILITY_CONFIG delegate
+    pub fn set_category_cap(
+        ctx: Context<UpdateConfigPolicy>,
+        source_category: SourceCategory,
+        cap: u64,
+    ) -> Result<()> {
+        instructions::set_category_cap::handler(ctx, source_category, cap)
+    }
+
+    /// Create a schedule whose cliff, vesting start and end come from its category's template
+    /// Other options take their defaults (round down, standalone, no claim interval) and the
+    /// derived end must lie in the future
+    /// Security: Same validation and accounts as `create_vesting_schedule`
+    pub fn create_from_template(
+        ctx: Context<CreateVestingSchedule>,
+        schedule_id: u64,
+        params: CreateFromTemplateParams,
+    ) -> Result<()> {
+        instructions::create_from_template::handler(ctx, schedule_id, params)
+    }
+
+    /// Claim the transferable amount of several schedules into one recipient token account
+    /// Remaining accounts: (vesting_schedule, vesting_vault) pairs
+    /// Schedules with nothing to release, or whose claim interval has not elapsed, are skipped.
+    /// Respects `max_release_per_transaction` like the batch crank.
+    /// Returns the total amount claimed.
+    /// Security: Every schedule must belong to the signer and record `recipient_token_account`
+    pub fn claim_all<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ClaimAll<'info>>,
+    ) -> Result<u64> {
+        instructions::claim_all::handler(ctx)
+    }
+
+    /// Allow or forbid creating placeholder schedules with `total_amount == 0`
+    /// Placeholders release nothing until `top_up_batch` sets their amount and cannot be closed
+    pub fn set_allow_zero_amount(ctx: Context<UpdateConfigPolicy>, allow_zero_amount: bool) -> Result<()> {
+        instructions::set_allow_zero_amount::handler(ctx, allow_zero_amount)
+    }
+
+    /// Estimated payout completion of a schedule for a keeper's crank cadence
+    /// See `VestingSchedule::completion_eta` for the model
+    pub fn get_completion_eta(
+        ctx: Context<ViewVestingSchedule>,
+        crank_interval: i64,
+        min_release: u64,
+    ) -> Result<i64> {
+        instructions::get_completion_eta::handler(ctx, crank_interval, min_release)
+    }
+
+    /// Vested and claimable amounts of a schedule computed by the on-chain vesting math
+    /// Call through simulation so front-ends never re-implement `calculate_unlocked_amount`
+    /// `min_release` only affects the `cranks_remaining` estimate (0 = no minimum)
+    pub fn get_vesting_status(ctx: Context<ViewVestingSchedule>, min_release: u64) -> Result<VestingStatus> {
+        instructions::get_vesting_status::handler(ctx, min_release)
+    }
+
+    /// Additional amount a schedule unlocks between `from_ts` and `to_ts` (`to_ts >= from_ts`)
+    /// Call through simulation to compute period vesting expense with the on-chain math
+    pub fn get_unlocked_between(ctx: Context<ViewVestingSchedule>, from_ts: i64, to_ts: i64) -> Result<u64> {
+        instructions::get_unlocked_between::handler(ctx, from_ts, to_ts)
+    }
+
+    /// Check that a schedule's on-chain terms still hash to the `terms_hash` recorded at creation
+    /// Returns true on a match; fails with TermsHashNotSet when no hash was recorded
+    pub fn verify_terms(ctx: Context<ViewVestingSchedule>) -> Result<bool> {
+        instructions::verify_terms::handler(ctx)
+    }
+
+    /// Grant capability bits to a delegate key, adding to any it already holds
+    /// Security: Admin-only, at most MAX_DELEGATES delegates
+    pub fn grant_capability(
+        ctx: Context<UpdateProgramConfig>,
+        delegate: Pubkey,
+        capabilities: u8,
+    ) -> Result<()> {
+        instructions::grant_capability::handler(ctx, delegate, capabilities)
+    }
+
+    /// Revoke capability bits from a delegate, removing it once it holds none
+    /// Security: Admin-only, takes effect immediately
+    pub fn revoke_capability(
+        ctx: Context<UpdateProgramConfig>,
+        delegate: Pubkey,
+        capabilities: u8,
+    ) -> Result<()> {
+        instructions::revoke_capability::handler(ctx, delegate, capabilities)
+    }
+
+    /// Enable or disable stretching underfunded schedules instead of clamping releases
+    /// See `VestingSchedule::extend_for_underfunding`. Extensions are permanent, so this is
+    /// meant for deployments that knowingly run partially funded schedules
+    /// Security: Admin or CAPABILITY_CONFIG delegate
+    pub fn set_auto_extend_underfunded(
+        ctx: Context<UpdateConfigPolicy>,
+        auto_extend_underfunded: bool,
+    ) -> Result<()> {
+        instructions::set_auto_extend_underfunded::handler(ctx, auto_extend_underfunded)
+    }
+
+    /// Keeper work queue: the schedules with the most to release right now
+    /// Remaining accounts: candidate vesting schedules (at most MAX_SCAN_BATCH_SIZE)
+    /// Returns up to `max_results` (at most MAX_CRANK_BATCH_SIZE, one batch crank) schedule_ids
+    /// with a positive `get_transferable_amount`, largest first, ties in input order.
+    /// Vault balances are not consulted, so an underfunded schedule may release less.
+    pub fn schedules_due<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ScanSchedules<'info>>,
+        max_results: u8,
+    ) -> Result<Vec<u64>> {
+        instructions::schedules_due::handler(ctx, max_results)
+    }
+
+    /// Move the balance left in a fully processed schedule's vault to the distribution hub
+    /// Releases are rounded on the cumulative unlocked amount, so a completed schedule has paid
+    /// its whole allocation and anything left in the vault is surplus. Sweeping it lets the
+    /// schedule be closed and adds it to `swept_dust`, so every deposited token is accounted for.
+    /// A crank of the schedule delivers the same surplus to the recipient instead.
+    /// Returns the amount swept (0 when the vault holds nothing beyond the allocation)
+    /// Security: Admin-only, never touches tokens still owed to the recipient
+    pub fn sweep_rounding_dust(ctx: Context<SweepRoundingDust>) -> Result<u64> {
+        instructions::sweep_rounding_dust::handler(ctx)
+    }
+
+    /// Crank a schedule and, when the release leaves it fully vested with an empty vault,
+    /// close the schedule and vault in the same transaction, saving a separate close
+    /// Returns true when the accounts were closed; otherwise this behaves like `crank_vesting_schedule`
+    /// Security: Closes only under the same conditions as `close_vesting_schedule`
+    pub fn crank_and_maybe_close(ctx: Context<CrankAndMaybeClose>) -> Result<bool> {
+        instructions::crank_and_maybe_close::handler(ctx)
+    }
+
+    /// Recipient-signed claim of everything currently vested, sent to the recorded recipient
+    /// token account. Lets beneficiaries pull tokens on their own schedule instead of waiting
+    /// for a crank; nothing claimable yet is not an error.
+    /// Security: Recipient-only, the token account must still be owned by the recipient
+    pub fn claim_vested(ctx: Context<ClaimVested>) -> Result<()> {
+        instructions::claim_vested::handler(ctx)
+    }
+
+    /// Set the maximum number of schedules of one mint that may exist at once (0 = unlimited)
+    /// Lowering it below a mint's current count only blocks new schedules of that mint
+    /// Security: Admin or CAPABILITY_CONFIG delegate
+    pub fn set_max_schedules_per_mint(
+        ctx: Context<UpdateConfigPolicy>,
+        max_schedules_per_mint: u64,
+    ) -> Result<()> {
+        instructions::set_max_schedules_per_mint::handler(ctx, max_schedules_per_mint)
+    }
+
+    /// Preview the amount a revocation would reclaim right now
+    /// Returns the unvested tokens held by the vault, see `VestingSchedule::clawback_amount`
+    pub fn get_clawback_preview(ctx: Context<GetClawbackPreview>) -> Result<u64> {
+        instructions::get_clawback_preview::handler(ctx)
+    }
+
+    /// Revoke a schedule: the vested-but-unclaimed amount goes to the recipient, the rest of
+    /// the vault is clawed back to an admin token account and the schedule stops releasing
+    /// Security: Admin-only, finalized schedules cannot be revoked
+    pub fn revoke_vesting_schedule(ctx: Context<RevokeVestingSchedule>) -> Result<()> {
+        instructions::revoke_vesting_schedule::handler(ctx)
+    }
+
+    /// Merge two schedules of the same recipient and mint with identical timing into one
+    /// The source schedule's amounts and vault balance move into the target, then the source
+    /// schedule and vault are closed
+    /// Security: Admin and recipient co-sign
+    pub fn merge_schedules(ctx: Context<MergeSchedules>) -> Result<()> {
+        instructions::merge_schedules::handler(ctx)
+    }
+
+    /// Break-glass recovery of a stuck vault: the whole balance goes to an admin token account
+    /// and the schedule is revoked
+    /// Security: Admin-only and only while the program is paused
+    pub fn emergency_withdraw(ctx: Context<EmergencyWithdraw>) -> Result<()> {
+        instructions::emergency_withdraw::handler(ctx)
+    }
+
+    /// Allow (or with `enabled = false`, clear) the swap program for swap-at-release
+    /// Only recorded for now: schedules are always funded in their release mint
+    /// Security: Admin-only, the program must be executable and cannot be the token program
+    pub fn set_swap_program(ctx: Context<SetSwapProgram>, enabled: bool) -> Result<()> {
+        instructions::set_swap_program::handler(ctx, enabled)
+    }
+
+    /// Emergency pause: while paused, cranks, claims and every other instruction that moves
+    /// tokens out of a vault fail with ProgramPaused. Creating schedules and closing empty
+    /// ones keep working so cleanup can continue.
+    /// Security: Admin-only
+    pub fn set_pause(ctx: Context<SetPause>, paused: bool) -> Result<()> {
+        instructions::set_pause::handler(ctx, paused)
+    }
+
+    /// Dry-run a creation batch: returns, per entry, the error code `create_vesting_schedule`
+    /// would fail with (timing, amounts, category policy and caps, recipient account ownership and
+    /// cumulative depositor funding) without creating anything or moving tokens
+    /// Remaining accounts: one recipient token account per entry
+    pub fn validate_batch<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ValidateBatch<'info>>,
+        params: Vec<CreateVestingScheduleParams>,
+    ) -> Result<Vec<BatchEntryValidation>> {
+        instructions::validate_batch::handler(ctx, params)
+    }
+
+    /// Set how long closed schedules are kept as archived records (0 = delete on close)
+    /// Applies to schedules closed afterwards; already archived ones keep their purge time
+    /// Security: Admin or CAPABILITY_CONFIG delegate
+    pub fn set_archive_retention(
+        ctx: Context<UpdateConfigPolicy>,
+        archive_retention_period: u64,
+    ) -> Result<()> {
+        instructions::set_archive_retention::handler(ctx, archive_retention_period)
+    }
+
+    /// Set how long a completed schedule must stay open before it can be closed (0 = no wait)
+    /// Counted from the release that completed the schedule, see `VestingSchedule::completed_at`
+    /// Security: Admin or CAPABILITY_CONFIG delegate
+    pub fn set_close_grace_period(ctx: Context<UpdateConfigPolicy>, close_grace_period: u64) -> Result<()> {
+        instructions::set_close_grace_period::handler(ctx, close_grace_period)
+    }
+
+    /// Delete an archived schedule once its retention period has elapsed
+    /// Rent goes to the signer, like `close_vesting_schedule`
+    pub fn purge_archived(ctx: Context<PurgeArchived>) -> Result<()> {
+        instructions::purge_archived::handler(ctx)
+    }
+}
+
+// ================================================================================================
+// EVENTS
+// ================================================================================================
+
+#[event]
+pub struct ProgramInitialized {
+    pub admin: Pubkey,
+    pub program_config: Pubkey,
+}
+
+#[event]
+pub struct ProgramConfigMigrated {
+    pub admin: Pubkey,
+    pub program_config: Pubkey,
+    pub old_len: u32,
+    pub new_len: u32,
+}
+
+#[event]
+pub struct VestingScheduleCreated {
+    pub schedule_id: u64,
+    pub recipient: Pubkey,
+    pub mint: Pubkey,
+    pub total_amount: u64,
+    pub cliff_timestamp: i64,
+    pub vesting_start_timestamp: i64,
+    pub vesting_end_timestamp: i64,
+    pub source_category: SourceCategory,
+    pub depositor: Pubkey,
+    pub grant_group_id: u64,
+    pub label: [u8; 32],
+}
+
+/// Token release event with recipient field for complete audit trail
+#[event]
+pub struct TokensReleased {
+    pub schedule_id: u64,
+    pub recipient: Pubkey,
+    pub mint: Pubkey,
+    pub amount: u64,
+    pub source_category: SourceCategory,
+    pub timestamp: i64,
+    pub total_released: u64,
+    /// Keeper reward drawn from the release and paid to the cranking keeper
+    pub keeper_fee: u64,
+}
+
+/// Emitted for each schedule whose allocation is increased after creation
+#[event]
+pub struct ScheduleToppedUp {
+    pub schedule_id: u64,
+    pub added_amount: u64,
+    pub new_total: u64,
+}
+
+#[event]
+pub struct KeeperRewardUpdated {
+    pub old_keeper_reward_bps: u16,
+    pub new_keeper_reward_bps: u16,
+}
+
+#[event]
+pub struct SchedulesRecounted {
+    pub old_total_schedules: u64,
+    pub new_total_schedules: u64,
+    pub highest_schedule_id: u64,
+}
+
+#[event]
+pub struct MaxReleasePerTransactionUpdated {
+    pub old_max_release_per_transaction: u64,
+    pub new_max_release_per_transaction: u64,
+}
+
+/// Emitted when the distribution hub is configured for the first time
+#[event]
+pub struct DistributionHubInitialized {
+    pub admin: Pubkey,
+    pub hub: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct DistributionHubUpdateProposed {
+    pub admin: Pubkey,
+    pub current_hub: Pubkey,
+    pub proposed_hub: Pubkey,
+    pub timelock_expiry: i64,
+}
+
+/// Emitted when a proposed hub change is confirmed after the timelock
+#[event]
+pub struct DistributionHubUpdated {
+    pub admin: Pubkey,
+    pub old_hub: Pubkey,
+    pub new_hub: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ClaimedWithSignature {
+    pub schedule_id: u64,
+    pub recipient: Pubkey,
+    pub relayer: Pubkey,
+    pub destination: Pubkey,
+    pub amount: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct RecipientAccountOwnershipChanged {
+    pub schedule_id: u64,
+    pub recipient: Pubkey,
+    pub recipient_token_account: Pubkey,
+    pub current_owner: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct CliffStartPolicyUpdated {
+    pub source_category: SourceCategory,
+    pub required: bool,
+}
+
+#[event]
+pub struct ReadOnlyModeUpdated {
+    pub admin: Pubkey,
+    pub read_only: bool,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct WithdrawalAuthorityUpdated {
+    pub schedule_id: u64,
+    pub recipient: Pubkey,
+    pub withdrawal_authority: Option<Pubkey>,
+}
+
+#[event]
+pub struct StreamWithdrawn {
+    pub schedule_id: u64,
+    pub withdrawal_authority: Pubkey,
+    pub amount: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct VaultFunded {
+    pub schedule_id: u64,
+    pub amount: u64,
+    pub vault_balance: u64,
+}
+
+#[event]
+pub struct ClaimIntervalCrankEnforcementUpdated {
+    pub enforce_claim_interval_on_crank: bool,
+}
+
+#[event]
+pub struct MaxClockSkewUpdated {
+    pub old_max_clock_skew: u64,
+    pub new_max_clock_skew: u64,
+}
+
+#[event]
+pub struct UnvestedReassigned {
+    pub source_schedule_id: u64,
+    pub new_schedule_id: u64,
+    pub amount: u64,
+    pub source_old_total: u64,
+    pub source_new_total: u64,
+    pub source_vesting_end_timestamp: i64,
+    pub timestamp: i64,
+}
+
+/// Emitted when a crank in release-batching mode accrues instead of transferring
+#[event]
+pub struct ReleaseAccrued {
+    pub schedule_id: u64,
+    pub amount: u64,
+    pub pending_release: u64,
+    pub timestamp: i64,
+}
+
+/// Emitted when a mid-vesting crank released nothing because the linear rate is below
+/// one token unit per crank interval
+#[event]
+pub struct ReleaseRateTooLow {
+    pub schedule_id: u64,
+    pub total_amount: u64,
+    pub suggested_crank_interval: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct CrankSkipped {
+    pub schedule_id: u64,
+    pub reason: CrankBlockReason,
+    pub timestamp: i64,
+}
+
+/// Emitted when a new upgrade authority record is proposed
+#[event]
+pub struct UpgradeAuthorityProposed {
+    pub admin: Pubkey,
+    pub current_authority: Pubkey,
+    pub proposed_authority: Pubkey,
+    pub timelock_expiry: i64,
+}
+
+/// Emitted when a proposed upgrade authority record is confirmed after the timelock
+#[event]
+pub struct UpgradeAuthorityConfirmed {
+    pub admin: Pubkey,
+    pub old_authority: Pubkey,
+    pub new_authority: Pubkey,
+    pub timestamp: i64,
+}
+
+/// Emitted when a new admin is proposed
+#[event]
+pub struct AdminTransferProposed {
+    pub admin: Pubkey,
+    pub proposed_admin: Pubkey,
+    pub timelock_expiry: i64,
+}
+
+/// Emitted when the proposed admin accepts the admin role
+#[event]
+pub struct AdminTransferred {
+    pub old_admin: Pubkey,
+    pub new_admin: Pubkey,
+    pub timestamp: i64,
+}
+
+/// Emitted when a schedule's terms are irreversibly locked
+#[event]
+pub struct ScheduleFinalized {
+    pub schedule_id: u64,
+    pub admin: Pubkey,
+    pub timestamp: i64,
+}
+
+/// Emitted when a creation fee is charged for a new schedule
+#[event]
+pub struct CreationFeeCharged {
+    pub schedule_id: u64,
+    pub fee_destination: Pubkey,
+    pub mint: Pubkey,
+    pub fee_bps: u16,
+    pub amount: u64,
+}
+
+/// Solvency attestation over the schedules passed to `emit_reconciliation`
+#[event]
+pub struct ReconciliationReport {
+    pub admin: Pubkey,
+    pub schedules_checked: u32,
+    pub total_deposited: u64,
+    pub total_released: u64,
+    pub total_outstanding: u64,
+    pub total_vault_balance: u64,
+    pub insolvent_schedules: u32,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct RewardBufferUpdated {
+    pub old_reward_buffer: u64,
+    pub new_reward_buffer: u64,
+}
+
+#[event]
+pub struct CategoryTemplateUpdated {
+    pub source_category: SourceCategory,
+    pub cliff_duration: i64,
+    pub vesting_duration: i64,
+}
+
+/// Emitted with the timestamps a category template produced for a new schedule
+#[event]
+pub struct TemplateApplied {
+    pub schedule_id: u64,
+    pub source_category: SourceCategory,
+    pub start_time: i64,
+    pub cliff_timestamp: i64,
+    pub vesting_start_timestamp: i64,
+    pub vesting_end_timestamp: i64,
+}
+
+#[event]
+pub struct DelegateCapabilitiesUpdated {
+    pub delegate: Pubkey,
+    pub old_capabilities: u8,
+    pub new_capabilities: u8,
+}
+
+/// Emitted when an underfunded schedule's vesting window was stretched to its funding
+#[event]
+pub struct ScheduleAutoExtended {
+    pub schedule_id: u64,
+    pub vault_balance: u64,
+    pub old_vesting_end_timestamp: i64,
+    pub new_vesting_end_timestamp: i64,
+    pub timestamp: i64,
+}
+
+/// Emitted when a completed schedule's leftover vault balance is swept to the distribution hub
+#[event]
+pub struct RoundingDustSwept {
+    pub schedule_id: u64,
+    pub mint: Pubkey,
+    pub amount: u64,
+    pub swept_dust: u64,
+    pub destination: Pubkey,
+    pub timestamp: i64,
+}
+
+/// Emitted when the admin revokes a schedule
+#[event]
+pub struct VestingScheduleRevoked {
+    pub schedule_id: u64,
+    pub vested_paid_amount: u64,
+    pub clawed_back_amount: u64,
+    pub total_released: u64,
+    pub timestamp: i64,
+}
+
+/// Emitted when the admin pauses or resumes token movements
+#[event]
+pub struct ProgramPaused {
+    pub paused: bool,
+    pub timestamp: i64,
+}
+
+/// Emitted when a completed schedule and its vault are closed
+/// `vault_lamports + schedule_lamports` is the rent returned to `beneficiary`
+#[event]
+pub struct VestingScheduleClosed {
+    pub schedule_id: u64,
+    pub beneficiary: Pubkey,
+    pub vault_lamports: u64,
+    /// 0 when the schedule was archived instead of deleted
+    pub schedule_lamports: u64,
+    pub amount_transferred: u64,
+    pub archived: bool,
+    pub timestamp: i64,
+}
+
+/// Emitted when a closed schedule is kept as an archived record
+#[event]
+pub struct ScheduleArchived {
+    pub schedule_id: u64,
+    pub purgeable_at: i64,
+    pub timestamp: i64,
+}
+
+/// Emitted when an archived schedule is deleted after its retention period
+#[event]
+pub struct SchedulePurged {
+    pub schedule_id: u64,
+    pub beneficiary: Pubkey,
+    pub timestamp: i64,
+}
+
+/// Emitted once per release milestone (`checkpoint_interval_pct` steps) a schedule crosses
+#[event]
+pub struct ReleaseCheckpoint {
+    pub schedule_id: u64,
+    pub milestone_pct: u8,
+    pub cumulative_released: u64,
+    pub timestamp: i64,
+}
+
+/// Emitted when `merge_schedules` folds one schedule into another
+#[event]
+pub struct SchedulesMerged {
+    pub target_schedule_id: u64,
+    /// Closed after the merge
+    pub source_schedule_id: u64,
+    /// `total_amount` of the source schedule added to the target
+    pub merged_amount: u64,
+    /// Source vault balance moved into the target vault
+    pub moved_amount: u64,
+    pub new_total_amount: u64,
+    pub timestamp: i64,
+}
+
+/// Emitted when a category's allocation cap is set or cleared
+#[event]
+pub struct CategoryCapUpdated {
+    pub source_category: SourceCategory,
+    pub old_cap: u64,
+    pub new_cap: u64,
+    /// Already allocated to the category when the cap changed
+    pub allocated: u64,
+}
+
+/// Emitted when the program-wide release rate limit is configured
+#[event]
+pub struct ReleaseRateLimitUpdated {
+    /// 0 = unlimited
+    pub max_release_per_window: u64,
+    pub window_duration: u64,
+}
+
+/// Emitted when a replacement recipient token account is proposed for a schedule
+#[event]
+pub struct RecipientTokenAccountChangeProposed {
+    pub schedule_id: u64,
+    pub current_account: Pubkey,
+    pub proposed_account: Pubkey,
+    pub timelock_expiry: i64,
+}
+
+/// Emitted when a schedule's recipient token account is replaced after the timelock
+#[event]
+pub struct RecipientTokenAccountChanged {
+    pub schedule_id: u64,
+    pub old_account: Pubkey,
+    pub new_account: Pubkey,
+    pub timestamp: i64,
+}
+
+/// Emitted when a paused program's admin recovers a schedule's vault balance
+#[event]
+pub struct EmergencyWithdrawn {
+    pub schedule_id: u64,
+    pub amount: u64,
+    pub recovery_token_account: Pubkey,
+    pub timestamp: i64,
+}
+
+/// Emitted when the per-schedule crank rate limit changes
+#[event]
+pub struct MinCrankIntervalUpdated {
+    pub old_min_crank_interval: i64,
+    pub new_min_crank_interval: i64,
+}
+
+/// Emitted when a pending distribution hub change is cancelled
+#[event]
+pub struct DistributionHubUpdateCancelled {
+    pub admin: Pubkey,
+    pub cancelled_hub: Pubkey,
+    pub timestamp: i64,
+}
+
+/// Emitted when a completed schedule's vault is closed and the schedule is kept
+#[event]
+pub struct VaultClosed {
+    pub schedule_id: u64,
+    pub beneficiary: Pubkey,
+    /// Vault rent returned to the beneficiary
+    pub vault_lamports: u64,
+    pub timestamp: i64,
+}
+
+/// Emitted when `CrankSkipped` events are turned on or off
+#[event]
+pub struct EmitSkipEventsUpdated {
+    pub old_emit_skip_events: bool,
+    pub new_emit_skip_events: bool,
+}
+
+/// Emitted when zero-amount `TokensReleased` events are turned on or off
+#[event]
+pub struct SkipZeroEventsUpdated {
+    pub old_skip_zero_events: bool,
+    pub new_skip_zero_events: bool,
+}
+
+/// Emitted when the `ReleaseCheckpoint` milestone step changes
+#[event]
+pub struct CheckpointIntervalUpdated {
+    pub old_checkpoint_interval_pct: u8,
+    pub new_checkpoint_interval_pct: u8,
+}
+
+/// Emitted when the even step division requirement for stepped schedules changes
+#[event]
+pub struct RequireEvenStepDivisionUpdated {
+    pub old_required: bool,
+    pub new_required: bool,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anchor_lang::solana_program::program_pack::Pack;
+    use anchor_spl::token::spl_token::state::{Account as SplTokenAccount, AccountState};
+
+    fn packed_vault(owner: Pubkey) -> Vec<u8> {
+        let vault = SplTokenAccount {
+            mint: Pubkey::new_unique(),
+            owner,
+            amount: 1_000,
+            state: AccountState::Initialized,
+            ..SplTokenAccount::default()
+        };
+        let mut data = vec![0u8; SplTokenAccount::LEN];
+        vault.pack_into_slice(&mut data);
+        data
+    }
+
+    #[test]
+    fn test_require_vault_authority_reads_current_owner() {
+        let vesting_schedule = Pubkey::new_unique();
+        assert!(require_vault_authority(&packed_vault(vesting_schedule), &vesting_schedule).is_ok());
+
+        // Authority moved after the account constraints ran
+        let reassigned = packed_vault(Pubkey::new_unique());
+        let error = require_vault_authority(&reassigned, &vesting_schedule).unwrap_err();
+        assert_eq!(error, VestingError::VaultAuthorityMismatch.into());
+    }
+
+    #[test]
+    fn test_require_vault_authority_accepts_token_2022_extensions() {
+        use anchor_spl::token_2022::spl_token_2022::{
+            extension::{transfer_fee::TransferFeeAmount, BaseStateWithExtensionsMut, ExtensionType, StateWithExtensionsMut},
+            state::{Account as Token2022Account, AccountState as Token2022AccountState},
+        };
+
+        // Vault of a transfer-fee mint: base account followed by a TransferFeeAmount extension
+        let vesting_schedule = Pubkey::new_unique();
+        let len = ExtensionType::try_calculate_account_len::<Token2022Account>(&[ExtensionType::TransferFeeAmount]).unwrap();
+        assert!(len > SplTokenAccount::LEN);
+        let mut data = vec![0u8; len];
+        let mut vault = StateWithExtensionsMut::<Token2022Account>::unpack_uninitialized(&mut data).unwrap();
+        vault.init_extension::<TransferFeeAmount>(true).unwrap();
+        vault.base = Token2022Account {
+            mint: Pubkey::new_unique(),
+            owner: vesting_schedule,
+            amount: 1_000,
+            state: Token2022AccountState::Initialized,
+            ..Token2022Account::default()
+        };
+        vault.pack_base();
+        vault.init_account_type().unwrap();
+
+        assert!(require_vault_authority(&data, &vesting_schedule).is_ok());
+        let error = require_vault_authority(&data, &Pubkey::new_unique()).unwrap_err();
+        assert_eq!(error, VestingError::VaultAuthorityMismatch.into());
+    }
+}
@@ -0,0 +1,159 @@
+use anchor_lang::prelude::*;
+use crate::constants::{BPS_DENOMINATOR, TARGET_MS_PER_SLOT};
+use crate::errors::VestingError;
+
+/// Validate a basis-points value
+///
+/// # Returns
+/// * `Ok(())` if `bps` is within 0..=10000
+/// * `InvalidBps` otherwise
+pub fn validate_bps(bps: u16) -> Result<()> {
+    require!(bps <= BPS_DENOMINATOR, VestingError::InvalidBps);
+    Ok(())
+}
+
+/// Portion of `amount` given by `bps`, rounded down
+///
+/// # Returns
+/// * `InvalidBps` if `bps` exceeds 10000
+pub fn apply_bps(amount: u64, bps: u16) -> Result<u64> {
+    validate_bps(bps)?;
+    to_u64(
+        to_u128(amount)?
+            .checked_mul(u128::from(bps))
+            .ok_or(VestingError::MathOverflow)?
+            / u128::from(BPS_DENOMINATOR)
+    )
+}
+
+/// Validate how a step schedule splits `total_amount` across `num_periods`
+///
+/// # Returns
+/// * Per-period amount; with dust allowed the remainder is released in the final step
+/// * `InvalidAmount` if `num_periods` is zero
+/// * `UnevenStepDivision` if `require_even_division` is set and the split leaves a remainder
+pub fn validate_step_division(
+    total_amount: u64,
+    num_periods: u64,
+    require_even_division: bool,
+) -> Result<u64> {
+    require!(num_periods > 0, VestingError::InvalidAmount);
+    let per_period_amount = total_amount / num_periods;
+    if require_even_division {
+        require!(per_period_amount * num_periods == total_amount, VestingError::UnevenStepDivision);
+    }
+    Ok(per_period_amount)
+}
+
+/// Checked conversion to u64
+///
+/// # Returns
+/// * `MathOverflow` if `value` does not fit (negative or too large)
+pub fn to_u64<T: TryInto<u64>>(value: T) -> Result<u64> {
+    value.try_into().map_err(|_| error!(VestingError::MathOverflow))
+}
+
+/// Checked conversion to u128
+///
+/// # Returns
+/// * `MathOverflow` if `value` does not fit (e.g. negative)
+pub fn to_u128<T: TryInto<u128>>(value: T) -> Result<u128> {
+    value.try_into().map_err(|_| error!(VestingError::MathOverflow))
+}
+
+/// Checked conversion of a non-negative i64 (timestamps, durations) to u64
+///
+/// # Returns
+/// * `MathOverflow` if `value` is negative
+pub fn i64_to_u64(value: i64) -> Result<u64> {
+    to_u64(value)
+}
+
+/// Checked conversion to i64
+///
+/// # Returns
+/// * `MathOverflow` if `value` does not fit
+pub fn to_i64<T: TryInto<i64>>(value: T) -> Result<i64> {
+    value.try_into().map_err(|_| error!(VestingError::MathOverflow))
+}
+
+/// Seconds between the cluster unix timestamp and the time implied by slot progression
+///
+/// The estimate is `epoch_start_timestamp + slots_since_epoch_start * TARGET_MS_PER_SLOT`.
+/// Real slot times drift from the target, so the estimate loses accuracy late in an epoch
+/// and any tolerance compared against it must be generous.
+pub fn clock_skew_seconds(
+    unix_timestamp: i64,
+    epoch_start_timestamp: i64,
+    slots_since_epoch_start: u64,
+) -> Result<u64> {
+    let expected_elapsed_seconds = to_i64(
+        slots_since_epoch_start
+            .checked_mul(TARGET_MS_PER_SLOT)
+            .ok_or(VestingError::MathOverflow)?
+            / 1_000,
+    )?;
+    let expected_timestamp = epoch_start_timestamp
+        .checked_add(expected_elapsed_seconds)
+        .ok_or(VestingError::MathOverflow)?;
+    Ok(unix_timestamp
+        .checked_sub(expected_timestamp)
+        .ok_or(VestingError::MathOverflow)?
+        .unsigned_abs())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_bps_bounds() {
+        assert!(validate_bps(0).is_ok());
+        assert!(validate_bps(BPS_DENOMINATOR).is_ok());
+        assert!(validate_bps(BPS_DENOMINATOR + 1).is_err());
+    }
+
+    #[test]
+    fn test_apply_bps() {
+        assert_eq!(apply_bps(10_000, 250).unwrap(), 250);
+        assert_eq!(apply_bps(999, 100).unwrap(), 9);
+        assert_eq!(apply_bps(u64::MAX, BPS_DENOMINATOR).unwrap(), u64::MAX);
+        assert!(apply_bps(1, BPS_DENOMINATOR + 1).is_err());
+    }
+
+    #[test]
+    fn test_validate_step_division() {
+        // Even split is accepted either way
+        assert_eq!(validate_step_division(1_200, 12, false).unwrap(), 100);
+        assert_eq!(validate_step_division(1_200, 12, true).unwrap(), 100);
+
+        // Uneven split leaves dust for the final step unless even division is required
+        assert_eq!(validate_step_division(1_000, 12, false).unwrap(), 83);
+        assert!(validate_step_division(1_000, 12, true).is_err());
+
+        assert!(validate_step_division(1_000, 0, false).is_err());
+    }
+
+    #[test]
+    fn test_checked_conversions_at_boundaries() {
+        assert_eq!(to_u64(u64::MAX as u128).unwrap(), u64::MAX);
+        assert!(to_u64(u64::MAX as u128 + 1).is_err());
+        assert!(to_u64(-1i64).is_err());
+
+        assert_eq!(to_u128(i64::MAX).unwrap(), i64::MAX as u128);
+        assert!(to_u128(-1i64).is_err());
+
+        assert_eq!(i64_to_u64(0).unwrap(), 0);
+        assert_eq!(i64_to_u64(i64::MAX).unwrap(), i64::MAX as u64);
+        assert!(i64_to_u64(i64::MIN).is_err());
+    }
+
+    #[test]
+    fn test_clock_skew_seconds() {
+        // 1000 slots at 400ms = 400s after epoch start
+        assert_eq!(clock_skew_seconds(1_400, 1_000, 1_000).unwrap(), 0);
+        // Clock running ahead or behind the slot estimate
+        assert_eq!(clock_skew_seconds(2_000, 1_000, 1_000).unwrap(), 600);
+        assert_eq!(clock_skew_seconds(1_100, 1_000, 1_000).unwrap(), 300);
+    }
+}
\ No newline at end of file
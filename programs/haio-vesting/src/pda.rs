@@ -0,0 +1,82 @@
+use anchor_lang::prelude::*;
+use crate::constants::{
+    NATIVE_VAULT_SEED, PRIMARY_SCHEDULE_SEED, PROGRAM_CONFIG_SEED, VESTING_SCHEDULE_SEED, VESTING_VAULT_SEED,
+};
+
+/// Program configuration PDA and bump
+pub fn program_config_pda() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[PROGRAM_CONFIG_SEED], &crate::ID)
+}
+
+/// Vesting schedule PDA and bump for `schedule_id`
+/// The ID is encoded little-endian, as in the account constraints
+pub fn vesting_schedule_pda(schedule_id: u64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[VESTING_SCHEDULE_SEED, schedule_id.to_le_bytes().as_ref()], &crate::ID)
+}
+
+/// Vesting vault PDA and bump for `schedule_id`
+/// The vault token account is owned by the token program, its authority is the schedule PDA
+pub fn vesting_vault_pda(schedule_id: u64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[VESTING_VAULT_SEED, schedule_id.to_le_bytes().as_ref()], &crate::ID)
+}
+
+/// Native SOL vault PDA and bump for `schedule_id`
+/// A system-owned account holding the lamports of a native schedule, it signs its own releases
+pub fn native_vault_pda(schedule_id: u64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[NATIVE_VAULT_SEED, schedule_id.to_le_bytes().as_ref()], &crate::ID)
+}
+
+/// Primary schedule index PDA and bump for a recipient and mint
+pub fn primary_schedule_index_pda(recipient: &Pubkey, mint: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[PRIMARY_SCHEDULE_SEED, recipient.as_ref(), mint.as_ref()], &crate::ID)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pdas_match_constraint_seeds() {
+        let (config_key, config_bump) = program_config_pda();
+        assert_eq!(
+            Pubkey::create_program_address(&[PROGRAM_CONFIG_SEED, &[config_bump]], &crate::ID).unwrap(),
+            config_key
+        );
+
+        for schedule_id in [0u64, 1, 255, 256, u64::MAX] {
+            let id_bytes = schedule_id.to_le_bytes();
+            let (schedule_key, schedule_bump) = vesting_schedule_pda(schedule_id);
+            assert_eq!(
+                Pubkey::create_program_address(&[VESTING_SCHEDULE_SEED, &id_bytes, &[schedule_bump]], &crate::ID).unwrap(),
+                schedule_key
+            );
+
+            let (vault_key, vault_bump) = vesting_vault_pda(schedule_id);
+            assert_eq!(
+                Pubkey::create_program_address(&[VESTING_VAULT_SEED, &id_bytes, &[vault_bump]], &crate::ID).unwrap(),
+                vault_key
+            );
+            assert_ne!(schedule_key, vault_key);
+
+            let (native_vault_key, native_vault_bump) = native_vault_pda(schedule_id);
+            assert_eq!(
+                Pubkey::create_program_address(&[NATIVE_VAULT_SEED, &id_bytes, &[native_vault_bump]], &crate::ID).unwrap(),
+                native_vault_key
+            );
+            assert_ne!(native_vault_key, vault_key);
+        }
+
+        // Little-endian: ID 1 and ID 1 << 56 must not collide
+        assert_ne!(vesting_schedule_pda(1).0, vesting_schedule_pda(1 << 56).0);
+
+        let (recipient, mint) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let (index_key, index_bump) = primary_schedule_index_pda(&recipient, &mint);
+        assert_eq!(
+            Pubkey::create_program_address(
+                &[PRIMARY_SCHEDULE_SEED, recipient.as_ref(), mint.as_ref(), &[index_bump]],
+                &crate::ID
+            ).unwrap(),
+            index_key
+        );
+    }
+}
@@ -0,0 +1,92 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::program::MAX_RETURN_DATA;
+
+// Fixed part of every page struct: Vec length prefix (4) + truncated (1) + next_offset (4)
+pub const PAGE_OVERHEAD: usize = 4 + 1 + 4;
+
+/// One page of a batch view's results
+///
+/// Pagination contract: results are produced in a deterministic order for a given input.
+/// Each view returns the results starting at `offset` that fit in the return data limit.
+/// When more remain, `truncated` is true and the client repeats the call with the same input
+/// and `offset = next_offset`. `next_offset` always points just past the last returned item.
+pub struct Page<T> {
+    pub items: Vec<T>,
+    pub truncated: bool,
+    pub next_offset: u32,
+}
+
+/// Cut `results` down to the page starting at `offset` that fits in MAX_RETURN_DATA
+/// Items are measured by their Borsh encoding, so variable-size items are handled too.
+/// An `offset` at or past the end yields an empty, non-truncated page.
+pub fn paginate<T: AnchorSerialize>(results: Vec<T>, offset: u32) -> Result<Page<T>> {
+    let total = results.len();
+    let mut used = PAGE_OVERHEAD;
+    let mut items = Vec::new();
+    let mut buffer = Vec::new();
+
+    for item in results.into_iter().skip(offset as usize) {
+        buffer.clear();
+        item.serialize(&mut buffer)?;
+        if used + buffer.len() > MAX_RETURN_DATA {
+            break;
+        }
+        used += buffer.len();
+        items.push(item);
+    }
+
+    let next_offset = (offset as usize).saturating_add(items.len()).min(total);
+    Ok(Page {
+        truncated: next_offset < total,
+        next_offset: next_offset as u32,
+        items,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_paginate_fits_small_results() {
+        let page = paginate(vec![1u64, 2, 3], 0).unwrap();
+        assert_eq!(page.items, vec![1, 2, 3]);
+        assert!(!page.truncated);
+        assert_eq!(page.next_offset, 3);
+    }
+
+    #[test]
+    fn test_paginate_truncates_and_resumes() {
+        let results: Vec<u64> = (0..300).collect();
+        let per_page = (MAX_RETURN_DATA - PAGE_OVERHEAD) / 8;
+
+        let first = paginate(results.clone(), 0).unwrap();
+        assert!(first.truncated);
+        assert_eq!(first.items.len(), per_page);
+        assert_eq!(first.next_offset as usize, per_page);
+
+        let second = paginate(results.clone(), first.next_offset).unwrap();
+        assert_eq!(second.items[0], per_page as u64);
+
+        // Walking every page returns each result exactly once
+        let mut collected = Vec::new();
+        let mut offset = 0;
+        loop {
+            let page = paginate(results.clone(), offset).unwrap();
+            collected.extend(page.items);
+            offset = page.next_offset;
+            if !page.truncated {
+                break;
+            }
+        }
+        assert_eq!(collected, results);
+    }
+
+    #[test]
+    fn test_paginate_offset_past_end() {
+        let page = paginate(vec![1u64, 2], 5).unwrap();
+        assert!(page.items.is_empty());
+        assert!(!page.truncated);
+        assert_eq!(page.next_offset, 2);
+    }
+}
@@ -0,0 +1,82 @@
+use anchor_lang::prelude::*;
+use crate::errors::VestingError;
+
+/// Number of open schedules of one mint
+///
+/// PDA with seeds ["mint_schedules", mint], created with the mint's first schedule. Incremented on
+/// every schedule creation and decremented when a schedule is closed, so `max_schedules_per_mint`
+/// can bound how many schedules of a mint exist at once.
+#[account]
+pub struct MintScheduleCount {
+    /// Mint whose schedules are counted
+    pub mint: Pubkey,
+    /// Schedules of this mint that have been created and not yet closed
+    pub active_schedules: u64,
+    /// Set once the counter has been written
+    pub is_initialized: bool,
+    /// PDA bump seed
+    pub bump: u8,
+}
+
+impl MintScheduleCount {
+    pub const LEN: usize =
+        32 +      // mint: Pubkey
+        8 +       // active_schedules: u64
+        1 +       // is_initialized: bool
+        1;        // bump: u8
+
+    /// Count a newly created schedule, rejecting it once `max_schedules` are active (0 = unlimited)
+    pub fn record_created(&mut self, mint: Pubkey, bump: u8, max_schedules: u64) -> Result<()> {
+        if !self.is_initialized {
+            self.mint = mint;
+            self.active_schedules = 0;
+            self.is_initialized = true;
+            self.bump = bump;
+        }
+        require!(
+            max_schedules == 0 || self.active_schedules < max_schedules,
+            VestingError::MaxSchedulesPerMintReached
+        );
+        self.active_schedules = self.active_schedules
+            .checked_add(1)
+            .ok_or(VestingError::MathOverflow)?;
+        Ok(())
+    }
+
+    /// Count a closed schedule
+    /// Saturates so a counter that missed a creation can never block closing
+    pub fn record_closed(&mut self) {
+        self.active_schedules = self.active_schedules.saturating_sub(1);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_limit_counts_open_schedules() {
+        let mut count = MintScheduleCount::deserialize(&mut &[0u8; MintScheduleCount::LEN][..]).unwrap();
+        let mint = Pubkey::new_unique();
+
+        count.record_created(mint, 254, 2).unwrap();
+        count.record_created(mint, 254, 2).unwrap();
+        assert!(count.record_created(mint, 254, 2).is_err());
+        assert_eq!(count.active_schedules, 2);
+        assert_eq!(count.mint, mint);
+
+        // Closing frees a slot
+        count.record_closed();
+        count.record_created(mint, 254, 2).unwrap();
+        assert_eq!(count.active_schedules, 2);
+
+        // 0 means unlimited
+        count.record_created(mint, 254, 0).unwrap();
+        assert_eq!(count.active_schedules, 3);
+
+        for _ in 0..5 {
+            count.record_closed();
+        }
+        assert_eq!(count.active_schedules, 0);
+    }
+}
@@ -1,5 +1,9 @@
+pub mod mint_schedule_count;
+pub mod primary_schedule_index;
 pub mod program_config;
 pub mod vesting_schedule;
 
-pub use program_config::ProgramConfig;
-pub use vesting_schedule::{VestingSchedule, SourceCategory};
\ No newline at end of file
+pub use mint_schedule_count::MintScheduleCount;
+pub use primary_schedule_index::PrimaryScheduleIndex;
+pub use program_config::{ProgramConfig, HubUpdateOutcome, CategoryTemplate, Delegate};
+pub use vesting_schedule::{VestingSchedule, SourceCategory, CrankBlockReason, VestingCurve};
\ No newline at end of file
@@ -0,0 +1,61 @@
+use anchor_lang::prelude::*;
+
+/// Canonical "primary" schedule of a recipient for one mint
+///
+/// PDA with seeds ["primary", recipient, mint], written when the first schedule of the pair is
+/// created and never changed afterwards, so integrators can resolve (recipient, mint) to a
+/// schedule_id without scanning. Later schedules of the same pair are not indexed.
+#[account]
+pub struct PrimaryScheduleIndex {
+    /// Recipient of the indexed schedule
+    pub recipient: Pubkey,
+    /// Mint of the indexed schedule
+    pub mint: Pubkey,
+    /// First schedule created for this recipient and mint
+    pub schedule_id: u64,
+    /// Set once the index has been written
+    pub is_initialized: bool,
+    /// PDA bump seed
+    pub bump: u8,
+}
+
+impl PrimaryScheduleIndex {
+    pub const LEN: usize =
+        32 +      // recipient: Pubkey
+        32 +      // mint: Pubkey
+        8 +       // schedule_id: u64
+        1 +       // is_initialized: bool
+        1;        // bump: u8
+
+    /// Record `schedule_id` as the primary schedule unless one is already recorded
+    /// Returns true when the index was written
+    pub fn record_if_first(&mut self, recipient: Pubkey, mint: Pubkey, schedule_id: u64, bump: u8) -> bool {
+        if self.is_initialized {
+            return false;
+        }
+        self.recipient = recipient;
+        self.mint = mint;
+        self.schedule_id = schedule_id;
+        self.is_initialized = true;
+        self.bump = bump;
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_keeps_first_schedule() {
+        let mut index = PrimaryScheduleIndex::deserialize(&mut &[0u8; PrimaryScheduleIndex::LEN][..]).unwrap();
+        let recipient = Pubkey::new_unique();
+        let mint = Pubkey::new_unique();
+
+        assert!(index.record_if_first(recipient, mint, 3, 254));
+        assert!(!index.record_if_first(recipient, mint, 9, 254));
+        assert_eq!(index.schedule_id, 3);
+        assert_eq!(index.recipient, recipient);
+        assert_eq!(index.mint, mint);
+    }
+}
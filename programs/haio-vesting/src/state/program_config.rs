@@ -1,4 +1,56 @@
 use anchor_lang::prelude::*;
+use crate::constants::{ADMIN_TRANSFER_TIMELOCK, ALL_CAPABILITIES, HUB_UPDATE_TIMELOCK, MAX_DELEGATES, UPGRADE_AUTHORITY_TIMELOCK};
+use crate::errors::VestingError;
+use crate::math;
+use crate::state::SourceCategory;
+
+/// Result of a distribution hub update request
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum HubUpdateOutcome {
+    /// First hub configured, effective immediately
+    Initialized,
+    /// Change proposed, confirmable once the timelock expires
+    Proposed { timelock_expiry: i64 },
+    /// Pending change confirmed after the timelock
+    Confirmed { old_hub: Pubkey },
+}
+
+/// Default timing for new schedules of a category, relative to a grant's start time
+/// The cliff falls `cliff_duration` after the start and linear vesting runs for
+/// `vesting_duration` from the cliff. `vesting_duration == 0` means no template is set.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq)]
+pub struct CategoryTemplate {
+    pub cliff_duration: i64,
+    pub vesting_duration: i64,
+}
+
+impl CategoryTemplate {
+    pub const LEN: usize = 8 + 8;
+
+    /// Derive (cliff, vesting_start, vesting_end) for a grant starting at `start_time`
+    pub fn derive_timestamps(&self, start_time: i64) -> Result<(i64, i64, i64)> {
+        require!(self.vesting_duration > 0, VestingError::TemplateNotFound);
+        let cliff_timestamp = start_time
+            .checked_add(self.cliff_duration)
+            .ok_or(VestingError::MathOverflow)?;
+        let vesting_end_timestamp = cliff_timestamp
+            .checked_add(self.vesting_duration)
+            .ok_or(VestingError::MathOverflow)?;
+        Ok((cliff_timestamp, cliff_timestamp, vesting_end_timestamp))
+    }
+}
+
+/// Key granted a subset of admin capabilities (`CAPABILITY_*` bits)
+/// An empty slot has `capabilities == 0`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq)]
+pub struct Delegate {
+    pub key: Pubkey,
+    pub capabilities: u8,
+}
+
+impl Delegate {
+    pub const LEN: usize = 32 + 1;
+}
 
 /// Global configuration for the vesting program
 /// 
@@ -6,20 +58,178 @@ use anchor_lang::prelude::*;
 /// It uses a PDA with seed "program_config" for deterministic addressing.
 /// 
 /// Security Features:
-/// - Immutable admin authority (transfer capability removed for enhanced security)
+/// - Admin rotation only through a timelocked, two-step transfer accepted by the new key
 /// - Atomic updates with proper validation
 /// - Event emission for transparency
 #[account]
 pub struct ProgramConfig {
     /// Current admin with full program control authority
     /// Can create vesting schedules and manage program state
-    /// Note: Admin transfer functionality has been removed for enhanced security
+    /// Rotated with transfer_admin + accept_admin, see `propose_admin_transfer`
     pub admin: Pubkey,
 
     /// Total number of vesting schedules created
     /// Used for sequential ID validation and program statistics
     pub total_schedules: u64,
 
+    /// Reward paid to keepers out of released tokens, in basis points
+    /// Bounded by MAX_KEEPER_REWARD_BPS, adjustable by admin via set_keeper_reward
+    /// Paid by crank_vesting_schedule to the keeper token account, when one is passed
+    pub keeper_reward_bps: u16,
+
+    /// Maximum tokens a single batch crank transaction may release (0 = unlimited)
+    pub max_release_per_transaction: u64,
+
+    /// Token account that receives releases routed through the distribution hub
+    /// Pubkey::default() until the first setup
+    pub distribution_hub: Pubkey,
+
+    /// Proposed distribution hub awaiting the timelock
+    pub pending_hub: Option<Pubkey>,
+
+    /// Unix timestamp after which the pending hub can be confirmed
+    pub hub_update_timelock: Option<i64>,
+
+    /// Categories whose schedules must start vesting exactly at the cliff
+    /// Bitmask of `SourceCategory::mask()`, empty by default
+    pub cliff_eq_start_categories: u8,
+
+    /// Wind-down mode: blocks every state mutation except closes, views stay available
+    /// Unlike an emergency pause (transfers only), this also freezes creation, top-ups
+    /// and config changes
+    pub read_only: bool,
+
+    /// Apply schedules' `min_claim_interval` to keeper cranks too (recipient claims always respect it)
+    pub enforce_claim_interval_on_crank: bool,
+
+    /// Maximum tolerated difference in seconds between the cluster clock and the time implied
+    /// by slot progression before releases are refused (0 = disabled)
+    pub max_clock_skew: u64,
+
+    /// Emit a `CrankSkipped` event for every schedule a crank passes over (off by default)
+    pub emit_skip_events: bool,
+
+    /// Release-batching mode: `crank_vesting_schedule` accrues into the schedule's
+    /// `pending_release` instead of transferring, and `flush_releases` moves the tokens in bulk
+    pub accumulate_releases: bool,
+
+    /// One-time fee on new schedules in basis points (0 = disabled)
+    /// Charged on top of `total_amount`: the depositor pays total + fee, the recipient's
+    /// allocation is never reduced
+    pub creation_fee_bps: u16,
+
+    /// Owner of the token accounts receiving creation fees (one per mint)
+    pub fee_destination: Pubkey,
+
+    /// Minimum release size that earns the keeper reward (0 = every release is rewarded)
+    /// Makes dust cranks unrewarded so bots gain nothing by front-running keepers with them
+    pub reward_buffer: u64,
+
+    /// Default timing per category for `create_from_template`, indexed by `SourceCategory::index()`
+    pub category_templates: [CategoryTemplate; SourceCategory::COUNT],
+
+    /// Permit placeholder schedules with `total_amount == 0` that reserve a schedule slot and
+    /// recipient before the amount is known; top_up_batch later sets the real total
+    pub allow_zero_amount: bool,
+
+    /// Keys allowed to perform routine admin operations, scoped by capability bits
+    /// Granted and revoked by the admin only
+    pub delegates: [Delegate; MAX_DELEGATES],
+
+    /// Stretch underfunded schedules instead of draining their vault at the first release
+    /// (off by default, see `VestingSchedule::extend_for_underfunding`)
+    pub auto_extend_underfunded: bool,
+
+    /// Running total of per-release truncation, in `1 / ROUNDING_DUST_SCALE` of a token base unit
+    /// Every release adds the fraction by which its schedule's ideal unlock exceeded the floored
+    /// unlock it paid against (see `VestingSchedule::release_truncation`)
+    pub rounding_dust: u64,
+
+    /// Running total of tokens swept from completed schedules' vaults by `sweep_rounding_dust`
+    pub swept_dust: u64,
+
+    /// Maximum schedules of one mint that may exist at once (0 = unlimited)
+    /// Counted per mint by `MintScheduleCount`, closing a schedule frees its slot
+    pub max_schedules_per_mint: u64,
+
+    /// Swap program allowed to convert a schedule's `funding_mint` into its release `mint`
+    /// Pubkey::default() keeps swap-at-release disabled, see `set_swap_program`
+    pub swap_program: Pubkey,
+
+    /// Emergency pause: stops every instruction that moves tokens out of a vault
+    /// Creating schedules and closing empty ones stay available, see `set_pause`
+    pub paused: bool,
+
+    /// Proposed admin awaiting the timelock and its own acceptance
+    pub pending_admin: Option<Pubkey>,
+
+    /// Unix timestamp after which the pending admin can accept
+    pub admin_transfer_timelock: Option<i64>,
+
+    /// Seconds a closed schedule is kept as an archived record before it can be purged
+    /// 0 = no archiving, `close_vesting_schedule` deletes the schedule account right away
+    pub archive_retention_period: u64,
+
+    /// Release milestone step in percent for `ReleaseCheckpoint` events (0 = disabled)
+    /// Must divide 100, e.g. 25 reports 25%, 50%, 75% and 100%
+    pub checkpoint_interval_pct: u8,
+
+    /// Reject stepped schedules whose `total_amount` does not split evenly across periods
+    /// Off by default: the rounding remainder is released with the final step
+    pub require_even_step_division: bool,
+
+    /// Hard cap on the tokens allocated to each category, indexed by `SourceCategory::index()`
+    /// 0 = uncapped. Enforced at creation and top-up against `category_allocated`
+    pub category_caps: [u64; SourceCategory::COUNT],
+
+    /// Running total of tokens allocated to each category by creations and top-ups
+    /// Never decreases: revoked and closed schedules keep their allocation counted
+    pub category_allocated: [u64; SourceCategory::COUNT],
+
+    /// Circuit breaker: most tokens that may leave all vaults within one release window
+    /// (0 = unlimited). Counts releases, claims and revocation payouts, see `record_outflow`
+    pub max_release_per_window: u64,
+
+    /// Length of the release window in seconds
+    pub release_window_duration: u64,
+
+    /// Unix timestamp at which the current release window started
+    pub release_window_start: i64,
+
+    /// Tokens released since `release_window_start`
+    pub released_in_window: u64,
+
+    /// Minimum seconds between two releases of the same schedule by the single-schedule cranks
+    /// (0 = no limit). Cranks arriving sooner are skipped, not failed; schedules past their
+    /// vesting end always release so the final amount is delivered
+    pub min_crank_interval: i64,
+
+    /// Seconds a completed schedule must stay open before it can be closed (0 = no wait)
+    /// Keeps the audit trail on-chain for a dispute window after the last release
+    pub close_grace_period: u64,
+
+    /// Suppress the zero-amount `TokensReleased` heartbeat a crank emits when nothing moves
+    /// (off by default, monitoring setups may rely on it)
+    pub skip_zero_events: bool,
+
+    /// Tokens allocated to schedules over the program's lifetime (creations and top-ups)
+    pub total_allocated: u64,
+
+    /// Tokens released to recipients over the program's lifetime, the sum of every
+    /// schedule's `amount_transferred` increments
+    pub total_distributed: u64,
+
+    /// Intended upgrade authority of the program, recorded for transparency
+    /// Informational only: the BPF loader's authority is not changed by this program,
+    /// auditors cross-check the two. Pubkey::default() until the first confirmation
+    pub upgrade_authority: Pubkey,
+
+    /// Proposed upgrade authority awaiting the timelock
+    pub pending_upgrade_authority: Option<Pubkey>,
+
+    /// Unix timestamp after which the pending upgrade authority can be confirmed
+    pub upgrade_authority_timelock: Option<i64>,
+
     /// PDA bump seed for secure account derivation
     pub bump: u8,
 }
@@ -30,6 +240,48 @@ impl ProgramConfig {
     pub const LEN: usize = 
         32 +      // admin: Pubkey
         8 +       // total_schedules: u64
+        2 +       // keeper_reward_bps: u16
+        8 +       // max_release_per_transaction: u64
+        32 +      // distribution_hub: Pubkey
+        33 +      // pending_hub: Option<Pubkey>
+        9 +       // hub_update_timelock: Option<i64>
+        1 +       // cliff_eq_start_categories: u8
+        1 +       // read_only: bool
+        1 +       // enforce_claim_interval_on_crank: bool
+        8 +       // max_clock_skew: u64
+        1 +       // emit_skip_events: bool
+        1 +       // accumulate_releases: bool
+        2 +       // creation_fee_bps: u16
+        32 +      // fee_destination: Pubkey
+        8 +       // reward_buffer: u64
+        CategoryTemplate::LEN * SourceCategory::COUNT + // category_templates
+        1 +       // allow_zero_amount: bool
+        Delegate::LEN * MAX_DELEGATES + // delegates
+        1 +       // auto_extend_underfunded: bool
+        8 +       // rounding_dust: u64
+        8 +       // swept_dust: u64
+        8 +       // max_schedules_per_mint: u64
+        32 +      // swap_program: Pubkey
+        1 +       // paused: bool
+        33 +      // pending_admin: Option<Pubkey>
+        9 +       // admin_transfer_timelock: Option<i64>
+        8 +       // archive_retention_period: u64
+        1 +       // checkpoint_interval_pct: u8
+        1 +       // require_even_step_division: bool
+        8 * SourceCategory::COUNT + // category_caps
+        8 * SourceCategory::COUNT + // category_allocated
+        8 +       // max_release_per_window: u64
+        8 +       // release_window_duration: u64
+        8 +       // release_window_start: i64
+        8 +       // released_in_window: u64
+        8 +       // min_crank_interval: i64
+        8 +       // close_grace_period: u64
+        1 +       // skip_zero_events: bool
+        8 +       // total_allocated: u64
+        8 +       // total_distributed: u64
+        32 +      // upgrade_authority: Pubkey
+        33 +      // pending_upgrade_authority: Option<Pubkey>
+        9 +       // upgrade_authority_timelock: Option<i64>
         1;        // bump: u8
 
     /// Initialize program configuration with admin
@@ -44,10 +296,77 @@ impl ProgramConfig {
     pub fn init(&mut self, admin: Pubkey, bump: u8) -> Result<()> {
         self.admin = admin;
         self.total_schedules = 0;
+        self.keeper_reward_bps = 0;
+        self.max_release_per_transaction = 0;
+        self.distribution_hub = Pubkey::default();
+        self.pending_hub = None;
+        self.hub_update_timelock = None;
+        self.cliff_eq_start_categories = 0;
+        self.read_only = false;
+        self.enforce_claim_interval_on_crank = false;
+        self.max_clock_skew = 0;
+        self.emit_skip_events = false;
+        self.accumulate_releases = false;
+        self.creation_fee_bps = 0;
+        self.fee_destination = Pubkey::default();
+        self.reward_buffer = 0;
+        self.category_templates = [CategoryTemplate::default(); SourceCategory::COUNT];
+        self.allow_zero_amount = false;
+        self.delegates = [Delegate::default(); MAX_DELEGATES];
+        self.auto_extend_underfunded = false;
+        self.rounding_dust = 0;
+        self.swept_dust = 0;
+        self.max_schedules_per_mint = 0;
+        self.swap_program = Pubkey::default();
+        self.paused = false;
+        self.pending_admin = None;
+        self.admin_transfer_timelock = None;
+        self.archive_retention_period = 0;
+        self.checkpoint_interval_pct = 0;
+        self.require_even_step_division = false;
+        self.category_caps = [0; SourceCategory::COUNT];
+        self.category_allocated = [0; SourceCategory::COUNT];
+        self.max_release_per_window = 0;
+        self.release_window_duration = 0;
+        self.release_window_start = 0;
+        self.released_in_window = 0;
+        self.min_crank_interval = 0;
+        self.close_grace_period = 0;
+        self.skip_zero_events = false;
+        self.total_allocated = 0;
+        self.total_distributed = 0;
+        self.upgrade_authority = Pubkey::default();
+        self.pending_upgrade_authority = None;
+        self.upgrade_authority_timelock = None;
         self.bump = bump;
         Ok(())
     }
 
+    /// Size of the original layout: admin, total_schedules and bump only
+    pub const LEGACY_LEN: usize =
+        32 +      // admin: Pubkey
+        8 +       // total_schedules: u64
+        1;        // bump: u8
+
+    /// Rebuild a config from its original 41-byte layout (without the discriminator)
+    ///
+    /// Admin, schedule counter and bump carry over; every field added since takes
+    /// the same default `init` gives a fresh deployment
+    pub fn from_legacy(data: &[u8]) -> Result<Self> {
+        require!(data.len() == Self::LEGACY_LEN, VestingError::UnexpectedConfigLayout);
+
+        let admin = Pubkey::try_from(&data[0..32])
+            .map_err(|_| error!(VestingError::UnexpectedConfigLayout))?;
+        let mut total_schedules_bytes = [0u8; 8];
+        total_schedules_bytes.copy_from_slice(&data[32..40]);
+        let bump = data[40];
+
+        let mut config = Self::deserialize(&mut &[0u8; Self::LEN][..])?;
+        config.init(admin, bump)?;
+        config.total_schedules = u64::from_le_bytes(total_schedules_bytes);
+        Ok(config)
+    }
+
     /// Increment total schedules counter atomically
     /// 
     /// # Returns
@@ -63,6 +382,367 @@ impl ProgramConfig {
         Ok(())
     }
 
+    /// Repair the schedule counter from the highest existing schedule ID
+    /// 
+    /// # Arguments
+    /// * `highest_schedule_id` - ID of an existing schedule whose successor slot is empty
+    /// 
+    /// # Returns
+    /// * `Result<u64>` - Previous counter value
+    /// 
+    /// # Security
+    /// - Only moves the counter forward past an existing schedule, never backwards,
+    ///   so IDs of closed schedules are never reused and no history is rewritten
+    pub fn repair_total_schedules(&mut self, highest_schedule_id: u64) -> Result<u64> {
+        require!(
+            highest_schedule_id >= self.total_schedules,
+            VestingError::ScheduleCountConsistent
+        );
+
+        let old_total_schedules = self.total_schedules;
+        self.total_schedules = highest_schedule_id
+            .checked_add(1)
+            .ok_or(VestingError::MathOverflow)?;
+        Ok(old_total_schedules)
+    }
+
+    /// Apply a distribution hub update request
+    /// 
+    /// # Arguments
+    /// * `new_hub` - Requested hub token account
+    /// * `current_timestamp` - Current unix timestamp
+    /// 
+    /// # Returns
+    /// * `HubUpdateOutcome` - Which step of the update flow was performed
+    /// 
+    /// # Security
+    /// - The first setup (from Pubkey::default()) takes effect immediately
+    /// - Later changes are proposed first and only confirmed after HUB_UPDATE_TIMELOCK
+    /// - Requesting a different hub while one is pending restarts the timelock
+    /// - A too-early confirmation logs the seconds remaining before failing
+    pub fn apply_hub_update(
+        &mut self,
+        new_hub: Pubkey,
+        current_timestamp: i64,
+    ) -> Result<HubUpdateOutcome> {
+        require!(new_hub != Pubkey::default(), VestingError::InvalidRecipient);
+
+        if self.distribution_hub == Pubkey::default() {
+            self.distribution_hub = new_hub;
+            self.pending_hub = None;
+            self.hub_update_timelock = None;
+            return Ok(HubUpdateOutcome::Initialized);
+        }
+
+        match (self.pending_hub, self.hub_update_timelock) {
+            (Some(pending_hub), Some(timelock_expiry)) if pending_hub == new_hub => {
+                if current_timestamp < timelock_expiry {
+                    msg!("Distribution hub change to {} confirmable in {} seconds (at {})",
+                         new_hub, timelock_expiry.saturating_sub(current_timestamp), timelock_expiry);
+                    return err!(VestingError::TimelockNotExpired);
+                }
+
+                let old_hub = self.distribution_hub;
+                self.distribution_hub = new_hub;
+                self.pending_hub = None;
+                self.hub_update_timelock = None;
+                Ok(HubUpdateOutcome::Confirmed { old_hub })
+            }
+            _ => {
+                let timelock_expiry = current_timestamp
+                    .checked_add(HUB_UPDATE_TIMELOCK)
+                    .ok_or(VestingError::MathOverflow)?;
+                self.pending_hub = Some(new_hub);
+                self.hub_update_timelock = Some(timelock_expiry);
+                Ok(HubUpdateOutcome::Proposed { timelock_expiry })
+            }
+        }
+    }
+
+    /// Drop the pending distribution hub proposal
+    /// Returns the cancelled hub, `NoPendingHubUpdate` if nothing was proposed
+    pub fn cancel_hub_update(&mut self) -> Result<Pubkey> {
+        let cancelled_hub = self.pending_hub.ok_or(VestingError::NoPendingHubUpdate)?;
+        self.pending_hub = None;
+        self.hub_update_timelock = None;
+        Ok(cancelled_hub)
+    }
+
+    /// Propose a new upgrade authority record
+    /// 
+    /// # Returns
+    /// * `Result<i64>` - Timestamp after which the proposal can be confirmed
+    /// 
+    /// # Security
+    /// - Every change waits UPGRADE_AUTHORITY_TIMELOCK, including the first one
+    /// - A new proposal replaces the pending one and restarts the timelock
+    pub fn propose_upgrade_authority(
+        &mut self,
+        new_authority: Pubkey,
+        current_timestamp: i64,
+    ) -> Result<i64> {
+        require!(new_authority != Pubkey::default(), VestingError::InvalidRecipient);
+
+        let timelock_expiry = current_timestamp
+            .checked_add(UPGRADE_AUTHORITY_TIMELOCK)
+            .ok_or(VestingError::MathOverflow)?;
+        self.pending_upgrade_authority = Some(new_authority);
+        self.upgrade_authority_timelock = Some(timelock_expiry);
+        Ok(timelock_expiry)
+    }
+
+    /// Confirm the pending upgrade authority record once its timelock has expired
+    /// 
+    /// # Returns
+    /// * `Result<Pubkey>` - Previous upgrade authority record
+    /// 
+    /// # Security
+    /// - `new_authority` must repeat the pending proposal, so a confirmation cannot race
+    ///   a newer proposal
+    pub fn confirm_upgrade_authority(
+        &mut self,
+        new_authority: Pubkey,
+        current_timestamp: i64,
+    ) -> Result<Pubkey> {
+        let timelock_expiry = match (self.pending_upgrade_authority, self.upgrade_authority_timelock) {
+            (Some(pending), Some(expiry)) if pending == new_authority => expiry,
+            _ => return Err(VestingError::NoPendingUpgradeAuthority.into()),
+        };
+        require!(current_timestamp >= timelock_expiry, VestingError::TimelockNotExpired);
+
+        let old_authority = self.upgrade_authority;
+        self.upgrade_authority = new_authority;
+        self.pending_upgrade_authority = None;
+        self.upgrade_authority_timelock = None;
+        Ok(old_authority)
+    }
+
+    /// Propose a new admin
+    /// 
+    /// # Returns
+    /// * `Result<i64>` - Timestamp after which the proposed admin can accept
+    /// 
+    /// # Security
+    /// - Every transfer waits ADMIN_TRANSFER_TIMELOCK
+    /// - A new proposal replaces the pending one and restarts the timelock
+    pub fn propose_admin_transfer(
+        &mut self,
+        new_admin: Pubkey,
+        current_timestamp: i64,
+    ) -> Result<i64> {
+        require!(new_admin != Pubkey::default(), VestingError::InvalidRecipient);
+
+        let timelock_expiry = current_timestamp
+            .checked_add(ADMIN_TRANSFER_TIMELOCK)
+            .ok_or(VestingError::MathOverflow)?;
+        self.pending_admin = Some(new_admin);
+        self.admin_transfer_timelock = Some(timelock_expiry);
+        Ok(timelock_expiry)
+    }
+
+    /// Hand the admin role to the pending admin once the timelock has expired
+    /// 
+    /// # Returns
+    /// * `Result<Pubkey>` - Previous admin
+    /// 
+    /// # Security
+    /// - `signer` must be the pending admin, proving control of the new key
+    pub fn accept_admin_transfer(
+        &mut self,
+        signer: Pubkey,
+        current_timestamp: i64,
+    ) -> Result<Pubkey> {
+        let timelock_expiry = match (self.pending_admin, self.admin_transfer_timelock) {
+            (Some(pending), Some(expiry)) if pending == signer => expiry,
+            _ => return Err(VestingError::NoPendingAdminTransfer.into()),
+        };
+        if current_timestamp < timelock_expiry {
+            msg!("Admin transfer to {} acceptable in {} seconds (at {})",
+                 signer, timelock_expiry.saturating_sub(current_timestamp), timelock_expiry);
+            return err!(VestingError::TimelockNotExpired);
+        }
+
+        let old_admin = self.admin;
+        self.admin = signer;
+        self.pending_admin = None;
+        self.admin_transfer_timelock = None;
+        Ok(old_admin)
+    }
+
+    /// Keeper reward owed for a release of `release_amount`
+    /// Zero when the release is below `reward_buffer`
+    pub fn keeper_reward_for(&self, release_amount: u64) -> Result<u64> {
+        if release_amount < self.reward_buffer {
+            return Ok(0);
+        }
+        math::apply_bps(release_amount, self.keeper_reward_bps)
+    }
+
+    /// Check whether a category requires `cliff_timestamp == vesting_start_timestamp`
+    pub fn requires_cliff_eq_start(&self, category: &SourceCategory) -> bool {
+        self.cliff_eq_start_categories & category.mask() != 0
+    }
+
+    /// Enable or disable the cliff/start equality policy for a category
+    pub fn set_requires_cliff_eq_start(&mut self, category: &SourceCategory, required: bool) {
+        if required {
+            self.cliff_eq_start_categories |= category.mask();
+        } else {
+            self.cliff_eq_start_categories &= !category.mask();
+        }
+    }
+
+    /// Tokens that can still be allocated to a category before its cap (u64::MAX when uncapped)
+    pub fn category_headroom(&self, category: &SourceCategory) -> u64 {
+        match self.category_caps[category.index()] {
+            0 => u64::MAX,
+            cap => cap.saturating_sub(self.category_allocated[category.index()]),
+        }
+    }
+
+    /// Count `amount` against a category's cap and the program-wide `total_allocated`
+    /// Fails with `CategoryCapExceeded` if it would take the category over its cap
+    pub fn allocate_to_category(&mut self, category: &SourceCategory, amount: u64) -> Result<()> {
+        require!(amount <= self.category_headroom(category), VestingError::CategoryCapExceeded);
+        let allocated = &mut self.category_allocated[category.index()];
+        *allocated = allocated
+            .checked_add(amount)
+            .ok_or(VestingError::MathOverflow)?;
+        self.total_allocated = self.total_allocated
+            .checked_add(amount)
+            .ok_or(VestingError::MathOverflow)?;
+        Ok(())
+    }
+
+    /// Add `amount` released to a recipient to `total_distributed`
+    pub fn record_distribution(&mut self, amount: u64) -> Result<()> {
+        self.total_distributed = self.total_distributed
+            .checked_add(amount)
+            .ok_or(VestingError::MathOverflow)?;
+        Ok(())
+    }
+
+    /// Count `amount` leaving the program against the release rate limit
+    /// A new window starts at `current_timestamp` once `release_window_duration` has elapsed.
+    /// Fails with `ReleaseRateLimitExceeded` if the window's cap would be exceeded; a no-op
+    /// while the limit is disabled
+    pub fn record_outflow(&mut self, amount: u64, current_timestamp: i64) -> Result<()> {
+        if self.max_release_per_window == 0 || amount == 0 {
+            return Ok(());
+        }
+
+        let window_duration = i64::try_from(self.release_window_duration).unwrap_or(i64::MAX);
+        if current_timestamp >= self.release_window_start.saturating_add(window_duration) {
+            self.release_window_start = current_timestamp;
+            self.released_in_window = 0;
+        }
+
+        let released_in_window = self.released_in_window
+            .checked_add(amount)
+            .ok_or(VestingError::MathOverflow)?;
+        require!(
+            released_in_window <= self.max_release_per_window,
+            VestingError::ReleaseRateLimitExceeded
+        );
+        self.released_in_window = released_in_window;
+        Ok(())
+    }
+
+    /// Reject state mutations while the program is in read-only mode
+    pub fn require_writable(&self) -> Result<()> {
+        require!(!self.read_only, VestingError::ProgramReadOnly);
+        Ok(())
+    }
+
+    /// Reject token movements while the program is paused
+    pub fn require_not_paused(&self) -> Result<()> {
+        require!(!self.paused, VestingError::ProgramPaused);
+        Ok(())
+    }
+
+    /// Require the program to be paused, for break-glass operations
+    pub fn require_paused(&self) -> Result<()> {
+        require!(self.paused, VestingError::ProgramNotPaused);
+        Ok(())
+    }
+
+    /// Check whether `signer` is the admin or a delegate holding every bit of `capability`
+    pub fn has_capability(&self, signer: &Pubkey, capability: u8) -> bool {
+        self.is_admin(signer)
+            || self.delegates.iter().any(|delegate| {
+                delegate.key == *signer && delegate.capabilities & capability == capability
+            })
+    }
+
+    /// Add `capabilities` to a delegate, taking a free slot for a new delegate
+    /// Returns the delegate's (old, new) capability bits
+    pub fn grant_capability(&mut self, delegate: Pubkey, capabilities: u8) -> Result<(u8, u8)> {
+        require!(
+            capabilities != 0 && capabilities & !ALL_CAPABILITIES == 0,
+            VestingError::InvalidCapability
+        );
+        require!(
+            delegate != Pubkey::default() && !self.is_admin(&delegate),
+            VestingError::InvalidDelegate
+        );
+
+        let slot = match self.delegates.iter().position(|entry| entry.capabilities != 0 && entry.key == delegate) {
+            Some(index) => index,
+            None => self.delegates
+                .iter()
+                .position(|entry| entry.capabilities == 0)
+                .ok_or(VestingError::DelegateLimitReached)?,
+        };
+
+        let entry = &mut self.delegates[slot];
+        let old_capabilities = if entry.key == delegate { entry.capabilities } else { 0 };
+        entry.key = delegate;
+        entry.capabilities = old_capabilities | capabilities;
+        Ok((old_capabilities, entry.capabilities))
+    }
+
+    /// Remove `capabilities` from a delegate, freeing its slot once none remain
+    /// Returns the delegate's (old, new) capability bits; revoking from a key that is not a
+    /// delegate is a no-op returning (0, 0)
+    pub fn revoke_capability(&mut self, delegate: Pubkey, capabilities: u8) -> Result<(u8, u8)> {
+        require!(
+            capabilities != 0 && capabilities & !ALL_CAPABILITIES == 0,
+            VestingError::InvalidCapability
+        );
+
+        let Some(entry) = self.delegates
+            .iter_mut()
+            .find(|entry| entry.capabilities != 0 && entry.key == delegate)
+        else {
+            return Ok((0, 0));
+        };
+
+        let old_capabilities = entry.capabilities;
+        entry.capabilities &= !capabilities;
+        if entry.capabilities == 0 {
+            *entry = Delegate::default();
+        }
+        Ok((old_capabilities, entry.capabilities))
+    }
+
+    /// Add a release's truncation (scaled by `ROUNDING_DUST_SCALE`) to the program-level total,
+    /// returning the new total
+    pub fn record_rounding_dust(&mut self, scaled_amount: u64) -> Result<u64> {
+        self.rounding_dust = self.rounding_dust
+            .checked_add(scaled_amount)
+            .ok_or(VestingError::MathOverflow)?;
+        Ok(self.rounding_dust)
+    }
+
+    /// Add tokens swept from a completed schedule's vault to the program-level total,
+    /// returning the new total
+    pub fn record_swept_dust(&mut self, amount: u64) -> Result<u64> {
+        self.swept_dust = self.swept_dust
+            .checked_add(amount)
+            .ok_or(VestingError::MathOverflow)?;
+        Ok(self.swept_dust)
+    }
+
     /// Validate admin authority
     /// 
     /// # Arguments
@@ -81,26 +761,70 @@ mod tests {
     use super::*;
     use anchor_lang::prelude::Pubkey;
 
+    fn test_config(admin: Pubkey) -> ProgramConfig {
+        let mut config = ProgramConfig::deserialize(&mut &[0u8; ProgramConfig::LEN][..]).unwrap();
+        config.init(admin, 255).unwrap();
+        config
+    }
+
     #[test]
     fn test_program_config_len() {
         // Verify our LEN calculation matches the actual struct size
         let expected_len = 
             32 +      // admin
             8 +       // total_schedules
+            2 +       // keeper_reward_bps
+            8 +       // max_release_per_transaction
+            32 +      // distribution_hub
+            33 +      // pending_hub
+            9 +       // hub_update_timelock
+            1 +       // cliff_eq_start_categories
+            1 +       // read_only
+            1 +       // enforce_claim_interval_on_crank
+            8 +       // max_clock_skew
+            1 +       // emit_skip_events
+            1 +       // accumulate_releases
+            2 +       // creation_fee_bps
+            32 +      // fee_destination
+            8 +       // reward_buffer
+            16 * 6 +  // category_templates
+            1 +       // allow_zero_amount
+            33 * 4 +  // delegates
+            1 +       // auto_extend_underfunded
+            8 +       // rounding_dust
+            8 +       // swept_dust
+            8 +       // max_schedules_per_mint
+            32 +      // swap_program
+            1 +       // paused
+            33 +      // pending_admin
+            9 +       // admin_transfer_timelock
+            8 +       // archive_retention_period
+            1 +       // checkpoint_interval_pct
+            1 +       // require_even_step_division
+            8 * 6 +   // category_caps
+            8 * 6 +   // category_allocated
+            8 +       // max_release_per_window
+            8 +       // release_window_duration
+            8 +       // release_window_start
+            8 +       // released_in_window
+            8 +       // min_crank_interval
+            8 +       // close_grace_period
+            1 +       // skip_zero_events
+            8 +       // total_allocated
+            8 +       // total_distributed
+            32 +      // upgrade_authority
+            33 +      // pending_upgrade_authority
+            9 +       // upgrade_authority_timelock
             1;        // bump
         
         assert_eq!(ProgramConfig::LEN, expected_len);
-        assert_eq!(ProgramConfig::LEN, 41);
+        assert_eq!(ProgramConfig::LEN, 754);
     }
 
     #[test]
     fn test_schedule_counter() {
         let admin = Pubkey::new_unique();
-        let mut config = ProgramConfig {
-            admin,
-            total_schedules: 0,
-            bump: 255,
-        };
+        let mut config = test_config(admin);
 
         assert_eq!(config.total_schedules, 0);
         
@@ -115,13 +839,329 @@ mod tests {
     fn test_admin_validation() {
         let admin = Pubkey::new_unique();
         let other = Pubkey::new_unique();
-        let config = ProgramConfig {
-            admin,
-            total_schedules: 0,
-            bump: 255,
-        };
+        let config = test_config(admin);
 
         assert!(config.is_admin(&admin));
         assert!(!config.is_admin(&other));
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_repair_total_schedules() {
+        let mut config = test_config(Pubkey::new_unique());
+        config.total_schedules = 3;
+
+        // Counter already past the highest schedule: nothing to repair
+        assert!(config.repair_total_schedules(2).is_err());
+        assert_eq!(config.total_schedules, 3);
+
+        // Counter behind an existing schedule: move past it
+        assert_eq!(config.repair_total_schedules(4).unwrap(), 3);
+        assert_eq!(config.total_schedules, 5);
+
+        // Overflow is rejected
+        assert!(config.repair_total_schedules(u64::MAX).is_err());
+    }
+
+    #[test]
+    fn test_hub_update_flow() {
+        let mut config = test_config(Pubkey::new_unique());
+        let first_hub = Pubkey::new_unique();
+        let second_hub = Pubkey::new_unique();
+
+        assert!(config.apply_hub_update(Pubkey::default(), 0).is_err());
+
+        // First setup is immediate
+        assert_eq!(config.apply_hub_update(first_hub, 100).unwrap(), HubUpdateOutcome::Initialized);
+        assert_eq!(config.distribution_hub, first_hub);
+
+        // Later changes are proposed behind the timelock
+        assert_eq!(
+            config.apply_hub_update(second_hub, 100).unwrap(),
+            HubUpdateOutcome::Proposed { timelock_expiry: 100 + HUB_UPDATE_TIMELOCK }
+        );
+        assert!(config.apply_hub_update(second_hub, 100 + HUB_UPDATE_TIMELOCK - 1).is_err());
+        assert_eq!(config.distribution_hub, first_hub);
+
+        assert_eq!(
+            config.apply_hub_update(second_hub, 100 + HUB_UPDATE_TIMELOCK).unwrap(),
+            HubUpdateOutcome::Confirmed { old_hub: first_hub }
+        );
+        assert_eq!(config.distribution_hub, second_hub);
+        assert_eq!(config.pending_hub, None);
+        assert_eq!(config.hub_update_timelock, None);
+
+        // A cancelled proposal cannot be confirmed, repeating the address starts a new timelock
+        assert!(config.cancel_hub_update().is_err());
+        config.apply_hub_update(first_hub, 200).unwrap();
+        assert_eq!(config.cancel_hub_update().unwrap(), first_hub);
+        assert_eq!(config.hub_update_timelock, None);
+        assert_eq!(
+            config.apply_hub_update(first_hub, 200 + HUB_UPDATE_TIMELOCK).unwrap(),
+            HubUpdateOutcome::Proposed { timelock_expiry: 200 + 2 * HUB_UPDATE_TIMELOCK }
+        );
+        assert_eq!(config.distribution_hub, second_hub);
+    }
+
+    #[test]
+    fn test_cliff_eq_start_policy() {
+        let mut config = test_config(Pubkey::new_unique());
+
+        // Off by default for every category
+        assert!(!config.requires_cliff_eq_start(&SourceCategory::Public));
+        assert!(!config.requires_cliff_eq_start(&SourceCategory::Team));
+
+        config.set_requires_cliff_eq_start(&SourceCategory::Public, true);
+        assert!(config.requires_cliff_eq_start(&SourceCategory::Public));
+        assert!(!config.requires_cliff_eq_start(&SourceCategory::Team));
+
+        config.set_requires_cliff_eq_start(&SourceCategory::Public, false);
+        assert!(!config.requires_cliff_eq_start(&SourceCategory::Public));
+    }
+
+    #[test]
+    fn test_read_only_blocks_writes() {
+        let mut config = test_config(Pubkey::new_unique());
+        assert!(config.require_writable().is_ok());
+
+        config.read_only = true;
+        assert!(config.require_writable().is_err());
+    }
+
+    #[test]
+    fn test_upgrade_authority_timelock_flow() {
+        let mut config = test_config(Pubkey::new_unique());
+        let authority = Pubkey::new_unique();
+        let other = Pubkey::new_unique();
+
+        // Nothing pending yet
+        assert!(config.confirm_upgrade_authority(authority, 0).is_err());
+        assert!(config.propose_upgrade_authority(Pubkey::default(), 0).is_err());
+
+        let expiry = config.propose_upgrade_authority(authority, 100).unwrap();
+        assert_eq!(expiry, 100 + UPGRADE_AUTHORITY_TIMELOCK);
+
+        // Too early, or confirming a different key than proposed
+        assert!(config.confirm_upgrade_authority(authority, expiry - 1).is_err());
+        assert!(config.confirm_upgrade_authority(other, expiry).is_err());
+        assert_eq!(config.upgrade_authority, Pubkey::default());
+
+        assert_eq!(config.confirm_upgrade_authority(authority, expiry).unwrap(), Pubkey::default());
+        assert_eq!(config.upgrade_authority, authority);
+        assert_eq!(config.pending_upgrade_authority, None);
+        assert_eq!(config.upgrade_authority_timelock, None);
+    }
+
+    #[test]
+    fn test_admin_transfer_timelock_flow() {
+        let admin = Pubkey::new_unique();
+        let mut config = test_config(admin);
+        let new_admin = Pubkey::new_unique();
+
+        // Nothing pending yet
+        assert!(config.accept_admin_transfer(new_admin, 0).is_err());
+        assert!(config.propose_admin_transfer(Pubkey::default(), 0).is_err());
+
+        let expiry = config.propose_admin_transfer(new_admin, 100).unwrap();
+        assert_eq!(expiry, 100 + ADMIN_TRANSFER_TIMELOCK);
+
+        // Too early, or accepted by anyone but the proposed admin
+        assert!(config.accept_admin_transfer(new_admin, expiry - 1).is_err());
+        assert!(config.accept_admin_transfer(admin, expiry).is_err());
+        assert_eq!(config.admin, admin);
+
+        assert_eq!(config.accept_admin_transfer(new_admin, expiry).unwrap(), admin);
+        assert_eq!(config.admin, new_admin);
+        assert_eq!(config.pending_admin, None);
+        assert_eq!(config.admin_transfer_timelock, None);
+    }
+
+    #[test]
+    fn test_keeper_reward_buffer() {
+        let mut config = test_config(Pubkey::new_unique());
+        // At the cap a keeper takes at most 1% of a release
+        config.keeper_reward_bps = crate::constants::MAX_KEEPER_REWARD_BPS;
+
+        // No buffer by default: every release is rewarded
+        assert_eq!(config.keeper_reward_for(1_000).unwrap(), 10);
+
+        // Sub-buffer releases pay nothing, releases at or above it are rewarded
+        config.reward_buffer = 5_000;
+        assert_eq!(config.keeper_reward_for(4_999).unwrap(), 0);
+        assert_eq!(config.keeper_reward_for(5_000).unwrap(), 50);
+    }
+
+    #[test]
+    fn test_category_template_timestamps() {
+        const MONTH: i64 = 30 * 24 * 60 * 60;
+        let team = CategoryTemplate { cliff_duration: 6 * MONTH, vesting_duration: 36 * MONTH };
+        assert_eq!(
+            team.derive_timestamps(1_000).unwrap(),
+            (1_000 + 6 * MONTH, 1_000 + 6 * MONTH, 1_000 + 42 * MONTH)
+        );
+
+        // Unset template
+        assert!(CategoryTemplate::default().derive_timestamps(1_000).is_err());
+
+        // Every category has a slot
+        let config = test_config(Pubkey::new_unique());
+        assert_eq!(config.category_templates.len(), SourceCategory::COUNT);
+        assert_eq!(SourceCategory::Foundation.index(), SourceCategory::COUNT - 1);
+    }
+
+    #[test]
+    fn test_capability_matrix() {
+        use crate::constants::{CAPABILITY_CONFIG, CAPABILITY_CRANK_MANAGE, CAPABILITY_CREATE};
+
+        let admin = Pubkey::new_unique();
+        let creator = Pubkey::new_unique();
+        let operator = Pubkey::new_unique();
+        let mut config = test_config(admin);
+
+        assert_eq!(config.grant_capability(creator, CAPABILITY_CREATE).unwrap(), (0, CAPABILITY_CREATE));
+        assert_eq!(
+            config.grant_capability(operator, CAPABILITY_CRANK_MANAGE | CAPABILITY_CONFIG).unwrap(),
+            (0, CAPABILITY_CRANK_MANAGE | CAPABILITY_CONFIG)
+        );
+
+        // The admin holds every capability, delegates only what they were granted
+        for capability in [CAPABILITY_CREATE, CAPABILITY_CRANK_MANAGE, CAPABILITY_CONFIG] {
+            assert!(config.has_capability(&admin, capability));
+            assert_eq!(config.has_capability(&creator, capability), capability == CAPABILITY_CREATE);
+            assert_eq!(config.has_capability(&operator, capability), capability != CAPABILITY_CREATE);
+            assert!(!config.has_capability(&Pubkey::new_unique(), capability));
+        }
+
+        // Grants accumulate, revokes remove bits and free the slot when empty
+        assert_eq!(
+            config.grant_capability(creator, CAPABILITY_CONFIG).unwrap(),
+            (CAPABILITY_CREATE, CAPABILITY_CREATE | CAPABILITY_CONFIG)
+        );
+        assert_eq!(
+            config.revoke_capability(operator, CAPABILITY_CONFIG).unwrap(),
+            (CAPABILITY_CRANK_MANAGE | CAPABILITY_CONFIG, CAPABILITY_CRANK_MANAGE)
+        );
+        assert!(!config.has_capability(&operator, CAPABILITY_CONFIG));
+        assert_eq!(config.revoke_capability(operator, ALL_CAPABILITIES).unwrap(), (CAPABILITY_CRANK_MANAGE, 0));
+        assert!(!config.has_capability(&operator, CAPABILITY_CRANK_MANAGE));
+        assert_eq!(config.revoke_capability(operator, ALL_CAPABILITIES).unwrap(), (0, 0));
+
+        // Invalid grants
+        assert!(config.grant_capability(creator, 0).is_err());
+        assert!(config.grant_capability(creator, 1 << 7).is_err());
+        assert!(config.grant_capability(admin, CAPABILITY_CREATE).is_err());
+        assert!(config.grant_capability(Pubkey::default(), CAPABILITY_CREATE).is_err());
+
+        // Slots are bounded
+        for _ in 1..MAX_DELEGATES {
+            config.grant_capability(Pubkey::new_unique(), CAPABILITY_CREATE).unwrap();
+        }
+        let error = config.grant_capability(Pubkey::new_unique(), CAPABILITY_CREATE).unwrap_err();
+        assert_eq!(error, VestingError::DelegateLimitReached.into());
+    }
+
+    #[test]
+    fn test_record_rounding_dust() {
+        let mut config = test_config(Pubkey::new_unique());
+
+        assert_eq!(config.record_rounding_dust(3).unwrap(), 3);
+        assert_eq!(config.record_rounding_dust(0).unwrap(), 3);
+        assert_eq!(config.record_rounding_dust(4).unwrap(), 7);
+
+        config.rounding_dust = u64::MAX;
+        assert!(config.record_rounding_dust(1).is_err());
+        assert_eq!(config.rounding_dust, u64::MAX);
+
+        // Swept tokens are counted separately from the scaled truncation
+        assert_eq!(config.record_swept_dust(2).unwrap(), 2);
+        assert_eq!(config.rounding_dust, u64::MAX);
+        config.swept_dust = u64::MAX;
+        assert!(config.record_swept_dust(1).is_err());
+    }
+
+    #[test]
+    fn test_pause_blocks_transfers_only() {
+        let mut config = test_config(Pubkey::new_unique());
+        assert!(config.require_not_paused().is_ok());
+
+        config.paused = true;
+        assert!(config.require_not_paused().is_err());
+        assert!(config.require_writable().is_ok());
+    }
+
+    #[test]
+    fn test_category_caps() {
+        let mut config = test_config(Pubkey::new_unique());
+
+        // Uncapped categories accept anything
+        config.allocate_to_category(&SourceCategory::Public, 1_000_000).unwrap();
+        assert_eq!(config.category_headroom(&SourceCategory::Public), u64::MAX);
+
+        config.category_caps[SourceCategory::Team.index()] = 150;
+        config.allocate_to_category(&SourceCategory::Team, 100).unwrap();
+        assert_eq!(config.category_headroom(&SourceCategory::Team), 50);
+
+        // One token over the cap fails without counting, hitting it exactly succeeds
+        assert!(config.allocate_to_category(&SourceCategory::Team, 51).is_err());
+        config.allocate_to_category(&SourceCategory::Team, 50).unwrap();
+        assert_eq!(config.category_allocated[SourceCategory::Team.index()], 150);
+        assert_eq!(config.total_allocated, 1_000_150);
+        assert!(config.allocate_to_category(&SourceCategory::Team, 1).is_err());
+    }
+
+    #[test]
+    fn test_release_rate_window() {
+        let mut config = test_config(Pubkey::new_unique());
+        let start = 1_700_000_000;
+
+        // Unlimited by default
+        config.record_outflow(u64::MAX, start).unwrap();
+        assert_eq!(config.released_in_window, 0);
+
+        config.max_release_per_window = 1_000;
+        config.release_window_duration = 3_600;
+        config.record_outflow(600, start).unwrap();
+        assert_eq!(config.release_window_start, start);
+        config.record_outflow(400, start + 3_599).unwrap();
+        assert!(config.record_outflow(1, start + 3_599).is_err());
+        assert_eq!(config.released_in_window, 1_000);
+
+        // The window rolls over once its duration has elapsed
+        config.record_outflow(1_000, start + 3_600).unwrap();
+        assert_eq!(config.release_window_start, start + 3_600);
+        assert_eq!(config.released_in_window, 1_000);
+        assert!(config.record_outflow(1_001, start + 7_200).is_err());
+    }
+
+    #[test]
+    fn test_migrate_from_legacy_layout() {
+        let admin = Pubkey::new_unique();
+        let mut legacy = Vec::with_capacity(ProgramConfig::LEGACY_LEN);
+        legacy.extend_from_slice(admin.as_ref());
+        legacy.extend_from_slice(&42u64.to_le_bytes());
+        legacy.push(254);
+        assert_eq!(legacy.len(), 41);
+
+        let migrated = ProgramConfig::from_legacy(&legacy).unwrap();
+        assert_eq!(migrated.admin, admin);
+        assert_eq!(migrated.total_schedules, 42);
+        assert_eq!(migrated.bump, 254);
+        assert_eq!(migrated.keeper_reward_bps, 0);
+        assert_eq!(migrated.distribution_hub, Pubkey::default());
+        assert!(migrated.pending_admin.is_none());
+        assert!(!migrated.paused);
+        assert_eq!(migrated.total_allocated, 0);
+
+        // Written into the resized account, it loads back as a current config
+        let mut account = vec![0u8; 8 + ProgramConfig::LEN];
+        let mut writer: &mut [u8] = &mut account;
+        migrated.try_serialize(&mut writer).unwrap();
+        let reloaded = ProgramConfig::try_deserialize(&mut &account[..]).unwrap();
+        assert_eq!(reloaded.admin, admin);
+        assert_eq!(reloaded.total_schedules, 42);
+        assert_eq!(reloaded.bump, 254);
+
+        // Anything other than the exact legacy size is refused, including an already migrated account
+        assert!(ProgramConfig::from_legacy(&legacy[..40]).is_err());
+        assert!(ProgramConfig::from_legacy(&account[8..]).is_err());
+    }
+}
@@ -1,6 +1,11 @@
 use anchor_lang::prelude::*;
-use crate::constants::DISCRIMINATOR_SIZE;
+use anchor_lang::solana_program::hash::hash;
+use crate::constants::{
+    BPS_DENOMINATOR, DISCRIMINATOR_SIZE, MAX_BACKDATE, MAX_FUTURE, RECIPIENT_CHANGE_TIMELOCK, ROUNDING_DUST_SCALE,
+    TERMS_HASH_DOMAIN,
+};
 use crate::errors::VestingError;
+use crate::math::{to_u64, to_u128};
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq)]
 pub enum SourceCategory {
@@ -12,6 +17,68 @@ pub enum SourceCategory {
     Foundation,  // Foundation & Treasury Reserve: 220M HAiO immediate distribution
 }
 
+impl SourceCategory {
+    /// Number of categories, sizes per-category config arrays
+    pub const COUNT: usize = 6;
+
+    /// Position of this category in per-category config arrays
+    pub fn index(&self) -> usize {
+        self.clone() as usize
+    }
+
+    /// Bit for this category in per-category policy masks
+    pub fn mask(&self) -> u8 {
+        1 << self.index()
+    }
+}
+
+/// Why a crank of a schedule would not release tokens right now
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq)]
+pub enum CrankBlockReason {
+    /// Nothing blocks the crank
+    None,
+    /// Program is in read-only mode
+    ReadOnly,
+    /// Cluster clock deviates from slot progression by more than `max_clock_skew`
+    ClockSkewTooHigh,
+    /// `min_claim_interval` has not elapsed and cranks are configured to respect it
+    ClaimIntervalNotElapsed,
+    /// Schedule account is not initialized
+    NotInitialized,
+    /// Full allocation already delivered
+    FullyProcessed,
+    /// Nothing unlocked beyond what was already released (e.g. before the cliff)
+    NothingVested,
+    /// Vault holds no tokens to release
+    VaultEmpty,
+    /// Recorded recipient token account is no longer owned by the recipient
+    RecipientAccountOwnershipChanged,
+    /// Schedule already appeared earlier in the same batch
+    DuplicateScheduleInBatch,
+    /// Schedule was revoked, nothing is released anymore
+    Revoked,
+    /// `min_crank_interval` has not elapsed since the schedule's last crank release
+    CrankIntervalNotElapsed,
+    /// Vault was closed by `close_vault_only`, only the schedule record remains
+    VaultClosed,
+}
+
+/// Shape of the unlock curve between `vesting_start_timestamp` and `vesting_end_timestamp`
+/// Applies to the allocation left after the initial unlock
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq)]
+pub enum VestingCurve {
+    /// Continuous unlock proportional to elapsed time
+    #[default]
+    Linear,
+    /// Unlocks in `num_periods` equal tranches, one at the end of each equal-length period
+    Stepped { num_periods: u32 },
+}
+
+impl VestingCurve {
+    /// Serialized size of the largest variant: tag (1) + num_periods (4)
+    pub const LEN: usize = 1 + 4;
+}
+
 #[account]
 pub struct VestingSchedule {
     /// Schedule ID, typically an incrementing number from program_config.total_schedules
@@ -20,7 +87,8 @@ pub struct VestingSchedule {
     /// Note: Recipient change functionality has been removed for enhanced security
     pub recipient: Pubkey,
     /// The specific token account of the recipient that will receive the vested tokens
-    /// Note: Recipient change functionality has been removed for enhanced security
+    /// Can only be replaced by another account owned by `recipient`, under
+    /// RECIPIENT_CHANGE_TIMELOCK (see `propose_recipient_token_account`)
     pub recipient_token_account: Pubkey,
     /// Token mint for this schedule
     pub mint: Pubkey,
@@ -40,6 +108,77 @@ pub struct VestingSchedule {
     pub amount_transferred: u64,
     /// Source category for fund tracking
     pub source_category: SourceCategory,
+    /// Round linear unlocks up instead of down, releasing slightly earlier in favor of the recipient
+    pub round_up: bool,
+    /// Logical grant this schedule belongs to, shared by schedules of different mints (0 = standalone)
+    pub grant_group_id: u64,
+    /// Optional delegate the recipient allows to pull releases via stream_withdraw
+    /// Releases still go to `recipient_token_account`, the authority only triggers them
+    pub withdrawal_authority: Option<Pubkey>,
+    /// Minimum seconds between recipient-initiated claims (0 = no limit)
+    pub min_claim_interval: i64,
+    /// Timestamp of the last claim counted against `min_claim_interval` (0 = never claimed)
+    pub last_claim_timestamp: i64,
+    /// Set while a release is in flight so a re-entrant call cannot release twice
+    pub locked: bool,
+    /// Released amount accrued by cranks in release-batching mode, awaiting `flush_releases`
+    /// Invariant: amount_transferred + pending_release <= unlocked amount
+    pub pending_release: u64,
+    /// Set by `finalize_schedule`, irreversibly blocks admin amendments of the terms
+    /// Releases and closing are unaffected
+    pub immutable: bool,
+    /// Created with `total_amount == 0` to reserve the slot and recipient, cleared by the
+    /// first top-up. Placeholders release nothing and are never fully processed
+    pub placeholder: bool,
+    /// SHA-256 of the off-chain agreed terms in `canonical_terms` layout, if provided at creation
+    pub terms_hash: Option<[u8; 32]>,
+    /// Basis points of `total_amount` unlocked at once at `cliff_timestamp` (TGE unlock)
+    /// Only the remainder vests linearly between vesting_start and vesting_end
+    pub initial_unlock_bps: u16,
+    /// Set by `revoke_vesting_schedule`: vested tokens were paid out, the rest clawed back,
+    /// and `total_amount` cut to what the recipient received. Cranks and claims skip it
+    pub revoked: bool,
+    /// Mint the vault is funded in. Equal to `mint` for every schedule today: swapping the
+    /// funding token into the release token at crank time is not supported yet, and release
+    /// paths refuse schedules where the two differ
+    pub funding_mint: Pubkey,
+    /// Set when `close_vesting_schedule` archives the schedule instead of deleting it: the vault
+    /// is closed but the terms stay on-chain for audits until `purge_archived`
+    pub closed: bool,
+    /// Unix timestamp from which an archived schedule may be purged (0 while open)
+    pub purgeable_at: i64,
+    /// Highest release milestone (percent of `total_amount`) already reported by a `ReleaseCheckpoint`
+    pub last_checkpoint_pct: u8,
+    /// Unlock curve, `Linear` unless created as a stepped/tranche grant
+    pub curve: VestingCurve,
+    /// Compliance-restricted grant: releases only ever go to `recipient_token_account`, and
+    /// never while that account has a delegate
+    pub non_transferable: bool,
+    /// Token-2022 transfer fees withheld from releases so far. The vault has been debited
+    /// `amount_transferred + transfer_fees_paid`, see `amount_debited`
+    pub transfer_fees_paid: u64,
+    /// Replacement recipient token account awaiting the timelock
+    pub pending_recipient_token_account: Option<Pubkey>,
+    /// Unix timestamp after which the pending recipient token account can be confirmed
+    pub recipient_change_timelock: Option<i64>,
+    /// Operator-facing name, UTF-8 padded with trailing zeros (see `validate_label`)
+    pub label: [u8; 32],
+    /// Unix timestamp of the last crank that moved tokens, for `min_crank_interval`
+    pub last_crank_timestamp: i64,
+    /// Set by `close_vault_only`: the vault is gone but the schedule is kept permanently as a
+    /// record. Batch cranks skip it, it can no longer be closed, archived or purged
+    pub vault_closed: bool,
+    /// Graded cliff: `second_cliff_bps` of `total_amount` unlocks on top of the curve once
+    /// `second_cliff_timestamp` is reached (`second_cliff_bps == 0` disables it)
+    pub second_cliff_timestamp: i64,
+    pub second_cliff_bps: u16,
+    /// Unix timestamp of the release that completed the schedule (0 while still owed tokens)
+    /// Starts the `close_grace_period` dispute window before the schedule can be closed
+    pub completed_at: i64,
+    /// Grant in native SOL: lamports are held by the system-owned PDA at `token_vault`
+    /// (`NATIVE_VAULT_SEED`) and `mint` and `recipient_token_account` are `Pubkey::default()`.
+    /// Only the `*_sol_*` instructions handle these schedules
+    pub is_native: bool,
     /// Flag to indicate if the schedule account is properly initialized
     pub is_initialized: bool,
     /// Bump seed for this PDA
@@ -60,9 +199,38 @@ impl VestingSchedule {
         + 8 // vesting_end_timestamp (i64)
         + 8 // amount_transferred (u64)
         + 1 // source_category (enum variant index only for simple enum)
+        + 1 // round_up (bool)
+        + 8 // grant_group_id (u64)
+        + 33 // withdrawal_authority (Option<Pubkey>)
+        + 8 // min_claim_interval (i64)
+        + 8 // last_claim_timestamp (i64)
+        + 1 // locked (bool)
+        + 8 // pending_release (u64)
+        + 1 // immutable (bool)
+        + 1 // placeholder (bool)
+        + 33 // terms_hash (Option<[u8; 32]>)
+        + 2 // initial_unlock_bps (u16)
+        + 1 // revoked (bool)
+        + 32 // funding_mint
+        + 1 // closed (bool)
+        + 8 // purgeable_at (i64)
+        + 1 // last_checkpoint_pct (u8)
+        + VestingCurve::LEN // curve
+        + 1 // non_transferable (bool)
+        + 8 // transfer_fees_paid (u64)
+        + 33 // pending_recipient_token_account (Option<Pubkey>)
+        + 9 // recipient_change_timelock (Option<i64>)
+        + 32 // label ([u8; 32])
+        + 8 // last_crank_timestamp (i64)
+        + 1 // vault_closed (bool)
+        + 8 // second_cliff_timestamp (i64)
+        + 2 // second_cliff_bps (u16)
+        + 8 // completed_at (i64)
+        + 1 // is_native (bool)
         + 1 // is_initialized (bool)
         + 1; // bump (u8)
 
+    #[allow(clippy::too_many_arguments)]
     pub fn init(
         &mut self,
         schedule_id: u64,
@@ -76,6 +244,9 @@ impl VestingSchedule {
         vesting_start_timestamp: i64,
         vesting_end_timestamp: i64,
         source_category: SourceCategory,
+        round_up: bool,
+        grant_group_id: u64,
+        min_claim_interval: i64,
         bump: u8,
     ) -> Result<()> {
         self.schedule_id = schedule_id;
@@ -90,14 +261,67 @@ impl VestingSchedule {
         self.vesting_end_timestamp = vesting_end_timestamp;
         self.amount_transferred = 0;
         self.source_category = source_category;
+        self.round_up = round_up;
+        self.grant_group_id = grant_group_id;
+        self.withdrawal_authority = None;
+        self.min_claim_interval = min_claim_interval;
+        self.last_claim_timestamp = 0;
+        self.locked = false;
+        self.pending_release = 0;
+        self.immutable = false;
+        self.placeholder = total_amount == 0;
+        self.terms_hash = None;
+        self.initial_unlock_bps = 0;
+        self.revoked = false;
+        self.funding_mint = mint;
+        self.closed = false;
+        self.purgeable_at = 0;
+        self.last_checkpoint_pct = 0;
+        self.curve = VestingCurve::Linear;
+        self.non_transferable = false;
+        self.transfer_fees_paid = 0;
+        self.pending_recipient_token_account = None;
+        self.recipient_change_timelock = None;
+        self.label = [0; 32];
+        self.last_crank_timestamp = 0;
+        self.vault_closed = false;
+        self.second_cliff_timestamp = 0;
+        self.second_cliff_bps = 0;
+        self.completed_at = 0;
+        self.is_native = false;
         self.is_initialized = true;
         self.bump = bump;
         Ok(())
     }
 
     /// Calculate unlocked amount at given timestamp
+    /// `initial_unlock_amount` unlocks at the cliff and the remainder vests linearly.
+    /// From `second_cliff_timestamp` the `second_cliff_amount` is added on top of that curve.
+    /// Linear unlocks round down by default; with `round_up` set they use ceiling division.
+    /// Either way the result is capped at `total_amount` and is zero before the cliff.
+    /// The cumulative amount is computed in one u128 step and rounded only at the final u64
+    /// conversion. Releases pay the difference to `amount_transferred`, so rounding never
+    /// compounds across cranks and a fixed-point precision factor would not change any result.
+    /// Floor (or ceiling) rather than round-half-up keeps the round-down default from ever
+    /// releasing ahead of schedule. Whatever the rounding, `vesting_end_timestamp` unlocks exactly
+    /// `total_amount`, so the last release delivers any truncated remainder, even for a
+    /// 1-second window (the shortest creation allows).
     /// Security: Uses checked arithmetic to prevent overflow
     pub fn calculate_unlocked_amount(&self, current_timestamp: i64) -> Result<u64> {
+        let curve_unlocked_amount = self.curve_unlocked_amount(current_timestamp)?;
+        if self.second_cliff_bps == 0 || current_timestamp < self.second_cliff_timestamp {
+            return Ok(curve_unlocked_amount);
+        }
+
+        // The second cliff's chunk never pushes the unlocked amount past the total
+        Ok(curve_unlocked_amount
+            .checked_add(self.second_cliff_amount()?)
+            .ok_or(VestingError::MathOverflow)?
+            .min(self.total_amount))
+    }
+
+    /// Unlocked amount of the initial unlock and the linear or stepped curve, without the second cliff
+    fn curve_unlocked_amount(&self, current_timestamp: i64) -> Result<u64> {
         // Validate initialized state
         if !self.is_initialized {
             return Err(VestingError::InvalidVestingScheduleData.into());
@@ -113,10 +337,13 @@ impl VestingSchedule {
             return Ok(self.total_amount);
         }
 
+        // Past the cliff the initial unlock is always available
+        let initial_unlock_amount = self.initial_unlock_amount()?;
+
         // If vesting_start_timestamp is at or after vesting_end_timestamp (invalid state, should be caught at creation)
         // or if current_timestamp is before vesting_start_timestamp (but after cliff)
         if self.vesting_start_timestamp >= self.vesting_end_timestamp || current_timestamp < self.vesting_start_timestamp {
-            return Ok(0);
+            return Ok(initial_unlock_amount);
         }
 
         // Linear vesting calculation
@@ -124,34 +351,1925 @@ impl VestingSchedule {
             .checked_sub(self.vesting_start_timestamp)
             .ok_or(VestingError::MathOverflow)?;
 
+        // At least 1: start >= end returned above
         let vesting_duration = self.vesting_end_timestamp
             .checked_sub(self.vesting_start_timestamp)
             .ok_or(VestingError::MathOverflow)?;
 
-        if vesting_duration == 0 {
-            return Ok(self.total_amount);
+        // Use u128 for intermediate multiplication to prevent overflow
+        let vesting_duration_u128 = to_u128(vesting_duration)?;
+
+        // Stepped vesting unlocks only whole periods: remainder * completed_periods / num_periods
+        if let VestingCurve::Stepped { num_periods } = self.curve {
+            let num_periods = u128::from(num_periods.max(1));
+            let completed_periods = to_u128(elapsed_since_vesting_start)?
+                .checked_mul(num_periods)
+                .ok_or(VestingError::MathOverflow)?
+                / vesting_duration_u128;
+            let stepped_amount = to_u128(self.total_amount - initial_unlock_amount)?
+                .checked_mul(completed_periods.min(num_periods))
+                .ok_or(VestingError::MathOverflow)?
+                / num_periods;
+            let unlocked_amount_u64 = to_u64(stepped_amount)?
+                .checked_add(initial_unlock_amount)
+                .ok_or(VestingError::MathOverflow)?;
+            return Ok(unlocked_amount_u64.min(self.total_amount));
         }
 
-        // Use u128 for intermediate multiplication to prevent overflow
-        let unlocked_amount_u128 = (self.total_amount as u128)
-            .checked_mul(elapsed_since_vesting_start as u128)
+        let unlocked_amount_u128 = to_u128(self.total_amount - initial_unlock_amount)?
+            .checked_mul(to_u128(elapsed_since_vesting_start)?)
             .ok_or(VestingError::MathOverflow)?;
 
+        // Ceiling division adds (divisor - 1) before dividing
+        let unlocked_amount_u128 = if self.round_up {
+            unlocked_amount_u128
+                .checked_add(vesting_duration_u128 - 1)
+                .ok_or(VestingError::MathOverflow)?
+        } else {
+            unlocked_amount_u128
+        };
+
         let unlocked_amount_u128 = unlocked_amount_u128
-            .checked_div(vesting_duration as u128)
+            .checked_div(vesting_duration_u128)
             .ok_or(VestingError::MathOverflow)?;
 
         // Safely convert back to u64
-        let unlocked_amount_u64 = u64::try_from(unlocked_amount_u128)
-            .map_err(|_| VestingError::MathOverflow)?;
+        let unlocked_amount_u64 = to_u64(unlocked_amount_u128)?
+            .checked_add(initial_unlock_amount)
+            .ok_or(VestingError::MathOverflow)?;
 
         Ok(unlocked_amount_u64.min(self.total_amount))
     }
 
+    /// Amount unlocked at the cliff: `initial_unlock_bps` of `total_amount`, rounded down
+    pub fn initial_unlock_amount(&self) -> Result<u64> {
+        let initial_unlock_amount = to_u128(self.total_amount)?
+            .checked_mul(u128::from(self.initial_unlock_bps))
+            .ok_or(VestingError::MathOverflow)?
+            / u128::from(BPS_DENOMINATOR);
+        Ok(to_u64(initial_unlock_amount)?.min(self.total_amount))
+    }
+
+    /// Amount unlocked at the second cliff: `second_cliff_bps` of `total_amount`, rounded down
+    pub fn second_cliff_amount(&self) -> Result<u64> {
+        let second_cliff_amount = to_u128(self.total_amount)?
+            .checked_mul(u128::from(self.second_cliff_bps))
+            .ok_or(VestingError::MathOverflow)?
+            / u128::from(BPS_DENOMINATOR);
+        Ok(to_u64(second_cliff_amount)?.min(self.total_amount))
+    }
+
+    /// Amount that vests linearly after the initial unlock
+    fn linear_amount(&self) -> u64 {
+        self.total_amount.saturating_sub(self.initial_unlock_amount().unwrap_or(0))
+    }
+
+    /// Truncation of a release at `current_timestamp`: the ideal (unrounded) curve unlock minus
+    /// the floored unlock the release pays against, scaled by `ROUNDING_DUST_SCALE`
+    /// Always below one base unit, since the floor is taken on the cumulative amount and a later
+    /// release pays the fraction once it adds up. Zero outside the vesting window, once the
+    /// total is unlocked and for round-up linear schedules, which never release below the ideal.
+    /// Security: Uses checked arithmetic to prevent overflow
+    pub fn release_truncation(&self, current_timestamp: i64) -> Result<u64> {
+        if current_timestamp < self.cliff_timestamp
+            || current_timestamp < self.vesting_start_timestamp
+            || self.vesting_start_timestamp >= self.vesting_end_timestamp
+            || self.calculate_unlocked_amount(current_timestamp)? >= self.total_amount
+        {
+            return Ok(0);
+        }
+
+        let elapsed_since_vesting_start = to_u128(
+            current_timestamp
+                .checked_sub(self.vesting_start_timestamp)
+                .ok_or(VestingError::MathOverflow)?,
+        )?;
+        let vesting_duration = to_u128(
+            self.vesting_end_timestamp
+                .checked_sub(self.vesting_start_timestamp)
+                .ok_or(VestingError::MathOverflow)?,
+        )?;
+        let linear_amount = to_u128(self.linear_amount())?;
+
+        // Same numerator and divisor as `curve_unlocked_amount`, keeping the remainder
+        let (numerator, divisor) = match self.curve {
+            VestingCurve::Stepped { num_periods } => {
+                let num_periods = u128::from(num_periods.max(1));
+                let completed_periods = (elapsed_since_vesting_start
+                    .checked_mul(num_periods)
+                    .ok_or(VestingError::MathOverflow)?
+                    / vesting_duration)
+                    .min(num_periods);
+                let numerator = linear_amount
+                    .checked_mul(completed_periods)
+                    .ok_or(VestingError::MathOverflow)?;
+                (numerator, num_periods)
+            }
+            VestingCurve::Linear if self.round_up => return Ok(0),
+            VestingCurve::Linear => {
+                let numerator = linear_amount
+                    .checked_mul(elapsed_since_vesting_start)
+                    .ok_or(VestingError::MathOverflow)?;
+                (numerator, vesting_duration)
+            }
+        };
+
+        let truncation = (numerator % divisor)
+            .checked_mul(ROUNDING_DUST_SCALE)
+            .ok_or(VestingError::MathOverflow)?
+            / divisor;
+        to_u64(truncation)
+    }
+
+    /// Check whether the full allocation has been delivered to the recipient
+    /// Releases only ever go to `recipient_token_account`, so this is the recipient's final receipt
+    /// Placeholders are never fully processed, they await a top-up
+    pub fn is_fully_processed(&self) -> bool {
+        !self.placeholder && self.amount_debited() >= self.total_amount
+    }
+
+    /// Add `amount` to the allocation of a schedule that is still vesting and not finalized
+    /// Unlocking is proportional to `total_amount`, so the top-up raises the vesting rate for
+    /// the rest of the schedule and immediately unlocks its share of the elapsed period
+    /// Returns the new `total_amount`
+    pub fn top_up(&mut self, amount: u64, current_timestamp: i64) -> Result<u64> {
+        require!(amount > 0, VestingError::InvalidAmount);
+        self.require_mutable()?;
+        require!(!self.revoked, VestingError::ScheduleRevoked);
+        require!(!self.is_fully_processed(), VestingError::ScheduleFullyProcessed);
+        // Placeholders await their first real amount whatever their timing
+        require!(
+            self.placeholder || current_timestamp < self.vesting_end_timestamp,
+            VestingError::ScheduleFullyVested
+        );
+
+        self.total_amount = self.total_amount
+            .checked_add(amount)
+            .ok_or(VestingError::MathOverflow)?;
+        self.placeholder = false;
+        Ok(self.total_amount)
+    }
+
+    /// Tokens released out of the vault so far: delivered to the recipient plus transfer fees
+    pub fn amount_debited(&self) -> u64 {
+        self.amount_transferred.saturating_add(self.transfer_fees_paid)
+    }
+
+    /// Tokens still owed to the recipient (vested or not)
+    pub fn outstanding_amount(&self) -> u64 {
+        self.total_amount.saturating_sub(self.amount_debited())
+    }
+
+    /// Solvency invariant: the vault holds at least the outstanding amount
+    pub fn is_solvent(&self, vault_amount: u64) -> bool {
+        vault_amount >= self.outstanding_amount()
+    }
+
+    /// Amount a revocation at `current_timestamp` would reclaim from a vault holding `vault_balance`
+    /// The unvested portion of the allocation, reduced when the vault cannot also cover what the
+    /// recipient is already owed (transferable plus pending releases). Never exceeds the vault
+    /// balance and is zero once everything has vested.
+    pub fn clawback_amount(&self, current_timestamp: i64, vault_balance: u64) -> Result<u64> {
+        let unvested_amount = self.total_amount
+            .saturating_sub(self.calculate_unlocked_amount(current_timestamp)?);
+        let owed_amount = self.get_transferable_amount(current_timestamp)?
+            .checked_add(self.pending_release)
+            .ok_or(VestingError::MathOverflow)?;
+        Ok(unvested_amount.min(vault_balance.saturating_sub(owed_amount)))
+    }
+
+    /// Release milestones (multiples of `step_pct` percent) crossed since the last checkpoint
+    /// Records the highest one so every milestone is reported exactly once. A release that
+    /// jumps several milestones returns all of them, in ascending order
+    pub fn advance_checkpoints(&mut self, step_pct: u8) -> Vec<u8> {
+        if step_pct == 0 || self.total_amount == 0 {
+            return Vec::new();
+        }
+
+        let released_pct = (self.amount_debited() as u128 * 100 / self.total_amount as u128).min(100) as u8;
+        let reached = released_pct - released_pct % step_pct;
+        let first = (self.last_checkpoint_pct / step_pct + 1).saturating_mul(step_pct);
+        if reached < first {
+            return Vec::new();
+        }
+
+        self.last_checkpoint_pct = reached;
+        (first..=reached).step_by(step_pct as usize).collect()
+    }
+
+    /// Mark the schedule archived at `current_timestamp`, keeping it for `retention_period` seconds
+    /// Returns the timestamp from which it may be purged
+    pub fn archive(&mut self, current_timestamp: i64, retention_period: u64) -> Result<i64> {
+        require!(!self.closed, VestingError::ScheduleAlreadyArchived);
+        let retention_period = i64::try_from(retention_period).map_err(|_| VestingError::MathOverflow)?;
+        self.purgeable_at = current_timestamp
+            .checked_add(retention_period)
+            .ok_or(VestingError::MathOverflow)?;
+        self.closed = true;
+        Ok(self.purgeable_at)
+    }
+
+    /// Check that an archived schedule's retention period has elapsed
+    pub fn require_purgeable(&self, current_timestamp: i64) -> Result<()> {
+        require!(self.closed, VestingError::ScheduleNotArchived);
+        require!(current_timestamp >= self.purgeable_at, VestingError::RetentionPeriodNotElapsed);
+        Ok(())
+    }
+
+    /// Terminate the schedule at `current_timestamp`
+    /// `paid_amount` is what the caller transfers to the recipient now (at most what is owed, see
+    /// `clawback_amount`); it settles any pending release and the allocation is cut to the total
+    /// received, so the schedule is fully processed and closable once the vault is emptied.
+    pub fn revoke(&mut self, paid_amount: u64) -> Result<()> {
+        require!(!self.revoked, VestingError::ScheduleRevoked);
+        self.amount_transferred = self.amount_transferred
+            .checked_add(paid_amount)
+            .ok_or(VestingError::MathOverflow)?;
+        self.pending_release = 0;
+        self.total_amount = self.amount_debited();
+        self.placeholder = false;
+        self.revoked = true;
+        Ok(())
+    }
+
+    /// Vault balance left over once the schedule is fully processed, swept by `sweep_rounding_dust`
+    /// Zero while anything is still owed to the recipient
+    pub fn sweepable_dust(&self, vault_amount: u64) -> u64 {
+        if !self.is_fully_processed() || self.pending_release > 0 {
+            return 0;
+        }
+        vault_amount.saturating_sub(self.outstanding_amount())
+    }
+
+    /// Additional amount unlocked between two timestamps under the current terms
+    /// Lets accounting systems accrue period-by-period vesting expense from the on-chain math
+    pub fn unlocked_between(&self, from_ts: i64, to_ts: i64) -> Result<u64> {
+        require!(to_ts >= from_ts, VestingError::InvalidTimestamps);
+        let unlocked_to = self.calculate_unlocked_amount(to_ts)?;
+        let unlocked_from = self.calculate_unlocked_amount(from_ts)?;
+        Ok(unlocked_to.checked_sub(unlocked_from).ok_or(VestingError::MathOverflow)?)
+    }
+
+    /// Check a schedule label: valid UTF-8 followed only by zero padding
+    /// An all-zero label means no label
+    pub fn validate_label(label: &[u8; 32]) -> Result<()> {
+        let len = label.iter().position(|&byte| byte == 0).unwrap_or(label.len());
+        require!(label[len..].iter().all(|&byte| byte == 0), VestingError::InvalidLabel);
+        require!(std::str::from_utf8(&label[..len]).is_ok(), VestingError::InvalidLabel);
+        Ok(())
+    }
+
+    /// Check a new schedule's timeline against the clock at creation
+    /// The cliff may be at most `MAX_BACKDATE` in the past and the end at most `MAX_FUTURE` ahead
+    pub fn validate_creation_window(cliff_timestamp: i64, vesting_end_timestamp: i64, current_timestamp: i64) -> Result<()> {
+        require!(
+            cliff_timestamp >= current_timestamp.saturating_sub(MAX_BACKDATE),
+            VestingError::CliffTooFarInPast
+        );
+        require!(
+            vesting_end_timestamp <= current_timestamp.saturating_add(MAX_FUTURE),
+            VestingError::VestingEndTooFarInFuture
+        );
+        Ok(())
+    }
+
     /// Get amount available to transfer
-    /// Returns the difference between unlocked and already transferred amounts
+    /// Returns the unlocked amount not yet transferred or accrued as `pending_release`
     pub fn get_transferable_amount(&self, current_timestamp: i64) -> Result<u64> {
         let unlocked_amount = self.calculate_unlocked_amount(current_timestamp)?;
-        Ok(unlocked_amount.saturating_sub(self.amount_transferred))
+        Ok(unlocked_amount
+            .saturating_sub(self.amount_debited())
+            .saturating_sub(self.pending_release))
+    }
+
+    /// Get amount a release can actually move: the transferable amount capped at `vault_balance`
+    /// Release paths use this so an under-funded vault never turns into a failed transfer
+    pub fn get_transferable_amount_capped(&self, current_timestamp: i64, vault_balance: u64) -> Result<u64> {
+        Ok(self.get_transferable_amount(current_timestamp)?.min(vault_balance))
+    }
+
+    /// Seconds it takes the linear rate to unlock at least one token unit (ceiling)
+    /// For stepped schedules this is the period length
+    pub fn min_release_interval(&self) -> Result<u64> {
+        let vesting_duration = to_u64(
+            self.vesting_end_timestamp
+                .checked_sub(self.vesting_start_timestamp)
+                .ok_or(VestingError::MathOverflow)?
+        )?;
+        if let VestingCurve::Stepped { num_periods } = self.curve {
+            return Ok(vesting_duration.div_ceil(u64::from(num_periods.max(1))));
+        }
+        let linear_amount = self.linear_amount();
+        if linear_amount == 0 {
+            return Ok(vesting_duration);
+        }
+        Ok(vesting_duration.div_ceil(linear_amount))
+    }
+
+    /// True when a crank mid-vesting finds nothing new because the linear rate rounds down
+    /// to zero since the last release, i.e. the crank cadence is shorter than
+    /// `min_release_interval`
+    /// Never true for stepped schedules, which are expected to release nothing between steps
+    pub fn is_release_rate_too_low(&self, current_timestamp: i64) -> Result<bool> {
+        if self.curve != VestingCurve::Linear
+            || current_timestamp < self.vesting_start_timestamp
+            || current_timestamp >= self.vesting_end_timestamp
+            || self.is_fully_processed()
+        {
+            return Ok(false);
+        }
+        Ok(self.get_transferable_amount(current_timestamp)? == 0)
+    }
+
+    /// Estimate how many more cranks a keeper needs to pay out in full when it cranks as soon as
+    /// at least `min_release` is releasable. The remaining vesting window is divided by the time
+    /// the linear rate takes to accrue `min_release`; a stepped curve counts its remaining steps,
+    /// grouped so each crank covers enough steps to reach `min_release`. An already-releasable
+    /// amount meeting the minimum adds a crank now, and the last crank may release less.
+    /// Without a minimum a linear schedule needs a single crank after vesting_end, as does any
+    /// schedule whose vesting has ended or that unlocks everything at the cliff.
+    pub fn cranks_remaining(&self, current_timestamp: i64, min_release: u64) -> u64 {
+        if self.outstanding_amount() == 0 {
+            return 0;
+        }
+        let linear_amount = self.linear_amount();
+        if current_timestamp >= self.vesting_end_timestamp || linear_amount == 0 {
+            return 1;
+        }
+        if min_release == 0 && self.curve == VestingCurve::Linear {
+            return 1;
+        }
+
+        let releasable_now = self.get_transferable_amount(current_timestamp).unwrap_or(0);
+        let crank_now = u64::from(releasable_now > 0 && releasable_now >= min_release);
+
+        let window_start = current_timestamp.max(self.vesting_start_timestamp);
+        let vesting_duration = self.vesting_end_timestamp
+            .saturating_sub(self.vesting_start_timestamp)
+            .max(1) as u128;
+        let remaining_window = self.vesting_end_timestamp.saturating_sub(window_start).max(0) as u128;
+
+        let remaining_cranks = match self.curve {
+            VestingCurve::Stepped { num_periods } => {
+                let num_periods = u128::from(num_periods.max(1));
+                let elapsed = window_start.saturating_sub(self.vesting_start_timestamp).max(0) as u128;
+                let completed_periods = (elapsed.saturating_mul(num_periods) / vesting_duration).min(num_periods);
+                let step_amount = (linear_amount as u128 / num_periods).max(1);
+                let steps_per_crank = (min_release as u128).div_ceil(step_amount).max(1);
+                (num_periods - completed_periods).div_ceil(steps_per_crank)
+            }
+            VestingCurve::Linear => {
+                // Seconds the linear rate (linear amount / duration) takes to accrue min_release
+                let accrual_interval = (min_release as u128)
+                    .saturating_mul(vesting_duration)
+                    .div_ceil(linear_amount as u128)
+                    .max(1);
+                remaining_window.div_ceil(accrual_interval)
+            }
+        };
+
+        u64::try_from(remaining_cranks)
+            .unwrap_or(u64::MAX)
+            .saturating_add(crank_now)
+    }
+
+    /// Estimate when the schedule will be fully paid out by a keeper that checks every
+    /// `crank_interval` seconds and only cranks once `min_release` has accrued.
+    /// A minimum larger than one interval's unlock stretches the effective cadence to the
+    /// smallest multiple of `crank_interval` that accrues it, and the crank following
+    /// vesting_end releases the remainder, so completion can land up to one effective
+    /// interval after `vesting_end_timestamp`. Cranks are assumed to start at `current_timestamp`
+    /// (or the first check after vesting_start). Saturates at i64::MAX.
+    pub fn completion_eta(&self, current_timestamp: i64, crank_interval: i64, min_release: u64) -> i64 {
+        if self.outstanding_amount() == 0 || current_timestamp >= self.vesting_end_timestamp {
+            return current_timestamp;
+        }
+
+        let crank_interval = crank_interval.max(1) as u128;
+        let next_check_at_or_after = |from: i64, target: i64| -> i64 {
+            let wait = target.saturating_sub(from).max(0) as u128;
+            let checks = wait.div_ceil(crank_interval);
+            i64::try_from(checks.saturating_mul(crank_interval))
+                .map_or(i64::MAX, |elapsed| from.saturating_add(elapsed))
+        };
+
+        // A 100% initial unlock has no linear part: everything is releasable from the cliff
+        let linear_amount = self.linear_amount();
+        if linear_amount == 0 {
+            return next_check_at_or_after(current_timestamp, self.cliff_timestamp);
+        }
+
+        // Nothing accrues before vesting_start, the first useful crank is the check after it
+        let first_crank = next_check_at_or_after(current_timestamp, self.vesting_start_timestamp);
+
+        // Checks needed to accrue min_release at the linear rate: linear amount / duration per second
+        let vesting_duration = self.vesting_end_timestamp
+            .saturating_sub(self.vesting_start_timestamp)
+            .max(1) as u128;
+        let accrued_per_check = (linear_amount as u128).saturating_mul(crank_interval);
+        let checks_per_crank = (min_release as u128)
+            .saturating_mul(vesting_duration)
+            .saturating_add(accrued_per_check - 1)
+            .checked_div(accrued_per_check)
+            .unwrap_or(1)
+            .max(1);
+        let effective_interval = crank_interval.saturating_mul(checks_per_crank);
+
+        let wait = self.vesting_end_timestamp.saturating_sub(first_crank).max(0) as u128;
+        let cranks = wait.div_ceil(effective_interval);
+        i64::try_from(cranks.saturating_mul(effective_interval))
+            .map_or(i64::MAX, |elapsed| first_crank.saturating_add(elapsed))
+    }
+
+    /// Canonical serialization of the schedule terms covered by `terms_hash`
+    ///
+    /// Layout (integers little-endian, 119 bytes for a plain linear schedule):
+    /// TERMS_HASH_DOMAIN (21) || recipient (32) || mint (32) || total_amount u64 (8) ||
+    /// cliff_timestamp i64 (8) || vesting_start_timestamp i64 (8) ||
+    /// vesting_end_timestamp i64 (8) || source_category index u8 (1) || round_up u8 (1)
+    /// followed, in this order and only when they differ from their defaults, by:
+    /// - initial_unlock_bps u16 (2), when non-zero
+    /// - curve tag u8 (1, 1 = stepped) || num_periods u32 (4), when the curve is stepped
+    /// - second_cliff_timestamp i64 (8) || second_cliff_bps u16 (2), when `second_cliff_bps > 0`
+    ///
+    /// Each combination of optional sections has a distinct length, so the encoding stays
+    /// unambiguous, and hashes recorded for the original layout still verify
+    pub fn canonical_terms(&self) -> Vec<u8> {
+        let mut terms = Vec::with_capacity(TERMS_HASH_DOMAIN.len() + 32 + 32 + 8 * 4 + 2 + 2 + VestingCurve::LEN + 10);
+        terms.extend_from_slice(TERMS_HASH_DOMAIN);
+        terms.extend_from_slice(self.recipient.as_ref());
+        terms.extend_from_slice(self.mint.as_ref());
+        terms.extend_from_slice(&self.total_amount.to_le_bytes());
+        terms.extend_from_slice(&self.cliff_timestamp.to_le_bytes());
+        terms.extend_from_slice(&self.vesting_start_timestamp.to_le_bytes());
+        terms.extend_from_slice(&self.vesting_end_timestamp.to_le_bytes());
+        terms.push(self.source_category.index() as u8);
+        terms.push(self.round_up as u8);
+        if self.initial_unlock_bps > 0 {
+            terms.extend_from_slice(&self.initial_unlock_bps.to_le_bytes());
+        }
+        if let VestingCurve::Stepped { num_periods } = self.curve {
+            terms.push(1u8);
+            terms.extend_from_slice(&num_periods.to_le_bytes());
+        }
+        if self.second_cliff_bps > 0 {
+            terms.extend_from_slice(&self.second_cliff_timestamp.to_le_bytes());
+            terms.extend_from_slice(&self.second_cliff_bps.to_le_bytes());
+        }
+        terms
+    }
+
+    /// Check the current on-chain terms against the recorded `terms_hash`
+    /// Fails with `TermsHashNotSet` when no hash was recorded at creation
+    pub fn verify_terms(&self) -> Result<bool> {
+        let terms_hash = self.terms_hash.ok_or(VestingError::TermsHashNotSet)?;
+        Ok(hash(&self.canonical_terms()).to_bytes() == terms_hash)
+    }
+
+    /// Auto-extend policy for an underfunded vault
+    ///
+    /// When the vault cannot cover the transferable amount mid-vesting, stretch the vesting
+    /// window so the tokens actually funded (`amount_transferred` plus the vault balance) would
+    /// vest by the original end: the duration is scaled by `total_amount / funded`. Releases
+    /// then follow the slower curve instead of draining the vault at once, and may pause until
+    /// the curve catches up with what was already released. The unfunded remainder unlocks
+    /// after the original end and is only paid if the vault is topped up.
+    ///
+    /// One-way: a later top-up does not shorten the window again. Skipped once vesting has
+    /// ended, for finalized schedules, and when nothing is funded at all, in which case the
+    /// usual clamp to the vault balance applies.
+    /// Returns the new `vesting_end_timestamp` when the schedule was extended.
+    pub fn extend_for_underfunding(&mut self, vault_balance: u64, current_timestamp: i64) -> Result<Option<i64>> {
+        if self.immutable || current_timestamp >= self.vesting_end_timestamp {
+            return Ok(None);
+        }
+
+        // Accrued-but-unflushed releases are still held by the vault
+        let available = vault_balance.saturating_sub(self.pending_release);
+        if self.get_transferable_amount(current_timestamp)? <= available {
+            return Ok(None);
+        }
+
+        let funded = u128::from(self.amount_debited())
+            .checked_add(u128::from(vault_balance))
+            .ok_or(VestingError::MathOverflow)?;
+        if funded == 0 || funded >= u128::from(self.total_amount) {
+            return Ok(None);
+        }
+
+        let vesting_duration = u128::from(to_u64(
+            self.vesting_end_timestamp
+                .checked_sub(self.vesting_start_timestamp)
+                .ok_or(VestingError::MathOverflow)?
+        )?);
+        let extended_duration = vesting_duration
+            .checked_mul(u128::from(self.total_amount))
+            .ok_or(VestingError::MathOverflow)?
+            .div_ceil(funded);
+        let extended_end = i64::try_from(extended_duration)
+            .ok()
+            .and_then(|duration| self.vesting_start_timestamp.checked_add(duration))
+            .unwrap_or(i64::MAX);
+
+        self.vesting_end_timestamp = extended_end;
+        Ok(Some(extended_end))
+    }
+
+    /// Reject amendments of a finalized schedule
+    pub fn require_mutable(&self) -> Result<()> {
+        require!(!self.immutable, VestingError::ScheduleImmutable);
+        Ok(())
+    }
+
+    /// Propose replacing `recipient_token_account`, e.g. after the recipient closed it
+    /// The caller checks that `new_account` is owned by `recipient`, so tokens still only
+    /// ever reach the original recipient
+    ///
+    /// # Returns
+    /// * Timestamp after which the change can be confirmed
+    /// * `NonTransferableSchedule` for non-transferable schedules, whose account is fixed
+    ///
+    /// A new proposal replaces the pending one and restarts the timelock
+    pub fn propose_recipient_token_account(&mut self, new_account: Pubkey, current_timestamp: i64) -> Result<i64> {
+        require!(!self.non_transferable, VestingError::NonTransferableSchedule);
+        require!(new_account != self.recipient_token_account, VestingError::InvalidRecipient);
+
+        let timelock_expiry = current_timestamp
+            .checked_add(RECIPIENT_CHANGE_TIMELOCK)
+            .ok_or(VestingError::MathOverflow)?;
+        self.pending_recipient_token_account = Some(new_account);
+        self.recipient_change_timelock = Some(timelock_expiry);
+        Ok(timelock_expiry)
+    }
+
+    /// Confirm the pending recipient token account once its timelock has expired
+    /// `new_account` must repeat the pending proposal, so a confirmation cannot race a newer one
+    /// Returns the previous recipient token account
+    pub fn confirm_recipient_token_account(&mut self, new_account: Pubkey, current_timestamp: i64) -> Result<Pubkey> {
+        let timelock_expiry = match (self.pending_recipient_token_account, self.recipient_change_timelock) {
+            (Some(pending), Some(expiry)) if pending == new_account => expiry,
+            _ => return Err(VestingError::NoPendingRecipientChange.into()),
+        };
+        require!(current_timestamp >= timelock_expiry, VestingError::TimelockNotExpired);
+
+        let old_account = self.recipient_token_account;
+        self.recipient_token_account = new_account;
+        self.pending_recipient_token_account = None;
+        self.recipient_change_timelock = None;
+        Ok(old_account)
+    }
+
+    /// Non-transferable schedules release only to the recorded recipient token account
+    pub fn require_transferable_to(&self, destination: &Pubkey) -> Result<()> {
+        require!(
+            !self.non_transferable || *destination == self.recipient_token_account,
+            VestingError::NonTransferableSchedule
+        );
+        Ok(())
+    }
+
+    /// Non-transferable schedules refuse releases while the recipient token account has a
+    /// delegate, which signals plans to move the tokens on
+    pub fn require_undelegated(&self, has_delegate: bool) -> Result<()> {
+        require!(!(self.non_transferable && has_delegate), VestingError::DelegatedAccountNotAllowed);
+        Ok(())
+    }
+
+    /// Accrue the transferable amount into `pending_release` instead of transferring it
+    /// Returns the newly accrued amount
+    pub fn accrue_release(&mut self, current_timestamp: i64) -> Result<u64> {
+        let accrued_amount = self.get_transferable_amount(current_timestamp)?;
+        self.pending_release = self.pending_release
+            .checked_add(accrued_amount)
+            .ok_or(VestingError::MathOverflow)?;
+        Ok(accrued_amount)
+    }
+
+    /// Record a release that debited the vault by `gross_amount` and delivered `delivered_amount`
+    /// The difference is the transfer fee withheld by a Token-2022 transfer-fee mint
+    pub fn record_release(&mut self, gross_amount: u64, delivered_amount: u64) -> Result<()> {
+        let fee = gross_amount
+            .checked_sub(delivered_amount)
+            .ok_or(VestingError::MathOverflow)?;
+        self.amount_transferred = self.amount_transferred
+            .checked_add(delivered_amount)
+            .ok_or(VestingError::MathOverflow)?;
+        self.transfer_fees_paid = self.transfer_fees_paid
+            .checked_add(fee)
+            .ok_or(VestingError::MathOverflow)?;
+        Ok(())
+    }
+
+    /// Stamp `completed_at` the first time a release leaves the schedule fully processed
+    pub fn record_completion(&mut self, current_timestamp: i64) {
+        if self.completed_at == 0 && self.is_fully_processed() {
+            self.completed_at = current_timestamp;
+        }
+    }
+
+    /// Check whether the close grace period since completion has elapsed
+    /// Schedules completed without a release (revoked, zero-amount) have no `completed_at` and
+    /// can be closed right away
+    pub fn close_grace_elapsed(&self, close_grace_period: u64, current_timestamp: i64) -> bool {
+        let close_grace_period = i64::try_from(close_grace_period).unwrap_or(i64::MAX);
+        self.completed_at == 0 || current_timestamp >= self.completed_at.saturating_add(close_grace_period)
+    }
+
+    /// Record that `gross_amount` of the pending release left the vault and `delivered_amount` arrived
+    pub fn settle_pending_release(&mut self, gross_amount: u64, delivered_amount: u64) -> Result<()> {
+        self.pending_release = self.pending_release
+            .checked_sub(gross_amount)
+            .ok_or(VestingError::MathOverflow)?;
+        self.record_release(gross_amount, delivered_amount)
+    }
+
+    /// Ensure `min_claim_interval` has passed since the last counted claim
+    /// Recipient-initiated claims always check this; keeper cranks only when configured
+    pub fn check_claim_interval(&self, current_timestamp: i64) -> Result<()> {
+        if self.min_claim_interval > 0 && self.last_claim_timestamp > 0 {
+            let next_claim_timestamp = self.last_claim_timestamp.saturating_add(self.min_claim_interval);
+            require!(current_timestamp >= next_claim_timestamp, VestingError::ClaimTooSoon);
+        }
+        Ok(())
+    }
+
+    /// Check whether `min_crank_interval` allows a crank release now
+    /// Always true once the schedule has fully vested, so the final release is never held back
+    pub fn crank_interval_elapsed(&self, min_crank_interval: i64, current_timestamp: i64) -> bool {
+        min_crank_interval <= 0
+            || current_timestamp >= self.vesting_end_timestamp
+            || current_timestamp.saturating_sub(self.last_crank_timestamp) >= min_crank_interval
+    }
+
+    /// Amount-weighted average unlock timestamp of the tokens not yet unlocked at `current_timestamp`
+    /// Each part of the schedule is weighted at the time it actually unlocks:
+    /// - the initial unlock as a lump at `cliff_timestamp`, while the cliff is ahead
+    /// - the second cliff's chunk as a lump at `second_cliff_timestamp`, while it is ahead
+    /// - a linear curve uniformly between max(now, vesting_start) and the time it is exhausted,
+    ///   so it averages to the midpoint of that window
+    /// - a stepped curve at the timestamps of its remaining steps
+    ///
+    /// The second cliff pulls the tail of the curve forward, so the curve only counts up to the
+    /// level it still has to deliver once the chunk is out.
+    /// Returns `vesting_end_timestamp` once everything has unlocked.
+    pub fn weighted_avg_unlock_timestamp(&self, current_timestamp: i64) -> Result<i64> {
+        if !self.is_initialized {
+            return Err(VestingError::InvalidVestingScheduleData.into());
+        }
+        if current_timestamp >= self.vesting_end_timestamp {
+            return Ok(self.vesting_end_timestamp);
+        }
+
+        let initial_unlock_amount = self.initial_unlock_amount()?;
+        let linear_amount = self.linear_amount();
+
+        // Effective second-cliff chunk: whatever the curve has not covered by then, at most its bps
+        let second_cliff_amount = if self.second_cliff_bps > 0 {
+            let curve_at_second_cliff = self.curve_unlocked_amount(self.second_cliff_timestamp)?;
+            self.second_cliff_amount()?.min(self.total_amount.saturating_sub(curve_at_second_cliff))
+        } else {
+            0
+        };
+        // Curve level (beyond the initial unlock) the curve still delivers itself
+        let curve_cap = linear_amount.saturating_sub(second_cliff_amount);
+
+        let mut weighted_sum: i128 = 0;
+        let mut remaining_amount: i128 = 0;
+        let mut add_lump = |amount: u64, timestamp: i64| {
+            weighted_sum += i128::from(amount) * i128::from(timestamp);
+            remaining_amount += i128::from(amount);
+        };
+
+        if current_timestamp < self.cliff_timestamp {
+            add_lump(initial_unlock_amount, self.cliff_timestamp);
+        }
+        if second_cliff_amount > 0 && current_timestamp < self.second_cliff_timestamp {
+            add_lump(second_cliff_amount, self.second_cliff_timestamp);
+        }
+
+        let curve_unlocked = if current_timestamp < self.cliff_timestamp {
+            0
+        } else {
+            self.curve_unlocked_amount(current_timestamp)?.saturating_sub(initial_unlock_amount)
+        };
+        let curve_remaining = curve_cap.saturating_sub(curve_unlocked);
+        if curve_remaining > 0 {
+            let vesting_duration = i128::from(
+                self.vesting_end_timestamp
+                    .checked_sub(self.vesting_start_timestamp)
+                    .ok_or(VestingError::MathOverflow)?,
+            );
+            let vesting_start = i128::from(self.vesting_start_timestamp);
+            let linear_amount = i128::from(linear_amount);
+            let curve_cap = i128::from(curve_cap);
+
+            let curve_avg_timestamp = match self.curve {
+                VestingCurve::Linear => {
+                    let window_start = i128::from(current_timestamp.max(self.vesting_start_timestamp));
+                    // Time the curve reaches `curve_cap` (ceiling)
+                    let window_end = vesting_start + (vesting_duration * curve_cap + linear_amount - 1) / linear_amount;
+                    (window_start + window_end) / 2
+                }
+                VestingCurve::Stepped { num_periods } => {
+                    let num_periods = i128::from(num_periods.max(1));
+                    let completed_periods = if current_timestamp < self.vesting_start_timestamp {
+                        0
+                    } else {
+                        (i128::from(current_timestamp) - vesting_start) * num_periods / vesting_duration
+                    };
+                    // Steps are equally spaced, so the remaining ones average to the mean of the
+                    // first and last step timestamps
+                    let first_step = completed_periods + 1;
+                    let last_step = (num_periods * curve_cap + linear_amount - 1) / linear_amount;
+                    vesting_start + vesting_duration * (first_step + last_step.max(first_step)) / (2 * num_periods)
+                }
+            };
+            let curve_avg_timestamp = i64::try_from(curve_avg_timestamp).map_err(|_| VestingError::MathOverflow)?;
+            add_lump(curve_remaining, curve_avg_timestamp);
+        }
+
+        if remaining_amount == 0 {
+            return Ok(self.vesting_end_timestamp);
+        }
+        i64::try_from(weighted_sum / remaining_amount)
+            .map_err(|_| VestingError::MathOverflow.into())
+    }
+
+    /// Cut the schedule down to what has already unlocked and return the unvested remainder
+    /// `total_amount` becomes the unlocked amount and vesting ends now, so the remaining
+    /// balance is immediately releasable and the schedule completes on its next crank.
+    /// A schedule still before its cliff is reduced to zero and counts as fully processed.
+    ///
+    /// # Returns
+    /// * The unvested amount removed from this schedule
+    /// * `InvalidAmount` if nothing remains unvested
+    pub fn split_unvested(&mut self, current_timestamp: i64) -> Result<u64> {
+        let unlocked_amount = self.calculate_unlocked_amount(current_timestamp)?;
+        let unvested_amount = self.total_amount.saturating_sub(unlocked_amount);
+        require!(unvested_amount > 0, VestingError::InvalidAmount);
+
+        self.total_amount = unlocked_amount;
+        if unlocked_amount > 0 {
+            // Unlocked tokens imply the cliff has passed, so ending now releases the rest
+            self.vesting_end_timestamp = current_timestamp;
+        }
+        Ok(unvested_amount)
+    }
+
+    /// Fold `other` into this schedule: allocations, releases, fees and pending releases add up
+    /// Only schedules of the same recipient, recipient account and mint with identical timing
+    /// and curve can be merged, so the combined curve is the sum of both. Schedules bound to
+    /// agreed terms (`terms_hash`) keep their own account and are refused.
+    ///
+    /// # Returns
+    /// * `SchedulesNotMergeable` if the two schedules differ in anything but their amounts
+    /// * `MergeDelaysVesting` if the merged schedule would have unlocked less by now than both together
+    pub fn merge_from(&mut self, other: &VestingSchedule, current_timestamp: i64) -> Result<()> {
+        require!(
+            self.schedule_id != other.schedule_id
+                && self.recipient == other.recipient
+                && self.recipient_token_account == other.recipient_token_account
+                && self.mint == other.mint
+                && self.funding_mint == other.funding_mint
+                && self.cliff_timestamp == other.cliff_timestamp
+                && self.vesting_start_timestamp == other.vesting_start_timestamp
+                && self.vesting_end_timestamp == other.vesting_end_timestamp
+                && self.curve == other.curve
+                && self.initial_unlock_bps == other.initial_unlock_bps
+                && self.second_cliff_timestamp == other.second_cliff_timestamp
+                && self.second_cliff_bps == other.second_cliff_bps
+                && self.round_up == other.round_up
+                && self.non_transferable == other.non_transferable
+                && self.terms_hash.is_none()
+                && other.terms_hash.is_none(),
+            VestingError::SchedulesNotMergeable
+        );
+
+        let unlocked_before = self.calculate_unlocked_amount(current_timestamp)?
+            .checked_add(other.calculate_unlocked_amount(current_timestamp)?)
+            .ok_or(VestingError::MathOverflow)?;
+
+        self.total_amount = self.total_amount
+            .checked_add(other.total_amount)
+            .ok_or(VestingError::MathOverflow)?;
+        self.amount_transferred = self.amount_transferred
+            .checked_add(other.amount_transferred)
+            .ok_or(VestingError::MathOverflow)?;
+        self.transfer_fees_paid = self.transfer_fees_paid
+            .checked_add(other.transfer_fees_paid)
+            .ok_or(VestingError::MathOverflow)?;
+        self.pending_release = self.pending_release
+            .checked_add(other.pending_release)
+            .ok_or(VestingError::MathOverflow)?;
+        self.last_claim_timestamp = self.last_claim_timestamp.max(other.last_claim_timestamp);
+
+        require!(
+            self.calculate_unlocked_amount(current_timestamp)? >= unlocked_before,
+            VestingError::MergeDelaysVesting
+        );
+        Ok(())
+    }
+
+    /// Mark a release as in flight
+    /// Security: Fails with `ScheduleLocked` if another release of this schedule has not finished
+    pub fn acquire_lock(&mut self) -> Result<()> {
+        require!(!self.locked, VestingError::ScheduleLocked);
+        self.locked = true;
+        Ok(())
+    }
+
+    /// Clear the in-flight release marker
+    pub fn release_lock(&mut self) {
+        self.locked = false;
+    }
+
+    /// Evaluate the schedule-level conditions that gate a crank
+    /// Mirrors the checks and skips of `crank_vesting_schedule`; program-wide gates are
+    /// evaluated by the caller
+    ///
+    /// # Returns
+    /// * The first blocking reason (`CrankBlockReason::None` if the crank would release)
+    /// * The amount a crank would release right now
+    pub fn crank_block_reason(
+        &self,
+        current_timestamp: i64,
+        vault_amount: u64,
+        recipient_account_owner: &Pubkey,
+    ) -> Result<(CrankBlockReason, u64)> {
+        if !self.is_initialized {
+            return Ok((CrankBlockReason::NotInitialized, 0));
+        }
+        if *recipient_account_owner != self.recipient {
+            return Ok((CrankBlockReason::RecipientAccountOwnershipChanged, 0));
+        }
+        if self.revoked {
+            return Ok((CrankBlockReason::Revoked, 0));
+        }
+        if self.is_fully_processed() {
+            return Ok((CrankBlockReason::FullyProcessed, 0));
+        }
+
+        let transferable_amount = self.get_transferable_amount(current_timestamp)?;
+        if transferable_amount == 0 {
+            return Ok((CrankBlockReason::NothingVested, 0));
+        }
+        if vault_amount == 0 {
+            return Ok((CrankBlockReason::VaultEmpty, 0));
+        }
+        Ok((CrankBlockReason::None, transferable_amount.min(vault_amount)))
+    }
+
+    /// Lamports returned to the beneficiary when this schedule and its vault are closed
+    /// Closing hands over whatever the accounts hold, so this is the sum of their actual balances
+    /// rather than a rent-exempt minimum: the schedule is allocated with an extra discriminator,
+    /// Token-2022 vaults carry extensions and a vault that is already closed holds nothing
+    pub fn reclaimable_rent(schedule_lamports: u64, vault_lamports: u64) -> u64 {
+        schedule_lamports.saturating_add(vault_lamports)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_schedule(total_amount: u64, amount_transferred: u64) -> VestingSchedule {
+        let mut schedule = VestingSchedule::deserialize(
+            &mut &[0u8; VestingSchedule::LEN - DISCRIMINATOR_SIZE][..]
+        ).unwrap();
+        schedule.init(
+            0,
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            total_amount,
+            1_000,
+            1_000,
+            2_000,
+            SourceCategory::Public,
+            false,
+            0,
+            0,
+            255,
+        ).unwrap();
+        schedule.amount_transferred = amount_transferred;
+        schedule
+    }
+
+    #[test]
+    fn test_is_fully_processed() {
+        assert!(!sample_schedule(1_000, 0).is_fully_processed());
+        assert!(!sample_schedule(1_000, 999).is_fully_processed());
+        assert!(sample_schedule(1_000, 1_000).is_fully_processed());
+    }
+
+    #[test]
+    fn test_round_up_vs_round_down() {
+        // 1000 tokens over 3 seconds: 333.33.. per second
+        let mut round_down = sample_schedule(1_000, 0);
+        round_down.vesting_end_timestamp = 1_003;
+        let mut round_up = round_down.clone();
+        round_up.round_up = true;
+
+        assert_eq!(round_down.calculate_unlocked_amount(1_001).unwrap(), 333);
+        assert_eq!(round_up.calculate_unlocked_amount(1_001).unwrap(), 334);
+        assert_eq!(round_down.calculate_unlocked_amount(1_002).unwrap(), 666);
+        assert_eq!(round_up.calculate_unlocked_amount(1_002).unwrap(), 667);
+
+        // Exact divisions are identical in both modes
+        assert_eq!(round_down.calculate_unlocked_amount(1_000).unwrap(), 0);
+        assert_eq!(round_up.calculate_unlocked_amount(1_000).unwrap(), 0);
+    }
+
+    #[test]
+    fn test_round_up_never_exceeds_bounds() {
+        let mut schedule = sample_schedule(1_000, 0);
+        schedule.round_up = true;
+        schedule.cliff_timestamp = 900;
+        schedule.vesting_end_timestamp = 1_003;
+
+        // Nothing before the cliff
+        assert_eq!(schedule.calculate_unlocked_amount(899).unwrap(), 0);
+        // Never more than total
+        assert_eq!(schedule.calculate_unlocked_amount(1_003).unwrap(), 1_000);
+        assert_eq!(schedule.calculate_unlocked_amount(5_000).unwrap(), 1_000);
+    }
+
+    #[test]
+    fn test_reclaimable_rent_uses_account_balances() {
+        let rent = Rent::default();
+        // Schedules are allocated as DISCRIMINATOR_SIZE + VESTING_SCHEDULE_LEN bytes
+        let schedule_lamports = rent.minimum_balance(DISCRIMINATOR_SIZE + crate::constants::VESTING_SCHEDULE_LEN);
+        // SPL vault, and a Token-2022 vault with an immutable-owner extension
+        let spl_vault_lamports = rent.minimum_balance(165);
+        let extended_vault_lamports = rent.minimum_balance(170);
+
+        assert_eq!(
+            VestingSchedule::reclaimable_rent(schedule_lamports, spl_vault_lamports),
+            schedule_lamports + spl_vault_lamports
+        );
+        // The minimum for the schedule's struct size alone understates the allocation
+        assert!(schedule_lamports > rent.minimum_balance(VestingSchedule::LEN));
+        assert!(
+            VestingSchedule::reclaimable_rent(schedule_lamports, extended_vault_lamports)
+                > VestingSchedule::reclaimable_rent(schedule_lamports, spl_vault_lamports)
+        );
+        // Only the schedule is left once the vault has been closed
+        assert_eq!(VestingSchedule::reclaimable_rent(schedule_lamports, 0), schedule_lamports);
+    }
+
+    #[test]
+    fn test_crank_block_reason() {
+        let schedule = sample_schedule(1_000, 0);
+        let owner = schedule.recipient;
+
+        assert_eq!(
+            schedule.crank_block_reason(1_500, 1_000, &owner).unwrap(),
+            (CrankBlockReason::None, 500)
+        );
+        // Vault balance caps the releasable amount
+        assert_eq!(
+            schedule.crank_block_reason(1_500, 200, &owner).unwrap(),
+            (CrankBlockReason::None, 200)
+        );
+        assert_eq!(
+            schedule.crank_block_reason(999, 1_000, &owner).unwrap(),
+            (CrankBlockReason::NothingVested, 0)
+        );
+        assert_eq!(
+            schedule.crank_block_reason(1_500, 0, &owner).unwrap(),
+            (CrankBlockReason::VaultEmpty, 0)
+        );
+        assert_eq!(
+            schedule.crank_block_reason(1_500, 1_000, &Pubkey::new_unique()).unwrap(),
+            (CrankBlockReason::RecipientAccountOwnershipChanged, 0)
+        );
+        let completed = sample_schedule(1_000, 1_000);
+        assert_eq!(
+            completed.crank_block_reason(2_500, 0, &completed.recipient).unwrap(),
+            (CrankBlockReason::FullyProcessed, 0)
+        );
+    }
+
+    #[test]
+    fn test_check_claim_interval() {
+        let mut schedule = sample_schedule(1_000, 0);
+
+        // No interval configured: always allowed
+        schedule.last_claim_timestamp = 1_500;
+        assert!(schedule.check_claim_interval(1_500).is_ok());
+
+        schedule.min_claim_interval = 100;
+        assert!(schedule.check_claim_interval(1_599).is_err());
+        assert!(schedule.check_claim_interval(1_600).is_ok());
+
+        // First claim is never blocked
+        schedule.last_claim_timestamp = 0;
+        assert!(schedule.check_claim_interval(1_000).is_ok());
+    }
+
+    #[test]
+    fn test_lock_rejects_reentrant_release() {
+        let mut schedule = sample_schedule(1_000, 0);
+
+        schedule.acquire_lock().unwrap();
+        // A re-entrant release while the first is in flight is refused
+        assert!(schedule.acquire_lock().is_err());
+
+        schedule.release_lock();
+        assert!(schedule.acquire_lock().is_ok());
+    }
+
+    #[test]
+    fn test_split_unvested() {
+        // Halfway through: half stays and is immediately releasable
+        let mut schedule = sample_schedule(1_000, 200);
+        assert_eq!(schedule.split_unvested(1_500).unwrap(), 500);
+        assert_eq!(schedule.total_amount, 500);
+        assert_eq!(schedule.vesting_end_timestamp, 1_500);
+        assert_eq!(schedule.get_transferable_amount(1_500).unwrap(), 300);
+
+        // Before the cliff everything moves
+        let mut schedule = sample_schedule(1_000, 0);
+        assert_eq!(schedule.split_unvested(500).unwrap(), 1_000);
+        assert!(schedule.is_fully_processed());
+
+        // Nothing left to reassign after vesting ends
+        let mut schedule = sample_schedule(1_000, 0);
+        assert!(schedule.split_unvested(2_000).is_err());
+    }
+
+    #[test]
+    fn test_weighted_avg_unlock_timestamp_linear() {
+        // Vesting 1_000..2_000
+        let schedule = sample_schedule(1_000, 0);
+
+        // Before vesting starts the whole period remains
+        assert_eq!(schedule.weighted_avg_unlock_timestamp(500).unwrap(), 1_500);
+        // Mid-way only the remaining half counts
+        assert_eq!(schedule.weighted_avg_unlock_timestamp(1_500).unwrap(), 1_750);
+        // Fully unlocked
+        assert_eq!(schedule.weighted_avg_unlock_timestamp(2_500).unwrap(), 2_000);
+    }
+
+    #[test]
+    fn test_weighted_avg_unlock_timestamp_with_cliff_gap() {
+        // Cliff before vesting start: tokens still unlock over the linear window only
+        let mut schedule = sample_schedule(1_000, 0);
+        schedule.cliff_timestamp = 800;
+        assert_eq!(schedule.weighted_avg_unlock_timestamp(700).unwrap(), 1_500);
+    }
+
+    #[test]
+    fn test_weighted_avg_unlock_timestamp_stepped() {
+        // 1_000 in 4 steps of 250 at 1_250, 1_500, 1_750 and 2_000
+        let mut schedule = sample_schedule(1_000, 0);
+        schedule.curve = VestingCurve::Stepped { num_periods: 4 };
+
+        // Every step ahead: the mean of the step timestamps, not the window midpoint
+        assert_eq!(schedule.weighted_avg_unlock_timestamp(900).unwrap(), 1_625);
+        // Two steps done, the ones at 1_750 and 2_000 remain
+        assert_eq!(schedule.weighted_avg_unlock_timestamp(1_600).unwrap(), 1_875);
+        // Only the final step remains
+        assert_eq!(schedule.weighted_avg_unlock_timestamp(1_800).unwrap(), 2_000);
+
+        // With a 40% initial unlock at the cliff: 400 at 1_000 and 600 at a mean of 1_625
+        schedule.initial_unlock_bps = 4_000;
+        assert_eq!(schedule.weighted_avg_unlock_timestamp(900).unwrap(), (400 * 1_000 + 600 * 1_625) / 1_000);
+    }
+
+    #[test]
+    fn test_weighted_avg_unlock_timestamp_second_cliff() {
+        // Linear 1_000..2_000 plus 25% at 1_200; the curve then only has 750 to deliver and is
+        // exhausted at 1_750
+        let mut schedule = sample_schedule(1_000, 0);
+        schedule.second_cliff_timestamp = 1_200;
+        schedule.second_cliff_bps = 2_500;
+
+        assert_eq!(
+            schedule.weighted_avg_unlock_timestamp(900).unwrap(),
+            (250 * 1_200 + 750 * 1_375) / 1_000
+        );
+        // Past the second cliff only the rest of the curve is left: 1_500..1_750
+        assert_eq!(schedule.weighted_avg_unlock_timestamp(1_500).unwrap(), 1_625);
+        assert_eq!(schedule.weighted_avg_unlock_timestamp(1_750).unwrap(), 2_000);
+
+        // Stepped: 250 at 1_200 and steps up to the one reaching 750 (at 1_750)
+        schedule.curve = VestingCurve::Stepped { num_periods: 4 };
+        assert_eq!(
+            schedule.weighted_avg_unlock_timestamp(900).unwrap(),
+            (250 * 1_200 + 750 * 1_500) / 1_000
+        );
+    }
+
+    #[test]
+    fn test_many_cranks_do_not_accumulate_rounding_loss() {
+        // Few-decimal token: 7 units vesting over 1_000 seconds
+        let mut schedule = sample_schedule(7, 0);
+        const PRECISION: u128 = 1_000_000_000;
+
+        for now in (1_000..=2_000).step_by(13) {
+            let released = schedule.get_transferable_amount(now).unwrap();
+            schedule.amount_transferred += released;
+
+            // Reference computed in a scaled-up fixed-point space, rounded only at the end
+            let elapsed = (now - 1_000) as u128;
+            let scaled = 7 * PRECISION * elapsed / 1_000;
+            assert_eq!(schedule.amount_transferred as u128, scaled / PRECISION);
+        }
+
+        schedule.amount_transferred += schedule.get_transferable_amount(2_000).unwrap();
+        assert_eq!(schedule.amount_transferred, 7);
+    }
+
+    #[test]
+    fn test_rounding_dust_over_many_cranks() {
+        // Few-decimal token: 7 units vesting over 1_000 seconds
+        let mut schedule = sample_schedule(7, 0);
+        let mut config = crate::state::ProgramConfig::deserialize(
+            &mut &[0u8; crate::state::ProgramConfig::LEN][..],
+        ).unwrap();
+        let mut expected_dust = 0u128;
+
+        for now in (1_000..2_000).step_by(13) {
+            let released = schedule.get_transferable_amount(now).unwrap();
+            schedule.amount_transferred += released;
+            config.record_rounding_dust(schedule.release_truncation(now).unwrap()).unwrap();
+
+            // Ideal unlock 7 * elapsed / 1_000, floored to whole units on release
+            let ideal_scaled = 7 * (now - 1_000) as u128 * ROUNDING_DUST_SCALE / 1_000;
+            let floored_scaled = u128::from(schedule.amount_transferred) * ROUNDING_DUST_SCALE;
+            assert!(ideal_scaled - floored_scaled < ROUNDING_DUST_SCALE);
+            expected_dust += ideal_scaled - floored_scaled;
+            assert_eq!(u128::from(config.rounding_dust), expected_dust);
+        }
+        assert!(config.rounding_dust > 0);
+
+        // The final release pays the carried fraction and truncates nothing
+        schedule.amount_transferred += schedule.get_transferable_amount(2_000).unwrap();
+        assert_eq!(schedule.amount_transferred, 7);
+        assert_eq!(schedule.release_truncation(2_000).unwrap(), 0);
+    }
+
+    #[test]
+    fn test_release_truncation() {
+        // 1_000 units over 1_000..2_000: every second unlocks a whole unit
+        let mut schedule = sample_schedule(1_000, 0);
+        assert_eq!(schedule.release_truncation(1_500).unwrap(), 0);
+
+        // 7 units: 7 * 300 / 1_000 = 2.1
+        schedule.total_amount = 7;
+        assert_eq!(schedule.release_truncation(999).unwrap(), 0);
+        assert_eq!(schedule.release_truncation(1_300).unwrap(), 100_000_000);
+        assert_eq!(schedule.release_truncation(2_000).unwrap(), 0);
+
+        // Rounding up never releases below the ideal
+        schedule.round_up = true;
+        assert_eq!(schedule.release_truncation(1_300).unwrap(), 0);
+        schedule.round_up = false;
+
+        // Stepped: 7 units over 4 steps, 2 steps complete at 1_500 = 3.5
+        schedule.curve = VestingCurve::Stepped { num_periods: 4 };
+        assert_eq!(schedule.release_truncation(1_500).unwrap(), 500_000_000);
+        assert_eq!(schedule.release_truncation(1_250).unwrap(), 750_000_000);
+    }
+
+    #[test]
+    fn test_accrue_then_settle_never_over_releases() {
+        // Vesting 1_000..2_000
+        let mut schedule = sample_schedule(1_000, 0);
+
+        assert_eq!(schedule.accrue_release(1_200).unwrap(), 200);
+        assert_eq!(schedule.accrue_release(1_500).unwrap(), 300);
+        // Accruing again at the same time adds nothing
+        assert_eq!(schedule.accrue_release(1_500).unwrap(), 0);
+        assert_eq!(schedule.pending_release, 500);
+        assert_eq!(schedule.get_transferable_amount(1_500).unwrap(), 0);
+
+        // Partial flush (e.g. short vault) keeps the remainder pending
+        schedule.settle_pending_release(300, 300).unwrap();
+        assert_eq!(schedule.amount_transferred, 300);
+        assert_eq!(schedule.pending_release, 200);
+        assert!(schedule.settle_pending_release(201, 201).is_err());
+
+        for now in [1_700, 1_900, 2_500] {
+            schedule.accrue_release(now).unwrap();
+            assert!(
+                schedule.amount_transferred + schedule.pending_release
+                    <= schedule.calculate_unlocked_amount(now).unwrap()
+            );
+        }
+        let pending_release = schedule.pending_release;
+        schedule.settle_pending_release(pending_release, pending_release).unwrap();
+        assert_eq!(schedule.amount_transferred, 1_000);
+        assert!(schedule.is_fully_processed());
+    }
+
+    #[test]
+    fn test_require_mutable() {
+        let mut schedule = sample_schedule(1_000, 0);
+        assert!(schedule.require_mutable().is_ok());
+
+        schedule.immutable = true;
+        assert!(schedule.require_mutable().is_err());
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_release_rate_too_low() {
+        // 3 units over 1_000 seconds: one unit every 334 seconds
+        let mut schedule = sample_schedule(3, 0);
+        assert_eq!(schedule.min_release_interval().unwrap(), 334);
+
+        // First unit unlocked at 1_334, released by a crank
+        schedule.amount_transferred = schedule.calculate_unlocked_amount(1_400).unwrap();
+        assert_eq!(schedule.amount_transferred, 1);
+
+        // A crank a minute later finds nothing new mid-vesting
+        assert!(schedule.is_release_rate_too_low(1_460).unwrap());
+        // Once the next unit unlocks the rate is fine again
+        assert!(!schedule.is_release_rate_too_low(1_700).unwrap());
+        // Outside the linear window the warning does not apply
+        assert!(!schedule.is_release_rate_too_low(900).unwrap());
+        assert!(!schedule.is_release_rate_too_low(2_000).unwrap());
+    }
+
+    #[test]
+    fn test_solvency() {
+        let schedule = sample_schedule(1_000, 400);
+        assert_eq!(schedule.outstanding_amount(), 600);
+        assert!(schedule.is_solvent(600));
+        assert!(schedule.is_solvent(700));
+        assert!(!schedule.is_solvent(599));
+    }
+
+    #[test]
+    fn test_cranks_remaining() {
+        // Linear 1_000 over 1_000..2_000, keeper cranks once 300 has accrued: 1_300, 1_600, 1_900, 2_000
+        let schedule = sample_schedule(1_000, 0);
+        assert_eq!(schedule.cranks_remaining(1_000, 300), 4);
+        assert_eq!(schedule.cranks_remaining(500, 300), 4);
+
+        // 500 already releasable at 1_500: a crank now, then 1_800 and 2_000
+        assert_eq!(schedule.cranks_remaining(1_500, 300), 3);
+        // 1_500, 1_750, 2_000
+        assert_eq!(schedule.cranks_remaining(1_500, 250), 3);
+        // The minimum never accrues before the end: one crank after it
+        assert_eq!(schedule.cranks_remaining(1_500, 5_000), 1);
+
+        // Part already released: 300 releasable now, then every 100 seconds until 2_000
+        let schedule = sample_schedule(1_000, 200);
+        assert_eq!(schedule.cranks_remaining(1_500, 100), 6);
+
+        // No minimum or vesting over: one crank after the end
+        assert_eq!(schedule.cranks_remaining(1_500, 0), 1);
+        assert_eq!(schedule.cranks_remaining(2_000, 100), 1);
+
+        // Fully paid out
+        assert_eq!(sample_schedule(1_000, 1_000).cranks_remaining(1_500, 100), 0);
+    }
+
+    #[test]
+    fn test_cranks_remaining_stepped() {
+        // 4 steps of 250 at 1_250, 1_500, 1_750 and 2_000
+        let mut schedule = sample_schedule(1_000, 0);
+        schedule.curve = VestingCurve::Stepped { num_periods: 4 };
+
+        // One crank per remaining step, even without a minimum
+        assert_eq!(schedule.cranks_remaining(1_100, 0), 4);
+        assert_eq!(schedule.cranks_remaining(1_600, 0), 3);
+        assert_eq!(schedule.cranks_remaining(1_750, 100), 2);
+
+        // A minimum of 400 needs two steps per crank: 1_500 and 2_000
+        assert_eq!(schedule.cranks_remaining(1_100, 400), 2);
+        // 500 releasable now, then both remaining steps together
+        assert_eq!(schedule.cranks_remaining(1_500, 400), 2);
+
+        // Vesting over
+        assert_eq!(schedule.cranks_remaining(2_000, 400), 1);
+    }
+
+
+    #[test]
+    fn test_placeholder_awaits_funding() {
+        let mut schedule = sample_schedule(0, 0);
+        assert!(schedule.placeholder);
+        assert!(!schedule.is_fully_processed());
+        assert_eq!(schedule.get_transferable_amount(i64::MAX - 1).unwrap(), 0);
+
+        // Top-up sets the real total
+        schedule.total_amount = 1_000;
+        schedule.placeholder = false;
+        assert!(!schedule.is_fully_processed());
+        assert_eq!(schedule.get_transferable_amount(2_000).unwrap(), 1_000);
+
+        // A schedule emptied by reassignment is not a placeholder
+        let mut schedule = sample_schedule(1_000, 0);
+        schedule.split_unvested(500).unwrap();
+        assert!(!schedule.placeholder);
+        assert!(schedule.is_fully_processed());
+    }
+
+    #[test]
+    fn test_completion_eta() {
+        // Vests 1 token per second from 1_000 to 2_000
+        let schedule = sample_schedule(1_000, 0);
+
+        // Checks that line up with vesting_end complete right at the end
+        assert_eq!(schedule.completion_eta(1_500, 100, 0), 2_000);
+        // Coarser cadence overshoots to the first check after the end
+        assert_eq!(schedule.completion_eta(1_500, 300, 0), 2_100);
+        // A 250 minimum needs 3 checks of 100 per crank, stretching the cadence to 300
+        assert_eq!(schedule.completion_eta(1_500, 100, 250), 2_100);
+        // A minimum already met by one interval's unlock does not delay
+        assert_eq!(schedule.completion_eta(1_500, 100, 100), 2_000);
+
+        // Before vesting starts, cranking begins at the first check after vesting_start
+        assert_eq!(schedule.completion_eta(500, 300, 0), 2_000);
+        assert_eq!(schedule.completion_eta(500, 400, 0), 2_100);
+
+        // Vesting over or already paid out: a crank now completes it
+        assert_eq!(schedule.completion_eta(2_500, 100, 5_000), 2_500);
+        assert_eq!(sample_schedule(1_000, 1_000).completion_eta(1_500, 100, 0), 1_500);
+
+        // Absurd cadences saturate instead of overflowing
+        assert_eq!(schedule.completion_eta(1_500, i64::MAX, u64::MAX), i64::MAX);
+    }
+
+    #[test]
+    fn test_completion_eta_full_initial_unlock() {
+        // Everything unlocks at the cliff (1_000), nothing vests linearly
+        let mut schedule = sample_schedule(1_000, 0);
+        schedule.initial_unlock_bps = 10_000;
+
+        assert_eq!(schedule.completion_eta(500, 300, 0), 1_100);
+        assert_eq!(schedule.completion_eta(500, 300, 1_000), 1_100);
+        assert_eq!(schedule.completion_eta(1_500, 100, 0), 1_500);
+    }
+
+    #[test]
+    fn test_verify_terms() {
+        let mut schedule = sample_schedule(1_000, 0);
+        assert!(schedule.verify_terms().is_err());
+
+        let terms = schedule.canonical_terms();
+        assert_eq!(terms.len(), 119);
+        assert_eq!(&terms[..TERMS_HASH_DOMAIN.len()], TERMS_HASH_DOMAIN);
+
+        schedule.terms_hash = Some(hash(&terms).to_bytes());
+        assert!(schedule.verify_terms().unwrap());
+
+        // Releases do not change the terms
+        schedule.amount_transferred = 400;
+        assert!(schedule.verify_terms().unwrap());
+
+        // Any change to a covered field is detected
+        schedule.vesting_end_timestamp += 1;
+        assert!(!schedule.verify_terms().unwrap());
+        schedule.vesting_end_timestamp -= 1;
+        schedule.total_amount += 1;
+        assert!(!schedule.verify_terms().unwrap());
+        schedule.total_amount -= 1;
+        schedule.curve = VestingCurve::Stepped { num_periods: 4 };
+        assert!(!schedule.verify_terms().unwrap());
+    }
+
+    #[test]
+    fn test_verify_terms_recorded_under_v1() {
+        let mut schedule = sample_schedule(1_000, 0);
+
+        // Original layout, built by hand as an off-chain signer would have
+        let mut v1_terms = b"haio-vesting:terms:v1".to_vec();
+        v1_terms.extend_from_slice(schedule.recipient.as_ref());
+        v1_terms.extend_from_slice(schedule.mint.as_ref());
+        v1_terms.extend_from_slice(&schedule.total_amount.to_le_bytes());
+        v1_terms.extend_from_slice(&schedule.cliff_timestamp.to_le_bytes());
+        v1_terms.extend_from_slice(&schedule.vesting_start_timestamp.to_le_bytes());
+        v1_terms.extend_from_slice(&schedule.vesting_end_timestamp.to_le_bytes());
+        v1_terms.push(schedule.source_category.index() as u8);
+        v1_terms.push(schedule.round_up as u8);
+        schedule.terms_hash = Some(hash(&v1_terms).to_bytes());
+        assert!(schedule.verify_terms().unwrap());
+
+        // Every later field is covered once it leaves its default
+        let plain_len = schedule.canonical_terms().len();
+        schedule.initial_unlock_bps = 1_000;
+        assert!(!schedule.verify_terms().unwrap());
+        assert_eq!(schedule.canonical_terms().len(), plain_len + 2);
+        schedule.curve = VestingCurve::Stepped { num_periods: 4 };
+        assert_eq!(schedule.canonical_terms().len(), plain_len + 7);
+        schedule.initial_unlock_bps = 0;
+        assert_eq!(schedule.canonical_terms().len(), plain_len + 5);
+        schedule.curve = VestingCurve::Linear;
+        assert!(schedule.verify_terms().unwrap());
+    }
+
+    #[test]
+    fn test_extend_for_underfunding() {
+        // Vests 1 token per second from 1_000 to 2_000, but only 250 were ever deposited
+        let clamped = sample_schedule(1_000, 0);
+        let mut extended = sample_schedule(1_000, 0);
+
+        // Clamp: the first crank drains the whole vault halfway through
+        assert_eq!(clamped.get_transferable_amount(1_500).unwrap().min(250), 250);
+
+        // Auto-extend: 4x the duration, so the 250 funded tokens vest by the original end
+        assert_eq!(extended.extend_for_underfunding(250, 1_500).unwrap(), Some(5_000));
+        assert_eq!(extended.get_transferable_amount(1_500).unwrap(), 125);
+        assert_eq!(extended.calculate_unlocked_amount(2_000).unwrap(), 250);
+
+        // Once on the slower curve the vault covers every release, no further extension
+        extended.amount_transferred = 125;
+        assert_eq!(extended.extend_for_underfunding(125, 1_800).unwrap(), None);
+        assert_eq!(extended.vesting_end_timestamp, 5_000);
+
+        // Fully funded, vesting ended, nothing funded or finalized: no change
+        let mut schedule = sample_schedule(1_000, 0);
+        assert_eq!(schedule.extend_for_underfunding(1_000, 1_500).unwrap(), None);
+        assert_eq!(schedule.extend_for_underfunding(250, 2_000).unwrap(), None);
+        assert_eq!(schedule.extend_for_underfunding(0, 1_500).unwrap(), None);
+        schedule.immutable = true;
+        assert_eq!(schedule.extend_for_underfunding(250, 1_500).unwrap(), None);
+        assert_eq!(schedule.vesting_end_timestamp, 2_000);
+
+        // Accrued releases still sit in the vault: they count as funded but not as available
+        let mut schedule = sample_schedule(1_000, 0);
+        schedule.pending_release = 200;
+        assert_eq!(schedule.extend_for_underfunding(500, 1_500).unwrap(), None);
+        assert_eq!(schedule.extend_for_underfunding(400, 1_500).unwrap(), Some(3_500));
+    }
+
+    #[test]
+    fn test_sweepable_dust_over_many_cranks() {
+        // 7 units over 1_000 seconds, vault holds 2 stray units beyond the allocation
+        let mut schedule = sample_schedule(7, 0);
+        let mut vault_amount = 9u64;
+
+        for now in (1_000..2_000).step_by(13) {
+            let released = schedule.get_transferable_amount(now).unwrap();
+            schedule.amount_transferred += released;
+            vault_amount -= released;
+
+            // Truncation is carried into later releases, so nothing is sweepable mid-vesting
+            assert_eq!(schedule.sweepable_dust(vault_amount), 0);
+        }
+
+        let released = schedule.get_transferable_amount(2_000).unwrap();
+        schedule.amount_transferred += released;
+        vault_amount -= released;
+
+        // Every allocated unit reached the recipient, only the stray units remain
+        assert_eq!(schedule.amount_transferred, 7);
+        assert_eq!(schedule.sweepable_dust(vault_amount), 2);
+
+        schedule.pending_release = 1;
+        assert_eq!(schedule.sweepable_dust(vault_amount), 0);
+    }
+
+    #[test]
+    fn test_initial_unlock() {
+        // Cliff and vesting start at 1_000, vesting end 2_000
+        let unlocked_at = |initial_unlock_bps: u16, now: i64| {
+            let mut schedule = sample_schedule(1_000, 0);
+            schedule.initial_unlock_bps = initial_unlock_bps;
+            schedule.calculate_unlocked_amount(now).unwrap()
+        };
+
+        // 0% behaves exactly like plain linear vesting
+        assert_eq!(unlocked_at(0, 999), 0);
+        assert_eq!(unlocked_at(0, 1_000), 0);
+        assert_eq!(unlocked_at(0, 1_500), 500);
+
+        // 50%: half at the cliff, the other half vests linearly
+        assert_eq!(unlocked_at(5_000, 999), 0);
+        assert_eq!(unlocked_at(5_000, 1_000), 500);
+        assert_eq!(unlocked_at(5_000, 1_500), 750);
+        assert_eq!(unlocked_at(5_000, 2_000), 1_000);
+
+        // 100%: everything at the cliff
+        assert_eq!(unlocked_at(10_000, 999), 0);
+        assert_eq!(unlocked_at(10_000, 1_000), 1_000);
+        assert_eq!(unlocked_at(10_000, 1_500), 1_000);
+    }
+
+    #[test]
+    fn test_initial_unlock_before_vesting_start() {
+        // Initial unlock at the cliff, linear part only from vesting start
+        let mut schedule = sample_schedule(1_000, 0);
+        schedule.cliff_timestamp = 800;
+        schedule.initial_unlock_bps = 2_500;
+
+        assert_eq!(schedule.calculate_unlocked_amount(799).unwrap(), 0);
+        assert_eq!(schedule.calculate_unlocked_amount(800).unwrap(), 250);
+        assert_eq!(schedule.calculate_unlocked_amount(999).unwrap(), 250);
+        assert_eq!(schedule.calculate_unlocked_amount(1_500).unwrap(), 625);
+
+        // The lump at the cliff pulls the weighted average earlier: (250 * 800 + 750 * 1_500) / 1_000
+        assert_eq!(schedule.weighted_avg_unlock_timestamp(700).unwrap(), 1_325);
+        // Once the lump has unlocked only the linear window counts
+        assert_eq!(schedule.weighted_avg_unlock_timestamp(900).unwrap(), 1_500);
+    }
+
+    #[test]
+    fn test_clawback_amount() {
+        // Vesting 1_000..2_000, vault fully funded
+        let mut schedule = sample_schedule(1_000, 0);
+
+        // Before the cliff everything is reclaimed
+        assert_eq!(schedule.clawback_amount(500, 1_000).unwrap(), 1_000);
+
+        // Mid-way the vested half stays with the recipient, released or not
+        assert_eq!(schedule.clawback_amount(1_500, 1_000).unwrap(), 500);
+        schedule.amount_transferred = 300;
+        assert_eq!(schedule.clawback_amount(1_500, 700).unwrap(), 500);
+        schedule.pending_release = 200;
+        assert_eq!(schedule.clawback_amount(1_500, 700).unwrap(), 500);
+
+        // An underfunded vault pays the recipient first and only the rest is reclaimed
+        assert_eq!(schedule.clawback_amount(1_500, 600).unwrap(), 400);
+        assert_eq!(schedule.clawback_amount(1_500, 100).unwrap(), 0);
+
+        // Surplus beyond the allocation is not part of the clawback
+        assert_eq!(schedule.clawback_amount(1_500, 5_000).unwrap(), 500);
+
+        // Fully vested: nothing to reclaim
+        assert_eq!(schedule.clawback_amount(2_000, 700).unwrap(), 0);
+    }
+
+    #[test]
+    fn test_revoke() {
+        // Vesting 1_000..2_000, 300 released, 100 accrued, revoked mid-way
+        let mut schedule = sample_schedule(1_000, 300);
+        schedule.pending_release = 100;
+        let owed = schedule.get_transferable_amount(1_500).unwrap() + schedule.pending_release;
+        assert_eq!(owed, 200);
+
+        schedule.revoke(owed).unwrap();
+        assert!(schedule.revoked);
+        assert_eq!(schedule.amount_transferred, 500);
+        assert_eq!(schedule.total_amount, 500);
+        assert_eq!(schedule.pending_release, 0);
+
+        // Nothing more is ever released and the schedule counts as complete
+        assert!(schedule.is_fully_processed());
+        assert_eq!(schedule.get_transferable_amount(5_000).unwrap(), 0);
+        let (reason, _) = schedule.crank_block_reason(5_000, 1_000, &schedule.recipient.clone()).unwrap();
+        assert_eq!(reason, CrankBlockReason::Revoked);
+
+        assert!(schedule.revoke(0).is_err());
+    }
+
+    #[test]
+    fn test_archive_then_purge() {
+        let mut schedule = sample_schedule(1_000, 1_000);
+        assert!(schedule.require_purgeable(0).is_err());
+
+        assert_eq!(schedule.archive(100, 50).unwrap(), 150);
+        assert!(schedule.closed);
+        assert!(schedule.archive(200, 50).is_err());
+
+        assert!(schedule.require_purgeable(149).is_err());
+        assert!(schedule.require_purgeable(150).is_ok());
+    }
+
+    #[test]
+    fn test_advance_checkpoints() {
+        let mut schedule = sample_schedule(1_000, 0);
+        assert!(schedule.advance_checkpoints(25).is_empty());
+
+        schedule.amount_transferred = 300;
+        assert_eq!(schedule.advance_checkpoints(25), vec![25]);
+        assert!(schedule.advance_checkpoints(25).is_empty());
+
+        // One release crossing two milestones reports both
+        schedule.amount_transferred = 760;
+        assert_eq!(schedule.advance_checkpoints(25), vec![50, 75]);
+
+        schedule.amount_transferred = 1_000;
+        assert_eq!(schedule.advance_checkpoints(25), vec![100]);
+        assert!(schedule.advance_checkpoints(25).is_empty());
+
+        // Disabled
+        let mut disabled = sample_schedule(1_000, 1_000);
+        assert!(disabled.advance_checkpoints(0).is_empty());
+        assert_eq!(disabled.last_checkpoint_pct, 0);
+    }
+
+    #[test]
+    fn test_stepped_curve_unlocks_whole_periods() {
+        // Vesting 1_000..2_000 in 4 periods of 250 seconds
+        let mut schedule = sample_schedule(1_000, 0);
+        schedule.curve = VestingCurve::Stepped { num_periods: 4 };
+
+        for (timestamp, expected) in [(1_000, 0), (1_250, 250), (1_500, 500), (1_750, 750), (2_000, 1_000)] {
+            assert_eq!(schedule.calculate_unlocked_amount(timestamp).unwrap(), expected);
+        }
+        // Nothing unlocks fractionally between boundaries
+        for (timestamp, expected) in [(1_001, 0), (1_249, 0), (1_251, 250), (1_999, 750)] {
+            assert_eq!(schedule.calculate_unlocked_amount(timestamp).unwrap(), expected);
+        }
+
+        // An uneven split leaves the dust to the final step
+        schedule.total_amount = 1_001;
+        assert_eq!(schedule.calculate_unlocked_amount(1_750).unwrap(), 750);
+        assert_eq!(schedule.calculate_unlocked_amount(2_000).unwrap(), 1_001);
+
+        assert_eq!(schedule.min_release_interval().unwrap(), 250);
+        assert!(!schedule.is_release_rate_too_low(1_100).unwrap());
+    }
+
+    #[test]
+    fn test_unlocked_between() {
+        // Cliff and start at 1_000, end at 2_000, 10% unlocked at the cliff
+        let mut schedule = sample_schedule(1_000, 0);
+        schedule.initial_unlock_bps = 1_000;
+
+        // Entirely before the cliff, then spanning it (initial unlock plus linear part)
+        assert_eq!(schedule.unlocked_between(0, 999).unwrap(), 0);
+        assert_eq!(schedule.unlocked_between(500, 1_500).unwrap(), 550);
+
+        // Within the linear period, and spanning the end
+        assert_eq!(schedule.unlocked_between(1_200, 1_400).unwrap(), 180);
+        assert_eq!(schedule.unlocked_between(1_800, 5_000).unwrap(), 180);
+
+        // Consecutive periods add up to the whole allocation
+        let periods = [0, 1_000, 1_250, 1_500, 1_750, 2_000, 3_000];
+        let total: u64 = periods
+            .windows(2)
+            .map(|period| schedule.unlocked_between(period[0], period[1]).unwrap())
+            .sum();
+        assert_eq!(total, 1_000);
+
+        assert_eq!(schedule.unlocked_between(1_500, 1_500).unwrap(), 0);
+        assert!(schedule.unlocked_between(1_500, 1_499).is_err());
+    }
+
+    #[test]
+    fn test_non_transferable_checks() {
+        let mut schedule = sample_schedule(1_000, 0);
+        let elsewhere = Pubkey::new_unique();
+
+        // Unrestricted schedules accept any destination and delegated accounts
+        assert!(schedule.require_transferable_to(&elsewhere).is_ok());
+        assert!(schedule.require_undelegated(true).is_ok());
+
+        schedule.non_transferable = true;
+        assert!(schedule.require_transferable_to(&schedule.recipient_token_account.clone()).is_ok());
+        assert!(schedule.require_transferable_to(&elsewhere).is_err());
+        assert!(schedule.require_undelegated(false).is_ok());
+        assert!(schedule.require_undelegated(true).is_err());
+    }
+
+    #[test]
+    fn test_record_release_tracks_transfer_fees() {
+        let mut schedule = sample_schedule(1_000, 0);
+
+        // 1% fee on each release: the recipient gets less, the vault is debited in full
+        schedule.record_release(600, 594).unwrap();
+        schedule.record_release(400, 396).unwrap();
+        assert_eq!(schedule.amount_transferred, 990);
+        assert_eq!(schedule.transfer_fees_paid, 10);
+        assert_eq!(schedule.amount_debited(), 1_000);
+        assert_eq!(schedule.outstanding_amount(), 0);
+        assert!(schedule.is_fully_processed());
+        assert_eq!(schedule.get_transferable_amount(2_000).unwrap(), 0);
+
+        assert!(schedule.record_release(10, 11).is_err());
+    }
+
+    #[test]
+    fn test_settle_pending_release_tracks_transfer_fees() {
+        let mut schedule = sample_schedule(1_000, 0);
+
+        schedule.accrue_release(2_000).unwrap();
+        schedule.settle_pending_release(1_000, 990).unwrap();
+        assert_eq!(schedule.pending_release, 0);
+        assert_eq!(schedule.amount_transferred, 990);
+        assert_eq!(schedule.transfer_fees_paid, 10);
+        assert!(schedule.is_fully_processed());
+        assert_eq!(schedule.get_transferable_amount(2_000).unwrap(), 0);
+    }
+
+    #[test]
+    fn test_merge_from() {
+        let mut target = sample_schedule(1_000, 300);
+        let mut source = sample_schedule(500, 100);
+        source.schedule_id = 1;
+        source.recipient = target.recipient;
+        source.recipient_token_account = target.recipient_token_account;
+        source.mint = target.mint;
+        source.funding_mint = target.funding_mint;
+        source.pending_release = 50;
+
+        // Different timing is refused
+        let mut later = source.clone();
+        later.vesting_end_timestamp += 1;
+        assert!(target.clone().merge_from(&later, 1_500).is_err());
+
+        // A schedule cannot absorb itself
+        assert!(target.clone().merge_from(&target.clone(), 1_500).is_err());
+
+        let unlocked_before = target.calculate_unlocked_amount(1_500).unwrap()
+            + source.calculate_unlocked_amount(1_500).unwrap();
+        target.merge_from(&source, 1_500).unwrap();
+        assert_eq!(target.total_amount, 1_500);
+        assert_eq!(target.amount_transferred, 400);
+        assert_eq!(target.pending_release, 50);
+        assert_eq!(target.calculate_unlocked_amount(1_500).unwrap(), unlocked_before);
+    }
+
+    #[test]
+    fn test_recipient_token_account_change_timelock() {
+        let mut schedule = sample_schedule(1_000, 0);
+        let original = schedule.recipient_token_account;
+        let replacement = Pubkey::new_unique();
+
+        assert!(schedule.confirm_recipient_token_account(replacement, 0).is_err());
+        assert!(schedule.propose_recipient_token_account(original, 0).is_err());
+
+        let expiry = schedule.propose_recipient_token_account(replacement, 1_000).unwrap();
+        assert_eq!(expiry, 1_000 + RECIPIENT_CHANGE_TIMELOCK);
+        assert!(schedule.confirm_recipient_token_account(replacement, expiry - 1).is_err());
+        assert!(schedule.confirm_recipient_token_account(Pubkey::new_unique(), expiry).is_err());
+
+        assert_eq!(schedule.confirm_recipient_token_account(replacement, expiry).unwrap(), original);
+        assert_eq!(schedule.recipient_token_account, replacement);
+        assert_eq!(schedule.pending_recipient_token_account, None);
+
+        // Non-transferable schedules keep their account
+        schedule.non_transferable = true;
+        assert!(schedule.propose_recipient_token_account(original, expiry).is_err());
+    }
+
+    #[test]
+    fn test_transferable_amount_capped() {
+        // 1000 over 1000..2000, half unlocked at t=1500
+        let mut schedule = sample_schedule(1_000, 0);
+
+        // Under-funded vault caps the amount
+        assert_eq!(schedule.get_transferable_amount_capped(1_500, 10_000).unwrap(), 500);
+        assert_eq!(schedule.get_transferable_amount_capped(1_500, 200).unwrap(), 200);
+        assert_eq!(schedule.get_transferable_amount_capped(1_500, 0).unwrap(), 0);
+
+        // Already transferred past what is unlocked: nothing to move
+        schedule.amount_transferred = 600;
+        assert_eq!(schedule.get_transferable_amount_capped(1_500, 10_000).unwrap(), 0);
+    }
+
+    #[test]
+    fn test_validate_label() {
+        let mut label = [0u8; 32];
+        assert!(VestingSchedule::validate_label(&label).is_ok());
+
+        label[..17].copy_from_slice("Seed investor #12".as_bytes());
+        assert!(VestingSchedule::validate_label(&label).is_ok());
+
+        // Exactly 32 bytes, no padding
+        let full = "Strategic partner allocation #01";
+        assert_eq!(full.len(), 32);
+        assert!(VestingSchedule::validate_label(full.as_bytes().try_into().unwrap()).is_ok());
+
+        // Data after the padding starts
+        label[20] = b'x';
+        assert!(VestingSchedule::validate_label(&label).is_err());
+
+        // Truncated multi-byte character
+        let mut invalid = [0u8; 32];
+        invalid[0] = "é".as_bytes()[0];
+        assert!(VestingSchedule::validate_label(&invalid).is_err());
+    }
+
+    #[test]
+    fn test_top_up() {
+        // 1000 over 1000..2000
+        let mut schedule = sample_schedule(1_000, 0);
+        assert_eq!(schedule.calculate_unlocked_amount(1_500).unwrap(), 500);
+
+        // Doubling the total doubles what has unlocked at the same moment
+        assert_eq!(schedule.top_up(1_000, 1_500).unwrap(), 2_000);
+        assert_eq!(schedule.calculate_unlocked_amount(1_500).unwrap(), 1_000);
+        assert_eq!(schedule.calculate_unlocked_amount(2_000).unwrap(), 2_000);
+
+        assert!(schedule.top_up(0, 1_500).is_err());
+        assert!(schedule.top_up(1_000, 2_000).is_err());
+
+        schedule.revoked = true;
+        assert!(schedule.top_up(1_000, 1_500).is_err());
+
+        // A finalized schedule keeps its allocation
+        let mut finalized = sample_schedule(1_000, 0);
+        finalized.immutable = true;
+        assert!(finalized.top_up(1_000, 1_500).is_err());
+        assert_eq!(finalized.total_amount, 1_000);
+    }
+
+    #[test]
+    fn test_crank_interval() {
+        // Vesting 1000..2000, last cranked at 1200
+        let mut schedule = sample_schedule(1_000, 0);
+        schedule.last_crank_timestamp = 1_200;
+
+        assert!(schedule.crank_interval_elapsed(0, 1_200));
+        assert!(!schedule.crank_interval_elapsed(100, 1_299));
+        assert!(schedule.crank_interval_elapsed(100, 1_300));
+
+        // The final release after the vesting end bypasses the limit
+        schedule.last_crank_timestamp = 1_990;
+        assert!(!schedule.crank_interval_elapsed(100, 1_999));
+        assert!(schedule.crank_interval_elapsed(100, 2_000));
+    }
+
+    #[test]
+    fn test_short_vesting_windows() {
+        // 1-second window: nothing linear before the end, everything at the end
+        let mut schedule = sample_schedule(u64::MAX, 0);
+        schedule.vesting_start_timestamp = 1_000;
+        schedule.vesting_end_timestamp = 1_001;
+        assert_eq!(schedule.calculate_unlocked_amount(1_000).unwrap(), 0);
+        assert_eq!(schedule.calculate_unlocked_amount(1_001).unwrap(), u64::MAX);
+
+        // 2-second window with the largest amount: the u128 product neither overflows nor truncates to 0
+        schedule.vesting_end_timestamp = 1_002;
+        assert_eq!(schedule.calculate_unlocked_amount(1_001).unwrap(), u64::MAX / 2);
+        schedule.round_up = true;
+        assert_eq!(schedule.calculate_unlocked_amount(1_001).unwrap(), u64::MAX / 2 + 1);
+
+        // Fewer tokens than seconds: per-second cranks truncate, the final one delivers the rest
+        let mut schedule = sample_schedule(7, 0);
+        let mut delivered = 0;
+        for timestamp in 1_000..=2_000 {
+            let amount = schedule.get_transferable_amount(timestamp).unwrap();
+            schedule.record_release(amount, amount).unwrap();
+            delivered += amount;
+        }
+        assert_eq!(delivered, 7);
+        assert!(schedule.is_fully_processed());
+    }
+
+    #[test]
+    fn test_validate_creation_window() {
+        let now = 1_700_000_000;
+        assert!(VestingSchedule::validate_creation_window(now - 1, now + 1_000, now).is_ok());
+        assert!(VestingSchedule::validate_creation_window(now - MAX_BACKDATE, now + MAX_FUTURE, now).is_ok());
+
+        let error = VestingSchedule::validate_creation_window(now - MAX_BACKDATE - 1, now + 1_000, now).unwrap_err();
+        assert_eq!(error, VestingError::CliffTooFarInPast.into());
+        let error = VestingSchedule::validate_creation_window(now, now + MAX_FUTURE + 1, now).unwrap_err();
+        assert_eq!(error, VestingError::VestingEndTooFarInFuture.into());
+    }
+
+    #[test]
+    fn test_second_cliff() {
+        // Cliff and vesting start at 1_000, vesting end 2_000, 25% at the cliff and 25% more at 1_500
+        let mut schedule = sample_schedule(1_000, 0);
+        schedule.initial_unlock_bps = 2_500;
+        schedule.second_cliff_timestamp = 1_500;
+        schedule.second_cliff_bps = 2_500;
+
+        // First cliff boundary
+        assert_eq!(schedule.calculate_unlocked_amount(999).unwrap(), 0);
+        assert_eq!(schedule.calculate_unlocked_amount(1_000).unwrap(), 250);
+
+        // Second cliff boundary: 250 + 375 linear, then the 250 chunk on top
+        assert_eq!(schedule.calculate_unlocked_amount(1_499).unwrap(), 250 + 374);
+        assert_eq!(schedule.calculate_unlocked_amount(1_500).unwrap(), 250 + 375 + 250);
+
+        // Clamped at the total instead of overshooting before the end
+        assert_eq!(schedule.calculate_unlocked_amount(1_900).unwrap(), 1_000);
+        assert_eq!(schedule.calculate_unlocked_amount(2_000).unwrap(), 1_000);
+
+        // Zero bps disables the second cliff whatever its timestamp
+        schedule.second_cliff_bps = 0;
+        assert_eq!(schedule.calculate_unlocked_amount(1_500).unwrap(), 250 + 375);
+
+        // The graded cliff is part of the hashed terms only when enabled
+        let plain_terms = schedule.canonical_terms();
+        schedule.second_cliff_bps = 2_500;
+        assert_eq!(schedule.canonical_terms().len(), plain_terms.len() + 10);
+    }
+
+    #[test]
+    fn test_close_grace_period() {
+        let mut schedule = sample_schedule(1_000, 0);
+
+        // Partial releases do not start the window
+        schedule.amount_transferred = 400;
+        schedule.record_completion(1_500);
+        assert_eq!(schedule.completed_at, 0);
+
+        // The completing release does, and later releases keep the first timestamp
+        schedule.amount_transferred = 1_000;
+        schedule.record_completion(2_000);
+        schedule.record_completion(2_100);
+        assert_eq!(schedule.completed_at, 2_000);
+
+        assert!(!schedule.close_grace_elapsed(3_600, 2_000));
+        assert!(!schedule.close_grace_elapsed(3_600, 5_599));
+        assert!(schedule.close_grace_elapsed(3_600, 5_600));
+        assert!(schedule.close_grace_elapsed(0, 2_000));
+
+        // Completed without a release, nothing to wait for
+        schedule.completed_at = 0;
+        assert!(schedule.close_grace_elapsed(3_600, 2_000));
+    }
+}
@@ -283,6 +283,18 @@ async function main() {
         vestingStartTimestamp: new anchor.BN(vestingStartTimestamp),
         vestingEndTimestamp: new anchor.BN(vestingEndTimestamp),
         sourceCategory: scheduleConfig.sourceCategory,
+        roundUp: false,
+        grantGroupId: new anchor.BN(0),
+        minClaimInterval: new anchor.BN(0),
+        allowSelfReturn: false,
+        allowPastEnd: false,
+        termsHash: null,
+        initialUnlockBps: 0,
+        vestingCurve: { linear: {} },
+        nonTransferable: false,
+        label: Array(32).fill(0),
+        secondCliffTimestamp: new anchor.BN(0),
+        secondCliffBps: 0,
       };
 
       // Create vesting schedule
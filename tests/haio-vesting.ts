@@ -3,11 +3,14 @@ import { Program } from "@coral-xyz/anchor";
 import { HaioVesting } from "../target/types/haio_vesting";
 import {
   Connection,
+  Ed25519Program,
   Keypair,
   LAMPORTS_PER_SOL,
   PublicKey,
   SystemProgram,
   SYSVAR_RENT_PUBKEY,
+  Transaction,
+  sendAndConfirmTransaction,
 } from "@solana/web3.js";
 import {
   createMint,
@@ -15,12 +18,23 @@ import {
   mintTo,
   getAccount,
   TOKEN_PROGRAM_ID,
+  TOKEN_2022_PROGRAM_ID,
   getOrCreateAssociatedTokenAccount,
   setAuthority,
   AuthorityType,
   Account as SplAccount,
+  NATIVE_MINT,
+  createSyncNativeInstruction,
+  approve,
+  revoke,
+  ExtensionType,
+  getMintLen,
+  createInitializeMintInstruction,
+  createInitializeTransferFeeConfigInstruction,
+  harvestWithheldTokensToMint,
 } from "@solana/spl-token";
 import { expect } from "chai";
+import { createHash } from "crypto";
 
 describe("haio-vesting", () => {
   const provider = anchor.AnchorProvider.env();
@@ -92,6 +106,100 @@ describe("haio-vesting", () => {
     );
   });
 
+  // Derive the schedule and vault PDAs for a schedule ID
+  const findSchedulePdas = (scheduleId: anchor.BN): [PublicKey, PublicKey] => [
+    PublicKey.findProgramAddressSync(
+      [Buffer.from("vesting_schedule"), scheduleId.toArrayLike(Buffer, "le", 8)],
+      program.programId
+    )[0],
+    PublicKey.findProgramAddressSync(
+      [Buffer.from("vesting_vault"), scheduleId.toArrayLike(Buffer, "le", 8)],
+      program.programId
+    )[0],
+  ];
+
+  // Derive the primary schedule index PDA for a recipient and mint
+  const findPrimaryScheduleIndex = (scheduleRecipient: PublicKey, scheduleMint: PublicKey) =>
+    PublicKey.findProgramAddressSync(
+      [Buffer.from("primary"), scheduleRecipient.toBuffer(), scheduleMint.toBuffer()],
+      program.programId
+    )[0];
+
+  // Create a schedule at the next sequential ID for the shared recipient
+  const createNextSchedule = async (
+    amount: anchor.BN,
+    cliff: number,
+    vestingStart: number,
+    vestingEnd: number,
+    paramOverrides: Record<string, any> = {},
+    accountOverrides: Record<string, PublicKey> = {}
+  ) => {
+    const configAccount = await program.account.programConfig.fetch(programConfigPda);
+    const scheduleId = new anchor.BN(configAccount.totalSchedules.toString());
+    const [vestingSchedulePda, vestingVaultPda] = findSchedulePdas(scheduleId);
+
+    const params = {
+      recipient: recipient.publicKey,
+      totalAmount: amount,
+      cliffTimestamp: new anchor.BN(cliff),
+      vestingStartTimestamp: new anchor.BN(vestingStart),
+      vestingEndTimestamp: new anchor.BN(vestingEnd),
+      sourceCategory: { public: {} },
+      roundUp: false,
+      grantGroupId: new anchor.BN(0),
+      minClaimInterval: new anchor.BN(0),
+      allowSelfReturn: false,
+      // Many tests need schedules that have already fully vested
+      allowPastEnd: true,
+      termsHash: null,
+      initialUnlockBps: 0,
+      vestingCurve: { linear: {} },
+      nonTransferable: false,
+      label: Array(32).fill(0),
+      secondCliffTimestamp: new anchor.BN(0),
+      secondCliffBps: 0,
+      ...paramOverrides,
+    };
+
+    await program.methods
+      .createVestingSchedule(scheduleId, params)
+      .accounts({
+        admin: admin.publicKey,
+        programConfig: programConfigPda,
+        vestingSchedule: vestingSchedulePda,
+        mint: mint,
+        depositorTokenAccount: adminTokenAccount,
+        recipientTokenAccount: recipientTokenAccount,
+        vestingVault: vestingVaultPda,
+        primaryScheduleIndex: findPrimaryScheduleIndex(
+          params.recipient, accountOverrides.mint ?? mint
+        ),
+        feeDestination: null,
+        systemProgram: SystemProgram.programId,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        rent: SYSVAR_RENT_PUBKEY,
+        ...accountOverrides,
+      })
+      .signers([admin])
+      .rpc();
+
+    return { scheduleId, vestingSchedulePda, vestingVaultPda };
+  };
+
+  // Crank a schedule to the shared recipient token account
+  const crankSchedule = async (vestingSchedulePda: PublicKey, vestingVaultPda: PublicKey) =>
+    program.methods
+      .crankVestingSchedule()
+      .accounts({
+        programConfig: programConfigPda,
+        vestingSchedule: vestingSchedulePda,
+        vestingVault: vestingVaultPda,
+        recipientTokenAccount: recipientTokenAccount,
+        mint: mint,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .rpc();
+
   it("Initialize program", async () => {
     await program.methods
       .initialize()
@@ -131,6 +239,18 @@ describe("haio-vesting", () => {
       vestingStartTimestamp: new anchor.BN(vestingStart),
       vestingEndTimestamp: new anchor.BN(vestingEnd),
       sourceCategory: { public: {} },
+      roundUp: false,
+      grantGroupId: new anchor.BN(0),
+      minClaimInterval: new anchor.BN(0),
+      allowSelfReturn: false,
+      allowPastEnd: false,
+      termsHash: null,
+      initialUnlockBps: 0,
+      vestingCurve: { linear: {} },
+      nonTransferable: false,
+      label: Array(32).fill(0),
+      secondCliffTimestamp: new anchor.BN(0),
+      secondCliffBps: 0,
     };
 
     await program.methods
@@ -329,6 +449,18 @@ describe("haio-vesting", () => {
       vestingStartTimestamp: new anchor.BN(vestingStart),
       vestingEndTimestamp: new anchor.BN(vestingEnd),
       sourceCategory: { public: {} },
+      roundUp: false,
+      grantGroupId: new anchor.BN(0),
+      minClaimInterval: new anchor.BN(0),
+      allowSelfReturn: false,
+      allowPastEnd: false,
+      termsHash: null,
+      initialUnlockBps: 0,
+      vestingCurve: { linear: {} },
+      nonTransferable: false,
+      label: Array(32).fill(0),
+      secondCliffTimestamp: new anchor.BN(0),
+      secondCliffBps: 0,
     };
 
     try {
@@ -378,6 +510,18 @@ describe("haio-vesting", () => {
       vestingStartTimestamp: new anchor.BN(vestingStart),
       vestingEndTimestamp: new anchor.BN(vestingEnd),
       sourceCategory: { public: {} },
+      roundUp: false,
+      grantGroupId: new anchor.BN(0),
+      minClaimInterval: new anchor.BN(0),
+      allowSelfReturn: false,
+      allowPastEnd: false,
+      termsHash: null,
+      initialUnlockBps: 0,
+      vestingCurve: { linear: {} },
+      nonTransferable: false,
+      label: Array(32).fill(0),
+      secondCliffTimestamp: new anchor.BN(0),
+      secondCliffBps: 0,
     };
 
     try {
@@ -432,6 +576,18 @@ describe("haio-vesting", () => {
       vestingStartTimestamp: new anchor.BN(vestingStart),
       vestingEndTimestamp: new anchor.BN(vestingEnd),
       sourceCategory: { public: {} },
+      roundUp: false,
+      grantGroupId: new anchor.BN(0),
+      minClaimInterval: new anchor.BN(0),
+      allowSelfReturn: false,
+      allowPastEnd: false,
+      termsHash: null,
+      initialUnlockBps: 0,
+      vestingCurve: { linear: {} },
+      nonTransferable: false,
+      label: Array(32).fill(0),
+      secondCliffTimestamp: new anchor.BN(0),
+      secondCliffBps: 0,
     };
 
     try {
@@ -454,7 +610,7 @@ describe("haio-vesting", () => {
       
       expect.fail("Should have rejected unauthorized user");
     } catch (error: any) {
-      expect(error.toString()).to.include("Unauthorized");
+      expect(error.toString()).to.include("MissingCapability");
     }
   });
 
@@ -503,6 +659,18 @@ describe("haio-vesting", () => {
       vestingStartTimestamp: new anchor.BN(vestingStart),
       vestingEndTimestamp: new anchor.BN(vestingEnd),
       sourceCategory: { public: {} },
+      roundUp: false,
+      grantGroupId: new anchor.BN(0),
+      minClaimInterval: new anchor.BN(0),
+      allowSelfReturn: false,
+      allowPastEnd: false,
+      termsHash: null,
+      initialUnlockBps: 0,
+      vestingCurve: { linear: {} },
+      nonTransferable: false,
+      label: Array(32).fill(0),
+      secondCliffTimestamp: new anchor.BN(0),
+      secondCliffBps: 0,
     };
 
     try {
@@ -578,6 +746,18 @@ describe("haio-vesting", () => {
       vestingStartTimestamp: new anchor.BN(vestingStart),
       vestingEndTimestamp: new anchor.BN(vestingEnd),
       sourceCategory: { public: {} },
+      roundUp: false,
+      grantGroupId: new anchor.BN(0),
+      minClaimInterval: new anchor.BN(0),
+      allowSelfReturn: false,
+      allowPastEnd: false,
+      termsHash: null,
+      initialUnlockBps: 0,
+      vestingCurve: { linear: {} },
+      nonTransferable: false,
+      label: Array(32).fill(0),
+      secondCliffTimestamp: new anchor.BN(0),
+      secondCliffBps: 0,
     };
 
     try {
@@ -628,6 +808,18 @@ describe("haio-vesting", () => {
       vestingStartTimestamp: new anchor.BN(vestingStart),
       vestingEndTimestamp: new anchor.BN(vestingEnd),
       sourceCategory: { public: {} },
+      roundUp: false,
+      grantGroupId: new anchor.BN(0),
+      minClaimInterval: new anchor.BN(0),
+      allowSelfReturn: false,
+      allowPastEnd: false,
+      termsHash: null,
+      initialUnlockBps: 0,
+      vestingCurve: { linear: {} },
+      nonTransferable: false,
+      label: Array(32).fill(0),
+      secondCliffTimestamp: new anchor.BN(0),
+      secondCliffBps: 0,
     };
 
     // Create the schedule
@@ -767,6 +959,18 @@ describe("haio-vesting", () => {
       vestingStartTimestamp: new anchor.BN(vestingStart),
       vestingEndTimestamp: new anchor.BN(vestingEnd),
       sourceCategory: { public: {} },
+      roundUp: false,
+      grantGroupId: new anchor.BN(0),
+      minClaimInterval: new anchor.BN(0),
+      allowSelfReturn: false,
+      allowPastEnd: false,
+      termsHash: null,
+      initialUnlockBps: 0,
+      vestingCurve: { linear: {} },
+      nonTransferable: false,
+      label: Array(32).fill(0),
+      secondCliffTimestamp: new anchor.BN(0),
+      secondCliffBps: 0,
     };
 
     // Create schedule with small amount
@@ -842,6 +1046,18 @@ describe("haio-vesting", () => {
       vestingStartTimestamp: new anchor.BN(vestingStart),
       vestingEndTimestamp: new anchor.BN(vestingEnd),
       sourceCategory: { public: {} },
+      roundUp: false,
+      grantGroupId: new anchor.BN(0),
+      minClaimInterval: new anchor.BN(0),
+      allowSelfReturn: false,
+      allowPastEnd: false,
+      termsHash: null,
+      initialUnlockBps: 0,
+      vestingCurve: { linear: {} },
+      nonTransferable: false,
+      label: Array(32).fill(0),
+      secondCliffTimestamp: new anchor.BN(0),
+      secondCliffBps: 0,
     };
 
     // Create schedule
@@ -926,6 +1142,18 @@ describe("haio-vesting", () => {
       vestingStartTimestamp: new anchor.BN(vestingStart),
       vestingEndTimestamp: new anchor.BN(vestingEnd),
       sourceCategory: { public: {} },
+      roundUp: false,
+      grantGroupId: new anchor.BN(0),
+      minClaimInterval: new anchor.BN(0),
+      allowSelfReturn: false,
+      allowPastEnd: false,
+      termsHash: null,
+      initialUnlockBps: 0,
+      vestingCurve: { linear: {} },
+      nonTransferable: false,
+      label: Array(32).fill(0),
+      secondCliffTimestamp: new anchor.BN(0),
+      secondCliffBps: 0,
     };
 
     // Create schedule
@@ -1015,6 +1243,18 @@ describe("haio-vesting", () => {
       vestingStartTimestamp: new anchor.BN(vestingStart),
       vestingEndTimestamp: new anchor.BN(vestingEnd),
       sourceCategory: { public: {} },
+      roundUp: false,
+      grantGroupId: new anchor.BN(0),
+      minClaimInterval: new anchor.BN(0),
+      allowSelfReturn: false,
+      allowPastEnd: false,
+      termsHash: null,
+      initialUnlockBps: 0,
+      vestingCurve: { linear: {} },
+      nonTransferable: false,
+      label: Array(32).fill(0),
+      secondCliffTimestamp: new anchor.BN(0),
+      secondCliffBps: 0,
     };
 
     try {
@@ -1076,6 +1316,18 @@ describe("haio-vesting", () => {
       vestingStartTimestamp: new anchor.BN(vestingStart),
       vestingEndTimestamp: new anchor.BN(vestingEnd),
       sourceCategory: { public: {} },
+      roundUp: false,
+      grantGroupId: new anchor.BN(0),
+      minClaimInterval: new anchor.BN(0),
+      allowSelfReturn: false,
+      allowPastEnd: false,
+      termsHash: null,
+      initialUnlockBps: 0,
+      vestingCurve: { linear: {} },
+      nonTransferable: false,
+      label: Array(32).fill(0),
+      secondCliffTimestamp: new anchor.BN(0),
+      secondCliffBps: 0,
     };
 
     // Create schedule
@@ -1119,4 +1371,4575 @@ describe("haio-vesting", () => {
     console.log("✅ Vault authority validation working correctly");
     console.log("Note: Full setAuthority attack simulation would require a malicious program");
   });
+
+  it("Should top up a batch of schedules and reject batches with a fully processed schedule", async () => {
+    const now = Math.floor(Date.now() / 1000);
+    const first = await createNextSchedule(new anchor.BN(1000), now + 60, now + 60, now + 3600);
+    const second = await createNextSchedule(new anchor.BN(2000), now + 60, now + 60, now + 3600);
+
+    // Fully vested in the past, cranked to completion
+    const completed = await createNextSchedule(new anchor.BN(500), now - 20, now - 20, now - 10);
+    await crankSchedule(completed.vestingSchedulePda, completed.vestingVaultPda);
+
+    const pairs = (entries: { vestingSchedulePda: PublicKey; vestingVaultPda: PublicKey }[]) =>
+      entries.flatMap((entry) => [
+        { pubkey: entry.vestingSchedulePda, isWritable: true, isSigner: false },
+        { pubkey: entry.vestingVaultPda, isWritable: true, isSigner: false },
+      ]);
+
+    const topUpAccounts = {
+      admin: admin.publicKey,
+      programConfig: programConfigPda,
+      mint: mint,
+      depositorTokenAccount: adminTokenAccount,
+      tokenProgram: TOKEN_PROGRAM_ID,
+    };
+
+    // Mixed batch including the completed schedule is rejected atomically
+    try {
+      await program.methods
+        .topUpBatch([new anchor.BN(100), new anchor.BN(200), new anchor.BN(300)])
+        .accounts(topUpAccounts)
+        .remainingAccounts(pairs([first, second, completed]))
+        .signers([admin])
+        .rpc();
+
+      expect.fail("Should have rejected batch containing a fully processed schedule");
+    } catch (error: any) {
+      expect(error.toString()).to.include("ScheduleFullyProcessed");
+    }
+
+    const firstAfterReject = await program.account.vestingSchedule.fetch(first.vestingSchedulePda);
+    expect(firstAfterReject.totalAmount.toString()).to.equal("1000");
+
+    // Valid batch updates every schedule and reports the new totals (Vec<u64>) via return data
+    const simulation = await program.methods
+      .topUpBatch([new anchor.BN(100), new anchor.BN(200)])
+      .accounts(topUpAccounts)
+      .remainingAccounts(pairs([first, second]))
+      .signers([admin])
+      .simulate();
+    const returnLog = (simulation.raw as string[]).find((log) => log.startsWith("Program return: "));
+    const returnData = Buffer.from(returnLog!.split(" ")[3], "base64");
+    expect(returnData.readUInt32LE(0)).to.equal(2);
+    expect(returnData.readBigUInt64LE(4).toString()).to.equal("1100");
+    expect(returnData.readBigUInt64LE(12).toString()).to.equal("2200");
+
+    await program.methods
+      .topUpBatch([new anchor.BN(100), new anchor.BN(200)])
+      .accounts(topUpAccounts)
+      .remainingAccounts(pairs([first, second]))
+      .signers([admin])
+      .rpc();
+
+    const firstSchedule = await program.account.vestingSchedule.fetch(first.vestingSchedulePda);
+    const secondSchedule = await program.account.vestingSchedule.fetch(second.vestingSchedulePda);
+    expect(firstSchedule.totalAmount.toString()).to.equal("1100");
+    expect(secondSchedule.totalAmount.toString()).to.equal("2200");
+
+    const firstVault = await getAccount(provider.connection, first.vestingVaultPda);
+    const secondVault = await getAccount(provider.connection, second.vestingVaultPda);
+    expect(firstVault.amount.toString()).to.equal("1100");
+    expect(secondVault.amount.toString()).to.equal("2200");
+  });
+
+  it("Should only close once the recipient received everything and the vault is empty", async () => {
+    const now = Math.floor(Date.now() / 1000);
+    const schedule = await createNextSchedule(new anchor.BN(700), now - 20, now - 20, now - 10);
+
+    const closeAccounts = {
+      beneficiary: admin.publicKey,
+      vestingSchedule: schedule.vestingSchedulePda,
+      vestingVault: schedule.vestingVaultPda,
+      tokenProgram: TOKEN_PROGRAM_ID,
+    };
+
+    // Nothing delivered to the recipient yet
+    try {
+      await program.methods.closeVestingSchedule().accounts(closeAccounts).signers([admin]).rpc();
+      expect.fail("Should not close before the recipient received the allocation");
+    } catch (error: any) {
+      expect(error.toString()).to.include("ScheduleNotFullyVested");
+    }
+
+    await crankSchedule(schedule.vestingSchedulePda, schedule.vestingVaultPda);
+    const scheduleAccount = await program.account.vestingSchedule.fetch(schedule.vestingSchedulePda);
+    expect(scheduleAccount.amountTransferred.toString()).to.equal("700");
+
+    // Stray tokens sent to the vault after completion block the close
+    await mintTo(provider.connection, admin, mint, schedule.vestingVaultPda, admin, BigInt(1));
+    try {
+      await program.methods.closeVestingSchedule().accounts(closeAccounts).signers([admin]).rpc();
+      expect.fail("Should not close while the vault still holds tokens");
+    } catch (error: any) {
+      expect(error.toString()).to.include("VaultNotEmpty");
+    }
+  });
+
+  it("Should bound keeper reward updates by MAX_KEEPER_REWARD_BPS", async () => {
+    const setKeeperReward = (bps: number) =>
+      program.methods
+        .setKeeperReward(bps)
+        .accounts({ authority: admin.publicKey, programConfig: programConfigPda })
+        .signers([admin])
+        .rpc();
+
+    // Exactly at the cap is accepted
+    await setKeeperReward(100);
+    let configAccount = await program.account.programConfig.fetch(programConfigPda);
+    expect(configAccount.keeperRewardBps).to.equal(100);
+
+    // One above the cap is rejected
+    try {
+      await setKeeperReward(101);
+      expect.fail("Should reject keeper reward above the cap");
+    } catch (error: any) {
+      expect(error.toString()).to.include("KeeperRewardTooHigh");
+    }
+
+    // Above 100% fails the shared bps validation
+    try {
+      await setKeeperReward(10_001);
+      expect.fail("Should reject invalid bps");
+    } catch (error: any) {
+      expect(error.toString()).to.include("InvalidBps");
+    }
+
+    await setKeeperReward(0);
+    configAccount = await program.account.programConfig.fetch(programConfigPda);
+    expect(configAccount.keeperRewardBps).to.equal(0);
+  });
+
+  it("Should report schedules whose vault is not owned by the schedule PDA", async () => {
+    const now = Math.floor(Date.now() / 1000);
+    const healthy = await createNextSchedule(new anchor.BN(100), now + 60, now + 60, now + 3600);
+    const compromised = await createNextSchedule(new anchor.BN(100), now + 60, now + 60, now + 3600);
+
+    // Pair the second schedule with an admin-owned token account to simulate a hijacked vault
+    const inconsistent = await program.methods
+      .checkVaultOwnership(0)
+      .accounts({ programConfig: programConfigPda })
+      .remainingAccounts([
+        { pubkey: healthy.vestingSchedulePda, isWritable: false, isSigner: false },
+        { pubkey: healthy.vestingVaultPda, isWritable: false, isSigner: false },
+        { pubkey: compromised.vestingSchedulePda, isWritable: false, isSigner: false },
+        { pubkey: adminTokenAccount, isWritable: false, isSigner: false },
+      ])
+      .view();
+
+    expect(inconsistent.scheduleIds.map((id: anchor.BN) => id.toString())).to.deep.equal([
+      compromised.scheduleId.toString(),
+    ]);
+  });
+
+  it("Should only repair the schedule counter when it lags behind existing schedules", async () => {
+    const configAccount = await program.account.programConfig.fetch(programConfigPda);
+    const total = new anchor.BN(configAccount.totalSchedules.toString());
+    const recount = (highestId: anchor.BN) =>
+      program.methods
+        .recountSchedules()
+        .accounts({
+          admin: admin.publicKey,
+          programConfig: programConfigPda,
+          highestSchedule: findSchedulePdas(highestId)[0],
+          nextSchedule: findSchedulePdas(highestId.addn(1))[0],
+        })
+        .signers([admin])
+        .rpc();
+
+    // The counter already points past the highest schedule
+    try {
+      await recount(total.subn(1));
+      expect.fail("Should reject recount when the counter is consistent");
+    } catch (error: any) {
+      expect(error.toString()).to.include("ScheduleCountConsistent");
+    }
+
+    // A lower schedule whose successor exists cannot be used to rewind the counter
+    try {
+      await recount(total.subn(3));
+      expect.fail("Should reject recount when the next slot is occupied");
+    } catch (error: any) {
+      expect(error.toString()).to.include("NextScheduleSlotOccupied");
+    }
+
+    const afterAccount = await program.account.programConfig.fetch(programConfigPda);
+    expect(afterAccount.totalSchedules.toString()).to.equal(total.toString());
+  });
+
+  it("Should aggregate grant group totals per mint and enforce group integrity", async () => {
+    const now = Math.floor(Date.now() / 1000);
+    const groupId = new anchor.BN(77);
+
+    // Second mint for the same logical grant
+    const lpMint = await createMint(provider.connection, admin, admin.publicKey, null, 6);
+    const adminLpAccount = (await getOrCreateAssociatedTokenAccount(
+      provider.connection, admin, lpMint, admin.publicKey
+    )).address;
+    const recipientLpAccount = (await getOrCreateAssociatedTokenAccount(
+      provider.connection, admin, lpMint, recipient.publicKey
+    )).address;
+    await mintTo(provider.connection, admin, lpMint, adminLpAccount, admin, BigInt(10_000));
+
+    const governance1 = await createNextSchedule(
+      new anchor.BN(1000), now + 60, now + 60, now + 3600, { grantGroupId: groupId }
+    );
+    const governance2 = await createNextSchedule(
+      new anchor.BN(500), now + 60, now + 60, now + 3600, { grantGroupId: groupId }
+    );
+    const lp = await createNextSchedule(
+      new anchor.BN(300), now + 60, now + 60, now + 3600, { grantGroupId: groupId },
+      { mint: lpMint, depositorTokenAccount: adminLpAccount, recipientTokenAccount: recipientLpAccount }
+    );
+    const outsider = await createNextSchedule(new anchor.BN(100), now + 60, now + 60, now + 3600);
+
+    const asAccounts = (pdas: PublicKey[]) =>
+      pdas.map((pubkey) => ({ pubkey, isWritable: false, isSigner: false }));
+
+    const totals = await program.methods
+      .getGrantGroup(groupId, 0)
+      .accounts({ programConfig: programConfigPda })
+      .remainingAccounts(asAccounts([
+        governance1.vestingSchedulePda, governance2.vestingSchedulePda, lp.vestingSchedulePda,
+      ]))
+      .view();
+
+    expect(totals.truncated).to.equal(false);
+    expect(totals.mintTotals.length).to.equal(2);
+    expect(totals.mintTotals[0].mint.toString()).to.equal(mint.toString());
+    expect(totals.mintTotals[0].totalAmount.toString()).to.equal("1500");
+    expect(totals.mintTotals[1].mint.toString()).to.equal(lpMint.toString());
+    expect(totals.mintTotals[1].totalAmount.toString()).to.equal("300");
+
+    // A schedule outside the group is rejected
+    try {
+      await program.methods
+        .getGrantGroup(groupId, 0)
+        .accounts({ programConfig: programConfigPda })
+        .remainingAccounts(asAccounts([governance1.vestingSchedulePda, outsider.vestingSchedulePda]))
+        .view();
+      expect.fail("Should reject a schedule outside the grant group");
+    } catch (error: any) {
+      expect(error.toString()).to.include("GrantGroupMismatch");
+    }
+  });
+
+  it("Should exit the batch crank early when no schedule is ready", async () => {
+    const now = Math.floor(Date.now() / 1000);
+    const notReady1 = await createNextSchedule(new anchor.BN(100), now + 600, now + 600, now + 3600);
+    const notReady2 = await createNextSchedule(new anchor.BN(100), now + 600, now + 600, now + 3600);
+    const ready = await createNextSchedule(new anchor.BN(400), now - 20, now - 20, now - 10);
+
+    const batchEntries = (entries: { vestingSchedulePda: PublicKey; vestingVaultPda: PublicKey }[]) =>
+      entries.flatMap((entry) => [
+        { pubkey: entry.vestingSchedulePda, isWritable: true, isSigner: false },
+        { pubkey: entry.vestingVaultPda, isWritable: true, isSigner: false },
+        { pubkey: recipientTokenAccount, isWritable: true, isSigner: false },
+        { pubkey: mint, isWritable: false, isSigner: false },
+      ]);
+    const batchCrank = (entries: { vestingSchedulePda: PublicKey; vestingVaultPda: PublicKey }[]) =>
+      program.methods
+        .crankVestingSchedulesBatch()
+        .accounts({ programConfig: programConfigPda, tokenProgram: TOKEN_PROGRAM_ID })
+        .remainingAccounts(batchEntries(entries));
+    const consumedUnits = (logs: string[]) =>
+      Number(logs.map((log) => log.match(/consumed (\d+) of/)).find((m) => m)?.[1] ?? 0);
+
+    // All-not-ready batch returns the BatchNothingReady summary
+    const idleSummary = await batchCrank([notReady1, notReady2]).view();
+    expect(idleSummary.nothingReady).to.be.true;
+    expect(idleSummary.schedulesProcessed).to.equal(0);
+
+    const idleSimulation = await batchCrank([notReady1, notReady2]).simulate();
+    const mixedSimulation = await batchCrank([notReady1, ready]).simulate();
+    console.log(
+      `Batch crank CU: all-not-ready ${consumedUnits(idleSimulation.raw as string[])}, ` +
+      `mixed ${consumedUnits(mixedSimulation.raw as string[])}`
+    );
+
+    // Mixed batch goes through the full path and only releases the ready schedule
+    const mixedSummary = await batchCrank([notReady1, ready]).view();
+    expect(mixedSummary.nothingReady).to.be.false;
+    expect(mixedSummary.schedulesProcessed).to.equal(2);
+    expect(mixedSummary.schedulesReleased).to.equal(1);
+    expect(mixedSummary.totalReleased.toString()).to.equal("400");
+
+    await batchCrank([notReady1, ready]).rpc();
+    const readySchedule = await program.account.vestingSchedule.fetch(ready.vestingSchedulePda);
+    const notReadySchedule = await program.account.vestingSchedule.fetch(notReady1.vestingSchedulePda);
+    expect(readySchedule.amountTransferred.toString()).to.equal("400");
+    expect(notReadySchedule.amountTransferred.toString()).to.equal("0");
+  });
+
+  it("Should stop the batch crank at max_release_per_transaction and report partial completion", async () => {
+    const now = Math.floor(Date.now() / 1000);
+    const schedules = [
+      await createNextSchedule(new anchor.BN(300), now - 20, now - 20, now - 10),
+      await createNextSchedule(new anchor.BN(300), now - 20, now - 20, now - 10),
+      await createNextSchedule(new anchor.BN(300), now - 20, now - 20, now - 10),
+    ];
+    const setCap = (cap: number) =>
+      program.methods
+        .setMaxReleasePerTransaction(new anchor.BN(cap))
+        .accounts({ authority: admin.publicKey, programConfig: programConfigPda })
+        .signers([admin])
+        .rpc();
+    const batchCrank = () =>
+      program.methods
+        .crankVestingSchedulesBatch()
+        .accounts({ programConfig: programConfigPda, tokenProgram: TOKEN_PROGRAM_ID })
+        .remainingAccounts(
+          schedules.flatMap((entry) => [
+            { pubkey: entry.vestingSchedulePda, isWritable: true, isSigner: false },
+            { pubkey: entry.vestingVaultPda, isWritable: true, isSigner: false },
+            { pubkey: recipientTokenAccount, isWritable: true, isSigner: false },
+            { pubkey: mint, isWritable: false, isSigner: false },
+          ])
+        );
+
+    await setCap(500);
+    try {
+      const summary = await batchCrank().view();
+      expect(summary.stoppedAtCap).to.be.true;
+      expect(summary.schedulesReleased).to.equal(2);
+      expect(summary.totalReleased.toString()).to.equal("500");
+
+      await batchCrank().rpc();
+      const released = await Promise.all(
+        schedules.map((entry) => program.account.vestingSchedule.fetch(entry.vestingSchedulePda))
+      );
+      expect(released.map((schedule) => schedule.amountTransferred.toString())).to.deep.equal([
+        "300", "200", "0",
+      ]);
+    } finally {
+      await setCap(0);
+    }
+  });
+
+  it("Should emit DistributionHubInitialized on first hub setup and timelock later changes", async () => {
+    const hubOwner = Keypair.generate();
+    const hubTokenAccount = (await getOrCreateAssociatedTokenAccount(
+      provider.connection, admin, mint, hubOwner.publicKey
+    )).address;
+    const nextHubOwner = Keypair.generate();
+    const nextHubTokenAccount = (await getOrCreateAssociatedTokenAccount(
+      provider.connection, admin, mint, nextHubOwner.publicKey
+    )).address;
+
+    const updateHub = (hub: PublicKey) =>
+      program.methods
+        .updateDistributionHub()
+        .accounts({ admin: admin.publicKey, programConfig: programConfigPda, mint, hubTokenAccount: hub })
+        .signers([admin]);
+
+    // First setup fires the dedicated initialization event
+    const setupSimulation = await updateHub(hubTokenAccount).simulate();
+    expect(setupSimulation.events.map((event) => event.name)).to.deep.equal([
+      "distributionHubInitialized",
+    ]);
+    await updateHub(hubTokenAccount).rpc();
+
+    let configAccount = await program.account.programConfig.fetch(programConfigPda);
+    expect(configAccount.distributionHub.toString()).to.equal(hubTokenAccount.toString());
+
+    // A later change is only proposed
+    const proposeSimulation = await updateHub(nextHubTokenAccount).simulate();
+    expect(proposeSimulation.events.map((event) => event.name)).to.deep.equal([
+      "distributionHubUpdateProposed",
+    ]);
+    await updateHub(nextHubTokenAccount).rpc();
+
+    configAccount = await program.account.programConfig.fetch(programConfigPda);
+    expect(configAccount.distributionHub.toString()).to.equal(hubTokenAccount.toString());
+    expect(configAccount.pendingHub!.toString()).to.equal(nextHubTokenAccount.toString());
+
+    // Confirming before the timelock expires fails
+    try {
+      await updateHub(nextHubTokenAccount).rpc();
+      expect.fail("Should not confirm before the timelock expires");
+    } catch (error: any) {
+      expect(error.toString()).to.include("TimelockNotExpired");
+    }
+  });
+
+  it("Should let a relayer claim with the recipient's Ed25519 authorization and reject forged ones", async () => {
+    const now = Math.floor(Date.now() / 1000);
+    const { vestingSchedulePda, vestingVaultPda } = await createNextSchedule(
+      new anchor.BN(1_000), now - 20, now - 20, now - 10
+    );
+    const relayer = Keypair.generate();
+    await provider.connection.confirmTransaction(
+      await provider.connection.requestAirdrop(relayer.publicKey, LAMPORTS_PER_SOL)
+    );
+    const custodian = Keypair.generate();
+    const destination = (await getOrCreateAssociatedTokenAccount(
+      provider.connection, admin, mint, custodian.publicKey
+    )).address;
+    const expiry = new anchor.BN(now + 600);
+
+    // domain || vesting_schedule || destination || amount_transferred (le) || expiry (le)
+    const claimMessage = Buffer.concat([
+      Buffer.from("haio-vesting:claim_with_signature"),
+      vestingSchedulePda.toBuffer(),
+      destination.toBuffer(),
+      new anchor.BN(0).toArrayLike(Buffer, "le", 8),
+      expiry.toArrayLike(Buffer, "le", 8),
+    ]);
+    const claim = (signatureIx: anchor.web3.TransactionInstruction, claimRelayer = relayer) =>
+      program.methods
+        .claimWithSignature(expiry)
+        .accounts({
+          relayer: claimRelayer.publicKey,
+          programConfig: programConfigPda,
+          vestingSchedule: vestingSchedulePda,
+          vestingVault: vestingVaultPda,
+          destinationTokenAccount: destination,
+          mint: mint,
+          instructions: anchor.web3.SYSVAR_INSTRUCTIONS_PUBKEY,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .preInstructions([signatureIx])
+        .signers([claimRelayer])
+        .rpc();
+
+    // Signed by a key other than the recipient
+    const attacker = Keypair.generate();
+    try {
+      await claim(Ed25519Program.createInstructionWithPrivateKey({
+        privateKey: attacker.secretKey,
+        message: claimMessage,
+      }));
+      expect.fail("Should reject a signature from a non-recipient key");
+    } catch (error: any) {
+      expect(error.toString()).to.include("SignatureMismatch");
+    }
+
+    // Recipient's key with a forged signature is rejected by the Ed25519 program
+    try {
+      await claim(Ed25519Program.createInstructionWithPublicKey({
+        publicKey: recipient.publicKey.toBytes(),
+        message: claimMessage,
+        signature: Keypair.generate().secretKey.slice(0, 64),
+      }));
+      expect.fail("Should reject a forged signature");
+    } catch (error: any) {
+      expect(error).to.exist;
+    }
+
+    const validIx = Ed25519Program.createInstructionWithPrivateKey({
+      privateKey: recipient.secretKey,
+      message: claimMessage,
+    });
+    await claim(validIx);
+
+    const destinationAccount = await getAccount(provider.connection, destination);
+    expect(destinationAccount.amount.toString()).to.equal("1000");
+
+    // The same authorization cannot be replayed once amount_transferred has moved
+    const otherRelayer = Keypair.generate();
+    await provider.connection.confirmTransaction(
+      await provider.connection.requestAirdrop(otherRelayer.publicKey, LAMPORTS_PER_SOL)
+    );
+    try {
+      await claim(validIx, otherRelayer);
+      expect.fail("Should not accept a replayed authorization");
+    } catch (error: any) {
+      expect(error.toString()).to.include("SignatureMismatch");
+    }
+  });
+
+  it("Should report RecipientAccountOwnershipChanged when the recipient token account changes owner", async () => {
+    const now = Math.floor(Date.now() / 1000);
+    const migratingTokenAccount = await createAccount(
+      provider.connection, admin, mint, recipient.publicKey, Keypair.generate()
+    );
+    const { vestingSchedulePda, vestingVaultPda } = await createNextSchedule(
+      new anchor.BN(1_000), now - 20, now - 20, now - 10, {},
+      { recipientTokenAccount: migratingTokenAccount }
+    );
+
+    // Custody migration moves ownership away from the recorded recipient
+    const custodian = Keypair.generate();
+    await setAuthority(
+      provider.connection, admin, migratingTokenAccount, recipient,
+      AuthorityType.AccountOwner, custodian.publicKey
+    );
+
+    try {
+      await program.methods
+        .crankVestingSchedule()
+        .accounts({
+          programConfig: programConfigPda,
+          vestingSchedule: vestingSchedulePda,
+          vestingVault: vestingVaultPda,
+          recipientTokenAccount: migratingTokenAccount,
+          mint: mint,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .rpc();
+      expect.fail("Should block releases after an ownership change");
+    } catch (error: any) {
+      expect(error.toString()).to.include("RecipientAccountOwnershipChanged");
+
+      const parser = new anchor.EventParser(program.programId, program.coder);
+      const events = [...parser.parseLogs(error.logs ?? [])];
+      const ownershipEvent = events.find((event) => event.name === "recipientAccountOwnershipChanged");
+      expect(ownershipEvent).to.exist;
+      expect(ownershipEvent!.data.currentOwner.toString()).to.equal(custodian.publicKey.toString());
+    }
+
+    const schedule = await program.account.vestingSchedule.fetch(vestingSchedulePda);
+    expect(schedule.amountTransferred.toString()).to.equal("0");
+  });
+
+  it("Should preview the rent reclaimed by closing a schedule and its vault", async () => {
+    const now = Math.floor(Date.now() / 1000);
+    const { vestingSchedulePda, vestingVaultPda } = await createNextSchedule(
+      new anchor.BN(1_000), now - 20, now - 20, now - 10
+    );
+
+    const preview = await program.methods
+      .getClosePreview()
+      .accounts({ vestingSchedule: vestingSchedulePda, vestingVault: vestingVaultPda })
+      .view();
+
+    // Closing hands over the accounts' actual balances
+    const scheduleInfo = await provider.connection.getAccountInfo(vestingSchedulePda);
+    const vaultInfo = await provider.connection.getAccountInfo(vestingVaultPda);
+    expect(preview.toNumber()).to.equal(scheduleInfo!.lamports + vaultInfo!.lamports);
+  });
+
+  it("Should enforce cliff == vesting start for categories configured with the policy", async () => {
+    const now = Math.floor(Date.now() / 1000);
+    const setPolicy = (required: boolean) =>
+      program.methods
+        .setCliffEqStartPolicy({ public: {} }, required)
+        .accounts({ authority: admin.publicKey, programConfig: programConfigPda })
+        .signers([admin])
+        .rpc();
+
+    // Off by default: a cliff-to-start gap is accepted
+    await createNextSchedule(new anchor.BN(100), now + 100, now + 200, now + 300);
+
+    await setPolicy(true);
+    try {
+      try {
+        await createNextSchedule(new anchor.BN(100), now + 100, now + 200, now + 300);
+        expect.fail("Should reject a cliff that differs from vesting start");
+      } catch (error: any) {
+        expect(error.toString()).to.include("CliffStartMismatch");
+      }
+
+      const { vestingSchedulePda } = await createNextSchedule(
+        new anchor.BN(100), now + 200, now + 200, now + 300
+      );
+      const schedule = await program.account.vestingSchedule.fetch(vestingSchedulePda);
+      expect(schedule.cliffTimestamp.toString()).to.equal(schedule.vestingStartTimestamp.toString());
+
+      // Other categories are unaffected
+      await createNextSchedule(new anchor.BN(100), now + 100, now + 200, now + 300, {
+        sourceCategory: { team: {} },
+        minClaimInterval: new anchor.BN(0),
+        allowSelfReturn: false,
+        allowPastEnd: false,
+        vestingCurve: { linear: {} },
+        nonTransferable: false,
+        label: Array(32).fill(0),
+        secondCliffTimestamp: new anchor.BN(0),
+        secondCliffBps: 0,
+      });
+    } finally {
+      await setPolicy(false);
+    }
+  });
+
+  it("Should freeze mutations in read-only mode while views and closes keep working", async () => {
+    const now = Math.floor(Date.now() / 1000);
+    const completed = await createNextSchedule(new anchor.BN(100), now - 20, now - 20, now - 10);
+    await crankSchedule(completed.vestingSchedulePda, completed.vestingVaultPda);
+    const active = await createNextSchedule(new anchor.BN(100), now - 20, now - 20, now + 1_000);
+
+    const setReadOnly = (readOnly: boolean) =>
+      program.methods
+        .setReadOnly(readOnly)
+        .accounts({ admin: admin.publicKey, programConfig: programConfigPda })
+        .signers([admin])
+        .rpc();
+    const expectReadOnly = async (action: () => Promise<unknown>) => {
+      try {
+        await action();
+        expect.fail("Should be blocked in read-only mode");
+      } catch (error: any) {
+        expect(error.toString()).to.include("ProgramReadOnly");
+      }
+    };
+
+    await setReadOnly(true);
+    try {
+      // Creation, top-up, cranks and config changes are blocked
+      await expectReadOnly(() =>
+        createNextSchedule(new anchor.BN(100), now - 20, now - 20, now + 1_000)
+      );
+      await expectReadOnly(() =>
+        program.methods
+          .topUpBatch([new anchor.BN(100)])
+          .accounts({
+            admin: admin.publicKey,
+            programConfig: programConfigPda,
+            mint: mint,
+            depositorTokenAccount: adminTokenAccount,
+            tokenProgram: TOKEN_PROGRAM_ID,
+          })
+          .remainingAccounts([
+            { pubkey: active.vestingSchedulePda, isWritable: true, isSigner: false },
+            { pubkey: active.vestingVaultPda, isWritable: true, isSigner: false },
+          ])
+          .signers([admin])
+          .rpc()
+      );
+      await expectReadOnly(() => crankSchedule(active.vestingSchedulePda, active.vestingVaultPda));
+      await expectReadOnly(() =>
+        program.methods
+          .setKeeperReward(100)
+          .accounts({ authority: admin.publicKey, programConfig: programConfigPda })
+          .signers([admin])
+          .rpc()
+      );
+
+      // Views still answer
+      const preview = await program.methods
+        .getClosePreview()
+        .accounts({ vestingSchedule: active.vestingSchedulePda, vestingVault: active.vestingVaultPda })
+        .view();
+      expect(preview.toNumber()).to.be.greaterThan(0);
+
+      // Closes still work
+      await program.methods
+        .closeVestingSchedule()
+        .accounts({
+          beneficiary: admin.publicKey,
+          vestingSchedule: completed.vestingSchedulePda,
+          vestingVault: completed.vestingVaultPda,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([admin])
+        .rpc();
+      expect(await provider.connection.getAccountInfo(completed.vestingSchedulePda)).to.be.null;
+    } finally {
+      await setReadOnly(false);
+    }
+
+    const configAccount = await program.account.programConfig.fetch(programConfigPda);
+    expect(configAccount.readOnly).to.be.false;
+  });
+
+  it("Should let the recipient's withdrawal authority pull releases and reject other signers", async () => {
+    const now = Math.floor(Date.now() / 1000);
+    const { vestingSchedulePda, vestingVaultPda } = await createNextSchedule(
+      new anchor.BN(1_000), now - 20, now - 20, now - 10
+    );
+    const streamer = Keypair.generate();
+    const intruder = Keypair.generate();
+
+    const streamWithdraw = (authority: Keypair) =>
+      program.methods
+        .streamWithdraw()
+        .accounts({
+          withdrawalAuthority: authority.publicKey,
+          programConfig: programConfigPda,
+          vestingSchedule: vestingSchedulePda,
+          vestingVault: vestingVaultPda,
+          recipientTokenAccount: recipientTokenAccount,
+          mint: mint,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([authority]);
+
+    // Nothing delegated yet
+    try {
+      await streamWithdraw(streamer).rpc();
+      expect.fail("Should reject an undelegated authority");
+    } catch (error: any) {
+      expect(error.toString()).to.include("UnauthorizedWithdrawalAuthority");
+    }
+
+    await program.methods
+      .setWithdrawalAuthority(streamer.publicKey)
+      .accounts({
+        recipient: recipient.publicKey,
+        programConfig: programConfigPda,
+        vestingSchedule: vestingSchedulePda,
+      })
+      .signers([recipient])
+      .rpc();
+
+    try {
+      await streamWithdraw(intruder).rpc();
+      expect.fail("Should reject a signer other than the withdrawal authority");
+    } catch (error: any) {
+      expect(error.toString()).to.include("UnauthorizedWithdrawalAuthority");
+    }
+
+    const simulation = await streamWithdraw(streamer).simulate();
+    const streamEvent = simulation.events.find((event) => event.name === "streamWithdrawn");
+    expect(streamEvent!.data.withdrawalAuthority.toString()).to.equal(streamer.publicKey.toString());
+
+    const before = await getAccount(provider.connection, recipientTokenAccount);
+    await streamWithdraw(streamer).rpc();
+    const after = await getAccount(provider.connection, recipientTokenAccount);
+    expect((after.amount - before.amount).toString()).to.equal("1000");
+  });
+
+  it("Should pre-flight cranks with can_crank across blocking conditions", async () => {
+    const now = Math.floor(Date.now() / 1000);
+    const keeper = Keypair.generate().publicKey;
+    const ready = await createNextSchedule(new anchor.BN(1_000), now - 20, now - 20, now - 10);
+    const locked = await createNextSchedule(new anchor.BN(1_000), now + 1_000, now + 1_000, now + 2_000);
+
+    const canCrank = (entry: { vestingSchedulePda: PublicKey; vestingVaultPda: PublicKey }) =>
+      program.methods
+        .canCrank(keeper)
+        .accounts({
+          programConfig: programConfigPda,
+          vestingSchedule: entry.vestingSchedulePda,
+          vestingVault: entry.vestingVaultPda,
+          recipientTokenAccount: recipientTokenAccount,
+        })
+        .view();
+
+    let preflight = await canCrank(ready);
+    expect(preflight.allowed).to.be.true;
+    expect(preflight.reason).to.deep.equal({ none: {} });
+    expect(preflight.transferable.toString()).to.equal("1000");
+
+    preflight = await canCrank(locked);
+    expect(preflight.allowed).to.be.false;
+    expect(preflight.reason).to.deep.equal({ nothingVested: {} });
+
+    const setReadOnly = (readOnly: boolean) =>
+      program.methods
+        .setReadOnly(readOnly)
+        .accounts({ admin: admin.publicKey, programConfig: programConfigPda })
+        .signers([admin])
+        .rpc();
+    await setReadOnly(true);
+    try {
+      preflight = await canCrank(ready);
+      expect(preflight.allowed).to.be.false;
+      expect(preflight.reason).to.deep.equal({ readOnly: {} });
+    } finally {
+      await setReadOnly(false);
+    }
+
+    await crankSchedule(ready.vestingSchedulePda, ready.vestingVaultPda);
+    preflight = await canCrank(ready);
+    expect(preflight.allowed).to.be.false;
+    expect(preflight.reason).to.deep.equal({ fullyProcessed: {} });
+  });
+
+  it("Should guard fund_vault against overfunding and fully processed schedules", async () => {
+    const now = Math.floor(Date.now() / 1000);
+    const active = await createNextSchedule(new anchor.BN(1_000), now - 20, now - 20, now + 1_000);
+    const completed = await createNextSchedule(new anchor.BN(100), now - 20, now - 20, now - 10);
+    await crankSchedule(completed.vestingSchedulePda, completed.vestingVaultPda);
+
+    const fundVault = (entry: { vestingSchedulePda: PublicKey; vestingVaultPda: PublicKey }, amount: number) =>
+      program.methods
+        .fundVault(new anchor.BN(amount))
+        .accounts({
+          admin: admin.publicKey,
+          programConfig: programConfigPda,
+          vestingSchedule: entry.vestingSchedulePda,
+          mint: mint,
+          depositorTokenAccount: adminTokenAccount,
+          vestingVault: entry.vestingVaultPda,
+          systemProgram: SystemProgram.programId,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          rent: SYSVAR_RENT_PUBKEY,
+        })
+        .signers([admin])
+        .rpc();
+
+    // The vault already holds everything still owed
+    try {
+      await fundVault(active, 1);
+      expect.fail("Should not overfund the vault");
+    } catch (error: any) {
+      expect(error.toString()).to.include("InvalidAmount");
+    }
+
+    try {
+      await fundVault(completed, 1);
+      expect.fail("Should not fund a fully processed schedule");
+    } catch (error: any) {
+      expect(error.toString()).to.include("ScheduleFullyProcessed");
+    }
+  });
+
+  it("Should enforce min_claim_interval on recipient-initiated claims while keeper cranks bypass it", async () => {
+    const now = Math.floor(Date.now() / 1000);
+    const { vestingSchedulePda, vestingVaultPda } = await createNextSchedule(
+      new anchor.BN(100_000), now - 100, now - 100, now + 10_000, { minClaimInterval: new anchor.BN(3_600) }
+    );
+    const streamer = Keypair.generate();
+    await program.methods
+      .setWithdrawalAuthority(streamer.publicKey)
+      .accounts({
+        recipient: recipient.publicKey,
+        programConfig: programConfigPda,
+        vestingSchedule: vestingSchedulePda,
+      })
+      .signers([recipient])
+      .rpc();
+
+    const streamWithdraw = () =>
+      program.methods
+        .streamWithdraw()
+        .accounts({
+          withdrawalAuthority: streamer.publicKey,
+          programConfig: programConfigPda,
+          vestingSchedule: vestingSchedulePda,
+          vestingVault: vestingVaultPda,
+          recipientTokenAccount: recipientTokenAccount,
+          mint: mint,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([streamer])
+        .rpc();
+
+    await streamWithdraw();
+    let schedule = await program.account.vestingSchedule.fetch(vestingSchedulePda);
+    expect(schedule.lastClaimTimestamp.toNumber()).to.be.greaterThan(0);
+    const claimed = schedule.amountTransferred.toNumber();
+    expect(claimed).to.be.greaterThan(0);
+
+    // A second recipient-initiated claim inside the interval is rejected
+    try {
+      await streamWithdraw();
+      expect.fail("Should reject a claim before min_claim_interval elapses");
+    } catch (error: any) {
+      expect(error.toString()).to.include("ClaimTooSoon");
+    }
+
+    // Keeper cranks are not bound by the interval by default
+    await new Promise((resolve) => setTimeout(resolve, 1_500));
+    await crankSchedule(vestingSchedulePda, vestingVaultPda);
+    schedule = await program.account.vestingSchedule.fetch(vestingSchedulePda);
+    expect(schedule.amountTransferred.toNumber()).to.be.greaterThan(claimed);
+  });
+
+  it("Should leave the schedule unlocked after a release", async () => {
+    const now = Math.floor(Date.now() / 1000);
+    const { vestingSchedulePda, vestingVaultPda } = await createNextSchedule(
+      new anchor.BN(1_000), now - 20, now - 20, now - 10
+    );
+
+    await crankSchedule(vestingSchedulePda, vestingVaultPda);
+
+    const schedule = await program.account.vestingSchedule.fetch(vestingSchedulePda);
+    expect(schedule.amountTransferred.toString()).to.equal("1000");
+    expect(schedule.locked).to.be.false;
+  });
+
+  it("Should release within a generous max_clock_skew and record the setting", async () => {
+    const now = Math.floor(Date.now() / 1000);
+    const { vestingSchedulePda, vestingVaultPda } = await createNextSchedule(
+      new anchor.BN(1_000), now - 20, now - 20, now - 10
+    );
+    const setSkew = (seconds: number) =>
+      program.methods
+        .setMaxClockSkew(new anchor.BN(seconds))
+        .accounts({ authority: admin.publicKey, programConfig: programConfigPda })
+        .signers([admin])
+        .rpc();
+
+    await setSkew(24 * 60 * 60);
+    try {
+      const configAccount = await program.account.programConfig.fetch(programConfigPda);
+      expect(configAccount.maxClockSkew.toNumber()).to.equal(24 * 60 * 60);
+
+      await crankSchedule(vestingSchedulePda, vestingVaultPda);
+      const schedule = await program.account.vestingSchedule.fetch(vestingSchedulePda);
+      expect(schedule.amountTransferred.toString()).to.equal("1000");
+    } finally {
+      await setSkew(0);
+    }
+  });
+
+  it("Should reassign the unvested remainder of a schedule to a new recipient", async () => {
+    const now = Math.floor(Date.now() / 1000);
+    const source = await createNextSchedule(new anchor.BN(1_000), now - 100, now - 100, now + 100);
+
+    const newRecipient = Keypair.generate();
+    const newRecipientTokenAccount = (await getOrCreateAssociatedTokenAccount(
+      provider.connection, admin, mint, newRecipient.publicKey
+    )).address;
+    const configAccount = await program.account.programConfig.fetch(programConfigPda);
+    const newScheduleId = new anchor.BN(configAccount.totalSchedules.toString());
+    const [newSchedulePda, newVaultPda] = findSchedulePdas(newScheduleId);
+
+    await program.methods
+      .reassignUnvested(newScheduleId, {
+        recipient: newRecipient.publicKey,
+        cliffTimestamp: new anchor.BN(now + 1_000),
+        vestingStartTimestamp: new anchor.BN(now + 1_000),
+        vestingEndTimestamp: new anchor.BN(now + 2_000),
+      })
+      .accounts({
+        admin: admin.publicKey,
+        programConfig: programConfigPda,
+        sourceSchedule: source.vestingSchedulePda,
+        sourceVault: source.vestingVaultPda,
+        newSchedule: newSchedulePda,
+        mint: mint,
+        newRecipientTokenAccount: newRecipientTokenAccount,
+        newVault: newVaultPda,
+        systemProgram: SystemProgram.programId,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        rent: SYSVAR_RENT_PUBKEY,
+      })
+      .signers([admin])
+      .rpc();
+
+    const sourceSchedule = await program.account.vestingSchedule.fetch(source.vestingSchedulePda);
+    const newSchedule = await program.account.vestingSchedule.fetch(newSchedulePda);
+    const sourceVault = await getAccount(provider.connection, source.vestingVaultPda);
+    const newVault = await getAccount(provider.connection, newVaultPda);
+
+    // The allocation is split without creating or losing tokens
+    expect(sourceSchedule.totalAmount.add(newSchedule.totalAmount).toString()).to.equal("1000");
+    expect(sourceSchedule.totalAmount.toNumber()).to.be.greaterThan(0);
+    expect(newSchedule.recipient.toString()).to.equal(newRecipient.publicKey.toString());
+    expect(newVault.amount.toString()).to.equal(newSchedule.totalAmount.toString());
+    expect(sourceVault.amount.toString()).to.equal(sourceSchedule.totalAmount.toString());
+
+    // The source completes on its next crank
+    await crankSchedule(source.vestingSchedulePda, source.vestingVaultPda);
+    const completedSource = await program.account.vestingSchedule.fetch(source.vestingSchedulePda);
+    expect(completedSource.amountTransferred.toString()).to.equal(completedSource.totalAmount.toString());
+  });
+
+  it("Should return the aggregate unlock curve across schedules", async () => {
+    const now = Math.floor(Date.now() / 1000);
+    const base = now + 10_000;
+    const linear = await createNextSchedule(new anchor.BN(1_000), base, base, base + 1_000);
+    const later = await createNextSchedule(new anchor.BN(500), base + 500, base + 500, base + 1_500);
+
+    const timestamps = [base - 1, base + 500, base + 1_000, base + 1_500].map((t) => new anchor.BN(t));
+    const curve = await program.methods
+      .aggregateUnlockCurve(timestamps, 0)
+      .accounts({ programConfig: programConfigPda })
+      .remainingAccounts(
+        [linear, later].map((entry) => ({
+          pubkey: entry.vestingSchedulePda, isWritable: false, isSigner: false,
+        }))
+      )
+      .view();
+
+    expect(curve.points.map((point: anchor.BN) => point.toString())).to.deep.equal([
+      "0", "500", "1250", "1500",
+    ]);
+
+    // Too many points for the return data budget
+    try {
+      await program.methods
+        .aggregateUnlockCurve(Array.from({ length: 65 }, (_, i) => new anchor.BN(base + i)), 0)
+        .accounts({ programConfig: programConfigPda })
+        .remainingAccounts([
+          { pubkey: linear.vestingSchedulePda, isWritable: false, isSigner: false },
+        ])
+        .view();
+      expect.fail("Should reject more than MAX_CURVE_POINTS timestamps");
+    } catch (error: any) {
+      expect(error.toString()).to.include("InvalidBatchSize");
+    }
+  });
+
+  it("Should pay a wrapped SOL schedule out as native SOL", async () => {
+    const now = Math.floor(Date.now() / 1000);
+    const amount = 100_000_000; // 0.1 SOL
+
+    // Admin wraps SOL to fund the schedule, recipient holds a wSOL account for the schedule record
+    const adminWsol = (await getOrCreateAssociatedTokenAccount(
+      provider.connection, admin, NATIVE_MINT, admin.publicKey
+    )).address;
+    await sendAndConfirmTransaction(
+      provider.connection,
+      new Transaction().add(
+        SystemProgram.transfer({ fromPubkey: admin.publicKey, toPubkey: adminWsol, lamports: amount }),
+        createSyncNativeInstruction(adminWsol)
+      ),
+      [admin]
+    );
+    const recipientWsol = (await getOrCreateAssociatedTokenAccount(
+      provider.connection, admin, NATIVE_MINT, recipient.publicKey
+    )).address;
+
+    const { scheduleId, vestingSchedulePda, vestingVaultPda } = await createNextSchedule(
+      new anchor.BN(amount), now - 20, now - 20, now - 10, {},
+      { mint: NATIVE_MINT, depositorTokenAccount: adminWsol, recipientTokenAccount: recipientWsol }
+    );
+    const [unwrapAccountPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("unwrap"), scheduleId.toArrayLike(Buffer, "le", 8)],
+      program.programId
+    );
+
+    const lamportsBefore = await provider.connection.getBalance(recipient.publicKey);
+    await program.methods
+      .claimUnwrappedSol()
+      .accounts({
+        recipient: recipient.publicKey,
+        programConfig: programConfigPda,
+        vestingSchedule: vestingSchedulePda,
+        vestingVault: vestingVaultPda,
+        unwrapAccount: unwrapAccountPda,
+        mint: NATIVE_MINT,
+        systemProgram: SystemProgram.programId,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        rent: SYSVAR_RENT_PUBKEY,
+      })
+      .signers([recipient])
+      .rpc();
+    const lamportsAfter = await provider.connection.getBalance(recipient.publicKey);
+
+    // Temporary account rent is refunded, so the recipient nets exactly the vested amount
+    expect(lamportsAfter - lamportsBefore).to.equal(amount);
+    expect(await provider.connection.getAccountInfo(unwrapAccountPda)).to.be.null;
+
+    const schedule = await program.account.vestingSchedule.fetch(vestingSchedulePda);
+    expect(schedule.amountTransferred.toString()).to.equal(amount.toString());
+
+    // Non-wSOL schedules are rejected
+    const splSchedule = await createNextSchedule(new anchor.BN(1_000), now - 20, now - 20, now - 10);
+    const [splUnwrapPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("unwrap"), splSchedule.scheduleId.toArrayLike(Buffer, "le", 8)],
+      program.programId
+    );
+    try {
+      await program.methods
+        .claimUnwrappedSol()
+        .accounts({
+          recipient: recipient.publicKey,
+          programConfig: programConfigPda,
+          vestingSchedule: splSchedule.vestingSchedulePda,
+          vestingVault: splSchedule.vestingVaultPda,
+          unwrapAccount: splUnwrapPda,
+          mint: mint,
+          systemProgram: SystemProgram.programId,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          rent: SYSVAR_RENT_PUBKEY,
+        })
+        .signers([recipient])
+        .rpc();
+      expect.fail("Should reject a schedule that is not wrapped SOL");
+    } catch (error: any) {
+      expect(error.toString()).to.include("NotWrappedSol");
+    }
+  });
+
+  it("Should emit CrankSkipped with the skip reason only when enabled", async () => {
+    const now = Math.floor(Date.now() / 1000);
+    const { vestingSchedulePda, vestingVaultPda } = await createNextSchedule(
+      new anchor.BN(1_000), now + 1_000, now + 1_000, now + 2_000
+    );
+    const setSkipEvents = (enabled: boolean) =>
+      program.methods
+        .setEmitSkipEvents(enabled)
+        .accounts({ authority: admin.publicKey, programConfig: programConfigPda })
+        .signers([admin])
+        .rpc();
+    const simulateCrank = () =>
+      program.methods
+        .crankVestingSchedule()
+        .accounts({
+          programConfig: programConfigPda,
+          vestingSchedule: vestingSchedulePda,
+          vestingVault: vestingVaultPda,
+          recipientTokenAccount: recipientTokenAccount,
+          mint: mint,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .simulate();
+
+    // Off by default
+    let simulation = await simulateCrank();
+    expect(simulation.events.find((event) => event.name === "crankSkipped")).to.be.undefined;
+
+    const toggle = await program.methods
+      .setEmitSkipEvents(true)
+      .accounts({ authority: admin.publicKey, programConfig: programConfigPda })
+      .signers([admin])
+      .simulate();
+    const updated = toggle.events.find((event) => event.name === "emitSkipEventsUpdated");
+    expect(updated!.data.oldEmitSkipEvents).to.be.false;
+    expect(updated!.data.newEmitSkipEvents).to.be.true;
+
+    await setSkipEvents(true);
+    try {
+      simulation = await simulateCrank();
+      const skipped = simulation.events.find((event) => event.name === "crankSkipped");
+      expect(skipped).to.exist;
+      expect(skipped!.data.reason).to.deep.equal({ nothingVested: {} });
+    } finally {
+      await setSkipEvents(false);
+    }
+  });
+
+
+  it("Should report the weighted-average unlock timestamp of remaining tokens", async () => {
+    const now = Math.floor(Date.now() / 1000);
+    const start = now + 1000;
+    const end = now + 3000;
+    const { vestingSchedulePda } = await createNextSchedule(new anchor.BN(1000), start, start, end);
+
+    const avg = await program.methods
+      .getWeightedAvgUnlockTimestamp()
+      .accounts({ vestingSchedule: vestingSchedulePda })
+      .view();
+
+    // Nothing has unlocked yet, so the average is the middle of the vesting window
+    expect(avg.toNumber()).to.equal(start + (end - start) / 2);
+  });
+
+
+  it("Should paginate batch views whose results exceed the return data limit", async () => {
+    const now = Math.floor(Date.now() / 1000);
+    const groupId = new anchor.BN(4_720);
+
+    // 22 mints x 48 bytes per entry exceeds the 1024-byte return data limit
+    const schedulePdas: PublicKey[] = [];
+    for (let i = 0; i < 22; i++) {
+      const groupMint = await createMint(provider.connection, admin, admin.publicKey, null, 6);
+      const adminAccount = (await getOrCreateAssociatedTokenAccount(
+        provider.connection, admin, groupMint, admin.publicKey
+      )).address;
+      const recipientAccount = (await getOrCreateAssociatedTokenAccount(
+        provider.connection, admin, groupMint, recipient.publicKey
+      )).address;
+      await mintTo(provider.connection, admin, groupMint, adminAccount, admin, BigInt(1_000));
+
+      const { vestingSchedulePda } = await createNextSchedule(
+        new anchor.BN(100 + i), now + 60, now + 60, now + 3600, { grantGroupId: groupId },
+        { mint: groupMint, depositorTokenAccount: adminAccount, recipientTokenAccount: recipientAccount }
+      );
+      schedulePdas.push(vestingSchedulePda);
+    }
+
+    const fetchPage = (offset: number) =>
+      program.methods
+        .getGrantGroup(groupId, offset)
+        .accounts({ programConfig: programConfigPda })
+        .remainingAccounts(
+          schedulePdas.map((pubkey) => ({ pubkey, isWritable: false, isSigner: false }))
+        )
+        .view();
+
+    const first = await fetchPage(0);
+    expect(first.truncated).to.equal(true);
+    expect(first.mintTotals.length).to.equal(21);
+    expect(first.nextOffset).to.equal(21);
+
+    const second = await fetchPage(first.nextOffset);
+    expect(second.truncated).to.equal(false);
+    expect(second.mintTotals.length).to.equal(1);
+    expect(second.mintTotals[0].totalAmount.toString()).to.equal("121");
+  });
+
+
+  it("Should timelock changes to the upgrade authority record", async () => {
+    const authority = Keypair.generate().publicKey;
+    const other = Keypair.generate().publicKey;
+    const confirm = (key: PublicKey) =>
+      program.methods
+        .confirmUpgradeAuthority(key)
+        .accounts({ admin: admin.publicKey, programConfig: programConfigPda })
+        .signers([admin]);
+
+    // Nothing to confirm before a proposal
+    try {
+      await confirm(authority).rpc();
+      expect.fail("Should reject a confirmation without a proposal");
+    } catch (error: any) {
+      expect(error.toString()).to.include("NoPendingUpgradeAuthority");
+    }
+
+    const propose = program.methods
+      .proposeUpgradeAuthority(authority)
+      .accounts({ admin: admin.publicKey, programConfig: programConfigPda })
+      .signers([admin]);
+    const simulation = await propose.simulate();
+    expect(simulation.events.map((event) => event.name)).to.deep.equal(["upgradeAuthorityProposed"]);
+    await propose.rpc();
+
+    const configAccount = await program.account.programConfig.fetch(programConfigPda);
+    expect(configAccount.pendingUpgradeAuthority!.toString()).to.equal(authority.toString());
+    expect(configAccount.upgradeAuthority.toString()).to.equal(PublicKey.default.toString());
+
+    // A different key than proposed is rejected, the proposed key waits for the timelock
+    try {
+      await confirm(other).rpc();
+      expect.fail("Should reject confirming a key that was not proposed");
+    } catch (error: any) {
+      expect(error.toString()).to.include("NoPendingUpgradeAuthority");
+    }
+    try {
+      await confirm(authority).rpc();
+      expect.fail("Should not confirm before the timelock expires");
+    } catch (error: any) {
+      expect(error.toString()).to.include("TimelockNotExpired");
+    }
+  });
+
+
+  it("Should reject vesting back into the depositor account unless self-return is allowed", async () => {
+    const now = Math.floor(Date.now() / 1000);
+    const selfVesting = (allowSelfReturn: boolean) =>
+      createNextSchedule(
+        new anchor.BN(100), now + 60, now + 60, now + 3600,
+        { recipient: admin.publicKey, allowSelfReturn },
+        { recipientTokenAccount: adminTokenAccount }
+      );
+
+    try {
+      await selfVesting(false);
+      expect.fail("Should reject recipient account == depositor account");
+    } catch (error: any) {
+      expect(error.toString()).to.include("RecipientEqualsDepositor");
+    }
+
+    const { vestingSchedulePda } = await selfVesting(true);
+    const scheduleAccount = await program.account.vestingSchedule.fetch(vestingSchedulePda);
+    expect(scheduleAccount.recipientTokenAccount.toString()).to.equal(adminTokenAccount.toString());
+  });
+
+
+  it("Should accrue cranks in release-batching mode and transfer them on flush", async () => {
+    const now = Math.floor(Date.now() / 1000);
+    const setBatching = (enabled: boolean) =>
+      program.methods
+        .setReleaseBatching(enabled)
+        .accounts({ authority: admin.publicKey, programConfig: programConfigPda })
+        .signers([admin])
+        .rpc();
+
+    const { vestingSchedulePda, vestingVaultPda } = await createNextSchedule(
+      new anchor.BN(600), now - 20, now - 20, now - 10
+    );
+    const balanceBefore = (await getAccount(provider.connection, recipientTokenAccount)).amount;
+
+    await setBatching(true);
+    try {
+      // Cranks only record what is owed
+      await crankSchedule(vestingSchedulePda, vestingVaultPda);
+      await crankSchedule(vestingSchedulePda, vestingVaultPda);
+
+      let scheduleAccount = await program.account.vestingSchedule.fetch(vestingSchedulePda);
+      expect(scheduleAccount.pendingRelease.toString()).to.equal("600");
+      expect(scheduleAccount.amountTransferred.toString()).to.equal("0");
+      expect((await getAccount(provider.connection, recipientTokenAccount)).amount).to.equal(balanceBefore);
+
+      await program.methods
+        .flushReleases()
+        .accounts({ programConfig: programConfigPda, tokenProgram: TOKEN_PROGRAM_ID })
+        .remainingAccounts([
+          { pubkey: vestingSchedulePda, isWritable: true, isSigner: false },
+          { pubkey: vestingVaultPda, isWritable: true, isSigner: false },
+          { pubkey: recipientTokenAccount, isWritable: true, isSigner: false },
+          { pubkey: mint, isWritable: false, isSigner: false },
+        ])
+        .rpc();
+
+      scheduleAccount = await program.account.vestingSchedule.fetch(vestingSchedulePda);
+      expect(scheduleAccount.pendingRelease.toString()).to.equal("0");
+      expect(scheduleAccount.amountTransferred.toString()).to.equal("600");
+      expect((await getAccount(provider.connection, recipientTokenAccount)).amount)
+        .to.equal(balanceBefore + BigInt(600));
+
+      // Nothing left to accrue: no over-release
+      await crankSchedule(vestingSchedulePda, vestingVaultPda);
+      scheduleAccount = await program.account.vestingSchedule.fetch(vestingSchedulePda);
+      expect(scheduleAccount.pendingRelease.toString()).to.equal("0");
+    } finally {
+      await setBatching(false);
+    }
+  });
+
+
+  it("Should resolve a recipient's primary schedule per mint and keep the first one", async () => {
+    const now = Math.floor(Date.now() / 1000);
+    const grantee = Keypair.generate();
+    const granteeTokenAccount = (await getOrCreateAssociatedTokenAccount(
+      provider.connection, admin, mint, grantee.publicKey
+    )).address;
+    const createForGrantee = () =>
+      createNextSchedule(
+        new anchor.BN(100), now + 60, now + 60, now + 3600,
+        { recipient: grantee.publicKey },
+        { recipientTokenAccount: granteeTokenAccount }
+      );
+
+    const first = await createForGrantee();
+    await createForGrantee();
+
+    const primaryId = await program.methods
+      .getPrimarySchedule(grantee.publicKey, mint)
+      .accounts({ primaryScheduleIndex: findPrimaryScheduleIndex(grantee.publicKey, mint) })
+      .view();
+    expect(primaryId.toString()).to.equal(first.scheduleId.toString());
+  });
+
+
+  it("Should reject amendments of a finalized schedule while releases still work", async () => {
+    const now = Math.floor(Date.now() / 1000);
+    const { vestingSchedulePda, vestingVaultPda } = await createNextSchedule(
+      new anchor.BN(1_000), now - 100, now - 100, now + 100
+    );
+    const finalize = () =>
+      program.methods
+        .finalizeSchedule()
+        .accounts({ admin: admin.publicKey, programConfig: programConfigPda, vestingSchedule: vestingSchedulePda })
+        .signers([admin]);
+
+    const simulation = await finalize().simulate();
+    expect(simulation.events.map((event) => event.name)).to.deep.equal(["scheduleFinalized"]);
+    await finalize().rpc();
+    expect((await program.account.vestingSchedule.fetch(vestingSchedulePda)).immutable).to.equal(true);
+
+    // Finalizing is one-way and cannot be repeated
+    try {
+      await finalize().rpc();
+      expect.fail("Should not finalize twice");
+    } catch (error: any) {
+      expect(error.toString()).to.include("ScheduleImmutable");
+    }
+
+    // Amendments are rejected
+    const configAccount = await program.account.programConfig.fetch(programConfigPda);
+    const newScheduleId = new anchor.BN(configAccount.totalSchedules.toString());
+    const [newSchedulePda, newVaultPda] = findSchedulePdas(newScheduleId);
+    try {
+      await program.methods
+        .reassignUnvested(newScheduleId, {
+          recipient: recipient.publicKey,
+          cliffTimestamp: new anchor.BN(now + 1_000),
+          vestingStartTimestamp: new anchor.BN(now + 1_000),
+          vestingEndTimestamp: new anchor.BN(now + 2_000),
+        })
+        .accounts({
+          admin: admin.publicKey,
+          programConfig: programConfigPda,
+          sourceSchedule: vestingSchedulePda,
+          sourceVault: vestingVaultPda,
+          newSchedule: newSchedulePda,
+          mint: mint,
+          newRecipientTokenAccount: recipientTokenAccount,
+          newVault: newVaultPda,
+          systemProgram: SystemProgram.programId,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          rent: SYSVAR_RENT_PUBKEY,
+        })
+        .signers([admin])
+        .rpc();
+      expect.fail("Should reject amending a finalized schedule");
+    } catch (error: any) {
+      expect(error.toString()).to.include("ScheduleImmutable");
+    }
+
+    // Releases still succeed
+    await crankSchedule(vestingSchedulePda, vestingVaultPda);
+    const scheduleAccount = await program.account.vestingSchedule.fetch(vestingSchedulePda);
+    expect(scheduleAccount.amountTransferred.toNumber()).to.be.greaterThan(0);
+  });
+
+
+  it("Should warn with ReleaseRateTooLow when a mid-vesting crank releases nothing", async () => {
+    const now = Math.floor(Date.now() / 1000);
+    // 3 units over 3000 seconds: one unit every 1000 seconds
+    const { vestingSchedulePda, vestingVaultPda } = await createNextSchedule(
+      new anchor.BN(3), now - 10, now - 10, now + 2_990
+    );
+
+    const simulation = await program.methods
+      .crankVestingSchedule()
+      .accounts({
+        programConfig: programConfigPda,
+        vestingSchedule: vestingSchedulePda,
+        vestingVault: vestingVaultPda,
+        recipientTokenAccount: recipientTokenAccount,
+        mint: mint,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .simulate();
+
+    const warning = simulation.events.find((event) => event.name === "releaseRateTooLow");
+    expect(warning).to.not.be.undefined;
+    expect(warning!.data.suggestedCrankInterval.toNumber()).to.equal(1_000);
+  });
+
+
+  it("Should charge the creation fee on top of the deposit", async () => {
+    const now = Math.floor(Date.now() / 1000);
+    const feeOwner = Keypair.generate();
+    const feeTokenAccount = (await getOrCreateAssociatedTokenAccount(
+      provider.connection, admin, mint, feeOwner.publicKey
+    )).address;
+    const setCreationFee = (bps: number, destination: PublicKey) =>
+      program.methods
+        .setCreationFee(bps, destination)
+        .accounts({ admin: admin.publicKey, programConfig: programConfigPda })
+        .signers([admin])
+        .rpc();
+
+    await setCreationFee(250, feeOwner.publicKey);
+    try {
+      // The fee account is required while the fee is on
+      try {
+        await createNextSchedule(new anchor.BN(1_000), now + 60, now + 60, now + 3600);
+        expect.fail("Should require the fee destination account");
+      } catch (error: any) {
+        expect(error.toString()).to.include("InvalidFeeDestination");
+      }
+
+      const depositorBefore = (await getAccount(provider.connection, adminTokenAccount)).amount;
+      const { vestingSchedulePda, vestingVaultPda } = await createNextSchedule(
+        new anchor.BN(1_000), now + 60, now + 60, now + 3600, {}, { feeDestination: feeTokenAccount }
+      );
+
+      // 2.5% of 1000 goes to the fee account, the schedule and vault keep the full 1000
+      expect((await getAccount(provider.connection, feeTokenAccount)).amount).to.equal(BigInt(25));
+      expect((await getAccount(provider.connection, vestingVaultPda)).amount).to.equal(BigInt(1_000));
+      expect((await getAccount(provider.connection, adminTokenAccount)).amount)
+        .to.equal(depositorBefore - BigInt(1_025));
+      const scheduleAccount = await program.account.vestingSchedule.fetch(vestingSchedulePda);
+      expect(scheduleAccount.totalAmount.toString()).to.equal("1000");
+    } finally {
+      await setCreationFee(0, PublicKey.default);
+    }
+  });
+
+
+  it("Should report the discriminator of every emitted event", async () => {
+    const schemas: { name: string; discriminator: number[] }[] = [];
+    let offset = 0;
+    for (;;) {
+      const page = await program.methods.getEventSchemas(offset).accounts({}).view();
+      schemas.push(...page.schemas);
+      offset = page.nextOffset;
+      if (!page.truncated) break;
+    }
+
+    // Matches the IDL exactly: same event set, same discriminators
+    const idlEvents = program.idl.events!;
+    expect(schemas.map((schema) => schema.name).sort())
+      .to.deep.equal(idlEvents.map((event) => event.name).sort());
+    for (const event of idlEvents) {
+      const schema = schemas.find((entry) => entry.name === event.name)!;
+      expect(Array.from(schema.discriminator)).to.deep.equal(Array.from(event.discriminator));
+    }
+  });
+
+
+  it("Should emit a reconciliation report over schedule and vault pairs", async () => {
+    const now = Math.floor(Date.now() / 1000);
+    const released = await createNextSchedule(new anchor.BN(400), now - 20, now - 20, now - 10);
+    await crankSchedule(released.vestingSchedulePda, released.vestingVaultPda);
+    const pending = await createNextSchedule(new anchor.BN(600), now + 60, now + 60, now + 3600);
+
+    const simulation = await program.methods
+      .emitReconciliation()
+      .accounts({ admin: admin.publicKey, programConfig: programConfigPda })
+      .remainingAccounts(
+        [released, pending].flatMap((entry) => [
+          { pubkey: entry.vestingSchedulePda, isWritable: false, isSigner: false },
+          { pubkey: entry.vestingVaultPda, isWritable: false, isSigner: false },
+        ])
+      )
+      .signers([admin])
+      .simulate();
+
+    const report = simulation.events.find((event) => event.name === "reconciliationReport")!.data;
+    expect(report.schedulesChecked).to.equal(2);
+    expect(report.totalDeposited.toString()).to.equal("1000");
+    expect(report.totalReleased.toString()).to.equal("400");
+    expect(report.totalOutstanding.toString()).to.equal("600");
+    expect(report.totalVaultBalance.toString()).to.equal("600");
+    expect(report.insolventSchedules).to.equal(0);
+
+    // Unpaired accounts are rejected
+    try {
+      await program.methods
+        .emitReconciliation()
+        .accounts({ admin: admin.publicKey, programConfig: programConfigPda })
+        .remainingAccounts([{ pubkey: pending.vestingSchedulePda, isWritable: false, isSigner: false }])
+        .signers([admin])
+        .rpc();
+      expect.fail("Should reject an odd number of remaining accounts");
+    } catch (error: any) {
+      expect(error.toString()).to.include("InvalidRemainingAccounts");
+    }
+  });
+
+
+  it("Should process a schedule passed twice to the batch crank only once", async () => {
+    const now = Math.floor(Date.now() / 1000);
+    const { vestingSchedulePda, vestingVaultPda } = await createNextSchedule(
+      new anchor.BN(300), now - 20, now - 20, now - 10
+    );
+    const entry = [
+      { pubkey: vestingSchedulePda, isWritable: true, isSigner: false },
+      { pubkey: vestingVaultPda, isWritable: true, isSigner: false },
+      { pubkey: recipientTokenAccount, isWritable: true, isSigner: false },
+      { pubkey: mint, isWritable: false, isSigner: false },
+    ];
+    const batchCrank = program.methods
+      .crankVestingSchedulesBatch()
+      .accounts({ programConfig: programConfigPda, tokenProgram: TOKEN_PROGRAM_ID })
+      .remainingAccounts([...entry, ...entry]);
+
+    const summary = await batchCrank.simulate();
+    expect(summary.raw.join("\n")).to.include("appears more than once in the batch");
+
+    const balanceBefore = (await getAccount(provider.connection, recipientTokenAccount)).amount;
+    await batchCrank.rpc();
+
+    const scheduleAccount = await program.account.vestingSchedule.fetch(vestingSchedulePda);
+    expect(scheduleAccount.amountTransferred.toString()).to.equal("300");
+    expect((await getAccount(provider.connection, recipientTokenAccount)).amount)
+      .to.equal(balanceBefore + BigInt(300));
+  });
+
+
+  it("Should let the admin set the keeper reward buffer", async () => {
+    const setRewardBuffer = (buffer: number) =>
+      program.methods
+        .setRewardBuffer(new anchor.BN(buffer))
+        .accounts({ authority: admin.publicKey, programConfig: programConfigPda })
+        .signers([admin]);
+
+    let configAccount = await program.account.programConfig.fetch(programConfigPda);
+    expect(configAccount.rewardBuffer.toString()).to.equal("0");
+
+    const simulation = await setRewardBuffer(5_000).simulate();
+    expect(simulation.events.map((event) => event.name)).to.deep.equal(["rewardBufferUpdated"]);
+    await setRewardBuffer(5_000).rpc();
+    configAccount = await program.account.programConfig.fetch(programConfigPda);
+    expect(configAccount.rewardBuffer.toString()).to.equal("5000");
+
+    await setRewardBuffer(0).rpc();
+  });
+
+
+  it("Should report every configured limit through get_limits", async () => {
+    const adminCall = (builder: any) =>
+      builder.accounts({ authority: admin.publicKey, programConfig: programConfigPda }).signers([admin]).rpc();
+
+    await adminCall(program.methods.setKeeperReward(80));
+    await adminCall(program.methods.setRewardBuffer(new anchor.BN(2_500)));
+    await adminCall(program.methods.setMaxReleasePerTransaction(new anchor.BN(9_000)));
+    try {
+      const limits = await program.methods
+        .getLimits()
+        .accounts({ programConfig: programConfigPda })
+        .view();
+      const configAccount = await program.account.programConfig.fetch(programConfigPda);
+
+      expect(limits.version).to.equal(10);
+      expect(limits.minCrankInterval.toString()).to.equal(configAccount.minCrankInterval.toString());
+      expect(limits.skipZeroEvents).to.equal(configAccount.skipZeroEvents);
+      expect(limits.maxCreateBatchSize).to.equal(10);
+      expect(limits.categoryAllocated.map((amount) => amount.toString()))
+        .to.deep.equal(configAccount.categoryAllocated.map((amount) => amount.toString()));
+      expect(limits.keeperRewardBps).to.equal(80);
+      expect(limits.rewardBuffer.toString()).to.equal("2500");
+      expect(limits.maxReleasePerTransaction.toString()).to.equal("9000");
+      expect(limits.readOnly).to.equal(configAccount.readOnly);
+      expect(limits.cliffEqStartCategories).to.equal(configAccount.cliffEqStartCategories);
+      expect(limits.maxClockSkew.toString()).to.equal(configAccount.maxClockSkew.toString());
+      expect(limits.creationFeeBps).to.equal(configAccount.creationFeeBps);
+      expect(limits.feeDestination.toString()).to.equal(configAccount.feeDestination.toString());
+      expect(limits.maxKeeperRewardBps).to.equal(100);
+      expect(limits.maxCrankBatchSize).to.equal(8);
+      expect(limits.hubUpdateTimelock.toNumber()).to.equal(48 * 60 * 60);
+    } finally {
+      await adminCall(program.methods.setKeeperReward(0));
+      await adminCall(program.methods.setRewardBuffer(new anchor.BN(0)));
+      await adminCall(program.methods.setMaxReleasePerTransaction(new anchor.BN(0)));
+    }
+  });
+
+
+  it("Should reject a vesting end in the past unless explicitly allowed", async () => {
+    const now = Math.floor(Date.now() / 1000);
+
+    try {
+      await createNextSchedule(new anchor.BN(100), now - 200, now - 200, now - 100, { allowPastEnd: false });
+      expect.fail("Should reject a vesting end in the past");
+    } catch (error: any) {
+      expect(error.toString()).to.include("PastVestingEnd");
+    }
+
+    // Future end is fine with the default
+    await createNextSchedule(new anchor.BN(100), now + 60, now + 60, now + 3600, { allowPastEnd: false });
+
+    // Historical schedule when requested
+    const { vestingSchedulePda } = await createNextSchedule(
+      new anchor.BN(100), now - 200, now - 200, now - 100, { allowPastEnd: true }
+    );
+    const scheduleAccount = await program.account.vestingSchedule.fetch(vestingSchedulePda);
+    expect(scheduleAccount.vestingEndTimestamp.toNumber()).to.equal(now - 100);
+  });
+
+
+  it("Should create schedules from a category timing template", async () => {
+    const month = 30 * 24 * 60 * 60;
+    const setTemplate = (cliffDuration: number, vestingDuration: number) =>
+      program.methods
+        .setCategoryTemplate({ team: {} }, {
+          cliffDuration: new anchor.BN(cliffDuration),
+          vestingDuration: new anchor.BN(vestingDuration),
+        })
+        .accounts({ authority: admin.publicKey, programConfig: programConfigPda })
+        .signers([admin])
+        .rpc();
+    const createFromTemplate = async (startTime: number) => {
+      const configAccount = await program.account.programConfig.fetch(programConfigPda);
+      const scheduleId = new anchor.BN(configAccount.totalSchedules.toString());
+      const [vestingSchedulePda, vestingVaultPda] = findSchedulePdas(scheduleId);
+      const builder = program.methods
+        .createFromTemplate(scheduleId, {
+          recipient: recipient.publicKey,
+          totalAmount: new anchor.BN(1_000),
+          sourceCategory: { team: {} },
+          startTime: new anchor.BN(startTime),
+          termsHash: null,
+        })
+        .accounts({
+          admin: admin.publicKey,
+          programConfig: programConfigPda,
+          vestingSchedule: vestingSchedulePda,
+          mint: mint,
+          depositorTokenAccount: adminTokenAccount,
+          recipientTokenAccount: recipientTokenAccount,
+          vestingVault: vestingVaultPda,
+          primaryScheduleIndex: findPrimaryScheduleIndex(recipient.publicKey, mint),
+          feeDestination: null,
+          systemProgram: SystemProgram.programId,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          rent: SYSVAR_RENT_PUBKEY,
+        })
+        .signers([admin]);
+      return { builder, vestingSchedulePda };
+    };
+    const startTime = Math.floor(Date.now() / 1000);
+
+    // No template configured for the category yet
+    try {
+      await (await createFromTemplate(startTime)).builder.rpc();
+      expect.fail("Should require a template for the category");
+    } catch (error: any) {
+      expect(error.toString()).to.include("TemplateNotFound");
+    }
+
+    await setTemplate(6 * month, 36 * month);
+    try {
+      const { builder, vestingSchedulePda } = await createFromTemplate(startTime);
+      const simulation = await builder.simulate();
+      const applied = simulation.events.find((event) => event.name === "templateApplied")!.data;
+      expect(applied.cliffTimestamp.toNumber()).to.equal(startTime + 6 * month);
+      expect(applied.vestingEndTimestamp.toNumber()).to.equal(startTime + 42 * month);
+      await builder.rpc();
+
+      const scheduleAccount = await program.account.vestingSchedule.fetch(vestingSchedulePda);
+      expect(scheduleAccount.cliffTimestamp.toNumber()).to.equal(startTime + 6 * month);
+      expect(scheduleAccount.vestingStartTimestamp.toNumber()).to.equal(startTime + 6 * month);
+      expect(scheduleAccount.vestingEndTimestamp.toNumber()).to.equal(startTime + 42 * month);
+      expect(scheduleAccount.sourceCategory).to.deep.equal({ team: {} });
+    } finally {
+      await setTemplate(0, 0);
+    }
+  });
+
+
+  it("Should claim several schedules in one recipient transaction", async () => {
+    const now = Math.floor(Date.now() / 1000);
+    const vestedA = await createNextSchedule(new anchor.BN(700), now - 200, now - 200, now - 100);
+    const vestedB = await createNextSchedule(new anchor.BN(300), now - 200, now - 200, now - 100);
+    const notReady = await createNextSchedule(new anchor.BN(500), now + 3600, now + 3600, now + 7200, {
+      allowPastEnd: false,
+    });
+    const pairs = (entries: { vestingSchedulePda: PublicKey; vestingVaultPda: PublicKey }[]) =>
+      entries.flatMap((entry) => [
+        { pubkey: entry.vestingSchedulePda, isWritable: true, isSigner: false },
+        { pubkey: entry.vestingVaultPda, isWritable: true, isSigner: false },
+      ]);
+    const claimAll = (signer: Keypair, entries: { vestingSchedulePda: PublicKey; vestingVaultPda: PublicKey }[]) =>
+      program.methods
+        .claimAll()
+        .accounts({
+          recipient: signer.publicKey,
+          programConfig: programConfigPda,
+          recipientTokenAccount: recipientTokenAccount,
+          mint: mint,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .remainingAccounts(pairs(entries))
+        .signers([signer]);
+
+    // Only the recipient of every schedule may claim
+    try {
+      await claimAll(admin, [vestedA]).rpc();
+      expect.fail("Should reject a signer that is not the recipient");
+    } catch (error: any) {
+      expect(error.toString()).to.include("Unauthorized");
+    }
+
+    // Return data reports the total; the not-ready schedule is skipped
+    const simulation = await claimAll(recipient, [vestedA, notReady, vestedB]).simulate();
+    const returnLog = (simulation.raw as string[]).find((log) => log.startsWith("Program return: "));
+    const returnData = Buffer.from(returnLog!.split(" ")[3], "base64");
+    expect(returnData.readBigUInt64LE(0).toString()).to.equal("1000");
+
+    const beforeBalance = await getAccount(provider.connection, recipientTokenAccount);
+    await claimAll(recipient, [vestedA, notReady, vestedB]).rpc();
+    const afterBalance = await getAccount(provider.connection, recipientTokenAccount);
+    expect((afterBalance.amount - beforeBalance.amount).toString()).to.equal("1000");
+
+    const scheduleA = await program.account.vestingSchedule.fetch(vestedA.vestingSchedulePda);
+    const scheduleB = await program.account.vestingSchedule.fetch(vestedB.vestingSchedulePda);
+    const pending = await program.account.vestingSchedule.fetch(notReady.vestingSchedulePda);
+    expect(scheduleA.amountTransferred.toString()).to.equal("700");
+    expect(scheduleB.amountTransferred.toString()).to.equal("300");
+    expect(pending.amountTransferred.toString()).to.equal("0");
+  });
+
+
+  it("Should reserve placeholder schedules and fund them later", async () => {
+    const now = Math.floor(Date.now() / 1000);
+    const setAllowZeroAmount = (allowZeroAmount: boolean) =>
+      program.methods
+        .setAllowZeroAmount(allowZeroAmount)
+        .accounts({ authority: admin.publicKey, programConfig: programConfigPda })
+        .signers([admin])
+        .rpc();
+
+    // Disabled by default
+    try {
+      await createNextSchedule(new anchor.BN(0), now - 200, now - 200, now - 100);
+      expect.fail("Should reject zero-amount schedules unless enabled");
+    } catch (error: any) {
+      expect(error.toString()).to.include("InvalidAmount");
+    }
+
+    await setAllowZeroAmount(true);
+    try {
+      const placeholder = await createNextSchedule(new anchor.BN(0), now - 200, now - 200, now - 100);
+      const reserved = await program.account.vestingSchedule.fetch(placeholder.vestingSchedulePda);
+      expect(reserved.placeholder).to.equal(true);
+      expect(reserved.recipient.toString()).to.equal(recipient.publicKey.toString());
+      expect((await getAccount(provider.connection, placeholder.vestingVaultPda)).amount.toString()).to.equal("0");
+
+      // Nothing to release until funded
+      const beforeCrank = await getAccount(provider.connection, recipientTokenAccount);
+      await crankSchedule(placeholder.vestingSchedulePda, placeholder.vestingVaultPda);
+      const afterCrank = await getAccount(provider.connection, recipientTokenAccount);
+      expect(afterCrank.amount.toString()).to.equal(beforeCrank.amount.toString());
+
+      // The top-up sets the real total, after which the (already vested) schedule releases
+      await program.methods
+        .topUpBatch([new anchor.BN(500)])
+        .accounts({
+          admin: admin.publicKey,
+          programConfig: programConfigPda,
+          mint: mint,
+          depositorTokenAccount: adminTokenAccount,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .remainingAccounts([
+          { pubkey: placeholder.vestingSchedulePda, isWritable: true, isSigner: false },
+          { pubkey: placeholder.vestingVaultPda, isWritable: true, isSigner: false },
+        ])
+        .signers([admin])
+        .rpc();
+      const funded = await program.account.vestingSchedule.fetch(placeholder.vestingSchedulePda);
+      expect(funded.placeholder).to.equal(false);
+      expect(funded.totalAmount.toString()).to.equal("500");
+
+      await crankSchedule(placeholder.vestingSchedulePda, placeholder.vestingVaultPda);
+      const afterFunding = await getAccount(provider.connection, recipientTokenAccount);
+      expect((afterFunding.amount - afterCrank.amount).toString()).to.equal("500");
+    } finally {
+      await setAllowZeroAmount(false);
+    }
+  });
+
+
+  it("Should estimate payout completion for a crank cadence", async () => {
+    const now = Math.floor(Date.now() / 1000);
+    // 36_000 tokens over 36_000 seconds, 1 token per second, already vesting
+    const vestingEnd = now + 36_000;
+    const { vestingSchedulePda } = await createNextSchedule(new anchor.BN(36_000), now - 100, now - 100, vestingEnd, {
+      allowPastEnd: false,
+    });
+    const eta = (crankInterval: number, minRelease: number) =>
+      program.methods
+        .getCompletionEta(new anchor.BN(crankInterval), new anchor.BN(minRelease))
+        .accounts({ vestingSchedule: vestingSchedulePda })
+        .view();
+
+    // Frequent cranks finish within one interval of vesting end
+    const hourly = (await eta(3_600, 0)).toNumber();
+    expect(hourly).to.be.at.least(vestingEnd);
+    expect(hourly).to.be.below(vestingEnd + 3_600);
+
+    // A minimum of 3 hours' unlock per crank stretches the cadence and the overshoot
+    const thresholded = (await eta(3_600, 3 * 3_600)).toNumber();
+    expect(thresholded).to.be.at.least(hourly);
+    expect(thresholded).to.be.below(vestingEnd + 3 * 3_600);
+
+    try {
+      await eta(0, 0);
+      expect.fail("Should reject a zero crank interval");
+    } catch (error: any) {
+      expect(error.toString()).to.include("InvalidTimestamps");
+    }
+  });
+
+
+  it("Should verify schedule terms against the agreed off-chain hash", async () => {
+    const now = Math.floor(Date.now() / 1000);
+    const terms = {
+      totalAmount: 1_000,
+      cliff: now + 100,
+      vestingStart: now + 200,
+      vestingEnd: now + 300,
+    };
+    // Canonical layout of VestingSchedule::canonical_terms
+    const termsHash = (totalAmount: number) => {
+      const numbers = Buffer.alloc(32);
+      numbers.writeBigUInt64LE(BigInt(totalAmount), 0);
+      numbers.writeBigInt64LE(BigInt(terms.cliff), 8);
+      numbers.writeBigInt64LE(BigInt(terms.vestingStart), 16);
+      numbers.writeBigInt64LE(BigInt(terms.vestingEnd), 24);
+      return [...createHash("sha256")
+        .update(Buffer.concat([
+          Buffer.from("haio-vesting:terms:v1"),
+          recipient.publicKey.toBuffer(),
+          mint.toBuffer(),
+          numbers,
+          // Linear curve, no initial unlock and no second cliff: nothing is appended
+          Buffer.from([0 /* public */, 0 /* round_up */]),
+        ]))
+        .digest()];
+    };
+    const verifyTerms = (vestingSchedulePda: PublicKey) =>
+      program.methods.verifyTerms().accounts({ vestingSchedule: vestingSchedulePda }).view();
+
+    const agreed = await createNextSchedule(
+      new anchor.BN(terms.totalAmount), terms.cliff, terms.vestingStart, terms.vestingEnd,
+      { termsHash: termsHash(terms.totalAmount) }
+    );
+    expect(await verifyTerms(agreed.vestingSchedulePda)).to.equal(true);
+
+    // Recorded against different terms than the ones created
+    const tampered = await createNextSchedule(
+      new anchor.BN(terms.totalAmount), terms.cliff, terms.vestingStart, terms.vestingEnd,
+      { termsHash: termsHash(terms.totalAmount + 1) }
+    );
+    expect(await verifyTerms(tampered.vestingSchedulePda)).to.equal(false);
+
+    // Amending a covered field after creation breaks the match
+    await program.methods
+      .topUpBatch([new anchor.BN(1)])
+      .accounts({
+        admin: admin.publicKey,
+        programConfig: programConfigPda,
+        mint: mint,
+        depositorTokenAccount: adminTokenAccount,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .remainingAccounts([
+        { pubkey: agreed.vestingSchedulePda, isWritable: true, isSigner: false },
+        { pubkey: agreed.vestingVaultPda, isWritable: true, isSigner: false },
+      ])
+      .signers([admin])
+      .rpc();
+    expect(await verifyTerms(agreed.vestingSchedulePda)).to.equal(false);
+
+    const unhashed = await createNextSchedule(
+      new anchor.BN(terms.totalAmount), terms.cliff, terms.vestingStart, terms.vestingEnd
+    );
+    try {
+      await verifyTerms(unhashed.vestingSchedulePda);
+      expect.fail("Should require a recorded terms hash");
+    } catch (error: any) {
+      expect(error.toString()).to.include("TermsHashNotSet");
+    }
+  });
+
+
+  it("Should scope delegated admin actions by capability", async () => {
+    const CAPABILITY_CREATE = 1;
+    const CAPABILITY_CRANK_MANAGE = 2;
+    const CAPABILITY_CONFIG = 4;
+    const creator = Keypair.generate();
+    const operator = Keypair.generate();
+    await provider.connection.confirmTransaction(
+      await provider.connection.requestAirdrop(creator.publicKey, LAMPORTS_PER_SOL)
+    );
+    const creatorTokenAccount = (await getOrCreateAssociatedTokenAccount(
+      provider.connection, admin, mint, creator.publicKey
+    )).address;
+    await mintTo(provider.connection, admin, mint, creatorTokenAccount, admin, BigInt(1_000));
+
+    const adminCall = (builder: any) =>
+      builder.accounts({ admin: admin.publicKey, programConfig: programConfigPda }).signers([admin]).rpc();
+    const delegateCall = (builder: any, signer: Keypair) =>
+      builder.accounts({ authority: signer.publicKey, programConfig: programConfigPda }).signers([signer]).rpc();
+    const expectMissingCapability = async (action: () => Promise<unknown>) => {
+      try {
+        await action();
+        expect.fail("Should require the capability");
+      } catch (error: any) {
+        expect(error.toString()).to.include("MissingCapability");
+      }
+    };
+    const createAs = async (signer: Keypair) => {
+      const now = Math.floor(Date.now() / 1000);
+      const configAccount = await program.account.programConfig.fetch(programConfigPda);
+      const scheduleId = new anchor.BN(configAccount.totalSchedules.toString());
+      const [vestingSchedulePda, vestingVaultPda] = findSchedulePdas(scheduleId);
+      await program.methods
+        .createVestingSchedule(scheduleId, {
+          recipient: recipient.publicKey,
+          totalAmount: new anchor.BN(1_000),
+          cliffTimestamp: new anchor.BN(now + 100),
+          vestingStartTimestamp: new anchor.BN(now + 100),
+          vestingEndTimestamp: new anchor.BN(now + 200),
+          sourceCategory: { public: {} },
+          roundUp: false,
+          grantGroupId: new anchor.BN(0),
+          minClaimInterval: new anchor.BN(0),
+          allowSelfReturn: false,
+          allowPastEnd: false,
+          termsHash: null,
+          initialUnlockBps: 0,
+          vestingCurve: { linear: {} },
+          nonTransferable: false,
+          label: Array(32).fill(0),
+          secondCliffTimestamp: new anchor.BN(0),
+          secondCliffBps: 0,
+        })
+        .accounts({
+          admin: signer.publicKey,
+          programConfig: programConfigPda,
+          vestingSchedule: vestingSchedulePda,
+          mint: mint,
+          depositorTokenAccount: creatorTokenAccount,
+          recipientTokenAccount: recipientTokenAccount,
+          vestingVault: vestingVaultPda,
+          primaryScheduleIndex: findPrimaryScheduleIndex(recipient.publicKey, mint),
+          feeDestination: null,
+          systemProgram: SystemProgram.programId,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          rent: SYSVAR_RENT_PUBKEY,
+        })
+        .signers([signer])
+        .rpc();
+      return vestingSchedulePda;
+    };
+
+    await adminCall(program.methods.grantCapability(creator.publicKey, CAPABILITY_CREATE));
+    await adminCall(program.methods.grantCapability(operator.publicKey, CAPABILITY_CRANK_MANAGE));
+    try {
+      // Create capability: creation only
+      const vestingSchedulePda = await createAs(creator);
+      const schedule = await program.account.vestingSchedule.fetch(vestingSchedulePda);
+      expect(schedule.depositor.toString()).to.equal(creator.publicKey.toString());
+      await expectMissingCapability(() => delegateCall(program.methods.setKeeperReward(50), creator));
+      await expectMissingCapability(() => delegateCall(program.methods.setMaxClockSkew(new anchor.BN(600)), creator));
+
+      // Crank-manage capability: crank policy only
+      await delegateCall(program.methods.setKeeperReward(50), operator);
+      expect((await program.account.programConfig.fetch(programConfigPda)).keeperRewardBps).to.equal(50);
+      await delegateCall(program.methods.setKeeperReward(0), operator);
+      await expectMissingCapability(() => createAs(operator));
+      await expectMissingCapability(() => delegateCall(program.methods.setMaxClockSkew(new anchor.BN(600)), operator));
+
+      // Granting and revoking stays with the admin
+      try {
+        await program.methods
+          .grantCapability(operator.publicKey, CAPABILITY_CREATE)
+          .accounts({ admin: operator.publicKey, programConfig: programConfigPda })
+          .signers([operator])
+          .rpc();
+        expect.fail("Delegates must not grant capabilities");
+      } catch (error: any) {
+        expect(error.toString()).to.include("Unauthorized");
+      }
+
+      // Read-only mode stays with the admin, even for config delegates
+      await adminCall(program.methods.grantCapability(creator.publicKey, CAPABILITY_CONFIG));
+      try {
+        await program.methods
+          .setReadOnly(true)
+          .accounts({ admin: creator.publicKey, programConfig: programConfigPda })
+          .signers([creator])
+          .rpc();
+        expect.fail("Delegates must not toggle read-only mode");
+      } catch (error: any) {
+        expect(error.toString()).to.include("Unauthorized");
+      }
+
+      // Revocation takes effect immediately
+      await adminCall(program.methods.revokeCapability(operator.publicKey, CAPABILITY_CRANK_MANAGE));
+      await expectMissingCapability(() => delegateCall(program.methods.setKeeperReward(50), operator));
+      const configAccount = await program.account.programConfig.fetch(programConfigPda);
+      expect(configAccount.delegates.some((delegate: any) => delegate.key.equals(operator.publicKey) && delegate.capabilities > 0))
+        .to.equal(false);
+    } finally {
+      await adminCall(program.methods.revokeCapability(creator.publicKey, 0b111));
+      await adminCall(program.methods.revokeCapability(operator.publicKey, 0b111));
+    }
+  });
+
+
+  it("Should leave funded schedules untouched when auto-extend is enabled", async () => {
+    const now = Math.floor(Date.now() / 1000);
+    const setAutoExtend = (enabled: boolean) =>
+      program.methods
+        .setAutoExtendUnderfunded(enabled)
+        .accounts({ authority: admin.publicKey, programConfig: programConfigPda })
+        .signers([admin])
+        .rpc();
+
+    await setAutoExtend(true);
+    try {
+      const limits = await program.methods.getLimits().accounts({ programConfig: programConfigPda }).view();
+      expect(limits.autoExtendUnderfunded).to.equal(true);
+
+      // Vault covers the transferable amount, so the crank releases on the original curve
+      const { vestingSchedulePda, vestingVaultPda } = await createNextSchedule(
+        new anchor.BN(1_000), now - 500, now - 500, now + 500, { allowPastEnd: false }
+      );
+      await crankSchedule(vestingSchedulePda, vestingVaultPda);
+      const schedule = await program.account.vestingSchedule.fetch(vestingSchedulePda);
+      expect(schedule.vestingEndTimestamp.toNumber()).to.equal(now + 500);
+      expect(schedule.amountTransferred.toNumber()).to.be.at.least(500);
+    } finally {
+      await setAutoExtend(false);
+    }
+  });
+
+
+  it("Should list the schedules due for cranking, largest first", async () => {
+    const now = Math.floor(Date.now() / 1000);
+    const small = await createNextSchedule(new anchor.BN(300), now - 200, now - 200, now - 100);
+    const large = await createNextSchedule(new anchor.BN(700), now - 200, now - 200, now - 100);
+    const notReady = await createNextSchedule(new anchor.BN(900), now + 3600, now + 3600, now + 7200, {
+      allowPastEnd: false,
+    });
+    const medium = await createNextSchedule(new anchor.BN(500), now - 200, now - 200, now - 100);
+    const schedulesDue = (entries: { vestingSchedulePda: PublicKey }[], maxResults: number) =>
+      program.methods
+        .schedulesDue(maxResults)
+        .accounts({ programConfig: programConfigPda })
+        .remainingAccounts(entries.map((entry) => ({
+          pubkey: entry.vestingSchedulePda, isWritable: false, isSigner: false,
+        })))
+        .view();
+    const ids = (result: anchor.BN[]) => result.map((id) => id.toString());
+
+    const candidates = [small, notReady, large, medium];
+    expect(ids(await schedulesDue(candidates, 8))).to.deep.equal(
+      [large, medium, small].map((entry) => entry.scheduleId.toString())
+    );
+    expect(ids(await schedulesDue(candidates, 2))).to.deep.equal(
+      [large, medium].map((entry) => entry.scheduleId.toString())
+    );
+
+    // Nothing due once released
+    await crankSchedule(large.vestingSchedulePda, large.vestingVaultPda);
+    expect(ids(await schedulesDue([notReady, large], 8))).to.deep.equal([]);
+
+    try {
+      await schedulesDue(candidates, 9);
+      expect.fail("Should bound the output to one crank batch");
+    } catch (error: any) {
+      expect(error.toString()).to.include("InvalidBatchSize");
+    }
+  });
+
+
+  it("Should report the seconds until a proposed hub change is confirmable", async () => {
+    const hubTimelock = 48 * 60 * 60;
+    const proposedOwner = Keypair.generate();
+    const proposedHub = (await getOrCreateAssociatedTokenAccount(
+      provider.connection, admin, mint, proposedOwner.publicKey
+    )).address;
+    const updateHub = () =>
+      program.methods
+        .updateDistributionHub()
+        .accounts({
+          admin: admin.publicKey,
+          programConfig: programConfigPda,
+          mint,
+          hubTokenAccount: proposedHub,
+        })
+        .signers([admin]);
+
+    // The hub is already set, so this proposes (or restarts) a timelocked change
+    const simulation = await updateHub().simulate();
+    const returnLog = (simulation.raw as string[]).find((log) => log.startsWith("Program return: "));
+    const secondsUntilConfirmable = Buffer.from(returnLog!.split(" ")[3], "base64").readBigInt64LE(0);
+    expect(Number(secondsUntilConfirmable)).to.equal(hubTimelock);
+    await updateHub().rpc();
+
+    // A too-early confirmation reports how long is left
+    try {
+      await updateHub().rpc();
+      expect.fail("Should not confirm before the timelock expires");
+    } catch (error: any) {
+      expect(error.toString()).to.include("TimelockNotExpired");
+      const remainingLog = (error.logs as string[]).find((log) => log.includes("confirmable in"));
+      const remaining = Number(remainingLog!.match(/confirmable in (\d+) seconds/)![1]);
+      expect(remaining).to.be.greaterThan(0);
+      expect(remaining).to.be.at.most(hubTimelock);
+    }
+  });
+
+
+  it("Should sweep leftover vault balance of a completed schedule and track it as swept dust", async () => {
+    const now = Math.floor(Date.now() / 1000);
+    const { vestingSchedulePda, vestingVaultPda } = await createNextSchedule(
+      new anchor.BN(1_000), now - 20, now - 20, now - 10
+    );
+    const configBefore = await program.account.programConfig.fetch(programConfigPda);
+    const sweep = () =>
+      program.methods
+        .sweepRoundingDust()
+        .accounts({
+          admin: admin.publicKey,
+          programConfig: programConfigPda,
+          vestingSchedule: vestingSchedulePda,
+          vestingVault: vestingVaultPda,
+          hubTokenAccount: configBefore.distributionHub,
+          mint: mint,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([admin]);
+
+    // Nothing can be swept while the recipient is still owed tokens
+    try {
+      await sweep().rpc();
+      expect.fail("Should not sweep an incomplete schedule");
+    } catch (error: any) {
+      expect(error.toString()).to.include("ScheduleNotFullyVested");
+    }
+
+    await crankSchedule(vestingSchedulePda, vestingVaultPda);
+    await mintTo(provider.connection, admin, mint, vestingVaultPda, admin, BigInt(3));
+
+    const simulation = await sweep().simulate();
+    const returnLog = (simulation.raw as string[]).find((log) => log.startsWith("Program return: "));
+    expect(Buffer.from(returnLog!.split(" ")[3], "base64").readBigUInt64LE(0).toString()).to.equal("3");
+    expect(simulation.events.map((event) => event.name)).to.deep.equal(["roundingDustSwept"]);
+    await sweep().rpc();
+
+    const configAfter = await program.account.programConfig.fetch(programConfigPda);
+    expect(configAfter.sweptDust.sub(configBefore.sweptDust).toString()).to.equal("3");
+    expect((await getAccount(provider.connection, vestingVaultPda)).amount.toString()).to.equal("0");
+
+    // The emptied vault can now be closed
+    await program.methods
+      .closeVestingSchedule()
+      .accounts({
+        beneficiary: admin.publicKey,
+        vestingSchedule: vestingSchedulePda,
+        vestingVault: vestingVaultPda,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([admin])
+      .rpc();
+  });
+
+
+  it("Should close a schedule and its vault in the same transaction as its final crank", async () => {
+    const now = Math.floor(Date.now() / 1000);
+    const keeper = Keypair.generate();
+    await provider.connection.confirmTransaction(
+      await provider.connection.requestAirdrop(keeper.publicKey, LAMPORTS_PER_SOL)
+    );
+    const crankAndMaybeClose = (vestingSchedulePda: PublicKey, vestingVaultPda: PublicKey) =>
+      program.methods
+        .crankAndMaybeClose()
+        .accounts({
+          keeper: keeper.publicKey,
+          programConfig: programConfigPda,
+          vestingSchedule: vestingSchedulePda,
+          vestingVault: vestingVaultPda,
+          recipientTokenAccount: recipientTokenAccount,
+          mint: mint,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([keeper]);
+    const returnedClosed = async (vestingSchedulePda: PublicKey, vestingVaultPda: PublicKey) => {
+      const simulation = await crankAndMaybeClose(vestingSchedulePda, vestingVaultPda).simulate();
+      const returnLog = (simulation.raw as string[]).find((log) => log.startsWith("Program return: "));
+      return Buffer.from(returnLog!.split(" ")[3], "base64")[0] === 1;
+    };
+
+    // A mid-vesting crank releases but keeps the accounts open
+    const ongoing = await createNextSchedule(new anchor.BN(1_000), now - 10, now - 10, now + 3_600);
+    expect(await returnedClosed(ongoing.vestingSchedulePda, ongoing.vestingVaultPda)).to.be.false;
+    await crankAndMaybeClose(ongoing.vestingSchedulePda, ongoing.vestingVaultPda).rpc();
+    const ongoingSchedule = await program.account.vestingSchedule.fetch(ongoing.vestingSchedulePda);
+    expect(ongoingSchedule.amountTransferred.toNumber()).to.be.greaterThan(0);
+
+    // The final crank releases the remainder and closes both accounts, rent going to the keeper
+    const completed = await createNextSchedule(new anchor.BN(1_000), now - 20, now - 20, now - 10);
+    expect(await returnedClosed(completed.vestingSchedulePda, completed.vestingVaultPda)).to.be.true;
+    const recipientBefore = (await getAccount(provider.connection, recipientTokenAccount)).amount;
+    const keeperBefore = await provider.connection.getBalance(keeper.publicKey);
+    await crankAndMaybeClose(completed.vestingSchedulePda, completed.vestingVaultPda).rpc();
+
+    const recipientAfter = (await getAccount(provider.connection, recipientTokenAccount)).amount;
+    expect((recipientAfter - recipientBefore).toString()).to.equal("1000");
+    expect(await program.account.vestingSchedule.fetchNullable(completed.vestingSchedulePda)).to.be.null;
+    expect(await provider.connection.getAccountInfo(completed.vestingVaultPda)).to.be.null;
+    expect(await provider.connection.getBalance(keeper.publicKey)).to.be.greaterThan(keeperBefore);
+  });
+
+
+  it("Should let the recipient claim vested tokens directly and succeed with nothing claimable", async () => {
+    const now = Math.floor(Date.now() / 1000);
+    const claimVested = (vestingSchedulePda: PublicKey, vestingVaultPda: PublicKey, signer = recipient) =>
+      program.methods
+        .claimVested()
+        .accounts({
+          recipient: signer.publicKey,
+          programConfig: programConfigPda,
+          vestingSchedule: vestingSchedulePda,
+          vestingVault: vestingVaultPda,
+          recipientTokenAccount: recipientTokenAccount,
+          mint: mint,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([signer]);
+
+    const vested = await createNextSchedule(new anchor.BN(1_000), now - 20, now - 20, now - 10);
+
+    // Only the recipient can claim
+    try {
+      await claimVested(vested.vestingSchedulePda, vested.vestingVaultPda, Keypair.generate()).rpc();
+      expect.fail("Should not let another signer claim");
+    } catch (error: any) {
+      expect(error.toString()).to.include("Unauthorized");
+    }
+
+    const recipientBefore = (await getAccount(provider.connection, recipientTokenAccount)).amount;
+    await claimVested(vested.vestingSchedulePda, vested.vestingVaultPda).rpc();
+    const recipientAfter = (await getAccount(provider.connection, recipientTokenAccount)).amount;
+    expect((recipientAfter - recipientBefore).toString()).to.equal("1000");
+    const claimed = await program.account.vestingSchedule.fetch(vested.vestingSchedulePda);
+    expect(claimed.amountTransferred.toString()).to.equal("1000");
+
+    // Before the cliff the claim succeeds with a zero-amount TokensReleased
+    const locked = await createNextSchedule(new anchor.BN(1_000), now + 600, now + 600, now + 3_600);
+    const simulation = await claimVested(locked.vestingSchedulePda, locked.vestingVaultPda).simulate();
+    const released = simulation.events.filter((event) => event.name === "tokensReleased");
+    expect(released).to.have.length(1);
+    expect(released[0].data.amount.toString()).to.equal("0");
+    await claimVested(locked.vestingSchedulePda, locked.vestingVaultPda).rpc();
+    const untouched = await program.account.vestingSchedule.fetch(locked.vestingSchedulePda);
+    expect(untouched.amountTransferred.toString()).to.equal("0");
+  });
+
+
+  it("Should cap the number of open schedules per mint and free a slot on close", async () => {
+    const now = Math.floor(Date.now() / 1000);
+    const capMint = await createMint(provider.connection, admin, admin.publicKey, null, 6);
+    const adminAccount = (await getOrCreateAssociatedTokenAccount(
+      provider.connection, admin, capMint, admin.publicKey
+    )).address;
+    const recipientAccount = (await getOrCreateAssociatedTokenAccount(
+      provider.connection, admin, capMint, recipient.publicKey
+    )).address;
+    await mintTo(provider.connection, admin, capMint, adminAccount, admin, BigInt(10_000));
+    const [mintScheduleCountPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("mint_schedules"), capMint.toBuffer()],
+      program.programId
+    );
+
+    const setMaxSchedulesPerMint = (maxSchedulesPerMint: number) =>
+      program.methods
+        .setMaxSchedulesPerMint(new anchor.BN(maxSchedulesPerMint))
+        .accounts({ authority: admin.publicKey, programConfig: programConfigPda })
+        .signers([admin])
+        .rpc();
+    const createCapped = () =>
+      createNextSchedule(
+        new anchor.BN(1_000), now - 20, now - 20, now - 10, {},
+        { mint: capMint, depositorTokenAccount: adminAccount, recipientTokenAccount: recipientAccount }
+      );
+
+    await setMaxSchedulesPerMint(2);
+    try {
+      const first = await createCapped();
+      await createCapped();
+      let counter = await program.account.mintScheduleCount.fetch(mintScheduleCountPda);
+      expect(counter.activeSchedules.toString()).to.equal("2");
+
+      // A third open schedule of the mint is rejected
+      try {
+        await createCapped();
+        expect.fail("Should not exceed the per-mint schedule limit");
+      } catch (error: any) {
+        expect(error.toString()).to.include("MaxSchedulesPerMintReached");
+      }
+
+      // Completing and closing a schedule frees its slot
+      await program.methods
+        .crankVestingSchedule()
+        .accounts({
+          programConfig: programConfigPda,
+          vestingSchedule: first.vestingSchedulePda,
+          vestingVault: first.vestingVaultPda,
+          recipientTokenAccount: recipientAccount,
+          mint: capMint,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .rpc();
+      await program.methods
+        .closeVestingSchedule()
+        .accounts({
+          beneficiary: admin.publicKey,
+          vestingSchedule: first.vestingSchedulePda,
+          vestingVault: first.vestingVaultPda,
+          mintScheduleCount: mintScheduleCountPda,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([admin])
+        .rpc();
+      counter = await program.account.mintScheduleCount.fetch(mintScheduleCountPda);
+      expect(counter.activeSchedules.toString()).to.equal("1");
+
+      await createCapped();
+      counter = await program.account.mintScheduleCount.fetch(mintScheduleCountPda);
+      expect(counter.activeSchedules.toString()).to.equal("2");
+    } finally {
+      await setMaxSchedulesPerMint(0);
+    }
+
+    // Unlimited again
+    await createCapped();
+    const limits = await program.methods
+      .getLimits()
+      .accounts({ programConfig: programConfigPda })
+      .view();
+    expect(limits.maxSchedulesPerMint.toString()).to.equal("0");
+  });
+
+
+  it("Should release the initial unlock at the cliff and reject more than 100%", async () => {
+    const now = Math.floor(Date.now() / 1000);
+
+    try {
+      await createNextSchedule(new anchor.BN(1_000), now + 60, now + 60, now + 3_600, {
+        initialUnlockBps: 10_001,
+      });
+      expect.fail("Should reject an initial unlock above 10000 bps");
+    } catch (error: any) {
+      expect(error.toString()).to.include("InvalidBps");
+    }
+
+    // Half unlocks at the cliff, the other half vests over a long window
+    const { vestingSchedulePda, vestingVaultPda } = await createNextSchedule(
+      new anchor.BN(1_000), now - 5, now - 5, now + 1_000_000, { initialUnlockBps: 5_000 }
+    );
+    const schedule = await program.account.vestingSchedule.fetch(vestingSchedulePda);
+    expect(schedule.initialUnlockBps).to.equal(5_000);
+
+    await crankSchedule(vestingSchedulePda, vestingVaultPda);
+    const released = (await program.account.vestingSchedule.fetch(vestingSchedulePda)).amountTransferred;
+    expect(released.toNumber()).to.be.at.least(500);
+    expect(released.toNumber()).to.be.below(510);
+  });
+
+
+  it("Should preview the clawback of a revocation across vesting stages", async () => {
+    const now = Math.floor(Date.now() / 1000);
+    const clawbackPreview = (vestingSchedulePda: PublicKey, vestingVaultPda: PublicKey) =>
+      program.methods
+        .getClawbackPreview()
+        .accounts({ vestingSchedule: vestingSchedulePda, vestingVault: vestingVaultPda })
+        .view();
+
+    // Before the cliff the whole vault would be reclaimed
+    const locked = await createNextSchedule(new anchor.BN(1_000), now + 600, now + 600, now + 3_600);
+    expect((await clawbackPreview(locked.vestingSchedulePda, locked.vestingVaultPda)).toString()).to.equal("1000");
+
+    // Mid-way only the unvested part would be reclaimed
+    const midway = await createNextSchedule(new anchor.BN(1_000), now - 500, now - 500, now + 500);
+    const midwayPreview = (await clawbackPreview(midway.vestingSchedulePda, midway.vestingVaultPda)).toNumber();
+    expect(midwayPreview).to.be.greaterThan(400);
+    expect(midwayPreview).to.be.lessThan(600);
+
+    // Fully vested schedules have nothing to reclaim even before the final crank
+    const vested = await createNextSchedule(new anchor.BN(1_000), now - 20, now - 20, now - 10);
+    expect((await clawbackPreview(vested.vestingSchedulePda, vested.vestingVaultPda)).toString()).to.equal("0");
+  });
+
+  it("Should revoke a schedule, paying the vested part and clawing back the rest", async () => {
+    const now = Math.floor(Date.now() / 1000);
+    const { vestingSchedulePda, vestingVaultPda } = await createNextSchedule(
+      new anchor.BN(1_000), now - 500, now - 500, now + 500
+    );
+    const revoke = () =>
+      program.methods
+        .revokeVestingSchedule()
+        .accounts({
+          admin: admin.publicKey,
+          programConfig: programConfigPda,
+          vestingSchedule: vestingSchedulePda,
+          vestingVault: vestingVaultPda,
+          recipientTokenAccount: recipientTokenAccount,
+          clawbackTokenAccount: adminTokenAccount,
+          mint: mint,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([admin])
+        .rpc();
+
+    const recipientBefore = (await getAccount(provider.connection, recipientTokenAccount)).amount;
+    const adminBefore = (await getAccount(provider.connection, adminTokenAccount)).amount;
+    await revoke();
+    const paid = Number((await getAccount(provider.connection, recipientTokenAccount)).amount - recipientBefore);
+    const clawedBack = Number((await getAccount(provider.connection, adminTokenAccount)).amount - adminBefore);
+
+    expect(paid).to.be.greaterThan(400);
+    expect(paid).to.be.lessThan(600);
+    expect(paid + clawedBack).to.equal(1_000);
+    expect((await getAccount(provider.connection, vestingVaultPda)).amount.toString()).to.equal("0");
+
+    const schedule = await program.account.vestingSchedule.fetch(vestingSchedulePda);
+    expect(schedule.revoked).to.be.true;
+    expect(schedule.amountTransferred.toNumber()).to.equal(paid);
+    expect(schedule.totalAmount.toNumber()).to.equal(paid);
+
+    // Later cranks release nothing
+    await crankSchedule(vestingSchedulePda, vestingVaultPda);
+    const afterCrank = await program.account.vestingSchedule.fetch(vestingSchedulePda);
+    expect(afterCrank.amountTransferred.toNumber()).to.equal(paid);
+
+    try {
+      await revoke();
+      expect.fail("Should not revoke a schedule twice");
+    } catch (error: any) {
+      expect(error.toString()).to.include("ScheduleRevoked");
+    }
+  });
+
+  it("Should record the swap program and keep schedules funded in their release mint", async () => {
+    const setSwapProgram = (swapProgram: PublicKey, enabled: boolean) =>
+      program.methods
+        .setSwapProgram(enabled)
+        .accounts({
+          admin: admin.publicKey,
+          programConfig: programConfigPda,
+          swapProgram,
+        })
+        .signers([admin])
+        .rpc();
+
+    for (const rejected of [TOKEN_PROGRAM_ID, program.programId, Keypair.generate().publicKey]) {
+      try {
+        await setSwapProgram(rejected, true);
+        expect.fail("Should reject an invalid swap program");
+      } catch (error: any) {
+        expect(error.toString()).to.include("InvalidSwapProgram");
+      }
+    }
+
+    await setSwapProgram(SystemProgram.programId, true);
+    let config = await program.account.programConfig.fetch(programConfigPda);
+    expect(config.swapProgram.toBase58()).to.equal(SystemProgram.programId.toBase58());
+
+    await setSwapProgram(SystemProgram.programId, false);
+    config = await program.account.programConfig.fetch(programConfigPda);
+    expect(config.swapProgram.toBase58()).to.equal(PublicKey.default.toBase58());
+
+    const now = Math.floor(Date.now() / 1000);
+    const { vestingSchedulePda } = await createNextSchedule(new anchor.BN(1_000), now, now, now + 3_600);
+    const schedule = await program.account.vestingSchedule.fetch(vestingSchedulePda);
+    expect(schedule.fundingMint.toBase58()).to.equal(schedule.mint.toBase58());
+  });
+
+  it("Should block cranks while paused and resume after unpausing", async () => {
+    const setPause = (paused: boolean) =>
+      program.methods
+        .setPause(paused)
+        .accounts({ admin: admin.publicKey, programConfig: programConfigPda })
+        .signers([admin])
+        .rpc();
+
+    const now = Math.floor(Date.now() / 1000);
+    const { vestingSchedulePda, vestingVaultPda } = await createNextSchedule(
+      new anchor.BN(1_000), now - 20, now - 20, now - 10
+    );
+
+    await setPause(true);
+    try {
+      expect((await program.account.programConfig.fetch(programConfigPda)).paused).to.be.true;
+
+      // Creating schedules keeps working while paused
+      await createNextSchedule(new anchor.BN(1_000), now, now, now + 3_600);
+
+      try {
+        await crankSchedule(vestingSchedulePda, vestingVaultPda);
+        expect.fail("Crank should fail while paused");
+      } catch (error: any) {
+        expect(error.toString()).to.include("ProgramPaused");
+      }
+    } finally {
+      await setPause(false);
+    }
+
+    await crankSchedule(vestingSchedulePda, vestingVaultPda);
+    const schedule = await program.account.vestingSchedule.fetch(vestingSchedulePda);
+    expect(schedule.amountTransferred.toString()).to.equal("1000");
+  });
+
+  it("Should propose an admin transfer that only the new admin can accept after the timelock", async () => {
+    const newAdmin = Keypair.generate();
+    const transferAdmin = (proposed: PublicKey) =>
+      program.methods
+        .transferAdmin(proposed)
+        .accounts({ admin: admin.publicKey, programConfig: programConfigPda })
+        .signers([admin])
+        .rpc();
+    const acceptAdmin = (signer: Keypair) =>
+      program.methods
+        .acceptAdmin()
+        .accounts({ pendingAdmin: signer.publicKey, programConfig: programConfigPda })
+        .signers([signer])
+        .rpc();
+
+    try {
+      await transferAdmin(PublicKey.default);
+      expect.fail("Should reject the default pubkey as admin");
+    } catch (error: any) {
+      expect(error.toString()).to.include("InvalidRecipient");
+    }
+
+    await transferAdmin(newAdmin.publicKey);
+    const config = await program.account.programConfig.fetch(programConfigPda);
+    expect(config.pendingAdmin!.toBase58()).to.equal(newAdmin.publicKey.toBase58());
+    expect(config.adminTransferTimelock).to.not.be.null;
+
+    // The old admin cannot accept on the new key's behalf
+    try {
+      await acceptAdmin(admin);
+      expect.fail("Only the pending admin may accept");
+    } catch (error: any) {
+      expect(error.toString()).to.include("NoPendingAdminTransfer");
+    }
+
+    await provider.connection.confirmTransaction(
+      await provider.connection.requestAirdrop(newAdmin.publicKey, LAMPORTS_PER_SOL)
+    );
+    try {
+      await acceptAdmin(newAdmin);
+      expect.fail("Should not accept before the timelock expires");
+    } catch (error: any) {
+      expect(error.toString()).to.include("TimelockNotExpired");
+    }
+
+    const unchanged = await program.account.programConfig.fetch(programConfigPda);
+    expect(unchanged.admin.toBase58()).to.equal(admin.publicKey.toBase58());
+  });
+
+  it("Should dry-run a creation batch and report each failing entry", async () => {
+    const now = Math.floor(Date.now() / 1000);
+    const errorCode = (name: string) =>
+      program.idl.errors.find((e) => e.name.toLowerCase() === name.toLowerCase())!.code;
+    const entry = (overrides: Record<string, any> = {}) => ({
+      recipient: recipient.publicKey,
+      totalAmount: new anchor.BN(1_000),
+      cliffTimestamp: new anchor.BN(now),
+      vestingStartTimestamp: new anchor.BN(now),
+      vestingEndTimestamp: new anchor.BN(now + 3_600),
+      sourceCategory: { public: {} },
+      roundUp: false,
+      grantGroupId: new anchor.BN(0),
+      minClaimInterval: new anchor.BN(0),
+      allowSelfReturn: false,
+      allowPastEnd: false,
+      termsHash: null,
+      initialUnlockBps: 0,
+      vestingCurve: { linear: {} },
+      nonTransferable: false,
+      label: Array(32).fill(0),
+      secondCliffTimestamp: new anchor.BN(0),
+      secondCliffBps: 0,
+      ...overrides,
+    });
+    const totalBefore = (await program.account.programConfig.fetch(programConfigPda)).totalSchedules;
+    const depositorBalance = (await getAccount(provider.connection, adminTokenAccount)).amount;
+
+    const batch = [
+      entry(),
+      entry({ vestingEndTimestamp: new anchor.BN(now - 10) }),
+      entry({ totalAmount: new anchor.BN(0) }),
+      entry({ initialUnlockBps: 10_001 }),
+      entry({ recipient: Keypair.generate().publicKey }),
+      entry({ totalAmount: new anchor.BN(depositorBalance.toString()) }),
+    ];
+    const results = await program.methods
+      .validateBatch(batch)
+      .accounts({
+        programConfig: programConfigPda,
+        mint: mint,
+        depositorTokenAccount: adminTokenAccount,
+      })
+      .remainingAccounts(batch.map(() => ({ pubkey: recipientTokenAccount, isSigner: false, isWritable: false })))
+      .view();
+
+    expect(results.map((result: any) => result.index)).to.deep.equal([0, 1, 2, 3, 4, 5]);
+    expect(results[0].errorCode).to.be.null;
+    expect(results[1].errorCode).to.equal(errorCode("InvalidTimestamps"));
+    expect(results[2].errorCode).to.equal(errorCode("InvalidAmount"));
+    expect(results[3].errorCode).to.equal(errorCode("InvalidBps"));
+    expect(results[4].errorCode).to.equal(errorCode("RecipientAccountOwnerMismatch"));
+    // The first entry already draws 1000 from the depositor
+    expect(results[5].errorCode).to.equal(errorCode("InsufficientDepositorBalance"));
+
+    // Nothing was created
+    const totalAfter = (await program.account.programConfig.fetch(programConfigPda)).totalSchedules;
+    expect(totalAfter.toString()).to.equal(totalBefore.toString());
+  });
+
+  it("Should archive a closed schedule and purge it after the retention period", async () => {
+    const setArchiveRetention = (seconds: number) =>
+      program.methods
+        .setArchiveRetention(new anchor.BN(seconds))
+        .accounts({ authority: admin.publicKey, programConfig: programConfigPda })
+        .signers([admin])
+        .rpc();
+    const purge = (vestingSchedulePda: PublicKey) =>
+      program.methods
+        .purgeArchived()
+        .accounts({ beneficiary: admin.publicKey, vestingSchedule: vestingSchedulePda })
+        .signers([admin])
+        .rpc();
+
+    const now = Math.floor(Date.now() / 1000);
+    const { vestingSchedulePda, vestingVaultPda } = await createNextSchedule(
+      new anchor.BN(1_000), now - 20, now - 20, now - 10
+    );
+    await crankSchedule(vestingSchedulePda, vestingVaultPda);
+
+    await setArchiveRetention(3);
+    try {
+      await program.methods
+        .closeVestingSchedule()
+        .accounts({
+          beneficiary: admin.publicKey,
+          vestingSchedule: vestingSchedulePda,
+          vestingVault: vestingVaultPda,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([admin])
+        .rpc();
+    } finally {
+      await setArchiveRetention(0);
+    }
+
+    // The vault is gone but the terms are kept for the record
+    expect(await provider.connection.getAccountInfo(vestingVaultPda)).to.be.null;
+    const archived = await program.account.vestingSchedule.fetch(vestingSchedulePda);
+    expect(archived.closed).to.be.true;
+    expect(archived.totalAmount.toString()).to.equal("1000");
+    expect(archived.purgeableAt.toNumber()).to.be.greaterThan(0);
+
+    try {
+      await purge(vestingSchedulePda);
+      expect.fail("Should not purge before the retention period elapses");
+    } catch (error: any) {
+      expect(error.toString()).to.include("RetentionPeriodNotElapsed");
+    }
+
+    await new Promise(resolve => setTimeout(resolve, 4000));
+    await purge(vestingSchedulePda);
+    expect(await provider.connection.getAccountInfo(vestingSchedulePda)).to.be.null;
+  });
+
+  it("Should report vesting status matching what a crank releases", async () => {
+    const now = Math.floor(Date.now() / 1000);
+    const vestingStatus = (vestingSchedulePda: PublicKey, minRelease = 0) =>
+      program.methods
+        .getVestingStatus(new anchor.BN(minRelease))
+        .accounts({ vestingSchedule: vestingSchedulePda })
+        .view();
+
+    const locked = await createNextSchedule(new anchor.BN(1_000), now + 600, now + 600, now + 3_600);
+    const lockedStatus = await vestingStatus(locked.vestingSchedulePda, 100);
+    expect(lockedStatus.unlockedAmount.toString()).to.equal("0");
+    expect(lockedStatus.transferableAmount.toString()).to.equal("0");
+    expect(lockedStatus.isFullyVested).to.be.false;
+    expect(lockedStatus.cranksRemaining.toString()).to.equal("10");
+
+    const { vestingSchedulePda, vestingVaultPda } = await createNextSchedule(
+      new anchor.BN(1_000), now - 20, now - 20, now - 10
+    );
+    const status = await vestingStatus(vestingSchedulePda);
+    expect(status.totalAmount.toString()).to.equal("1000");
+    expect(status.amountTransferred.toString()).to.equal("0");
+    expect(status.unlockedAmount.toString()).to.equal("1000");
+    expect(status.isFullyVested).to.be.true;
+    expect(status.cranksRemaining.toString()).to.equal("1");
+
+    const before = (await getAccount(provider.connection, recipientTokenAccount)).amount;
+    await crankSchedule(vestingSchedulePda, vestingVaultPda);
+    const after = (await getAccount(provider.connection, recipientTokenAccount)).amount;
+    expect((after - before).toString()).to.equal(status.transferableAmount.toString());
+
+    const done = await vestingStatus(vestingSchedulePda);
+    expect(done.transferableAmount.toString()).to.equal("0");
+    expect(done.cranksRemaining.toString()).to.equal("0");
+  });
+
+  it("Should emit each release checkpoint exactly once as releases progress", async () => {
+    const now = Math.floor(Date.now() / 1000);
+    const { vestingSchedulePda, vestingVaultPda } = await createNextSchedule(
+      new anchor.BN(1_000), now - 20, now - 20, now - 10
+    );
+    const crankAndCollectCheckpoints = async () => {
+      const signature = await program.methods
+        .crankVestingSchedulesBatch()
+        .accounts({ programConfig: programConfigPda, tokenProgram: TOKEN_PROGRAM_ID })
+        .remainingAccounts([
+          { pubkey: vestingSchedulePda, isWritable: true, isSigner: false },
+          { pubkey: vestingVaultPda, isWritable: true, isSigner: false },
+          { pubkey: recipientTokenAccount, isWritable: true, isSigner: false },
+          { pubkey: mint, isWritable: false, isSigner: false },
+        ])
+        .rpc({ commitment: "confirmed" });
+      const tx = await provider.connection.getTransaction(signature, {
+        commitment: "confirmed",
+        maxSupportedTransactionVersion: 0,
+      });
+      const parser = new anchor.EventParser(program.programId, program.coder);
+      return [...parser.parseLogs(tx!.meta!.logMessages ?? [])]
+        .filter((event) => event.name === "releaseCheckpoint")
+        .map((event) => event.data.milestonePct);
+    };
+    const configure = (cap: number, checkpointPct: number) =>
+      Promise.all([
+        program.methods
+          .setMaxReleasePerTransaction(new anchor.BN(cap))
+          .accounts({ authority: admin.publicKey, programConfig: programConfigPda })
+          .signers([admin])
+          .rpc(),
+        program.methods
+          .setCheckpointInterval(checkpointPct)
+          .accounts({ authority: admin.publicKey, programConfig: programConfigPda })
+          .signers([admin])
+          .rpc(),
+      ]);
+
+    try {
+      await program.methods
+        .setCheckpointInterval(30)
+        .accounts({ authority: admin.publicKey, programConfig: programConfigPda })
+        .signers([admin])
+        .rpc();
+      expect.fail("Should reject a step that does not divide 100");
+    } catch (error: any) {
+      expect(error.toString()).to.include("InvalidCheckpointInterval");
+    }
+
+    const simulation = await program.methods
+      .setCheckpointInterval(25)
+      .accounts({ authority: admin.publicKey, programConfig: programConfigPda })
+      .signers([admin])
+      .simulate();
+    const updated = simulation.events.find((event) => event.name === "checkpointIntervalUpdated");
+    expect(updated!.data.oldCheckpointIntervalPct).to.equal(0);
+    expect(updated!.data.newCheckpointIntervalPct).to.equal(25);
+
+    // Releases of 300 per crank: 30%, 60%, 90%, 100%
+    await configure(300, 25);
+    try {
+      expect(await crankAndCollectCheckpoints()).to.deep.equal([25]);
+      expect(await crankAndCollectCheckpoints()).to.deep.equal([50]);
+      expect(await crankAndCollectCheckpoints()).to.deep.equal([75]);
+      expect(await crankAndCollectCheckpoints()).to.deep.equal([100]);
+    } finally {
+      await configure(0, 0);
+    }
+
+    const schedule = await program.account.vestingSchedule.fetch(vestingSchedulePda);
+    expect(schedule.lastCheckpointPct).to.equal(100);
+  });
+
+
+  it("Should unlock a stepped schedule only in whole tranches", async () => {
+    const now = Math.floor(Date.now() / 1000);
+    const unlockedAmount = async (vestingSchedulePda: PublicKey) =>
+      (await program.methods
+        .getVestingStatus(new anchor.BN(0))
+        .accounts({ vestingSchedule: vestingSchedulePda })
+        .view()).unlockedAmount.toString();
+
+    try {
+      await createNextSchedule(new anchor.BN(1_000), now, now, now + 400, {
+        vestingCurve: { stepped: { numPeriods: 0 } },
+      });
+      expect.fail("Should reject a stepped schedule without periods");
+    } catch (error: any) {
+      expect(error.toString()).to.include("InvalidAmount");
+    }
+
+    // 4 periods of 400 seconds; a little over one period has elapsed
+    const { vestingSchedulePda } = await createNextSchedule(
+      new anchor.BN(1_000), now - 500, now - 500, now + 1_100,
+      { vestingCurve: { stepped: { numPeriods: 4 } } }
+    );
+    const schedule = await program.account.vestingSchedule.fetch(vestingSchedulePda);
+    expect(schedule.curve).to.deep.equal({ stepped: { numPeriods: 4 } });
+    expect(await unlockedAmount(vestingSchedulePda)).to.equal("250");
+
+    const simulation = await program.methods
+      .setRequireEvenStepDivision(true)
+      .accounts({ authority: admin.publicKey, programConfig: programConfigPda })
+      .signers([admin])
+      .simulate();
+    const updated = simulation.events.find((event) => event.name === "requireEvenStepDivisionUpdated");
+    expect(updated!.data.oldRequired).to.be.false;
+    expect(updated!.data.newRequired).to.be.true;
+
+    await program.methods
+      .setRequireEvenStepDivision(true)
+      .accounts({ authority: admin.publicKey, programConfig: programConfigPda })
+      .signers([admin])
+      .rpc();
+    try {
+      await createNextSchedule(new anchor.BN(1_001), now, now, now + 400, {
+        vestingCurve: { stepped: { numPeriods: 4 } },
+      });
+      expect.fail("Should reject an uneven split when even division is required");
+    } catch (error: any) {
+      expect(error.toString()).to.include("UnevenStepDivision");
+    } finally {
+      await program.methods
+        .setRequireEvenStepDivision(false)
+        .accounts({ authority: admin.publicKey, programConfig: programConfigPda })
+        .signers([admin])
+        .rpc();
+    }
+  });
+
+
+  it("Should reject a distribution hub that is not a token account of the mint", async () => {
+    const updateHub = (hubTokenAccount: PublicKey) =>
+      program.methods
+        .updateDistributionHub()
+        .accounts({ admin: admin.publicKey, programConfig: programConfigPda, mint, hubTokenAccount })
+        .signers([admin])
+        .rpc();
+    const configBefore = await program.account.programConfig.fetch(programConfigPda);
+
+    // A wallet address is not a token account
+    try {
+      await updateHub(Keypair.generate().publicKey);
+      expect.fail("Should reject an address with no token account");
+    } catch (error: any) {
+      expect(error.toString()).to.include("AccountNotInitialized");
+    }
+    try {
+      await updateHub(admin.publicKey);
+      expect.fail("Should reject a wallet");
+    } catch (error: any) {
+      expect(error.toString()).to.include("AccountOwnedByWrongProgram");
+    }
+
+    // A token account of another mint
+    const otherMint = await createMint(provider.connection, admin, admin.publicKey, null, 6);
+    const otherMintAccount = (await getOrCreateAssociatedTokenAccount(
+      provider.connection, admin, otherMint, Keypair.generate().publicKey
+    )).address;
+    try {
+      await updateHub(otherMintAccount);
+      expect.fail("Should reject a token account of another mint");
+    } catch (error: any) {
+      expect(error.toString()).to.include("MintMismatch");
+    }
+
+    const configAfter = await program.account.programConfig.fetch(programConfigPda);
+    expect(configAfter.distributionHub.toString()).to.equal(configBefore.distributionHub.toString());
+    expect(String(configAfter.pendingHub)).to.equal(String(configBefore.pendingHub));
+  });
+
+
+  it("Should create a batch of schedules atomically with sequential IDs", async () => {
+    const now = Math.floor(Date.now() / 1000);
+    const secondRecipient = Keypair.generate();
+    const secondRecipientTokenAccount = (await getOrCreateAssociatedTokenAccount(
+      provider.connection, admin, mint, secondRecipient.publicKey
+    )).address;
+    const entry = (entryRecipient: PublicKey, amount: number) => ({
+      recipient: entryRecipient,
+      totalAmount: new anchor.BN(amount),
+      cliffTimestamp: new anchor.BN(now),
+      vestingStartTimestamp: new anchor.BN(now),
+      vestingEndTimestamp: new anchor.BN(now + 3_600),
+      sourceCategory: { public: {} },
+      roundUp: false,
+      grantGroupId: new anchor.BN(0),
+      minClaimInterval: new anchor.BN(0),
+      allowSelfReturn: false,
+      allowPastEnd: false,
+      termsHash: null,
+      initialUnlockBps: 0,
+      vestingCurve: { linear: {} },
+      nonTransferable: false,
+      label: Array(32).fill(0),
+      secondCliffTimestamp: new anchor.BN(0),
+      secondCliffBps: 0,
+    });
+    const createBatch = async (entries: { params: any; tokenAccount: PublicKey }[]) => {
+      const configAccount = await program.account.programConfig.fetch(programConfigPda);
+      const firstId = Number(configAccount.totalSchedules);
+      const remainingAccounts = entries.flatMap(({ params, tokenAccount }, index) => {
+        const [vestingSchedulePda, vestingVaultPda] = findSchedulePdas(new anchor.BN(firstId + index));
+        return [
+          { pubkey: vestingSchedulePda, isWritable: true, isSigner: false },
+          { pubkey: vestingVaultPda, isWritable: true, isSigner: false },
+          { pubkey: tokenAccount, isWritable: false, isSigner: false },
+          { pubkey: findPrimaryScheduleIndex(params.recipient, mint), isWritable: true, isSigner: false },
+        ];
+      });
+      const signature = await program.methods
+        .createVestingSchedulesBatch(entries.map(({ params }) => params))
+        .accounts({
+          admin: admin.publicKey,
+          programConfig: programConfigPda,
+          mint,
+          depositorTokenAccount: adminTokenAccount,
+          feeDestination: null,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .remainingAccounts(remainingAccounts)
+        .signers([admin])
+        .rpc({ commitment: "confirmed" });
+      return { firstId, signature };
+    };
+
+    // A mismatched recipient token account in the second entry fails the whole batch
+    const totalBefore = (await program.account.programConfig.fetch(programConfigPda)).totalSchedules;
+    try {
+      await createBatch([
+        { params: entry(recipient.publicKey, 1_000), tokenAccount: recipientTokenAccount },
+        { params: entry(secondRecipient.publicKey, 2_000), tokenAccount: recipientTokenAccount },
+      ]);
+      expect.fail("Should reject the batch when any entry is invalid");
+    } catch (error: any) {
+      expect(error.toString()).to.include("RecipientAccountOwnerMismatch");
+    }
+    const configAfterFailure = await program.account.programConfig.fetch(programConfigPda);
+    expect(configAfterFailure.totalSchedules.toString()).to.equal(totalBefore.toString());
+
+    const depositorBefore = (await getAccount(provider.connection, adminTokenAccount)).amount;
+    const { firstId, signature } = await createBatch([
+      { params: entry(recipient.publicKey, 1_000), tokenAccount: recipientTokenAccount },
+      { params: entry(secondRecipient.publicKey, 2_000), tokenAccount: secondRecipientTokenAccount },
+    ]);
+    const depositorAfter = (await getAccount(provider.connection, adminTokenAccount)).amount;
+    expect((depositorBefore - depositorAfter).toString()).to.equal("3000");
+
+    const schedules = await Promise.all([0, 1].map(async (index) => {
+      const [vestingSchedulePda, vestingVaultPda] = findSchedulePdas(new anchor.BN(firstId + index));
+      return {
+        schedule: await program.account.vestingSchedule.fetch(vestingSchedulePda),
+        vault: await getAccount(provider.connection, vestingVaultPda),
+        vestingSchedulePda,
+      };
+    }));
+    expect(schedules.map(({ schedule }) => schedule.scheduleId.toNumber())).to.deep.equal([firstId, firstId + 1]);
+    expect(schedules[1].schedule.recipient.toString()).to.equal(secondRecipient.publicKey.toString());
+    expect(schedules.map(({ vault }) => vault.amount.toString())).to.deep.equal(["1000", "2000"]);
+    expect(schedules[1].vault.owner.toString()).to.equal(schedules[1].vestingSchedulePda.toString());
+
+    // The second recipient's first schedule becomes its primary one
+    const primaryIndex = await program.account.primaryScheduleIndex.fetch(
+      findPrimaryScheduleIndex(secondRecipient.publicKey, mint)
+    );
+    expect(primaryIndex.scheduleId.toNumber()).to.equal(firstId + 1);
+
+    // One creation event per schedule
+    const tx = await provider.connection.getTransaction(signature, {
+      commitment: "confirmed",
+      maxSupportedTransactionVersion: 0,
+    });
+    const parser = new anchor.EventParser(program.programId, program.coder);
+    const created = [...parser.parseLogs(tx!.meta!.logMessages ?? [])]
+      .filter((event) => event.name === "vestingScheduleCreated")
+      .map((event) => event.data.scheduleId.toNumber());
+    expect(created).to.deep.equal([firstId, firstId + 1]);
+  });
+
+
+  it("Should report the amount unlocked between two timestamps", async () => {
+    const now = Math.floor(Date.now() / 1000);
+    const { vestingSchedulePda } = await createNextSchedule(
+      new anchor.BN(1_000), now + 1_000, now + 1_000, now + 2_000
+    );
+    const unlockedBetween = (from: number, to: number) =>
+      program.methods
+        .getUnlockedBetween(new anchor.BN(from), new anchor.BN(to))
+        .accounts({ vestingSchedule: vestingSchedulePda })
+        .view();
+
+    expect((await unlockedBetween(now, now + 999)).toString()).to.equal("0");
+    expect((await unlockedBetween(now, now + 1_500)).toString()).to.equal("500");
+    expect((await unlockedBetween(now + 1_500, now + 5_000)).toString()).to.equal("500");
+
+    try {
+      await unlockedBetween(now + 1_500, now + 1_000);
+      expect.fail("Should reject a reversed interval");
+    } catch (error: any) {
+      expect(error.toString()).to.include("InvalidTimestamps");
+    }
+  });
+
+
+  it("Should run the create, crank and close lifecycle against a Token-2022 mint", async () => {
+    const now = Math.floor(Date.now() / 1000);
+    const mint2022 = await createMint(
+      provider.connection, admin, admin.publicKey, null, 6, undefined, undefined, TOKEN_2022_PROGRAM_ID
+    );
+    const tokenAccount2022 = async (owner: PublicKey) =>
+      (await getOrCreateAssociatedTokenAccount(
+        provider.connection, admin, mint2022, owner, false, undefined, undefined, TOKEN_2022_PROGRAM_ID
+      )).address;
+    const depositor2022 = await tokenAccount2022(admin.publicKey);
+    const recipient2022 = await tokenAccount2022(recipient.publicKey);
+    await mintTo(
+      provider.connection, admin, mint2022, depositor2022, admin, 5_000, [], undefined, TOKEN_2022_PROGRAM_ID
+    );
+    const balance = async (tokenAccount: PublicKey) =>
+      (await getAccount(provider.connection, tokenAccount, undefined, TOKEN_2022_PROGRAM_ID)).amount.toString();
+
+    const { vestingSchedulePda, vestingVaultPda } = await createNextSchedule(
+      new anchor.BN(1_000), now - 20, now - 20, now - 10, {}, {
+        mint: mint2022,
+        depositorTokenAccount: depositor2022,
+        recipientTokenAccount: recipient2022,
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
+      }
+    );
+    expect(await balance(depositor2022)).to.equal("4000");
+    expect(await balance(vestingVaultPda)).to.equal("1000");
+    const vault = await provider.connection.getAccountInfo(vestingVaultPda);
+    expect(vault!.owner.toString()).to.equal(TOKEN_2022_PROGRAM_ID.toString());
+
+    await program.methods
+      .crankVestingSchedule()
+      .accounts({
+        programConfig: programConfigPda,
+        vestingSchedule: vestingSchedulePda,
+        vestingVault: vestingVaultPda,
+        recipientTokenAccount: recipient2022,
+        mint: mint2022,
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
+      })
+      .rpc();
+    expect(await balance(recipient2022)).to.equal("1000");
+    expect(await balance(vestingVaultPda)).to.equal("0");
+
+    await program.methods
+      .closeVestingSchedule()
+      .accounts({
+        beneficiary: admin.publicKey,
+        vestingSchedule: vestingSchedulePda,
+        vestingVault: vestingVaultPda,
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
+      })
+      .signers([admin])
+      .rpc();
+    expect(await provider.connection.getAccountInfo(vestingVaultPda)).to.be.null;
+  });
+
+  it("Should refuse releases to a delegated account for non-transferable schedules", async () => {
+    const now = Math.floor(Date.now() / 1000);
+    const { vestingSchedulePda, vestingVaultPda } = await createNextSchedule(
+      new anchor.BN(1_000), now - 100, now - 100, now - 1,
+      { nonTransferable: true }
+    );
+    const schedule = await program.account.vestingSchedule.fetch(vestingSchedulePda);
+    expect(schedule.nonTransferable).to.be.true;
+
+    await approve(
+      provider.connection, admin, recipientTokenAccount, Keypair.generate().publicKey, recipient, 1
+    );
+    try {
+      await crankSchedule(vestingSchedulePda, vestingVaultPda);
+      expect.fail("Should refuse a release while the recipient account has a delegate");
+    } catch (error: any) {
+      expect(error.toString()).to.include("DelegatedAccountNotAllowed");
+    } finally {
+      await revoke(provider.connection, admin, recipientTokenAccount, recipient);
+    }
+
+    const balanceBefore = await getAccount(provider.connection, recipientTokenAccount);
+    await crankSchedule(vestingSchedulePda, vestingVaultPda);
+    const balanceAfter = await getAccount(provider.connection, recipientTokenAccount);
+    expect((balanceAfter.amount - balanceBefore.amount).toString()).to.equal("1000");
+  });
+
+  it("Should account for Token-2022 transfer fees on release", async () => {
+    const now = Math.floor(Date.now() / 1000);
+
+    // 1% transfer fee, no cap
+    const mintKeypair = Keypair.generate();
+    const mintLen = getMintLen([ExtensionType.TransferFeeConfig]);
+    await sendAndConfirmTransaction(
+      provider.connection,
+      new Transaction().add(
+        SystemProgram.createAccount({
+          fromPubkey: admin.publicKey,
+          newAccountPubkey: mintKeypair.publicKey,
+          space: mintLen,
+          lamports: await provider.connection.getMinimumBalanceForRentExemption(mintLen),
+          programId: TOKEN_2022_PROGRAM_ID,
+        }),
+        createInitializeTransferFeeConfigInstruction(
+          mintKeypair.publicKey, admin.publicKey, admin.publicKey, 100, BigInt(1_000_000), TOKEN_2022_PROGRAM_ID
+        ),
+        createInitializeMintInstruction(mintKeypair.publicKey, 6, admin.publicKey, null, TOKEN_2022_PROGRAM_ID)
+      ),
+      [admin, mintKeypair]
+    );
+    const feeMint = mintKeypair.publicKey;
+    const tokenAccount2022 = async (owner: PublicKey) =>
+      (await getOrCreateAssociatedTokenAccount(
+        provider.connection, admin, feeMint, owner, false, undefined, undefined, TOKEN_2022_PROGRAM_ID
+      )).address;
+    const depositor2022 = await tokenAccount2022(admin.publicKey);
+    const recipient2022 = await tokenAccount2022(recipient.publicKey);
+    await mintTo(
+      provider.connection, admin, feeMint, depositor2022, admin, 5_000, [], undefined, TOKEN_2022_PROGRAM_ID
+    );
+    const balance = async (tokenAccount: PublicKey) =>
+      (await getAccount(provider.connection, tokenAccount, undefined, TOKEN_2022_PROGRAM_ID)).amount.toString();
+
+    // The depositor pays the deposit fee on top, so the vault holds the full allocation
+    const { vestingSchedulePda, vestingVaultPda } = await createNextSchedule(
+      new anchor.BN(1_000), now - 20, now - 20, now - 10, {}, {
+        mint: feeMint,
+        depositorTokenAccount: depositor2022,
+        recipientTokenAccount: recipient2022,
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
+      }
+    );
+    expect(await balance(depositor2022)).to.equal("3989");
+    expect(await balance(vestingVaultPda)).to.equal("1000");
+
+    await program.methods
+      .crankVestingSchedule()
+      .accounts({
+        programConfig: programConfigPda,
+        vestingSchedule: vestingSchedulePda,
+        vestingVault: vestingVaultPda,
+        recipientTokenAccount: recipient2022,
+        mint: feeMint,
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
+      })
+      .rpc();
+
+    // The recipient nets the allocation minus the 1% fee, the vault is emptied in full
+    const schedule = await program.account.vestingSchedule.fetch(vestingSchedulePda);
+    expect(await balance(recipient2022)).to.equal("990");
+    expect(await balance(vestingVaultPda)).to.equal("0");
+    expect(schedule.amountTransferred.toString()).to.equal("990");
+    expect(schedule.transferFeesPaid.toString()).to.equal("10");
+    expect(schedule.amountTransferred.add(schedule.transferFeesPaid).toString())
+      .to.equal(schedule.totalAmount.toString());
+
+    // Fees withheld in the vault from the deposit must be harvested before it can be closed
+    await harvestWithheldTokensToMint(
+      provider.connection, admin, feeMint, [vestingVaultPda], undefined, TOKEN_2022_PROGRAM_ID
+    );
+    await program.methods
+      .closeVestingSchedule()
+      .accounts({
+        beneficiary: admin.publicKey,
+        vestingSchedule: vestingSchedulePda,
+        vestingVault: vestingVaultPda,
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
+      })
+      .signers([admin])
+      .rpc();
+    expect(await provider.connection.getAccountInfo(vestingVaultPda)).to.be.null;
+  });
+
+  it("Should merge two schedules with identical timing", async () => {
+    const now = Math.floor(Date.now() / 1000);
+    const [mintScheduleCountPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("mint_schedules"), mint.toBuffer()],
+      program.programId
+    );
+    const merge = (
+      target: { vestingSchedulePda: PublicKey; vestingVaultPda: PublicKey },
+      source: { vestingSchedulePda: PublicKey; vestingVaultPda: PublicKey }
+    ) =>
+      program.methods
+        .mergeSchedules()
+        .accounts({
+          admin: admin.publicKey,
+          recipient: recipient.publicKey,
+          programConfig: programConfigPda,
+          targetSchedule: target.vestingSchedulePda,
+          targetVault: target.vestingVaultPda,
+          sourceSchedule: source.vestingSchedulePda,
+          sourceVault: source.vestingVaultPda,
+          mint,
+          mintScheduleCount: mintScheduleCountPda,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([admin, recipient])
+        .rpc();
+
+    const target = await createNextSchedule(new anchor.BN(1_000), now - 100, now - 100, now + 10_000);
+    const source = await createNextSchedule(new anchor.BN(500), now - 100, now - 100, now + 10_000);
+    const laterEnd = await createNextSchedule(new anchor.BN(500), now - 100, now - 100, now + 10_001);
+
+    try {
+      await merge(target, laterEnd);
+      expect.fail("Should refuse schedules with different timing");
+    } catch (error: any) {
+      expect(error.toString()).to.include("SchedulesNotMergeable");
+    }
+
+    const openBefore = (await program.account.mintScheduleCount.fetch(mintScheduleCountPda)).activeSchedules;
+    await merge(target, source);
+
+    const merged = await program.account.vestingSchedule.fetch(target.vestingSchedulePda);
+    expect(merged.totalAmount.toString()).to.equal("1500");
+    const vault = await getAccount(provider.connection, target.vestingVaultPda);
+    expect(vault.amount.toString()).to.equal("1500");
+    expect(await provider.connection.getAccountInfo(source.vestingSchedulePda)).to.be.null;
+    expect(await provider.connection.getAccountInfo(source.vestingVaultPda)).to.be.null;
+    const openAfter = (await program.account.mintScheduleCount.fetch(mintScheduleCountPda)).activeSchedules;
+    expect(openBefore.sub(openAfter).toString()).to.equal("1");
+  });
+
+  it("Should enforce per-category allocation caps", async () => {
+    const now = Math.floor(Date.now() / 1000);
+    const strategicIndex = 4;
+    const setStrategicCap = (cap: anchor.BN) =>
+      program.methods
+        .setCategoryCap({ strategic: {} }, cap)
+        .accounts({ authority: admin.publicKey, programConfig: programConfigPda })
+        .signers([admin])
+        .rpc();
+    const createStrategic = (amount: number) =>
+      createNextSchedule(new anchor.BN(amount), now, now, now + 1_000, { sourceCategory: { strategic: {} } });
+
+    const allocatedBefore = (await program.account.programConfig.fetch(programConfigPda))
+      .categoryAllocated[strategicIndex];
+    await setStrategicCap(allocatedBefore.add(new anchor.BN(1_500)));
+    try {
+      await createStrategic(1_000);
+      // Hitting the cap exactly is allowed
+      await createStrategic(500);
+      const config = await program.account.programConfig.fetch(programConfigPda);
+      expect(config.categoryAllocated[strategicIndex].sub(allocatedBefore).toString()).to.equal("1500");
+
+      try {
+        await createStrategic(1);
+        expect.fail("Should refuse one token over the category cap");
+      } catch (error: any) {
+        expect(error.toString()).to.include("CategoryCapExceeded");
+      }
+    } finally {
+      await setStrategicCap(new anchor.BN(0));
+    }
+
+    // Uncapped again
+    await createStrategic(1);
+  });
+
+  it("Should cap releases per window with the circuit breaker", async () => {
+    const now = Math.floor(Date.now() / 1000);
+    const setReleaseRateLimit = (maxReleasePerWindow: number, windowDuration: number) =>
+      program.methods
+        .setReleaseRateLimit(new anchor.BN(maxReleasePerWindow), new anchor.BN(windowDuration))
+        .accounts({ admin: admin.publicKey, programConfig: programConfigPda })
+        .signers([admin])
+        .rpc();
+
+    try {
+      await setReleaseRateLimit(1_000, 0);
+      expect.fail("Should refuse a limit without a window");
+    } catch (error: any) {
+      expect(error.toString()).to.include("InvalidTimestamps");
+    }
+
+    const first = await createNextSchedule(new anchor.BN(800), now - 20, now - 20, now - 10);
+    const second = await createNextSchedule(new anchor.BN(800), now - 20, now - 20, now - 10);
+
+    await setReleaseRateLimit(1_000, 3);
+    try {
+      await crankSchedule(first.vestingSchedulePda, first.vestingVaultPda);
+      try {
+        await crankSchedule(second.vestingSchedulePda, second.vestingVaultPda);
+        expect.fail("Should refuse a release over the window cap");
+      } catch (error: any) {
+        expect(error.toString()).to.include("ReleaseRateLimitExceeded");
+      }
+      const config = await program.account.programConfig.fetch(programConfigPda);
+      expect(config.releasedInWindow.toString()).to.equal("800");
+
+      // Once the window has rolled over the release goes through
+      await new Promise(resolve => setTimeout(resolve, 4000));
+      await crankSchedule(second.vestingSchedulePda, second.vestingVaultPda);
+      const schedule = await program.account.vestingSchedule.fetch(second.vestingSchedulePda);
+      expect(schedule.amountTransferred.toString()).to.equal("800");
+    } finally {
+      await setReleaseRateLimit(0, 0);
+    }
+  });
+
+  it("Should report the reclaimed rent when closing a schedule", async () => {
+    const now = Math.floor(Date.now() / 1000);
+    const { vestingSchedulePda, vestingVaultPda } = await createNextSchedule(
+      new anchor.BN(1_000), now - 20, now - 20, now - 10
+    );
+    await crankSchedule(vestingSchedulePda, vestingVaultPda);
+
+    const vaultLamports = await provider.connection.getBalance(vestingVaultPda);
+    const scheduleLamports = await provider.connection.getBalance(vestingSchedulePda);
+
+    const signature = await program.methods
+      .closeVestingSchedule()
+      .accounts({
+        beneficiary: admin.publicKey,
+        vestingSchedule: vestingSchedulePda,
+        vestingVault: vestingVaultPda,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([admin])
+      .rpc({ commitment: "confirmed" });
+    const tx = await provider.connection.getTransaction(signature, {
+      commitment: "confirmed",
+      maxSupportedTransactionVersion: 0,
+    });
+    const parser = new anchor.EventParser(program.programId, program.coder);
+    const closed = [...parser.parseLogs(tx!.meta!.logMessages ?? [])]
+      .find((event) => event.name === "vestingScheduleClosed");
+
+    expect(closed).to.not.be.undefined;
+    expect(closed!.data.beneficiary.toString()).to.equal(admin.publicKey.toString());
+    expect(closed!.data.vaultLamports.toString()).to.equal(vaultLamports.toString());
+    expect(closed!.data.scheduleLamports.toString()).to.equal(scheduleLamports.toString());
+    expect(closed!.data.amountTransferred.toString()).to.equal("1000");
+    expect(closed!.data.archived).to.be.false;
+  });
+
+  it("Should change the recipient token account only after the timelock", async () => {
+    const now = Math.floor(Date.now() / 1000);
+    const { vestingSchedulePda } = await createNextSchedule(
+      new anchor.BN(1_000), now + 3600, now + 3600, now + 7200
+    );
+    const changeRecipientAccount = (method: "proposeRecipientTokenAccount" | "confirmRecipientTokenAccount",
+                                    newRecipientTokenAccount: PublicKey) =>
+      program.methods[method]()
+        .accounts({
+          admin: admin.publicKey,
+          programConfig: programConfigPda,
+          vestingSchedule: vestingSchedulePda,
+          newRecipientTokenAccount,
+        })
+        .signers([admin])
+        .rpc();
+
+    // The replacement must belong to the schedule's recipient
+    const foreignTokenAccount = await createAccount(
+      provider.connection, admin, mint, Keypair.generate().publicKey, Keypair.generate()
+    );
+    try {
+      await changeRecipientAccount("proposeRecipientTokenAccount", foreignTokenAccount);
+      expect.fail("Should reject an account owned by someone else");
+    } catch (error: any) {
+      expect(error.toString()).to.include("RecipientAccountOwnerMismatch");
+    }
+
+    const replacementTokenAccount = await createAccount(
+      provider.connection, admin, mint, recipient.publicKey, Keypair.generate()
+    );
+    await changeRecipientAccount("proposeRecipientTokenAccount", replacementTokenAccount);
+    const pending = await program.account.vestingSchedule.fetch(vestingSchedulePda);
+    expect(pending.pendingRecipientTokenAccount.toString()).to.equal(replacementTokenAccount.toString());
+    expect(pending.recipientTokenAccount.toString()).to.equal(recipientTokenAccount.toString());
+
+    try {
+      await changeRecipientAccount("confirmRecipientTokenAccount", replacementTokenAccount);
+      expect.fail("Should reject confirmation before the timelock expires");
+    } catch (error: any) {
+      expect(error.toString()).to.include("TimelockNotExpired");
+    }
+    const unchanged = await program.account.vestingSchedule.fetch(vestingSchedulePda);
+    expect(unchanged.recipientTokenAccount.toString()).to.equal(recipientTokenAccount.toString());
+  });
+
+  it("Should emit the full TokensReleased record from the direct-to-recipient crank", async () => {
+    const now = Math.floor(Date.now() / 1000);
+    const { scheduleId, vestingSchedulePda, vestingVaultPda } = await createNextSchedule(
+      new anchor.BN(1_000), now - 100, now - 100, now - 1,
+      { sourceCategory: { ecosystem: {} } }
+    );
+
+    const balanceBefore = await getAccount(provider.connection, recipientTokenAccount);
+    const signature = await crankSchedule(vestingSchedulePda, vestingVaultPda);
+    const balanceAfter = await getAccount(provider.connection, recipientTokenAccount);
+    expect((balanceAfter.amount - balanceBefore.amount).toString()).to.equal("1000");
+
+    const tx = await provider.connection.getTransaction(signature, {
+      commitment: "confirmed",
+      maxSupportedTransactionVersion: 0,
+    });
+    const parser = new anchor.EventParser(program.programId, program.coder);
+    const released = [...parser.parseLogs(tx!.meta!.logMessages ?? [])]
+      .filter((event) => event.name === "tokensReleased");
+
+    expect(released).to.have.length(1);
+    expect(released[0].data.scheduleId.toString()).to.equal(scheduleId.toString());
+    expect(released[0].data.recipient.toString()).to.equal(recipient.publicKey.toString());
+    expect(released[0].data.mint.toString()).to.equal(mint.toString());
+    expect(released[0].data.amount.toString()).to.equal("1000");
+    expect(released[0].data.sourceCategory).to.deep.equal({ ecosystem: {} });
+    expect(released[0].data.totalReleased.toString()).to.equal("1000");
+  });
+
+  it("Should pay the keeper reward out of a crank release", async () => {
+    const setKeeperReward = (bps: number) =>
+      program.methods
+        .setKeeperReward(bps)
+        .accounts({ authority: admin.publicKey, programConfig: programConfigPda })
+        .signers([admin])
+        .rpc();
+    const releasedEvent = async (signature: string) => {
+      const tx = await provider.connection.getTransaction(signature, {
+        commitment: "confirmed",
+        maxSupportedTransactionVersion: 0,
+      });
+      const parser = new anchor.EventParser(program.programId, program.coder);
+      return [...parser.parseLogs(tx!.meta!.logMessages ?? [])]
+        .find((event) => event.name === "tokensReleased")!;
+    };
+    const now = Math.floor(Date.now() / 1000);
+
+    // 0 bps: no keeper accounts needed, the recipient gets everything
+    const unrewarded = await createNextSchedule(new anchor.BN(10_000), now - 100, now - 100, now - 1);
+    let balanceBefore = await getAccount(provider.connection, recipientTokenAccount);
+    let released = await releasedEvent(
+      await crankSchedule(unrewarded.vestingSchedulePda, unrewarded.vestingVaultPda)
+    );
+    let balanceAfter = await getAccount(provider.connection, recipientTokenAccount);
+    expect((balanceAfter.amount - balanceBefore.amount).toString()).to.equal("10000");
+    expect(released.data.keeperFee.toString()).to.equal("0");
+
+    const keeper = Keypair.generate();
+    const keeperTokenAccount = await createAccount(
+      provider.connection, admin, mint, keeper.publicKey, Keypair.generate()
+    );
+    const rewarded = await createNextSchedule(new anchor.BN(10_000), now - 100, now - 100, now - 1);
+    const crankAsKeeper = (keeperAccount: PublicKey) =>
+      program.methods
+        .crankVestingSchedule()
+        .accounts({
+          programConfig: programConfigPda,
+          vestingSchedule: rewarded.vestingSchedulePda,
+          vestingVault: rewarded.vestingVaultPda,
+          recipientTokenAccount: recipientTokenAccount,
+          mint: mint,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          keeper: keeper.publicKey,
+          keeperTokenAccount: keeperAccount,
+        })
+        .signers([keeper])
+        .rpc();
+
+    await setKeeperReward(100);
+    try {
+      // The reward account must belong to the keeper signing the crank
+      try {
+        await crankAsKeeper(recipientTokenAccount);
+        expect.fail("Should reject a reward account the keeper does not own");
+      } catch (error: any) {
+        expect(error.toString()).to.include("Unauthorized");
+      }
+
+      balanceBefore = await getAccount(provider.connection, recipientTokenAccount);
+      released = await releasedEvent(await crankAsKeeper(keeperTokenAccount));
+      balanceAfter = await getAccount(provider.connection, recipientTokenAccount);
+      const keeperBalance = await getAccount(provider.connection, keeperTokenAccount);
+
+      expect((balanceAfter.amount - balanceBefore.amount).toString()).to.equal("9900");
+      expect(keeperBalance.amount.toString()).to.equal("100");
+      expect(released.data.amount.toString()).to.equal("9900");
+      expect(released.data.keeperFee.toString()).to.equal("100");
+      expect(released.data.totalReleased.toString()).to.equal("10000");
+
+      const schedule = await program.account.vestingSchedule.fetch(rewarded.vestingSchedulePda);
+      expect(schedule.amountTransferred.toString()).to.equal("10000");
+    } finally {
+      await setKeeperReward(0);
+    }
+  });
+
+  it("Should allow emergency withdraw only while the program is paused", async () => {
+    const now = Math.floor(Date.now() / 1000);
+    const { scheduleId, vestingSchedulePda, vestingVaultPda } = await createNextSchedule(
+      new anchor.BN(1_000), now + 3600, now + 3600, now + 7200
+    );
+    const setPause = (paused: boolean) =>
+      program.methods
+        .setPause(paused)
+        .accounts({ admin: admin.publicKey, programConfig: programConfigPda })
+        .signers([admin])
+        .rpc();
+    const emergencyWithdraw = () =>
+      program.methods
+        .emergencyWithdraw()
+        .accounts({
+          admin: admin.publicKey,
+          programConfig: programConfigPda,
+          vestingSchedule: vestingSchedulePda,
+          vestingVault: vestingVaultPda,
+          recoveryTokenAccount: adminTokenAccount,
+          mint: mint,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([admin])
+        .rpc();
+
+    try {
+      await emergencyWithdraw();
+      expect.fail("Should reject emergency withdraw while the program is running");
+    } catch (error: any) {
+      expect(error.toString()).to.include("ProgramNotPaused");
+    }
+
+    await setPause(true);
+    try {
+      const balanceBefore = await getAccount(provider.connection, adminTokenAccount);
+      const signature = await emergencyWithdraw();
+      const balanceAfter = await getAccount(provider.connection, adminTokenAccount);
+      expect((balanceAfter.amount - balanceBefore.amount).toString()).to.equal("1000");
+
+      const vault = await getAccount(provider.connection, vestingVaultPda);
+      expect(vault.amount.toString()).to.equal("0");
+      const schedule = await program.account.vestingSchedule.fetch(vestingSchedulePda);
+      expect(schedule.revoked).to.be.true;
+
+      const tx = await provider.connection.getTransaction(signature, {
+        commitment: "confirmed",
+        maxSupportedTransactionVersion: 0,
+      });
+      const parser = new anchor.EventParser(program.programId, program.coder);
+      const withdrawn = [...parser.parseLogs(tx!.meta!.logMessages ?? [])]
+        .find((event) => event.name === "emergencyWithdrawn");
+      expect(withdrawn!.data.scheduleId.toString()).to.equal(scheduleId.toString());
+      expect(withdrawn!.data.amount.toString()).to.equal("1000");
+    } finally {
+      await setPause(false);
+    }
+  });
+
+  it("Should round-trip schedule labels up to 32 bytes", async () => {
+    const encodeLabel = (text: string) => {
+      const label = Buffer.alloc(32);
+      Buffer.from(text, "utf8").copy(label);
+      return [...label];
+    };
+    const now = Math.floor(Date.now() / 1000);
+
+    for (const text of ["Seed investor #12", "Strategic partner allocation #01"]) {
+      const { vestingSchedulePda } = await createNextSchedule(
+        new anchor.BN(1_000), now + 3600, now + 3600, now + 7200, { label: encodeLabel(text) }
+      );
+      const schedule = await program.account.vestingSchedule.fetch(vestingSchedulePda);
+      expect(Buffer.from(schedule.label).toString("utf8").replace(/\0+$/, "")).to.equal(text);
+    }
+
+    // Bytes after the zero padding starts are rejected
+    const invalidLabel = encodeLabel("Seed investor");
+    invalidLabel[20] = 0x41;
+    try {
+      await createNextSchedule(
+        new anchor.BN(1_000), now + 3600, now + 3600, now + 7200, { label: invalidLabel }
+      );
+      expect.fail("Should reject a label with data after the padding");
+    } catch (error: any) {
+      expect(error.toString()).to.include("InvalidLabel");
+    }
+  });
+
+  it("Should top up a single schedule and unlock against the new total immediately", async () => {
+    const now = Math.floor(Date.now() / 1000);
+    // Four 500s tranches, three of them already unlocked
+    const { scheduleId, vestingSchedulePda, vestingVaultPda } = await createNextSchedule(
+      new anchor.BN(1_000), now - 1_500, now - 1_500, now + 500,
+      { vestingCurve: { stepped: { numPeriods: 4 } } }
+    );
+    const unlockedAmount = async () =>
+      (await program.methods
+        .getVestingStatus(new anchor.BN(0))
+        .accounts({ vestingSchedule: vestingSchedulePda })
+        .view()).unlockedAmount.toString();
+    const topUp = (amount: number) =>
+      program.methods
+        .topUpSchedule(new anchor.BN(amount))
+        .accounts({
+          admin: admin.publicKey,
+          programConfig: programConfigPda,
+          vestingSchedule: vestingSchedulePda,
+          vestingVault: vestingVaultPda,
+          mint: mint,
+          depositorTokenAccount: adminTokenAccount,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([admin]);
+
+    expect(await unlockedAmount()).to.equal("750");
+
+    const simulation = await topUp(1_000).simulate();
+    const toppedUp = simulation.events.find((event) => event.name === "scheduleToppedUp");
+    expect(toppedUp!.data.scheduleId.toString()).to.equal(scheduleId.toString());
+    expect(toppedUp!.data.addedAmount.toString()).to.equal("1000");
+    expect(toppedUp!.data.newTotal.toString()).to.equal("2000");
+
+    await topUp(1_000).rpc();
+    const schedule = await program.account.vestingSchedule.fetch(vestingSchedulePda);
+    expect(schedule.totalAmount.toString()).to.equal("2000");
+    const vault = await getAccount(provider.connection, vestingVaultPda);
+    expect(vault.amount.toString()).to.equal("2000");
+    expect(await unlockedAmount()).to.equal("1500");
+
+    try {
+      await topUp(0).rpc();
+      expect.fail("Should reject an empty top-up");
+    } catch (error: any) {
+      expect(error.toString()).to.include("InvalidAmount");
+    }
+  });
+
+  it("Should release a schedule passed twice in one batch crank only once", async () => {
+    const now = Math.floor(Date.now() / 1000);
+    const { vestingSchedulePda, vestingVaultPda } = await createNextSchedule(
+      new anchor.BN(1_000), now - 100, now - 100, now - 1
+    );
+    const entry = [
+      { pubkey: vestingSchedulePda, isWritable: true, isSigner: false },
+      { pubkey: vestingVaultPda, isWritable: true, isSigner: false },
+      { pubkey: recipientTokenAccount, isWritable: true, isSigner: false },
+      { pubkey: mint, isWritable: false, isSigner: false },
+    ];
+    const batchCrank = () =>
+      program.methods
+        .crankVestingSchedulesBatch()
+        .accounts({ programConfig: programConfigPda, tokenProgram: TOKEN_PROGRAM_ID })
+        .remainingAccounts([...entry, ...entry]);
+
+    const summary = await batchCrank().view();
+    expect(summary.schedulesReleased).to.equal(1);
+    expect(summary.totalReleased.toString()).to.equal("1000");
+
+    const balanceBefore = await getAccount(provider.connection, recipientTokenAccount);
+    await batchCrank().rpc();
+    const balanceAfter = await getAccount(provider.connection, recipientTokenAccount);
+    expect((balanceAfter.amount - balanceBefore.amount).toString()).to.equal("1000");
+
+    const schedule = await program.account.vestingSchedule.fetch(vestingSchedulePda);
+    expect(schedule.amountTransferred.toString()).to.equal("1000");
+  });
+
+  it("Should skip cranks inside min_crank_interval until the schedule has fully vested", async () => {
+    const setMinCrankInterval = (seconds: number) =>
+      program.methods
+        .setMinCrankInterval(new anchor.BN(seconds))
+        .accounts({ authority: admin.publicKey, programConfig: programConfigPda })
+        .signers([admin])
+        .rpc();
+    const now = Math.floor(Date.now() / 1000);
+    const { vestingSchedulePda, vestingVaultPda } = await createNextSchedule(
+      new anchor.BN(1_000), now - 100, now - 100, now + 5
+    );
+    const transferred = async () =>
+      (await program.account.vestingSchedule.fetch(vestingSchedulePda)).amountTransferred.toString();
+
+    await setMinCrankInterval(3_600);
+    try {
+      await crankSchedule(vestingSchedulePda, vestingVaultPda);
+      const firstRelease = await transferred();
+      expect(Number(firstRelease)).to.be.greaterThan(0).and.lessThan(1_000);
+      const schedule = await program.account.vestingSchedule.fetch(vestingSchedulePda);
+      expect(schedule.lastCrankTimestamp.toNumber()).to.be.greaterThan(0);
+
+      // Just under the interval: skipped without an error, nothing moves
+      await new Promise((resolve) => setTimeout(resolve, 1000));
+      await crankSchedule(vestingSchedulePda, vestingVaultPda);
+      expect(await transferred()).to.equal(firstRelease);
+
+      // Past the vesting end the final release bypasses the interval
+      await new Promise((resolve) => setTimeout(resolve, 6000));
+      await crankSchedule(vestingSchedulePda, vestingVaultPda);
+      expect(await transferred()).to.equal("1000");
+    } finally {
+      await setMinCrankInterval(0);
+    }
+  });
+
+  it("Should cancel a pending distribution hub change", async () => {
+    const proposedHub = (await getOrCreateAssociatedTokenAccount(
+      provider.connection, admin, mint, Keypair.generate().publicKey
+    )).address;
+    const updateHub = () =>
+      program.methods
+        .updateDistributionHub()
+        .accounts({ admin: admin.publicKey, programConfig: programConfigPda, mint, hubTokenAccount: proposedHub })
+        .signers([admin]);
+    const cancelHubUpdate = () =>
+      program.methods
+        .cancelHubUpdate()
+        .accounts({ admin: admin.publicKey, programConfig: programConfigPda })
+        .signers([admin]);
+
+    await updateHub().rpc();
+    const configBefore = await program.account.programConfig.fetch(programConfigPda);
+    expect(configBefore.pendingHub.toString()).to.equal(proposedHub.toString());
+
+    const simulation = await cancelHubUpdate().simulate();
+    const cancelled = simulation.events.find((event) => event.name === "distributionHubUpdateCancelled");
+    expect(cancelled!.data.cancelledHub.toString()).to.equal(proposedHub.toString());
+    await cancelHubUpdate().rpc();
+
+    const configAfter = await program.account.programConfig.fetch(programConfigPda);
+    expect(configAfter.pendingHub).to.be.null;
+    expect(configAfter.hubUpdateTimelock).to.be.null;
+    expect(configAfter.distributionHub.toString()).to.equal(configBefore.distributionHub.toString());
+
+    try {
+      await cancelHubUpdate().rpc();
+      expect.fail("Should reject a cancel with nothing pending");
+    } catch (error: any) {
+      expect(error.toString()).to.include("NoPendingHubUpdate");
+    }
+
+    // Nothing left to confirm: the same address starts a fresh proposal
+    const retry = await updateHub().simulate();
+    expect(retry.events.map((event) => event.name)).to.deep.equal(["distributionHubUpdateProposed"]);
+  });
+
+
+  it("Should close only the vault of a completed schedule and keep the schedule", async () => {
+    const now = Math.floor(Date.now() / 1000);
+    const { vestingSchedulePda, vestingVaultPda } = await createNextSchedule(
+      new anchor.BN(1_000), now - 20, now - 20, now - 10
+    );
+    await crankSchedule(vestingSchedulePda, vestingVaultPda);
+
+    const vaultLamports = await provider.connection.getBalance(vestingVaultPda);
+    const signature = await program.methods
+      .closeVaultOnly()
+      .accounts({
+        beneficiary: admin.publicKey,
+        vestingSchedule: vestingSchedulePda,
+        vestingVault: vestingVaultPda,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([admin])
+      .rpc({ commitment: "confirmed" });
+    const tx = await provider.connection.getTransaction(signature, {
+      commitment: "confirmed",
+      maxSupportedTransactionVersion: 0,
+    });
+    const parser = new anchor.EventParser(program.programId, program.coder);
+    const closed = [...parser.parseLogs(tx!.meta!.logMessages ?? [])]
+      .find((event) => event.name === "vaultClosed");
+    expect(closed).to.not.be.undefined;
+    expect(closed!.data.vaultLamports.toString()).to.equal(vaultLamports.toString());
+
+    // The vault is gone, the schedule history stays
+    expect(await provider.connection.getAccountInfo(vestingVaultPda)).to.be.null;
+    const schedule = await program.account.vestingSchedule.fetch(vestingSchedulePda);
+    expect(schedule.vaultClosed).to.be.true;
+    expect(schedule.closed).to.be.false;
+    expect(schedule.amountTransferred.toString()).to.equal("1000");
+
+    // A crank over it is a no-op
+    const balanceBefore = (await getAccount(provider.connection, recipientTokenAccount)).amount;
+    const summary = await program.methods
+      .crankVestingSchedulesBatch()
+      .accounts({ programConfig: programConfigPda, tokenProgram: TOKEN_PROGRAM_ID })
+      .remainingAccounts([
+        { pubkey: vestingSchedulePda, isWritable: true, isSigner: false },
+        { pubkey: vestingVaultPda, isWritable: true, isSigner: false },
+        { pubkey: recipientTokenAccount, isWritable: true, isSigner: false },
+        { pubkey: mint, isWritable: false, isSigner: false },
+      ])
+      .view();
+    expect(summary.nothingReady).to.be.true;
+    expect(summary.schedulesReleased).to.equal(0);
+    expect((await getAccount(provider.connection, recipientTokenAccount)).amount).to.equal(balanceBefore);
+
+    // The vault cannot be recreated through fund_vault
+    try {
+      await program.methods
+        .fundVault(new anchor.BN(1))
+        .accounts({
+          admin: admin.publicKey,
+          programConfig: programConfigPda,
+          vestingSchedule: vestingSchedulePda,
+          mint: mint,
+          depositorTokenAccount: adminTokenAccount,
+          vestingVault: vestingVaultPda,
+          systemProgram: SystemProgram.programId,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          rent: SYSVAR_RENT_PUBKEY,
+        })
+        .signers([admin])
+        .rpc();
+      expect.fail("Should not recreate the vault of a completed schedule");
+    } catch (error: any) {
+      expect(error.toString()).to.include("ScheduleFullyProcessed");
+    }
+  });
+
+
+  it("Should bound how far back the cliff and how far ahead the end may be set", async () => {
+    const now = Math.floor(Date.now() / 1000);
+    const day = 24 * 60 * 60;
+
+    // Starting one second ago is fine
+    const { vestingSchedulePda } = await createNextSchedule(
+      new anchor.BN(1_000), now - 1, now - 1, now + 1_000
+    );
+    const schedule = await program.account.vestingSchedule.fetch(vestingSchedulePda);
+    expect(schedule.cliffTimestamp.toNumber()).to.equal(now - 1);
+
+    try {
+      await createNextSchedule(new anchor.BN(1_000), now - 365 * day, now - 365 * day, now + 1_000);
+      expect.fail("Should reject a cliff far in the past");
+    } catch (error: any) {
+      expect(error.toString()).to.include("CliffTooFarInPast");
+    }
+
+    try {
+      await createNextSchedule(new anchor.BN(1_000), now + 60, now + 60, now + 20 * 365 * day);
+      expect.fail("Should reject a vesting end far in the future");
+    } catch (error: any) {
+      expect(error.toString()).to.include("VestingEndTooFarInFuture");
+    }
+  });
+
+
+  it("Should unlock the second cliff chunk on top of the curve once reached", async () => {
+    const now = Math.floor(Date.now() / 1000);
+    const gradedCliff = (secondCliff: number) => ({
+      initialUnlockBps: 2_500,
+      secondCliffTimestamp: new anchor.BN(secondCliff),
+      secondCliffBps: 2_500,
+    });
+
+    // Past the second cliff: 25% initial + 25% second cliff + a sliver of linear vesting
+    const crossed = await createNextSchedule(
+      new anchor.BN(10_000), now - 20, now - 20, now + 100_000, gradedCliff(now - 10)
+    );
+    await crankSchedule(crossed.vestingSchedulePda, crossed.vestingVaultPda);
+    const crossedSchedule = await program.account.vestingSchedule.fetch(crossed.vestingSchedulePda);
+    expect(crossedSchedule.secondCliffBps).to.equal(2_500);
+    expect(crossedSchedule.amountTransferred.toNumber()).to.be.within(5_000, 5_100);
+
+    // Before the second cliff only the first cliff and the linear part have unlocked
+    const pending = await createNextSchedule(
+      new anchor.BN(10_000), now - 20, now - 20, now + 100_000, gradedCliff(now + 50_000)
+    );
+    await crankSchedule(pending.vestingSchedulePda, pending.vestingVaultPda);
+    const pendingSchedule = await program.account.vestingSchedule.fetch(pending.vestingSchedulePda);
+    expect(pendingSchedule.amountTransferred.toNumber()).to.be.within(2_500, 2_600);
+
+    // Both cliffs together cannot exceed 100%
+    try {
+      await createNextSchedule(
+        new anchor.BN(10_000), now - 20, now - 20, now + 100_000,
+        { initialUnlockBps: 6_000, secondCliffTimestamp: new anchor.BN(now + 50_000), secondCliffBps: 5_000 }
+      );
+      expect.fail("Should reject cliffs unlocking more than the total");
+    } catch (error: any) {
+      expect(error.toString()).to.include("InvalidBps");
+    }
+  });
+
+
+  it("Should deliver residual vault dust of a completed schedule on crank", async () => {
+    const now = Math.floor(Date.now() / 1000);
+    const { vestingSchedulePda, vestingVaultPda } = await createNextSchedule(
+      new anchor.BN(1_000), now - 20, now - 20, now - 10
+    );
+    await crankSchedule(vestingSchedulePda, vestingVaultPda);
+
+    // Seed a residual into the completed vault
+    await mintTo(provider.connection, admin, mint, vestingVaultPda, admin, BigInt(2));
+    const balanceBefore = (await getAccount(provider.connection, recipientTokenAccount)).amount;
+
+    const signature = await crankSchedule(vestingSchedulePda, vestingVaultPda);
+    const tx = await provider.connection.getTransaction(signature, {
+      commitment: "confirmed",
+      maxSupportedTransactionVersion: 0,
+    });
+    const parser = new anchor.EventParser(program.programId, program.coder);
+    const released = [...parser.parseLogs(tx!.meta!.logMessages ?? [])]
+      .find((event) => event.name === "tokensReleased");
+    expect(released!.data.amount.toString()).to.equal("2");
+    expect(released!.data.totalReleased.toString()).to.equal("1000");
+
+    const balanceAfter = (await getAccount(provider.connection, recipientTokenAccount)).amount;
+    expect((balanceAfter - balanceBefore).toString()).to.equal("2");
+    expect((await getAccount(provider.connection, vestingVaultPda)).amount.toString()).to.equal("0");
+    const schedule = await program.account.vestingSchedule.fetch(vestingSchedulePda);
+    expect(schedule.amountTransferred.toString()).to.equal("1000");
+
+    // The emptied vault can now be closed
+    await program.methods
+      .closeVestingSchedule()
+      .accounts({
+        beneficiary: admin.publicKey,
+        vestingSchedule: vestingSchedulePda,
+        vestingVault: vestingVaultPda,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([admin])
+      .rpc();
+    expect(await provider.connection.getAccountInfo(vestingVaultPda)).to.be.null;
+  });
+
+
+  it("Should keep a completed schedule open for the close grace period", async () => {
+    const setCloseGracePeriod = (seconds: number) =>
+      program.methods
+        .setCloseGracePeriod(new anchor.BN(seconds))
+        .accounts({ authority: admin.publicKey, programConfig: programConfigPda })
+        .signers([admin])
+        .rpc();
+
+    const now = Math.floor(Date.now() / 1000);
+    const { vestingSchedulePda, vestingVaultPda } = await createNextSchedule(
+      new anchor.BN(1_000), now - 20, now - 20, now - 10
+    );
+    const close = () =>
+      program.methods
+        .closeVestingSchedule()
+        .accounts({
+          beneficiary: admin.publicKey,
+          vestingSchedule: vestingSchedulePda,
+          vestingVault: vestingVaultPda,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([admin])
+        .rpc();
+
+    await setCloseGracePeriod(3);
+    try {
+      await crankSchedule(vestingSchedulePda, vestingVaultPda);
+      const schedule = await program.account.vestingSchedule.fetch(vestingSchedulePda);
+      expect(schedule.completedAt.toNumber()).to.be.greaterThan(0);
+
+      // Rejected right after completion
+      try {
+        await close();
+        expect.fail("Should not close within the grace period");
+      } catch (error: any) {
+        expect(error.toString()).to.include("CloseGracePeriodNotElapsed");
+      }
+
+      // Allowed once the grace period has passed
+      await new Promise(resolve => setTimeout(resolve, 4000));
+      await close();
+      expect(await provider.connection.getAccountInfo(vestingSchedulePda)).to.be.null;
+    } finally {
+      await setCloseGracePeriod(0);
+    }
+  });
+
+
+  it("Should suppress zero-amount release events only when skip_zero_events is set", async () => {
+    const now = Math.floor(Date.now() / 1000);
+    const { vestingSchedulePda, vestingVaultPda } = await createNextSchedule(
+      new anchor.BN(1_000), now + 1_000, now + 1_000, now + 2_000
+    );
+    const setSkipZeroEvents = (enabled: boolean) =>
+      program.methods
+        .setSkipZeroEvents(enabled)
+        .accounts({ authority: admin.publicKey, programConfig: programConfigPda })
+        .signers([admin])
+        .rpc();
+    const simulateCrank = () =>
+      program.methods
+        .crankVestingSchedule()
+        .accounts({
+          programConfig: programConfigPda,
+          vestingSchedule: vestingSchedulePda,
+          vestingVault: vestingVaultPda,
+          recipientTokenAccount: recipientTokenAccount,
+          mint: mint,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .simulate();
+
+    // The zero-amount heartbeat is emitted by default
+    let simulation = await simulateCrank();
+    const heartbeat = simulation.events.find((event) => event.name === "tokensReleased");
+    expect(heartbeat).to.exist;
+    expect(heartbeat!.data.amount.toString()).to.equal("0");
+
+    const toggle = await program.methods
+      .setSkipZeroEvents(true)
+      .accounts({ authority: admin.publicKey, programConfig: programConfigPda })
+      .signers([admin])
+      .simulate();
+    const updated = toggle.events.find((event) => event.name === "skipZeroEventsUpdated");
+    expect(updated!.data.oldSkipZeroEvents).to.be.false;
+    expect(updated!.data.newSkipZeroEvents).to.be.true;
+
+    await setSkipZeroEvents(true);
+    try {
+      simulation = await simulateCrank();
+      expect(simulation.events.find((event) => event.name === "tokensReleased")).to.be.undefined;
+    } finally {
+      await setSkipZeroEvents(false);
+    }
+  });
+
+
+  it("Should keep program stats consistent across a create and crank", async () => {
+    const programStats = () =>
+      program.methods.getProgramStats().accounts({ programConfig: programConfigPda }).view();
+    const before = await programStats();
+
+    const now = Math.floor(Date.now() / 1000);
+    const { vestingSchedulePda, vestingVaultPda } = await createNextSchedule(
+      new anchor.BN(1_000), now - 20, now - 20, now - 10
+    );
+    const created = await programStats();
+    expect(created.totalSchedules.sub(before.totalSchedules).toString()).to.equal("1");
+    expect(created.totalAllocated.sub(before.totalAllocated).toString()).to.equal("1000");
+    expect(created.totalDistributed.toString()).to.equal(before.totalDistributed.toString());
+
+    await crankSchedule(vestingSchedulePda, vestingVaultPda);
+    const cranked = await programStats();
+    const schedule = await program.account.vestingSchedule.fetch(vestingSchedulePda);
+    expect(cranked.totalDistributed.sub(created.totalDistributed).toString())
+      .to.equal(schedule.amountTransferred.toString());
+    expect(cranked.totalAllocated.toString()).to.equal(created.totalAllocated.toString());
+
+    const configAccount = await program.account.programConfig.fetch(programConfigPda);
+    expect(cranked.totalSchedules.toString()).to.equal(configAccount.totalSchedules.toString());
+  });
+
+
+  it("Should vest native SOL through a rent-exempt vault PDA", async () => {
+    const rentReserve = await provider.connection.getMinimumBalanceForRentExemption(0);
+    const nativeParams = (amount: number, cliff: number, start: number, end: number) => ({
+      recipient: recipient.publicKey,
+      totalAmount: new anchor.BN(amount),
+      cliffTimestamp: new anchor.BN(cliff),
+      vestingStartTimestamp: new anchor.BN(start),
+      vestingEndTimestamp: new anchor.BN(end),
+      sourceCategory: { public: {} },
+      roundUp: false,
+      grantGroupId: new anchor.BN(0),
+      minClaimInterval: new anchor.BN(0),
+      allowSelfReturn: false,
+      allowPastEnd: true,
+      termsHash: null,
+      initialUnlockBps: 0,
+      vestingCurve: { linear: {} },
+      nonTransferable: false,
+      label: Array(32).fill(0),
+      secondCliffTimestamp: new anchor.BN(0),
+      secondCliffBps: 0,
+    });
+    const createSolSchedule = async (amount: number, cliff: number, start: number, end: number) => {
+      const configAccount = await program.account.programConfig.fetch(programConfigPda);
+      const scheduleId = new anchor.BN(configAccount.totalSchedules.toString());
+      const [vestingSchedulePda] = findSchedulePdas(scheduleId);
+      const [nativeVaultPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("native_vault"), scheduleId.toArrayLike(Buffer, "le", 8)],
+        program.programId
+      );
+      await program.methods
+        .createSolVestingSchedule(scheduleId, nativeParams(amount, cliff, start, end))
+        .accounts({
+          admin: admin.publicKey,
+          programConfig: programConfigPda,
+          vestingSchedule: vestingSchedulePda,
+          nativeVault: nativeVaultPda,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([admin])
+        .rpc();
+      return { vestingSchedulePda, nativeVaultPda };
+    };
+    const crankSol = (vestingSchedulePda: PublicKey, nativeVaultPda: PublicKey) =>
+      program.methods
+        .crankSolVestingSchedule()
+        .accounts({
+          programConfig: programConfigPda,
+          vestingSchedule: vestingSchedulePda,
+          nativeVault: nativeVaultPda,
+          recipient: recipient.publicKey,
+          depositor: admin.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .rpc({ commitment: "confirmed" });
+
+    await provider.connection.confirmTransaction(
+      await provider.connection.requestAirdrop(admin.publicKey, LAMPORTS_PER_SOL)
+    );
+    const now = Math.floor(Date.now() / 1000);
+    const amount = LAMPORTS_PER_SOL / 10;
+
+    // Completed grant: the vault holds the allocation on top of its rent reserve
+    const completed = await createSolSchedule(amount, now - 20, now - 20, now - 10);
+    expect(await provider.connection.getBalance(completed.nativeVaultPda)).to.equal(amount + rentReserve);
+    const created = await program.account.vestingSchedule.fetch(completed.vestingSchedulePda);
+    expect(created.isNative).to.be.true;
+    expect(created.mint.toString()).to.equal(PublicKey.default.toString());
+    expect(created.recipientTokenAccount.toString()).to.equal(PublicKey.default.toString());
+
+    const recipientBefore = await provider.connection.getBalance(recipient.publicKey);
+    const signature = await crankSol(completed.vestingSchedulePda, completed.nativeVaultPda);
+    expect(await provider.connection.getBalance(recipient.publicKey)).to.equal(recipientBefore + amount);
+
+    // The final release returns the rent reserve to the depositor and empties the vault
+    const tx = await provider.connection.getTransaction(signature, {
+      commitment: "confirmed",
+      maxSupportedTransactionVersion: 0,
+    });
+    const parser = new anchor.EventParser(program.programId, program.coder);
+    const events = [...parser.parseLogs(tx!.meta!.logMessages ?? [])];
+    const released = events.find((event) => event.name === "tokensReleased");
+    expect(released!.data.amount.toString()).to.equal(amount.toString());
+    const vaultClosed = events.find((event) => event.name === "vaultClosed");
+    expect(vaultClosed!.data.beneficiary.toString()).to.equal(admin.publicKey.toString());
+    expect(vaultClosed!.data.vaultLamports.toString()).to.equal(rentReserve.toString());
+    expect(await provider.connection.getAccountInfo(completed.nativeVaultPda)).to.be.null;
+
+    const finished = await program.account.vestingSchedule.fetch(completed.vestingSchedulePda);
+    expect(finished.amountTransferred.toString()).to.equal(amount.toString());
+    expect(finished.vaultClosed).to.be.true;
+
+    // A later crank is a no-op
+    await crankSol(completed.vestingSchedulePda, completed.nativeVaultPda);
+    expect(await provider.connection.getBalance(recipient.publicKey)).to.equal(recipientBefore + amount);
+
+    // Mid-vesting releases never dip into the rent reserve
+    const vesting = await createSolSchedule(amount, now - 20, now - 20, now + 100_000);
+    await crankSol(vesting.vestingSchedulePda, vesting.nativeVaultPda);
+    const partial = await program.account.vestingSchedule.fetch(vesting.vestingSchedulePda);
+    expect(partial.amountTransferred.toNumber()).to.be.greaterThan(0);
+    expect(partial.amountTransferred.toNumber()).to.be.lessThan(amount);
+    expect(await provider.connection.getBalance(vesting.nativeVaultPda))
+      .to.equal(amount - partial.amountTransferred.toNumber() + rentReserve);
+
+    // Token release paths cannot be pointed at a native schedule
+    try {
+      await crankSchedule(vesting.vestingSchedulePda, findSchedulePdas(partial.scheduleId)[1]);
+      expect.fail("Should not crank a native schedule through the token path");
+    } catch (error: any) {
+      // No token vault exists at the token vault seeds of a native schedule
+      expect(error.toString()).to.include("AccountNotInitialized");
+    }
+  });
+
+  it("Should reject top-ups and recipient account changes of a finalized schedule", async () => {
+    const now = Math.floor(Date.now() / 1000);
+    const { vestingSchedulePda, vestingVaultPda } = await createNextSchedule(
+      new anchor.BN(1_000), now + 100, now + 100, now + 1_000
+    );
+    await program.methods
+      .finalizeSchedule()
+      .accounts({ admin: admin.publicKey, programConfig: programConfigPda, vestingSchedule: vestingSchedulePda })
+      .signers([admin])
+      .rpc();
+
+    try {
+      await program.methods
+        .topUpBatch([new anchor.BN(100)])
+        .accounts({
+          admin: admin.publicKey,
+          programConfig: programConfigPda,
+          mint: mint,
+          depositorTokenAccount: adminTokenAccount,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .remainingAccounts([
+          { pubkey: vestingSchedulePda, isWritable: true, isSigner: false },
+          { pubkey: vestingVaultPda, isWritable: true, isSigner: false },
+        ])
+        .signers([admin])
+        .rpc();
+      expect.fail("Should reject topping up a finalized schedule");
+    } catch (error: any) {
+      expect(error.toString()).to.include("ScheduleImmutable");
+    }
+
+    try {
+      await program.methods
+        .topUpSchedule(new anchor.BN(100))
+        .accounts({
+          admin: admin.publicKey,
+          programConfig: programConfigPda,
+          vestingSchedule: vestingSchedulePda,
+          vestingVault: vestingVaultPda,
+          mint: mint,
+          depositorTokenAccount: adminTokenAccount,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([admin])
+        .rpc();
+      expect.fail("Should reject topping up a finalized schedule on its own");
+    } catch (error: any) {
+      expect(error.toString()).to.include("ScheduleImmutable");
+    }
+
+    try {
+      await program.methods
+        .proposeRecipientTokenAccount()
+        .accounts({
+          admin: admin.publicKey,
+          programConfig: programConfigPda,
+          vestingSchedule: vestingSchedulePda,
+          newRecipientTokenAccount: recipientTokenAccount,
+        })
+        .signers([admin])
+        .rpc();
+      expect.fail("Should reject changing the recipient account of a finalized schedule");
+    } catch (error: any) {
+      expect(error.toString()).to.include("ScheduleImmutable");
+    }
+
+    const scheduleAccount = await program.account.vestingSchedule.fetch(vestingSchedulePda);
+    expect(scheduleAccount.totalAmount.toString()).to.equal("1000");
+  });
+
+  it("Should revoke native schedules and close completed ones after the grace period", async () => {
+    const rentReserve = await provider.connection.getMinimumBalanceForRentExemption(0);
+    const createSolSchedule = async (amount: number, cliff: number, start: number, end: number) => {
+      const configAccount = await program.account.programConfig.fetch(programConfigPda);
+      const scheduleId = new anchor.BN(configAccount.totalSchedules.toString());
+      const [vestingSchedulePda] = findSchedulePdas(scheduleId);
+      const [nativeVaultPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("native_vault"), scheduleId.toArrayLike(Buffer, "le", 8)],
+        program.programId
+      );
+      await program.methods
+        .createSolVestingSchedule(scheduleId, {
+          recipient: recipient.publicKey,
+          totalAmount: new anchor.BN(amount),
+          cliffTimestamp: new anchor.BN(cliff),
+          vestingStartTimestamp: new anchor.BN(start),
+          vestingEndTimestamp: new anchor.BN(end),
+          sourceCategory: { public: {} },
+          roundUp: false,
+          grantGroupId: new anchor.BN(0),
+          minClaimInterval: new anchor.BN(0),
+          allowSelfReturn: false,
+          allowPastEnd: true,
+          termsHash: null,
+          initialUnlockBps: 0,
+          vestingCurve: { linear: {} },
+          nonTransferable: false,
+          label: Array(32).fill(0),
+          secondCliffTimestamp: new anchor.BN(0),
+          secondCliffBps: 0,
+        })
+        .accounts({
+          admin: admin.publicKey,
+          programConfig: programConfigPda,
+          vestingSchedule: vestingSchedulePda,
+          nativeVault: nativeVaultPda,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([admin])
+        .rpc();
+      return { vestingSchedulePda, nativeVaultPda };
+    };
+    const nativeAccounts = (schedule: { vestingSchedulePda: PublicKey; nativeVaultPda: PublicKey }) => ({
+      programConfig: programConfigPda,
+      vestingSchedule: schedule.vestingSchedulePda,
+      nativeVault: schedule.nativeVaultPda,
+      recipient: recipient.publicKey,
+      depositor: admin.publicKey,
+      systemProgram: SystemProgram.programId,
+    });
+    const crankSol = (schedule: { vestingSchedulePda: PublicKey; nativeVaultPda: PublicKey }) =>
+      program.methods.crankSolVestingSchedule().accounts(nativeAccounts(schedule)).rpc();
+    const closeSol = (vestingSchedulePda: PublicKey) =>
+      program.methods
+        .closeSolVestingSchedule()
+        .accounts({ beneficiary: admin.publicKey, programConfig: programConfigPda, vestingSchedule: vestingSchedulePda })
+        .signers([admin])
+        .rpc();
+    const setCloseGracePeriod = (seconds: number) =>
+      program.methods
+        .setCloseGracePeriod(new anchor.BN(seconds))
+        .accounts({ authority: admin.publicKey, programConfig: programConfigPda })
+        .signers([admin])
+        .rpc();
+
+    await provider.connection.confirmTransaction(
+      await provider.connection.requestAirdrop(admin.publicKey, LAMPORTS_PER_SOL)
+    );
+    const now = Math.floor(Date.now() / 1000);
+    const amount = LAMPORTS_PER_SOL / 10;
+
+    // Revocation pays the vested part, claws back the rest and empties the vault
+    const revoked = await createSolSchedule(amount, now - 100, now - 100, now + 100);
+    const recipientBefore = await provider.connection.getBalance(recipient.publicKey);
+    await program.methods
+      .revokeSolVestingSchedule()
+      .accounts({ admin: admin.publicKey, ...nativeAccounts(revoked) })
+      .signers([admin])
+      .rpc();
+    const revokedSchedule = await program.account.vestingSchedule.fetch(revoked.vestingSchedulePda);
+    expect(revokedSchedule.revoked).to.be.true;
+    expect(revokedSchedule.vaultClosed).to.be.true;
+    const paid = revokedSchedule.amountTransferred.toNumber();
+    expect(paid).to.be.greaterThan(0);
+    expect(paid).to.be.lessThan(amount);
+    expect(await provider.connection.getBalance(recipient.publicKey)).to.equal(recipientBefore + paid);
+    expect(await provider.connection.getAccountInfo(revoked.nativeVaultPda)).to.be.null;
+
+    // A crank of the revoked schedule is skipped and releases nothing, even once it would have vested
+    const simulation = await program.methods.crankSolVestingSchedule().accounts(nativeAccounts(revoked)).simulate();
+    expect(simulation.events.map((event) => event.name)).to.not.include("tokensReleased");
+    await crankSol(revoked);
+    expect(await provider.connection.getBalance(recipient.publicKey)).to.equal(recipientBefore + paid);
+    expect((await program.account.vestingSchedule.fetch(revoked.vestingSchedulePda)).amountTransferred.toNumber())
+      .to.equal(paid);
+
+    // Completed native schedules respect the close grace period, then give their rent back
+    const completed = await createSolSchedule(amount, now - 20, now - 20, now - 10);
+    await crankSol(completed);
+    await setCloseGracePeriod(3_600);
+    try {
+      await closeSol(completed.vestingSchedulePda);
+      expect.fail("Should keep the schedule open during the grace period");
+    } catch (error: any) {
+      expect(error.toString()).to.include("CloseGracePeriodNotElapsed");
+    } finally {
+      await setCloseGracePeriod(0);
+    }
+
+    const scheduleRent = await provider.connection.getBalance(completed.vestingSchedulePda);
+    const adminBefore = await provider.connection.getBalance(admin.publicKey);
+    await closeSol(completed.vestingSchedulePda);
+    expect(await provider.connection.getAccountInfo(completed.vestingSchedulePda)).to.be.null;
+    // The admin pays the transaction fee out of the reclaimed rent
+    expect(await provider.connection.getBalance(admin.publicKey)).to.be.greaterThan(adminBefore + scheduleRent - 10_000);
+
+    // The revoked schedule is fully processed as well and closes the same way
+    await closeSol(revoked.vestingSchedulePda);
+    expect(await provider.connection.getAccountInfo(revoked.vestingSchedulePda)).to.be.null;
+
+    // Schedules still vesting cannot be closed
+    const vesting = await createSolSchedule(amount, now - 20, now - 20, now + 100_000);
+    try {
+      await closeSol(vesting.vestingSchedulePda);
+      expect.fail("Should not close a schedule that is still vesting");
+    } catch (error: any) {
+      expect(error.toString()).to.include("ScheduleNotFullyVested");
+    }
+    expect(await provider.connection.getBalance(vesting.nativeVaultPda)).to.equal(amount + rentReserve);
+  });
+
+  it("Should apply min_crank_interval to batch cranks and claim_all", async () => {
+    const setMinCrankInterval = (seconds: number) =>
+      program.methods
+        .setMinCrankInterval(new anchor.BN(seconds))
+        .accounts({ authority: admin.publicKey, programConfig: programConfigPda })
+        .signers([admin])
+        .rpc();
+    const now = Math.floor(Date.now() / 1000);
+    const { vestingSchedulePda, vestingVaultPda } = await createNextSchedule(
+      new anchor.BN(1_000_000), now - 100, now - 100, now + 100_000
+    );
+    const transferred = async () =>
+      (await program.account.vestingSchedule.fetch(vestingSchedulePda)).amountTransferred.toString();
+
+    await setMinCrankInterval(3_600);
+    try {
+      await crankSchedule(vestingSchedulePda, vestingVaultPda);
+      const firstRelease = await transferred();
+      expect(Number(firstRelease)).to.be.greaterThan(0);
+
+      // More has vested since, but the batch crank is still inside the interval
+      await new Promise((resolve) => setTimeout(resolve, 2000));
+      const batchCrank = () =>
+        program.methods
+          .crankVestingSchedulesBatch()
+          .accounts({ programConfig: programConfigPda, tokenProgram: TOKEN_PROGRAM_ID })
+          .remainingAccounts([
+            { pubkey: vestingSchedulePda, isWritable: true, isSigner: false },
+            { pubkey: vestingVaultPda, isWritable: true, isSigner: false },
+            { pubkey: recipientTokenAccount, isWritable: true, isSigner: false },
+            { pubkey: mint, isWritable: false, isSigner: false },
+          ]);
+      const summary = await batchCrank().view();
+      expect(summary.schedulesReleased).to.equal(0);
+      expect(summary.totalReleased.toString()).to.equal("0");
+      await batchCrank().rpc();
+      expect(await transferred()).to.equal(firstRelease);
+
+      // Claiming every schedule at once does not bypass it either
+      await program.methods
+        .claimAll()
+        .accounts({
+          recipient: recipient.publicKey,
+          programConfig: programConfigPda,
+          recipientTokenAccount: recipientTokenAccount,
+          mint: mint,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .remainingAccounts([
+          { pubkey: vestingSchedulePda, isWritable: true, isSigner: false },
+          { pubkey: vestingVaultPda, isWritable: true, isSigner: false },
+        ])
+        .signers([recipient])
+        .rpc();
+      expect(await transferred()).to.equal(firstRelease);
+    } finally {
+      await setMinCrankInterval(0);
+    }
+  });
+
+  it("Should accrue batch cranks in release-batching mode instead of transferring", async () => {
+    const now = Math.floor(Date.now() / 1000);
+    const setBatching = (enabled: boolean) =>
+      program.methods
+        .setReleaseBatching(enabled)
+        .accounts({ authority: admin.publicKey, programConfig: programConfigPda })
+        .signers([admin])
+        .rpc();
+    const { vestingSchedulePda, vestingVaultPda } = await createNextSchedule(
+      new anchor.BN(700), now - 20, now - 20, now - 10
+    );
+    const entry = [
+      { pubkey: vestingSchedulePda, isWritable: true, isSigner: false },
+      { pubkey: vestingVaultPda, isWritable: true, isSigner: false },
+      { pubkey: recipientTokenAccount, isWritable: true, isSigner: false },
+      { pubkey: mint, isWritable: false, isSigner: false },
+    ];
+    const batchCrank = () =>
+      program.methods
+        .crankVestingSchedulesBatch()
+        .accounts({ programConfig: programConfigPda, tokenProgram: TOKEN_PROGRAM_ID })
+        .remainingAccounts(entry);
+    const balanceBefore = (await getAccount(provider.connection, recipientTokenAccount)).amount;
+
+    await setBatching(true);
+    try {
+      const simulation = await batchCrank().simulate();
+      expect(simulation.events.map((event) => event.name)).to.deep.equal(["releaseAccrued"]);
+      const summary = await batchCrank().view();
+      expect(summary.totalAccrued.toString()).to.equal("700");
+      expect(summary.totalReleased.toString()).to.equal("0");
+
+      await batchCrank().rpc();
+      let scheduleAccount = await program.account.vestingSchedule.fetch(vestingSchedulePda);
+      expect(scheduleAccount.pendingRelease.toString()).to.equal("700");
+      expect(scheduleAccount.amountTransferred.toString()).to.equal("0");
+      expect((await getAccount(provider.connection, recipientTokenAccount)).amount).to.equal(balanceBefore);
+
+      // Accrued amounts are not accrued twice, and flush delivers them
+      await batchCrank().rpc();
+      scheduleAccount = await program.account.vestingSchedule.fetch(vestingSchedulePda);
+      expect(scheduleAccount.pendingRelease.toString()).to.equal("700");
+
+      await program.methods
+        .flushReleases()
+        .accounts({ programConfig: programConfigPda, tokenProgram: TOKEN_PROGRAM_ID })
+        .remainingAccounts(entry)
+        .rpc();
+      expect((await getAccount(provider.connection, recipientTokenAccount)).amount)
+        .to.equal(balanceBefore + BigInt(700));
+    } finally {
+      await setBatching(false);
+    }
+  });
+
+
+  it("Should batch crank and claim_all schedules of a Token-2022 mint", async () => {
+    const now = Math.floor(Date.now() / 1000);
+    const mint2022 = await createMint(
+      provider.connection, admin, admin.publicKey, null, 6, undefined, undefined, TOKEN_2022_PROGRAM_ID
+    );
+    const tokenAccount2022 = async (owner: PublicKey) =>
+      (await getOrCreateAssociatedTokenAccount(
+        provider.connection, admin, mint2022, owner, false, undefined, undefined, TOKEN_2022_PROGRAM_ID
+      )).address;
+    const depositor2022 = await tokenAccount2022(admin.publicKey);
+    const recipient2022 = await tokenAccount2022(recipient.publicKey);
+    await mintTo(
+      provider.connection, admin, mint2022, depositor2022, admin, 5_000, [], undefined, TOKEN_2022_PROGRAM_ID
+    );
+    const balance = async (tokenAccount: PublicKey) =>
+      (await getAccount(provider.connection, tokenAccount, undefined, TOKEN_2022_PROGRAM_ID)).amount.toString();
+    const createSchedule2022 = (amount: number) =>
+      createNextSchedule(new anchor.BN(amount), now - 20, now - 20, now - 10, {}, {
+        mint: mint2022,
+        depositorTokenAccount: depositor2022,
+        recipientTokenAccount: recipient2022,
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
+      });
+
+    const cranked = await createSchedule2022(600);
+    await program.methods
+      .crankVestingSchedulesBatch()
+      .accounts({ programConfig: programConfigPda, tokenProgram: TOKEN_2022_PROGRAM_ID })
+      .remainingAccounts([
+        { pubkey: cranked.vestingSchedulePda, isWritable: true, isSigner: false },
+        { pubkey: cranked.vestingVaultPda, isWritable: true, isSigner: false },
+        { pubkey: recipient2022, isWritable: true, isSigner: false },
+        { pubkey: mint2022, isWritable: false, isSigner: false },
+      ])
+      .rpc();
+    expect(await balance(recipient2022)).to.equal("600");
+    expect(await balance(cranked.vestingVaultPda)).to.equal("0");
+
+    const claimedA = await createSchedule2022(300);
+    const claimedB = await createSchedule2022(100);
+    await program.methods
+      .claimAll()
+      .accounts({
+        recipient: recipient.publicKey,
+        programConfig: programConfigPda,
+        recipientTokenAccount: recipient2022,
+        mint: mint2022,
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
+      })
+      .remainingAccounts([claimedA, claimedB].flatMap((entry) => [
+        { pubkey: entry.vestingSchedulePda, isWritable: true, isSigner: false },
+        { pubkey: entry.vestingVaultPda, isWritable: true, isSigner: false },
+      ]))
+      .signers([recipient])
+      .rpc();
+    expect(await balance(recipient2022)).to.equal("1000");
+    expect(await balance(claimedA.vestingVaultPda)).to.equal("0");
+    expect(await balance(claimedB.vestingVaultPda)).to.equal("0");
+  });
 }); 
\ No newline at end of file
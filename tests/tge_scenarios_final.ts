@@ -491,6 +491,18 @@ describe('🚀 TGE Complete Distribution Test (Updated)', () => {
               startTimestamp + monthsToSeconds(ALLOCATION.PUBLIC_ROUND.VESTING_MONTHS)
             ),
             sourceCategory: { public: {} },
+            roundUp: false,
+            grantGroupId: new BN(0),
+            minClaimInterval: new BN(0),
+            allowSelfReturn: false,
+            allowPastEnd: false,
+            termsHash: null,
+            initialUnlockBps: 0,
+            vestingCurve: { linear: {} },
+            nonTransferable: false,
+            label: Array(32).fill(0),
+            secondCliffTimestamp: new BN(0),
+            secondCliffBps: 0,
           })
           .accounts({
             admin: actualAdminKeypair.publicKey,
@@ -537,6 +549,18 @@ describe('🚀 TGE Complete Distribution Test (Updated)', () => {
               startTimestamp + monthsToSeconds(ALLOCATION.ECOSYSTEM.VESTING_MONTHS)
             ),
             sourceCategory: { ecosystem: {} },
+            roundUp: false,
+            grantGroupId: new BN(0),
+            minClaimInterval: new BN(0),
+            allowSelfReturn: false,
+            allowPastEnd: false,
+            termsHash: null,
+            initialUnlockBps: 0,
+            vestingCurve: { linear: {} },
+            nonTransferable: false,
+            label: Array(32).fill(0),
+            secondCliffTimestamp: new BN(0),
+            secondCliffBps: 0,
           })
           .accounts({
             admin: actualAdminKeypair.publicKey,
@@ -590,6 +614,18 @@ describe('🚀 TGE Complete Distribution Test (Updated)', () => {
                 )
             ),
             sourceCategory: { team: {} },
+            roundUp: false,
+            grantGroupId: new BN(0),
+            minClaimInterval: new BN(0),
+            allowSelfReturn: false,
+            allowPastEnd: false,
+            termsHash: null,
+            initialUnlockBps: 0,
+            vestingCurve: { linear: {} },
+            nonTransferable: false,
+            label: Array(32).fill(0),
+            secondCliffTimestamp: new BN(0),
+            secondCliffBps: 0,
           })
           .accounts({
             admin: actualAdminKeypair.publicKey,
@@ -636,6 +672,18 @@ describe('🚀 TGE Complete Distribution Test (Updated)', () => {
               startTimestamp + monthsToSeconds(ALLOCATION.FOUNDATION.VESTING_MONTHS)
             ),
             sourceCategory: { foundation: {} },
+            roundUp: false,
+            grantGroupId: new BN(0),
+            minClaimInterval: new BN(0),
+            allowSelfReturn: false,
+            allowPastEnd: false,
+            termsHash: null,
+            initialUnlockBps: 0,
+            vestingCurve: { linear: {} },
+            nonTransferable: false,
+            label: Array(32).fill(0),
+            secondCliffTimestamp: new BN(0),
+            secondCliffBps: 0,
           })
           .accounts({
             admin: actualAdminKeypair.publicKey,